@@ -7,7 +7,7 @@ use tempfile::tempdir;
 use tokio::fs::OpenOptions;
 use tokio_util::codec::{BytesCodec, FramedWrite};
 use vector::{
-    config, sinks, sources,
+    codecs, config, sinks, sources,
     test_util::{random_lines, runtime, start_topology},
 };
 
@@ -54,7 +54,8 @@ fn benchmark_files_without_partitions(c: &mut Criterion) {
                     sinks::file::FileSinkConfig {
                         path: output.try_into().unwrap(),
                         idle_timeout_secs: None,
-                        encoding: sinks::file::Encoding::Text.into(),
+                        encoding: codecs::Encoding::Text.into(),
+                        framing: None,
                         compression: sinks::file::Compression::None,
                     },
                 );
@@ -7,7 +7,7 @@ use hyper::{
 use std::net::SocketAddr;
 use tokio::runtime::Runtime;
 use vector::{
-    config, sinks,
+    codecs, config, sinks,
     sinks::util::Compression,
     sources,
     test_util::{next_addr, random_lines, runtime, send_lines, start_topology, wait_for_tcp},
@@ -52,7 +52,8 @@ fn benchmark_http(c: &mut Criterion) {
                                     max_bytes: Some(num_lines * line_size),
                                     ..Default::default()
                                 },
-                                encoding: sinks::http::Encoding::Text.into(),
+                                encoding: codecs::Encoding::Text.into(),
+                                framing: Default::default(),
                                 request: Default::default(),
                                 tls: Default::default(),
                             },
@@ -14,5 +14,23 @@ fn main() {
             &["proto/"],
         )
         .unwrap();
+
+    // The gRPC services are generated with `tonic`, which is only a dependency
+    // of the `opentelemetry` source. The sink only needs the messages.
+    let otlp_server = std::env::var_os("CARGO_FEATURE_SOURCES_OPENTELEMETRY").is_some();
+    if otlp_server || std::env::var_os("CARGO_FEATURE_SINKS_OPENTELEMETRY").is_some() {
+        println!("cargo:rerun-if-changed=proto/opentelemetry");
+        tonic_build::configure()
+            .build_client(false)
+            .build_server(otlp_server)
+            .compile(
+                &[
+                    "proto/opentelemetry/proto/collector/logs/v1/logs_service.proto",
+                    "proto/opentelemetry/proto/collector/metrics/v1/metrics_service.proto",
+                ],
+                &["proto/"],
+            )
+            .unwrap();
+    }
     built::write_built_file().expect("Failed to acquire build-time information");
 }
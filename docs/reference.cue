@@ -58,7 +58,7 @@ _values: {
 // * `deprecated` - The component will be removed in a future version.
 #DevelopmentStatus: "beta" | "stable" | "deprecated"
 
-#EncodingCodec: "json" | "ndjson" | "text"
+#EncodingCodec: "avro" | "csv" | "json" | "logfmt" | "ndjson" | "protobuf" | "text"
 
 #Endpoint: {
 	description: string
@@ -155,6 +155,22 @@ cli: {
 			type:    "string"
 			env_var: "VECTOR_CONFIG_YAML"
 		}
+		"config-url": {
+			description: """
+				Read configuration from one or more HTTP(S) URLs. The format is
+				detected from the path of the URL. The URLs are polled for changes,
+				and Vector reloads when any of them changed. Changes that fail to
+				load are skipped, the last configuration that loaded stays in use.
+				"""
+			type:    "string"
+			env_var: "VECTOR_CONFIG_URL"
+		}
+		"config-url-interval-secs": {
+			description: "How often to poll the configuration URLs for changes, in seconds"
+			default:     60
+			type:        "integer"
+			env_var:     "VECTOR_CONFIG_URL_INTERVAL_SECS"
+		}
 		"threads": {
 			_short: "t"
 			description: """
@@ -167,6 +167,7 @@ components: {
 			collect?:  #FeaturesCollect
 			generate?: #FeaturesGenerate
 			multiline: #FeaturesMultiline
+			codecs?:   #FeaturesCodecs
 			encoding?: #FeaturesEncoding
 			receive?:  #FeaturesReceive
 		}
@@ -258,6 +259,11 @@ components: {
 		enabled: bool
 	}
 
+	#FeaturesCodecs: {
+		enabled:         bool
+		default_framing: string
+	}
+
 	#FeaturesEncoding: {
 		enabled: bool
 	}
@@ -342,6 +348,10 @@ components: {
 						enum:    [#EncodingCodec, ...#EncodingCodec] | null
 					}
 				}
+
+				// `default_framing` is set by sinks supporting the `framing`
+				// option.
+				default_framing?: string
 			}
 		}
 
@@ -803,7 +813,7 @@ components: {
 
 			if Kind != "source" {
 				inputs: {
-					description: "A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms)) IDs. See [configuration](\(urls.vector_configuration)) for more info."
+					description: "A list of upstream [source](\(urls.vector_sources)) or [transform](\(urls.vector_transforms)) IDs. Transforms with named outputs, like `route`, are referenced as `<transform_id>.<output>`. See [configuration](\(urls.vector_configuration)) for more info."
 					required:    true
 					sort:        -1
 					type: array: items: type: string: {
@@ -854,6 +864,12 @@ components: {
 					}
 				}
 
+				if features.codecs != _|_ {
+					if features.codecs.enabled == true {
+						codecs: "Splits the received data into frames and parses them, e.g. as JSON or syslog, at the source."
+					}
+				}
+
 				if features.receive != _|_ {
 					if features.receive.from != _|_ {
 						receive_context: "Enriches data with useful \(features.receive.from.service.name) context."
@@ -0,0 +1,153 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		tls: {
+			enabled:                true
+			can_enable:             true
+			can_verify_certificate: false
+			can_verify_hostname:    false
+			enabled_default:        false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		client_id: {
+			common:      false
+			description: "The client identifier to connect to the broker with. A random identifier prefixed with `vector-` is generated if it's not set."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		host: {
+			description: "The host name or IP address of the MQTT broker."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["127.0.0.1", "mqtt.example.com"]
+				syntax: "literal"
+			}
+		}
+		keep_alive_secs: {
+			common:      false
+			description: "The interval at which the broker is pinged when no other packets are exchanged."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+		password: {
+			common:      false
+			description: "The password to authenticate with. Only used together with `user`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["${MQTT_PASSWORD}"]
+				syntax: "literal"
+			}
+		}
+		port: {
+			common:      true
+			description: "The port of the MQTT broker."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 1883
+				examples: [1883, 8883]
+				unit: null
+			}
+		}
+		qos: {
+			common:      false
+			description: "The quality of service level of the messages."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "at_least_once"
+				enum: {
+					at_most_once:  "Messages are delivered at most once (QoS 0)."
+					at_least_once: "Messages are delivered at least once (QoS 1)."
+					exactly_once:  "Messages are delivered exactly once (QoS 2)."
+				}
+				syntax: "literal"
+			}
+		}
+		user: {
+			common:      false
+			description: "The user name to authenticate with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+	}
+
+	how_it_works: {
+		tls: {
+			title: "TLS"
+			body: """
+				TLS is enabled with `tls.enabled`, and requires the CA certificate
+				of the broker in `tls.ca_file`. Client certificates are set with
+				`tls.crt_file` and `tls.key_file`, both in PEM format. Certificates
+				are always verified.
+				"""
+		}
+	}
+}
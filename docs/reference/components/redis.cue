@@ -0,0 +1,64 @@
+package metadata
+
+components: _redis: {
+	features: {
+		collect: from: {
+			service: services.redis
+			interface: {
+				socket: {
+					api: {
+						title: "Redis protocol"
+						url:   urls.redis_protocol
+					}
+					direction: "outgoing"
+					port:      6379
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.redis
+			interface: {
+				socket: {
+					api: {
+						title: "Redis protocol"
+						url:   urls.redis_protocol
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The URL of the Redis server, including the credentials and the database number."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["redis://127.0.0.1:6379/0", "redis://:password@redis.example.com:6379/1"]
+				syntax: "literal"
+			}
+		}
+	}
+}
@@ -56,6 +56,20 @@ components: sinks: [Name=string]: {
 			}
 		}
 
+		dead_letter: {
+			common:      false
+			description: "The ID of a sink or transform that events this sink permanently rejects are sent to, instead of being dropped. Events are rejected when they can't be encoded, or when the downstream service responds with an error that won't succeed if retried. They are annotated with the `dead_letter.error`, `dead_letter.sink` and `dead_letter.attempts` fields, or the `dead_letter_error`, `dead_letter_sink` and `dead_letter_attempts` tags for metrics. While the component they are sent to falls behind, the sink stops reading further events."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["my-dead-letter-sink"]
+				syntax: "literal"
+			}
+		}
+
+		proxy: components._proxy
+
 		if sinks[Name].features.buffer.enabled {
 			buffer: {
 				common:      false
@@ -77,12 +91,22 @@ components: sinks: [Name=string]: {
 						max_size: {
 							description:   "The maximum size of the buffer on the disk."
 							required:      true
-							relevant_when: "type = \"disk\""
+							relevant_when: "type = \"disk\" or type = \"disk_v2\""
 							type: uint: {
 								examples: [104900000]
 								unit: "bytes"
 							}
 						}
+						overflow: {
+							common:        false
+							description:   "The disk buffer that data spills into when the in-memory buffer is full. Takes the same `type`, `max_size` and `when_full` options as the top-level buffer, where `type` must be `disk` or `disk_v2`."
+							required:      false
+							relevant_when: "when_full = \"overflow\""
+							type: object: {
+								examples: [{type: "disk_v2", max_size: 104900000}]
+								options: {}
+							}
+						}
 						type: {
 							common:      true
 							description: "The buffer's type and storage mechanism."
@@ -90,8 +114,9 @@ components: sinks: [Name=string]: {
 							type: string: {
 								default: "memory"
 								enum: {
-									memory: "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
-									disk:   "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									memory:  "Stores the sink's buffer in memory. This is more performant, but less durable. Data will be lost if Vector is restarted forcefully."
+									disk:    "Stores the sink's buffer on disk. This is less performant, but durable. Data will not be lost between restarts."
+									disk_v2: "Stores the sink's buffer on disk in append-only, checksummed segment files. Unlike `disk`, this doesn't depend on LevelDB and is available on every platform."
 								}
 								syntax: "literal"
 							}
@@ -105,6 +130,7 @@ components: sinks: [Name=string]: {
 								enum: {
 									block:       "Applies back pressure when the buffer is full. This prevents data loss, but will cause data to pile up on the edge."
 									drop_newest: "Drops new data as it's received. This data is lost. This should be used when performance is the highest priority."
+									overflow:    "Spills new data into the `overflow` buffer while the in-memory buffer is full. Data is drained in order once the sink catches up. Only supported by `memory` buffers."
 								}
 								syntax: "literal"
 							}
@@ -193,6 +219,64 @@ components: sinks: [Name=string]: {
 									syntax: "literal"
 								}
 							}
+
+							if sinks[Name].features.send.encoding.default_framing != _|_ {
+								schema: {
+									common:        false
+									description:   "The Avro schema, as JSON, the events are encoded with."
+									relevant_when: "codec = `avro`"
+									required:      false
+									type: string: {
+										default: null
+										examples: [#"{"type": "record", "name": "log", "fields": [{"name": "message", "type": "string"}]}"#]
+										syntax: "literal"
+									}
+								}
+							}
+						}
+					}
+				}
+
+				if sinks[Name].features.send.encoding.default_framing != _|_ {
+					framing: {
+						common:      false
+						description: "Configures how the encoded events are framed, i.e. separated from each other, when written. With the `csv` codec, the columns are the fields listed in `encoding.only_fields`."
+						required:    false
+						type: object: options: {
+							method: {
+								description: "The framing method."
+								required:    false
+								type: string: {
+									default: sinks[Name].features.send.encoding.default_framing
+									enum: {
+										bytes:               "Events are written as is, without anything separating them. Best suited for sinks sending each event as a separate message."
+										newline_delimited:   "Events are followed by a newline."
+										character_delimited: "Events are followed by the character given in `delimiter`."
+										octet_counting:      "Events are prefixed with their length as in [RFC 6587](\(urls.rfc_6587))."
+										length_delimited:    "Events are prefixed with their length as a 4 byte big-endian integer."
+									}
+									syntax: "literal"
+								}
+							}
+							delimiter: {
+								description:   "The ASCII character following each event."
+								relevant_when: "method = `character_delimited`"
+								required:      true
+								type: string: {
+									examples: [";", "|"]
+									syntax: "literal"
+								}
+							}
+							max_length: {
+								common:        false
+								description:   "The maximum length of an encoded event in bytes. Longer events are dropped."
+								relevant_when: "method != `bytes`"
+								required:      false
+								type: uint: {
+									default: null
+									unit:    "bytes"
+								}
+							}
 						}
 					}
 				}
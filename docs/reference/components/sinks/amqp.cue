@@ -0,0 +1,88 @@
+package metadata
+
+components: sinks: amqp: {
+	title: "AMQP"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      false
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["avro", "csv", "gelf", "json", "logfmt", "ndjson", "protobuf", "text"]
+				}
+				default_framing: "bytes"
+			}
+			request: enabled: false
+			tls: enabled:     false
+			to: components._amqp.features.send.to
+		}
+	}
+
+	support: components._amqp.support
+
+	configuration: {
+		connection_string: components._amqp.configuration.connection_string
+		exchange: {
+			description: "The exchange to publish messages to. The empty string is the default exchange, which routes messages to the queue named by their routing key."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["logs", ""]
+				syntax: "literal"
+			}
+		}
+		routing_key: {
+			common:      true
+			description: "The routing key to publish messages with."
+			required:    false
+			warnings: []
+			type: string: {
+				default: ""
+				examples: ["{{ application }}", "logs.web"]
+				syntax: "template"
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		missing_keys_total:      components.sources.internal_metrics.output.metrics.missing_keys_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:       components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+
+	how_it_works: {
+		publisher_confirms: {
+			title: "Publisher confirms"
+			body: """
+				Messages are published on a channel in [confirm mode](\(urls.rabbitmq_confirms)),
+				and events are only acknowledged once the broker confirmed their
+				message. Up to 100 messages wait for a confirmation at once.
+				Messages the broker rejects, or that aren't confirmed because the
+				connection failed, are published again after reconnecting if
+				needed, up to 5 times in total before their events are reported
+				as not delivered.
+				"""
+		}
+	}
+}
@@ -28,8 +28,9 @@ components: sinks: file: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["ndjson", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "newline_delimited"
 			}
 			request: enabled: false
 			tls: enabled:     false
@@ -34,8 +34,9 @@ components: sinks: gcp_cloud_storage: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["ndjson", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "newline_delimited"
 			}
 			request: {
 				enabled:                    true
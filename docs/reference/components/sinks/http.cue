@@ -34,8 +34,9 @@ components: sinks: http: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "ndjson", "text"]
+					enum: ["avro", "csv", "gelf", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "newline_delimited"
 			}
 			request: {
 				enabled:                    true
@@ -34,8 +34,9 @@ components: sinks: kafka: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "bytes"
 			}
 			request: enabled: false
 			tls: {
@@ -0,0 +1,79 @@
+package metadata
+
+components: sinks: mqtt: {
+	title: "MQTT"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      false
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["avro", "csv", "gelf", "json", "logfmt", "ndjson", "protobuf", "text"]
+				}
+				default_framing: "bytes"
+			}
+			request: enabled: false
+			tls: components._mqtt.features.tls
+			to:  components._mqtt.features.send.to
+		}
+	}
+
+	support: components._mqtt.support
+
+	configuration: {
+		client_id:       components._mqtt.configuration.client_id
+		host:            components._mqtt.configuration.host
+		keep_alive_secs: components._mqtt.configuration.keep_alive_secs
+		password:        components._mqtt.configuration.password
+		port:            components._mqtt.configuration.port
+		qos:             components._mqtt.configuration.qos & {
+			description: "The quality of service level to publish the messages with."
+		}
+		retain: {
+			common:      false
+			description: "Ask the broker to retain the last message of each topic for new subscribers."
+			required:    false
+			warnings: []
+			type: bool: default: false
+		}
+		topic: {
+			description: "The MQTT topic to publish messages to."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector", "devices/{{ host }}/logs"]
+				syntax: "template"
+			}
+		}
+		user: components._mqtt.configuration.user
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		missing_keys_total:      components.sources.internal_metrics.output.metrics.missing_keys_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+		send_errors_total:       components.sources.internal_metrics.output.metrics.send_errors_total
+	}
+
+	how_it_works: components._mqtt.how_it_works
+}
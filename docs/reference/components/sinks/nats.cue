@@ -22,8 +22,9 @@ components: sinks: nats: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "bytes"
 			}
 			request: enabled: false
 			tls: enabled:     false
@@ -0,0 +1,144 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10485760
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
+			encoding: enabled: false
+			request: {
+				enabled:                    true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             5
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               60
+				headers:                    false
+			}
+			tls: {
+				enabled:                true
+				can_enable:             false
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						api: {
+							title: "OpenTelemetry protocol (OTLP)"
+							url:   urls.opentelemetry_otlp
+						}
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		endpoint: {
+			description: "The base URL of the OTLP/HTTP receiver. Logs are posted to its `/v1/logs` path and metrics to its `/v1/metrics` path."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["http://localhost:4318"]
+				syntax: "literal"
+			}
+		}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${HTTP_PASSWORD}"
+			username_example: "${HTTP_USERNAME}"
+		}}
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: false
+			gauge:        true
+			histogram:    true
+			set:          false
+			summary:      false
+		}
+	}
+
+	how_it_works: {
+		log_records: {
+			title: "Log records"
+			body: """
+				Logs are sent as OTLP log records, with the `message` field as
+				their body and the `timestamp` field as their time. The fields
+				set by the [`opentelemetry` source][docs.sources.opentelemetry],
+				such as `severity_text`, `trace_id` or `resources`, are sent as
+				the fields of the record, its resource and its instrumentation
+				scope, so logs received from OpenTelemetry are exported as they
+				were. All other fields are sent as attributes of the record.
+				"""
+		}
+		metric_temporality: {
+			title: "Metric temporality"
+			body: """
+				Incremental metrics are sent with the delta aggregation
+				temporality, and absolute ones with the cumulative aggregation
+				temporality. Counters become monotonic sums and absolute gauges
+				become gauges. As OTLP gauges can't be incremental, incremental
+				gauges become sums that aren't monotonic. Tags are sent as the
+				attributes of the data points, and the namespace of a metric is
+				prefixed to its name, separated by a dot.
+
+				Distributions, sets and summaries are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
+	}
+}
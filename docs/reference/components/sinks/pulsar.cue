@@ -22,8 +22,9 @@ components: sinks: pulsar: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["text", "json"]
+					enum: ["avro", "csv", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "bytes"
 			}
 			request: enabled: false
 			tls: enabled:     false
@@ -0,0 +1,124 @@
+package metadata
+
+components: sinks: redis: {
+	title: "Redis"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1000000
+				max_events:   100
+				timeout_secs: 1
+			}
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["avro", "csv", "gelf", "json", "logfmt", "ndjson", "protobuf", "text"]
+				}
+				default_framing: "bytes"
+			}
+			request: {
+				enabled:                    true
+				concurrency:                5
+				rate_limit_duration_secs:   1
+				rate_limit_num:             9223372036854775807
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               60
+				headers:                    false
+			}
+			tls: enabled: false
+			to: components._redis.features.send.to
+		}
+	}
+
+	support: components._redis.support
+
+	configuration: {
+		data_type: {
+			common:      true
+			description: "The Redis data type to send messages to."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "list"
+				enum: {
+					list:    "Push messages to a list."
+					channel: "Publish messages to a Pub/Sub channel."
+					stream:  "Append messages to a stream with `XADD`, in the `message` field of each entry."
+				}
+				syntax: "literal"
+			}
+		}
+		endpoint: components._redis.configuration.endpoint
+		key: {
+			description: "The name of the list, channel, or stream to send messages to."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector", "logs-{{ application }}"]
+				syntax: "template"
+			}
+		}
+		list: {
+			common:      false
+			description: "Options for the `list` data type."
+			required:    false
+			warnings: []
+			type: object: options: {
+				method: {
+					common:      false
+					description: "The command pushing messages to the list."
+					required:    false
+					warnings: []
+					type: string: {
+						default: "rpush"
+						enum: {
+							lpush: "Push messages to the head of the list with `LPUSH`."
+							rpush: "Push messages to the tail of the list with `RPUSH`."
+						}
+						syntax: "literal"
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	telemetry: metrics: {
+		missing_keys_total:     components.sources.internal_metrics.output.metrics.missing_keys_total
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+
+	how_it_works: {
+		batching: {
+			title: "Batching"
+			body: """
+				Events are batched by key. A batch is sent as one pipeline: a
+				single `LPUSH` or `RPUSH` for lists, and one `PUBLISH` or `XADD`
+				per message for channels and streams.
+				"""
+		}
+	}
+}
@@ -22,8 +22,9 @@ components: sinks: socket: {
 				codec: {
 					enabled: true
 					default: null
-					enum: ["json", "text"]
+					enum: ["avro", "csv", "gelf", "json", "logfmt", "ndjson", "protobuf", "text"]
 				}
+				default_framing: "newline_delimited"
 			}
 			send_buffer_bytes: {
 				enabled:       true
@@ -109,6 +110,21 @@ components: sinks: socket: {
 		metrics: null
 	}
 
+	how_it_works: {
+		gelf: {
+			title: "GELF"
+			body: """
+				With the `gelf` codec, logs are sent as uncompressed
+				[GELF](\(urls.gelf)) messages, with the `message` field as the
+				`short_message` and the `hostname` or `host` field as the `host`,
+				either of which is required. Other fields are sent as additional
+				fields, prefixed with `_`. Unless `framing` is set, messages are
+				sent as whole datagrams over UDP and null delimited otherwise.
+				Messages aren't chunked, so they have to fit in a datagram.
+				"""
+		}
+	}
+
 	telemetry: metrics: {
 		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
 		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
@@ -0,0 +1,238 @@
+package metadata
+
+components: sinks: syslog: {
+	title: "Syslog"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		buffer: enabled:      true
+		healthcheck: enabled: true
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					default: null
+					enum: ["avro", "csv", "gelf", "json", "logfmt", "ndjson", "protobuf", "text"]
+				}
+				default_framing: "octet_counting"
+			}
+			send_buffer_bytes: {
+				enabled:       true
+				relevant_when: "mode = `tcp` or mode = `udp` && os = `unix`"
+			}
+			keepalive: enabled: true
+			request: enabled:   false
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+			to: {
+				service: services.syslog
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp", "udp", "unix"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: {
+		address: {
+			description:   "The address to connect to. The address _must_ include a port."
+			relevant_when: "mode = `tcp` or `udp`"
+			required:      true
+			warnings: []
+			type: string: {
+				examples: ["92.12.333.224:514"]
+				syntax: "literal"
+			}
+		}
+		app_name: {
+			common:      true
+			description: "The name of the application sending the message."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "vector"
+				examples: ["{{ application }}"]
+				syntax: "template"
+			}
+		}
+		facility: {
+			common:      true
+			description: "The facility of the message, given by name, such as `local0`, or by code, such as `16`. Invalid facilities are replaced by `user`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "user"
+				examples: ["local0", "{{ facility }}"]
+				syntax: "template"
+			}
+		}
+		format: {
+			common:      true
+			description: "The syslog message format."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "rfc5424"
+				enum: {
+					rfc5424: "The [RFC 5424](\(urls.syslog_5424)) format."
+					rfc3164: "The [RFC 3164](\(urls.syslog_3164)) format, which has no message ID or structured data."
+				}
+				syntax: "literal"
+			}
+		}
+		hostname: {
+			common:      false
+			description: "The hostname of the message. Defaults to the value of the `host` field, or `-` if the event doesn't have one."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ hostname }}"]
+				syntax: "template"
+			}
+		}
+		mode: {
+			description: "The type of socket to use."
+			required:    true
+			warnings: []
+			type: string: {
+				enum: {
+					tcp:  "TCP socket"
+					udp:  "UDP socket"
+					unix: "Unix domain socket"
+				}
+				syntax: "literal"
+			}
+		}
+		msgid: {
+			common:      false
+			description: "The type of the message, only sent in the RFC 5424 format."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["ID47", "{{ event_type }}"]
+				syntax: "template"
+			}
+		}
+		path: {
+			description:   "The unix socket path. This should be the absolute path."
+			relevant_when: "mode = `unix`"
+			required:      true
+			warnings: []
+			type: string: {
+				examples: ["/dev/log"]
+				syntax: "literal"
+			}
+		}
+		procid: {
+			common:      false
+			description: "The ID of the process sending the message. Defaults to the process ID of Vector."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["{{ pid }}"]
+				syntax: "template"
+			}
+		}
+		severity: {
+			common:      true
+			description: "The severity of the message, given by name, such as `err`, or by code, such as `3`. Invalid severities are replaced by `info`."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "info"
+				examples: ["warning", "{{ level }}"]
+				syntax: "template"
+			}
+		}
+		structured_data: {
+			common:      false
+			description: "The structured data elements of the message, keyed by their ID, each of which is a table of parameters. Only sent in the RFC 5424 format."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						"origin": {
+							"ip": "{{ ip }}"
+						}
+					},
+				]
+				options: {
+					"*": {
+						common:      false
+						description: "A structured data element, whose parameters are templates."
+						required:    false
+						type: object: {
+							examples: []
+							options: {}
+						}
+					}
+				}
+			}
+		}
+	}
+
+	input: {
+		logs:    true
+		metrics: null
+	}
+
+	how_it_works: {
+		framing: {
+			title: "Framing"
+			body: """
+				Unless `framing` is set, messages are sent with octet counting
+				as in [RFC 6587](\(urls.syslog_6587)) over TCP and Unix sockets,
+				and as whole datagrams over UDP. Events missing a field
+				referenced by a template are dropped.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		invalid_priority_total:  components.sources.internal_metrics.output.metrics.invalid_priority_total
+		missing_keys_total:      components.sources.internal_metrics.output.metrics.missing_keys_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
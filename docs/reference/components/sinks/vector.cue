@@ -20,14 +20,35 @@ components: sinks: vector: {
 		buffer: enabled:      true
 		healthcheck: enabled: true
 		send: {
-			compression: enabled: false
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    1048576
+				max_events:   1000
+				timeout_secs: 1
+			}
+			compression: {
+				enabled: true
+				default: "none"
+				algorithms: ["none", "gzip"]
+				levels: ["none", "fast", "default", "best", 0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+			}
 			encoding: {
 				enabled: true
 				codec: enabled: false
 			}
 			send_buffer_bytes: enabled: true
 			keepalive: enabled:         true
-			request: enabled:           false
+			request: {
+				enabled:                    true
+				concurrency:                10
+				rate_limit_duration_secs:   1
+				rate_limit_num:             9223372036854775807
+				retry_initial_backoff_secs: 1
+				retry_max_duration_secs:    10
+				timeout_secs:               30
+				headers:                    false
+			}
 			tls: {
 				enabled:                true
 				can_enable:             true
@@ -41,7 +62,7 @@ components: sinks: vector: {
 				interface: {
 					socket: {
 						direction: "outgoing"
-						protocols: ["tcp"]
+						protocols: ["tcp", "http"]
 						ssl: "optional"
 					}
 				}
@@ -87,6 +108,20 @@ components: sinks: vector: {
 				syntax: "literal"
 			}
 		}
+		version: {
+			common:      true
+			description: "The version of the protocol to speak to the `vector` source, which has to use the same version."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "1"
+				enum: {
+					"1": "Sends length delimited events over TCP. Events in flight are lost if the connection breaks."
+					"2": "Sends batches of events over HTTP, each acknowledged by the source. Batches that aren't acknowledged are retried. Only batching, compression and requests can be configured with this version, while keepalive and the send buffer size only apply to version 1. Setting an option of the other version is an error."
+				}
+				syntax: "literal"
+			}
+		}
 	}
 
 	how_it_works: components.sources.vector.how_it_works
@@ -70,6 +70,76 @@ components: sources: [Name=string]: {
 			}
 		}
 
+		if sources[Name].features.codecs != _|_ {
+			if sources[Name].features.codecs.enabled {
+				framing: {
+					common:      false
+					description: "Configures how the received data is split into frames, each of which is decoded separately."
+					required:    false
+					type: object: options: {
+						method: {
+							description: "The framing method."
+							required:    false
+							type: string: {
+								default: sources[Name].features.codecs.default_framing
+								enum: {
+									bytes:               "All data received at once, such as a whole datagram or message, forms a single frame."
+									newline_delimited:   "Frames are delimited by a newline, and a carriage return before it is trimmed."
+									character_delimited: "Frames are delimited by the character given in `delimiter`."
+									octet_counting:      "Frames are prefixed with their length as in [RFC 6587](\(urls.rfc_6587)). Frames not starting with a length are newline delimited."
+									length_delimited:    "Frames are prefixed with their length as a 4 byte big-endian integer."
+									chunked_gelf:        "Each datagram forms a frame, except for the chunks of [chunked GELF messages](\(urls.gelf_chunking)), which are reassembled into one frame. Messages not fully received within 5 seconds are dropped, as are the chunks of further messages while 1000 are being reassembled."
+								}
+								syntax: "literal"
+							}
+						}
+						delimiter: {
+							description:   "The ASCII character delimiting the frames."
+							relevant_when: "method = `character_delimited`"
+							required:      true
+							type: string: {
+								examples: [";", "|"]
+								syntax: "literal"
+							}
+						}
+						max_length: {
+							common:        false
+							description:   "The maximum length of a frame in bytes. Longer frames are dropped. Defaults to the `max_length` option of sources having one, and to 1 MiB otherwise."
+							relevant_when: "method != `bytes`"
+							required:      false
+							type: uint: {
+								default: null
+								unit:    "bytes"
+							}
+						}
+					}
+				}
+
+				decoding: {
+					common:      false
+					description: "Configures how each frame is parsed into events. Frames that can't be parsed are dropped."
+					required:    false
+					type: object: options: {
+						codec: {
+							description: "The codec used to parse the frames."
+							required:    false
+							type: string: {
+								default: "bytes"
+								enum: {
+									bytes:    "The frame is used as the `message` of the event as is."
+									json:     "The frame is a JSON object, or an array of them, whose fields become the fields of the events."
+									syslog:   "The frame is a syslog message in [RFC 5424](\(urls.syslog_5424)) or [RFC 3164](\(urls.syslog_3164)) format, which is parsed like the `syslog` source does."
+									protobuf: "The frame is an event encoded with Vector's protobuf schema, as sent by the `vector` sink."
+									gelf:     "The frame is a [GELF](\(urls.gelf)) message, optionally compressed with gzip or zlib, in which case it is dropped if it decompresses to more than the `max_length` of the framing, or 1 MiB. Messages missing the `version`, `host` or `short_message` fields are dropped. The `short_message` becomes the `message` of the event, the `host` is kept as `hostname`, and additional fields lose their `_` prefix."
+								}
+								syntax: "literal"
+							}
+						}
+					}
+				}
+			}
+		}
+
 		if sources[Name].features.encoding != _|_ {
 			if sources[Name].features.encoding.enabled {
 				encoding: {
@@ -0,0 +1,150 @@
+package metadata
+
+components: sources: amqp: {
+	title: "AMQP"
+
+	description: """
+		Consumes messages from an AMQP 0.9.1 queue, such as on RabbitMQ.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: enabled:        false
+			from: components._amqp.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._amqp.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only acknowledge a message once its events have been delivered by all sinks. Messages whose events failed to be delivered are returned to the queue, or rejected if a sink rejected them."
+			required:    false
+			type: bool: default: false
+		}
+		connection_string: components._amqp.configuration.connection_string
+		consumer_tag: {
+			common:      false
+			description: "The tag identifying the consumer on the channel."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "vector"
+				syntax:  "literal"
+			}
+		}
+		exchange_key: {
+			common:      false
+			description: "The log field name to store the exchange the message was published to in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "exchange"
+				syntax:  "literal"
+			}
+		}
+		prefetch_count: {
+			common:      false
+			description: "The maximum number of messages that the broker delivers before they are acknowledged."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 100
+				unit:    null
+			}
+		}
+		queue: {
+			description: "The name of the queue to consume from."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		routing_key_field: {
+			common:      false
+			description: "The log field name to store the routing key of the message in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "routing_key"
+				syntax:  "literal"
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual AMQP message"
+		fields: {
+			exchange: {
+				description: "The exchange the message was published to, which is empty for the default exchange."
+				required:    true
+				type: string: {
+					examples: ["logs"]
+					syntax: "literal"
+				}
+			}
+			message: {
+				description: "The body of the AMQP message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			routing_key: {
+				description: "The routing key the message was published with."
+				required:    true
+				type: string: {
+					examples: ["app.web"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The `timestamp` property of the message or the current time if it's not set."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		acknowledgement_errors_total: components.sources.internal_metrics.output.metrics.acknowledgement_errors_total
+		connection_errors_total:      components.sources.internal_metrics.output.metrics.connection_errors_total
+		delivery_failed_total:        components.sources.internal_metrics.output.metrics.delivery_failed_total
+		processed_bytes_total:        components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:       components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body: """
+				Messages are acknowledged manually, once their events are sent
+				to the next components, or once they are delivered by all sinks
+				if `acknowledgements` is enabled. The broker redelivers the
+				messages that aren't acknowledged when the connection is lost,
+				and `prefetch_count` bounds how many of them are in flight.
+				"""
+		}
+	}
+}
@@ -88,6 +88,7 @@ components: sources: apache_metrics: {
 				syntax:  "literal"
 			}
 		}
+		proxy: components._proxy
 	}
 
 	output: metrics: {
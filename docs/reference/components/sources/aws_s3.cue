@@ -47,6 +47,12 @@ components: sources: aws_s3: components._aws & {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Wait for the events of an SQS message to be delivered by all sinks before deleting the message. Messages with events that failed to be delivered are left in the queue and retried once their visibility timeout expires."
+			required:    false
+			type: bool: default: false
+		}
 		strategy: {
 			common:      false
 			description: "The strategy to use to consume objects from AWS S3."
@@ -94,9 +100,9 @@ components: sources: aws_s3: components._aws & {
 					}
 					visibility_timeout_secs: {
 						common:      false
-						description: "The visibility timeout to use for messages in secords. This controls how long a message is left unavailable when a Vector receives it. If a `vector` does not delete the message before the timeout expires, it will be made reavailable for another consumer; this can happen if, for example, the `vector` process crashes."
+						description: "The visibility timeout to use for messages in secords. This controls how long a message is left unavailable when a Vector receives it. If a `vector` does not delete the message before the timeout expires, it will be made reavailable for another consumer; this can happen if, for example, the `vector` process crashes. The timeout is extended every half of it while the message is being processed."
 						required:    false
-						warnings: ["Should be set higher than the length of time it takes to process an individual message to avoid that message being reprocessed."]
+						warnings: []
 						type: uint: {
 							default: 300
 							unit:    "seconds"
@@ -121,6 +127,7 @@ components: sources: aws_s3: components._aws & {
 				}
 			}
 		}
+		proxy: components._proxy
 	}
 
 	output: logs: object: {
@@ -265,6 +272,10 @@ components: sources: aws_s3: components._aws & {
 					_action:       "ReceiveMessage"
 					required_when: "[`strategy`](#strategy) is set to `sqs`"
 				},
+				{
+					_action:       "ChangeMessageVisibility"
+					required_when: "[`strategy`](#strategy) is set to `sqs`"
+				},
 				{
 					_action:       "DeleteMessage"
 					required_when: "[`strategy`](#strategy) is set to `sqs` and [`delete_message`](#delete_message) is set to `true`"
@@ -274,13 +285,15 @@ components: sources: aws_s3: components._aws & {
 	]
 
 	telemetry: metrics: {
-		sqs_message_delete_failed_total:        components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
-		sqs_message_delete_succeeded_total:     components.sources.internal_metrics.output.metrics.sqs_message_delete_succeeded_total
-		sqs_message_processing_failed_total:    components.sources.internal_metrics.output.metrics.sqs_message_processing_failed_total
-		sqs_message_processing_succeeded_total: components.sources.internal_metrics.output.metrics.sqs_message_processing_succeeded_total
-		sqs_message_receive_failed_total:       components.sources.internal_metrics.output.metrics.sqs_message_receive_failed_total
-		sqs_message_receive_succeeded_total:    components.sources.internal_metrics.output.metrics.sqs_message_receive_succeeded_total
-		sqs_message_received_messages_total:    components.sources.internal_metrics.output.metrics.sqs_message_received_messages_total
-		sqs_s3_event_record_ignored_total:      components.sources.internal_metrics.output.metrics.sqs_s3_event_record_ignored_total
+		sqs_message_delete_failed_total:            components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
+		sqs_message_delete_succeeded_total:         components.sources.internal_metrics.output.metrics.sqs_message_delete_succeeded_total
+		sqs_message_delivery_failed_total:          components.sources.internal_metrics.output.metrics.sqs_message_delivery_failed_total
+		sqs_message_processing_failed_total:        components.sources.internal_metrics.output.metrics.sqs_message_processing_failed_total
+		sqs_message_processing_succeeded_total:     components.sources.internal_metrics.output.metrics.sqs_message_processing_succeeded_total
+		sqs_message_receive_failed_total:           components.sources.internal_metrics.output.metrics.sqs_message_receive_failed_total
+		sqs_message_receive_succeeded_total:        components.sources.internal_metrics.output.metrics.sqs_message_receive_succeeded_total
+		sqs_message_received_messages_total:        components.sources.internal_metrics.output.metrics.sqs_message_received_messages_total
+		sqs_message_visibility_extend_failed_total: components.sources.internal_metrics.output.metrics.sqs_message_visibility_extend_failed_total
+		sqs_s3_event_record_ignored_total:          components.sources.internal_metrics.output.metrics.sqs_s3_event_record_ignored_total
 	}
 }
@@ -0,0 +1,219 @@
+package metadata
+
+components: sources: aws_sqs: components._aws & {
+	title: "AWS SQS"
+
+	features: {
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+		collect: {
+			tls: enabled:        false
+			checkpoint: enabled: false
+			from: service:       services.aws_sqs
+		}
+	}
+
+	classes: {
+		commonly_used: true
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Wait for the events of an SQS message to be delivered by all sinks before deleting the message. Messages with events that failed to be delivered are left in the queue and retried once their visibility timeout expires."
+			required:    false
+			type: bool: default: false
+		}
+		queue_url: {
+			description: "The URL of the SQS queue to receive messages from."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["https://sqs.us-east-2.amazonaws.com/123456789012/MyQueue"]
+				syntax: "literal"
+			}
+		}
+		poll_secs: {
+			common:      true
+			description: "How long to wait for messages when polling the queue, at most 20 seconds. Polling returns as soon as messages are available."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		visibility_timeout_secs: {
+			common:      false
+			description: "The visibility timeout to use for messages. This controls how long a message is left unavailable to other consumers once Vector receives it. It's extended while the events of the message are being processed, so that a message is only made available again if Vector stops, e.g. if it crashes."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 300
+				unit:    "seconds"
+			}
+		}
+		delete_message: {
+			common:      true
+			description: "Whether to delete the message once Vector processes it. It can be useful to set this to `false` to debug or during initial Vector setup."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		client_concurrency: {
+			common:      false
+			description: "How many clients receive messages from the queue concurrently."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 1
+				unit:    null
+			}
+		}
+		proxy: components._proxy
+	}
+
+	output: logs: event: {
+		description: "An event decoded from the body of an SQS message."
+		fields: {
+			message: {
+				description: "The frame the event was decoded from, with the `bytes` codec."
+				required:    false
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			message_id: {
+				description: "The ID of the SQS message."
+				required:    true
+				type: string: {
+					examples: ["5fea7756-0ea4-451a-a703-a558b933e274"]
+					syntax: "literal"
+				}
+			}
+			message_attributes: {
+				description: "The message attributes of the SQS message. `String` and `Number` attributes are strings, `Binary` ones are bytes."
+				required:    false
+				type: object: {
+					examples: [{"team": "infra"}]
+					options: {}
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time the SQS message was sent."
+			}
+		}
+	}
+
+	examples: [
+		{
+			_body: """
+				{"service": "api", "status": "ok"}
+				"""
+			title: "JSON message"
+			configuration: {
+				queue_url: "https://sqs.us-east-2.amazonaws.com/123456789012/MyQueue"
+				decoding: codec: "json"
+			}
+			input: """
+				```json
+				\( _body )
+				```
+				"""
+			output: log: {
+				message_id:         "5fea7756-0ea4-451a-a703-a558b933e274"
+				message_attributes: team: "infra"
+				service:            "api"
+				status:             "ok"
+				timestamp:          _values.current_timestamp
+			}
+		},
+	]
+
+	how_it_works: {
+		decoding: {
+			title: "Decoding"
+			body: """
+				The whole body of a message is decoded with the `decoding`
+				codec by default, so a message holds a single event. Messages
+				holding several lines are split into one event per line by
+				setting `framing.method` to `newline_delimited`.
+				"""
+		}
+		visibility_timeout: {
+			title: "Visibility timeout"
+			body: """
+				A message received by Vector isn't visible to other consumers of
+				the queue for `visibility_timeout_secs` seconds. The messages
+				received together are handled concurrently, and Vector extends
+				the timeout of each one every half of it until its events are
+				processed, or delivered if `acknowledgements` is enabled, and
+				then deletes the message. A message whose events couldn't
+				be delivered is left in the queue, and received again once its
+				visibility timeout expires.
+				"""
+		}
+	}
+
+	permissions: iam: [
+		{
+			platform:  "aws"
+			_service:  "sqs"
+			_docs_tag: "AWSSimpleQueueService"
+
+			policies: [
+				{
+					_action: "ReceiveMessage"
+				},
+				{
+					_action: "ChangeMessageVisibility"
+				},
+				{
+					_action:       "DeleteMessage"
+					required_when: "[`delete_message`](#delete_message) is set to `true`"
+				},
+			]
+		},
+	]
+
+	telemetry: metrics: {
+		processed_bytes_total:                      components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                     components.sources.internal_metrics.output.metrics.processed_events_total
+		sqs_message_delete_failed_total:            components.sources.internal_metrics.output.metrics.sqs_message_delete_failed_total
+		sqs_message_delete_succeeded_total:         components.sources.internal_metrics.output.metrics.sqs_message_delete_succeeded_total
+		sqs_message_delivery_failed_total:          components.sources.internal_metrics.output.metrics.sqs_message_delivery_failed_total
+		sqs_message_receive_failed_total:           components.sources.internal_metrics.output.metrics.sqs_message_receive_failed_total
+		sqs_message_receive_succeeded_total:        components.sources.internal_metrics.output.metrics.sqs_message_receive_succeeded_total
+		sqs_message_received_messages_total:        components.sources.internal_metrics.output.metrics.sqs_message_received_messages_total
+		sqs_message_visibility_extend_failed_total: components.sources.internal_metrics.output.metrics.sqs_message_visibility_extend_failed_total
+	}
+}
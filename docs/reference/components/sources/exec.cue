@@ -0,0 +1,277 @@
+package metadata
+
+components: sources: exec: {
+	title: "Exec"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
+		collect: checkpoint: enabled: false
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		command: {
+			description: "The command to run, as the program followed by its arguments."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["echo", "Hello World!"]
+				syntax: "literal"
+			}
+		}
+		mode: {
+			common:      true
+			description: "How the command is run."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "scheduled"
+				enum: {
+					scheduled: "Run the command every `scheduled.exec_interval_secs` seconds."
+					streaming: "Keep the command running, and respawn it when it exits."
+				}
+			}
+		}
+		scheduled: {
+			common:      true
+			description: "The options of the `scheduled` mode."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					exec_interval_secs: {
+						common:      true
+						description: "How often the command is run. If a run takes longer, the next one starts as soon as it's over."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+					max_buffered_bytes: {
+						common:      false
+						description: "The maximum amount of output buffered during a run. If it's exceeded, the command is killed and the events read so far are sent without an exit code."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 10485760
+							unit:    "bytes"
+						}
+					}
+					timeout_secs: {
+						common:      false
+						description: "How long a run may take. If it takes longer, the command is killed and the events read so far are sent without an exit code. Defaults to `exec_interval_secs`."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: null
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+		streaming: {
+			common:      false
+			description: "The options of the `streaming` mode."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					respawn_on_exit: {
+						common:      false
+						description: "Whether the command is respawned when it exits. Otherwise the source stops."
+						required:    false
+						warnings: []
+						type: bool: default: true
+					}
+					respawn_max_delay_secs: {
+						common:      false
+						description: "The maximum delay before respawning the command. The delay starts at half a second and doubles each time the command exits, and is reset once the command has run for this long."
+						required:    false
+						warnings: []
+						type: uint: {
+							default: 60
+							unit:    "seconds"
+						}
+					}
+				}
+			}
+		}
+		working_directory: {
+			common:      false
+			description: "The directory the command is run in. Defaults to the working directory of Vector."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["/var/lib/vector"]
+				syntax: "literal"
+			}
+		}
+		include_stderr: {
+			common:      false
+			description: "Whether events are read from the standard error of the command as well as its standard output."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		host_key: {
+			category:    "Context"
+			common:      false
+			description: "The key name added to each event representing the current host. This can also be globally set via the [global `host_key` option][docs.reference.configuration.global-options#host_key]."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "host"
+				syntax:  "literal"
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum bytes size of a message before rest of it will be discarded. Only used if `framing` isn't set."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 102400
+				unit:    "bytes"
+			}
+		}
+	}
+
+	output: logs: line: {
+		description: "An individual event from the output of the command."
+		fields: {
+			command: {
+				description: "The command that was run."
+				required:    true
+				type: array: items: type: string: {
+					examples: ["echo", "Hello World!"]
+					syntax: "literal"
+				}
+			}
+			exit_code: {
+				description: "The exit code of the command. Only set in the `scheduled` mode, and if the command wasn't killed by a signal."
+				required:    false
+				type: uint: {
+					examples: [0, 1]
+					unit: null
+				}
+			}
+			host:    fields._local_host
+			message: fields._raw_line
+			pid: {
+				description: "The process ID of the command."
+				required:    true
+				type: uint: {
+					examples: [60085]
+					unit: null
+				}
+			}
+			stream: {
+				description: "The output the event was read from."
+				required:    true
+				type: string: {
+					enum: {
+						stdout: "The standard output of the command."
+						stderr: "The standard error of the command."
+					}
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	examples: [
+		{
+			_line: "Hello World!"
+			title: "Scheduled command"
+			configuration: {
+				command: ["echo", "Hello World!"]
+				mode: "scheduled"
+			}
+			input: """
+				```text
+				\( _line )
+				```
+				"""
+			output: log: {
+				command: ["echo", "Hello World!"]
+				exit_code: 0
+				host:      _values.local_host
+				message:   _line
+				pid:       60085
+				stream:    "stdout"
+				timestamp: _values.current_timestamp
+			}
+		},
+	]
+
+	how_it_works: {
+		modes: {
+			title: "Modes"
+			body: """
+				In the `scheduled` mode, the command is run every
+				`scheduled.exec_interval_secs` seconds, and the events read from
+				its output are sent once it exits, along with its exit code.
+
+				In the `streaming` mode, the command is kept running and the
+				events read from its output are sent as they come. When the
+				command exits it's respawned, after a delay that grows each time
+				it exits early, unless `streaming.respawn_on_exit` is false.
+
+				The command is killed when Vector shuts down.
+				"""
+		}
+		line_delimiters: {
+			title: "Line Delimiters"
+			body: """
+				Each line is read until a new line delimiter, the `0xA` byte, is
+				found, unless `framing` is set.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		command_executed_total:                 components.sources.internal_metrics.output.metrics.command_executed_total
+		command_execution_duration_nanoseconds: components.sources.internal_metrics.output.metrics.command_execution_duration_nanoseconds
+		command_executions_failed_total:        components.sources.internal_metrics.output.metrics.command_executions_failed_total
+		processed_bytes_total:                  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:                 components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
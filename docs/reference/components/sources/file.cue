@@ -50,6 +50,12 @@ components: sources: file: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only checkpoint a line once its event has been delivered by all sinks. Permanently rejected lines are skipped, while lines that errored are read again after a restart."
+			required:    false
+			type: bool: default: false
+		}
 		exclude: {
 			common:      false
 			description: "Array of file patterns to exclude. [Globbing](#globbing) is supported.*Takes precedence over the [`include` option](#include).*"
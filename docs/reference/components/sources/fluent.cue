@@ -0,0 +1,167 @@
+package metadata
+
+components: sources: fluent: {
+	_port: 24224
+
+	title: "Fluent"
+
+	description: """
+		Receives logs from Fluentd and Fluent Bit over the Fluent Forward
+		protocol.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator"]
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.fluent
+
+				interface: socket: {
+					api: {
+						title: "Fluent Forward protocol"
+						url:   urls.fluent_forward_protocol
+					}
+					direction: "incoming"
+					port:      _port
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+			receive_buffer_bytes: enabled: true
+			keepalive: enabled:            true
+			tls: {
+				enabled:                true
+				can_enable:             true
+				can_verify_certificate: true
+				enabled_default:        false
+			}
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only acknowledge a message once its entries have been delivered by all sinks, so that messages that failed to be delivered are sent again. Only applies to messages asking for an acknowledgement with the `chunk` option, as sent when `require_ack_response` is enabled in Fluentd or Fluent Bit."
+			required:    false
+			type: bool: default: false
+		}
+		address: {
+			description: "The TCP address to listen for connections on, or `systemd#N` to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#1"]
+				syntax: "literal"
+			}
+		}
+		max_length: {
+			common:      false
+			description: "The maximum bytes size of a message, including all of its entries, before it is discarded. Entries compressed with gzip are also discarded if they decompress to more than this."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 16777216
+				unit:    "bytes"
+			}
+		}
+		shutdown_timeout_secs: {
+			common:      false
+			description: "The timeout before a connection is forcefully closed during shutdown."
+			required:    false
+			warnings: []
+			type: uint: {
+				default: 30
+				unit:    "seconds"
+			}
+		}
+	}
+
+	output: logs: entry: {
+		description: "An entry of a Fluent message."
+		fields: {
+			host: {
+				description: "The IP address of the client that sent the message."
+				required:    true
+				type: string: {
+					examples: ["127.0.0.1"]
+					syntax: "literal"
+				}
+			}
+			tag: {
+				description: "The tag of the message."
+				required:    true
+				type: string: {
+					examples: ["dummy.0"]
+					syntax: "literal"
+				}
+			}
+			timestamp: {
+				description: "The time of the entry, with nanoseconds when it was sent as an `EventTime`."
+				required:    true
+				type: timestamp: {}
+			}
+			"*": {
+				description: "The fields of the record of the entry, with their MessagePack types."
+				required:    true
+				type: "*": {}
+			}
+		}
+	}
+
+	how_it_works: {
+		forward_modes: {
+			title: "Forward modes"
+			body: """
+				All modes of the [Forward protocol](\(urls.fluent_forward_protocol))
+				are supported: single entries in Message mode, arrays of entries
+				in Forward mode, and MessagePack encoded entries in PackedForward
+				mode, which may be compressed with gzip (CompressedPackedForward).
+				Each entry becomes an event. Entries and messages that can't be
+				decoded are dropped.
+				"""
+		}
+		message_acknowledgement: {
+			title: "Message Acknowledgement"
+			body: """
+				Messages with a `chunk` option are acknowledged once their entries
+				are accepted, or once they are delivered if `acknowledgements` is
+				enabled. Clients resend messages that aren't acknowledged.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		parse_errors_total:     components.sources.internal_metrics.output.metrics.parse_errors_total
+		processed_bytes_total:  components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total: components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+}
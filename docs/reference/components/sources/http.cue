@@ -16,6 +16,10 @@ components: sources: http: {
 
 	features: {
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		receive: {
 			from: {
 				service: services.http
@@ -70,7 +74,7 @@ components: sources: http: {
 		}
 		encoding: {
 			common:      true
-			description: "The expected encoding of received data. Note that for `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields."
+			description: "The expected encoding of received data. Note that for `json` and `ndjson` encodings, the fields of the JSON objects are output as separate fields. Ignored if `framing` or `decoding` is set."
 			required:    false
 			type: string: {
 				default: "text"
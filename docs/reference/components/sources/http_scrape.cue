@@ -0,0 +1,207 @@
+package metadata
+
+components: sources: http_scrape: {
+	title: "HTTP Scrape"
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["daemon", "sidecar", "aggregator"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: {
+					name:     "HTTP"
+					thing:    "an \(name) server"
+					url:      urls.http_server
+					versions: null
+				}
+
+				interface: socket: {
+					direction: "outgoing"
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		endpoints: {
+			description: "The endpoints to scrape."
+			required:    true
+			type: array: {
+				items: type: string: {
+					examples: ["http://localhost:8080/status", "https://example.com/api/v1/status?verbose=true"]
+					syntax: "literal"
+				}
+			}
+		}
+		scrape_interval_secs: {
+			description: "The interval between scrapes."
+			common:      true
+			required:    false
+			type: uint: {
+				default: 15
+				unit:    "seconds"
+			}
+		}
+		query: {
+			common:      false
+			description: "Query parameters added to those of the endpoints. Their values are rendered at each scrape, so they can hold strftime specifiers, but they can't reference event fields."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						"since":  "%Y-%m-%d"
+						"format": "json"
+					},
+				]
+				options: {}
+			}
+		}
+		headers: {
+			common:      false
+			description: "Headers added to the requests."
+			required:    false
+			warnings: []
+			type: object: {
+				examples: [
+					{
+						"X-API-Key": "${API_KEY}"
+					},
+				]
+				options: {}
+			}
+		}
+		tls: configuration._tls_connect & {_args: {
+			can_enable:             true
+			can_verify_certificate: true
+			can_verify_hostname:    true
+			enabled_default:        false
+		}}
+		auth: configuration._http_auth & {_args: {
+			password_example: "${HTTP_PASSWORD}"
+			username_example: "${HTTP_USERNAME}"
+		}}
+		proxy: components._proxy
+	}
+
+	output: logs: event: {
+		description: "An event decoded from the body of a response."
+		fields: {
+			endpoint: {
+				description: "The endpoint the event was scraped from, as configured."
+				required:    true
+				type: string: {
+					examples: ["http://localhost:8080/status"]
+					syntax: "literal"
+				}
+			}
+			message: {
+				description: "The frame the event was decoded from, with the `bytes` codec."
+				required:    false
+				type: string: {
+					examples: ["status=ok"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	examples: [
+		{
+			_body: """
+				[{"service": "api", "status": "ok"}, {"service": "db", "status": "degraded"}]
+				"""
+			title: "JSON document"
+			configuration: {
+				endpoints: ["http://localhost:8080/status"]
+				decoding: codec: "json"
+			}
+			input: """
+				```json
+				\( _body )
+				```
+				"""
+			output: [
+				{log: {
+					endpoint:  "http://localhost:8080/status"
+					service:   "api"
+					status:    "ok"
+					timestamp: _values.current_timestamp
+				}},
+				{log: {
+					endpoint:  "http://localhost:8080/status"
+					service:   "db"
+					status:    "degraded"
+					timestamp: _values.current_timestamp
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		decoding: {
+			title: "Decoding"
+			body: """
+				The whole body of a response is decoded with the `decoding`
+				codec by default. JSON documents are decoded with the `json`
+				codec, whose events are the object or each of the objects of an
+				array. NDJSON is decoded by setting `framing.method` to
+				`newline_delimited` as well, and lines of text by only setting
+				it.
+				"""
+		}
+		query_templates: {
+			title: "Templated query parameters"
+			body: """
+				The values of `query` are rendered before each scrape, so they
+				can hold the current time, e.g. `%Y-%m-%d`. They are added to the
+				query of each endpoint, after being URL encoded.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		http_error_response_total:    components.sources.internal_metrics.output.metrics.http_error_response_total
+		http_request_errors_total:    components.sources.internal_metrics.output.metrics.http_request_errors_total
+		processed_bytes_total:        components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:       components.sources.internal_metrics.output.metrics.processed_events_total
+		request_duration_nanoseconds: components.sources.internal_metrics.output.metrics.request_duration_nanoseconds
+		requests_completed_total:     components.sources.internal_metrics.output.metrics.requests_completed_total
+	}
+}
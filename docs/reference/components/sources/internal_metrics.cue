@@ -142,6 +142,12 @@ components: sources: internal_metrics: {
 
 		// Metrics emitted by one or more components
 		// Reusable metric definitions
+		acknowledgement_errors_total: {
+			description:       "The total number of errors acknowledging messages to the broker."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		adaptive_concurrency_averaged_rtt: {
 			description:       "The average round-trip time (RTT) from the HTTP sink across the current window."
 			type:              "histogram"
@@ -186,6 +192,24 @@ components: sources: internal_metrics: {
 				file: _file
 			}
 		}
+		command_executed_total: {
+			description:       "The total number of times a command has been executed."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		command_execution_duration_nanoseconds: {
+			description:       "The command execution duration in nanoseconds."
+			type:              "histogram"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		command_executions_failed_total: {
+			description:       "The total number of errors running commands or reading their output."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		collect_completed_total: {
 			description:       "The total number of metrics collections completed for this component."
 			type:              "counter"
@@ -198,6 +222,48 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _internal_metrics_tags
 		}
+		buffer_byte_size: {
+			description:       "The estimated number of bytes of the events currently held in the sink's buffer, updated every second."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_discarded_events_total: {
+			description:       "The total number of events dropped by the sink's buffer because it was full."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_events: {
+			description:       "The number of events currently held in the sink's buffer, updated every second."
+			type:              "gauge"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_received_bytes_total: {
+			description:       "The estimated total number of bytes written into the sink's buffer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_received_events_total: {
+			description:       "The total number of events written into the sink's buffer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_sent_bytes_total: {
+			description:       "The estimated total number of bytes read from the sink's buffer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		buffer_sent_events_total: {
+			description:       "The total number of events read from the sink's buffer."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		communication_errors_total: {
 			description:       "The total number of errors stemming from communication with the Docker daemon."
 			type:              "counter"
@@ -224,6 +290,24 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		dead_letter_events_dropped_total: {
+			description:       "The total number of events rejected by a sink that could not be sent to its dead letter output, because it was closed or the sink stopped."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		dead_letter_events_total: {
+			description:       "The total number of events rejected by a sink and sent to its dead letter output."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
+		delivery_failed_total: {
+			description:       "The total number of Kafka and AMQP messages whose events could not be delivered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		container_processed_events_total: {
 			description:       "The total number of container events processed."
 			type:              "counter"
@@ -380,6 +464,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		invalid_priority_total: {
+			description:       "The total number of syslog facilities or severities that were invalid and replaced by the default."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		invalid_record_total: {
 			description:       "The total number of invalid records that have been discarded."
 			type:              "counter"
@@ -505,6 +595,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sqs_message_delivery_failed_total: {
+			description:       "The total number of SQS messages whose events could not be delivered."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		sqs_message_processing_failed_total: {
 			description:       "The total number of failures to process SQS messages."
 			type:              "counter"
@@ -535,6 +631,12 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		sqs_message_visibility_extend_failed_total: {
+			description:       "The total number of failures to extend the visibility timeout of SQS messages."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags
+		}
 		sqs_s3_event_record_ignored_total: {
 			description:       "The total number of times an S3 record in an SQS message was ignored (for an event that was not `ObjectCreated`)."
 			type:              "counter"
@@ -49,6 +49,12 @@ components: sources: journald: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only update the journal cursor once the events of a batch have been delivered by all sinks. Batches with permanently rejected events are skipped, while a batch that errored stops further updates, so that it is read again on restart."
+			required:    false
+			type: bool: default: false
+		}
 		batch_size: {
 			common:      false
 			description: "The systemd journal is read in batches, and a checkpoint is set at the end of each batch. This option limits the size of the batch."
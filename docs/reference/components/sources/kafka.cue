@@ -16,6 +16,10 @@ components: sources: kafka: {
 			from: components._kafka.features.collect.from
 		}
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
 	}
 
 	classes: {
@@ -34,6 +38,12 @@ components: sources: kafka: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only commit the offset of a message once its event has been delivered by all sinks. Permanently rejected messages are skipped, while a message that errored stops further commits for its partition, so that it is consumed again on restart."
+			required:    false
+			type: bool: default: false
+		}
 		auto_offset_reset: {
 			common:      false
 			description: "If offsets for consumer group do not exist, set them using this strategy. [librdkafka documentation][urls.librdkafka_config] for `auto.offset.reset` option for explanation."
@@ -239,6 +249,7 @@ components: sources: kafka: {
 
 	telemetry: metrics: {
 		consumer_offset_updates_failed_total: components.sources.internal_metrics.output.metrics.consumer_offset_updates_failed_total
+		delivery_failed_total:                components.sources.internal_metrics.output.metrics.delivery_failed_total
 		events_failed_total:                  components.sources.internal_metrics.output.metrics.events_failed_total
 		processed_bytes_total:                components.sources.internal_metrics.output.metrics.processed_bytes_total
 		processed_events_total:               components.sources.internal_metrics.output.metrics.processed_events_total
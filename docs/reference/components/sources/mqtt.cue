@@ -0,0 +1,114 @@
+package metadata
+
+components: sources: mqtt: {
+	title: "MQTT"
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls:  components._mqtt.features.tls
+			from: components._mqtt.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._mqtt.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		clean_session: {
+			common:      false
+			description: "Start a new session on each connection. Disable it to keep the subscriptions and the messages queued by the broker while Vector is disconnected, which requires a fixed `client_id`."
+			required:    false
+			warnings: []
+			type: bool: default: true
+		}
+		client_id:       components._mqtt.configuration.client_id
+		host:            components._mqtt.configuration.host
+		keep_alive_secs: components._mqtt.configuration.keep_alive_secs
+		password:        components._mqtt.configuration.password
+		port:            components._mqtt.configuration.port
+		qos:             components._mqtt.configuration.qos & {
+			description: "The maximum quality of service level to subscribe with. The broker delivers messages with the lower of this level and the level they were published with."
+		}
+		topic_key: {
+			common:      false
+			description: "The log field name to store the topic of the message in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "topic"
+				examples: ["topic", "mqtt_topic"]
+				syntax: "literal"
+			}
+		}
+		topics: {
+			description: "The topic filters to subscribe to, which may contain the `+` and `#` wildcards."
+			required:    true
+			warnings: []
+			type: array: items: type: string: {
+				examples: ["sensors/+/temperature", "vector/#"]
+				syntax: "literal"
+			}
+		}
+		user: components._mqtt.configuration.user
+	}
+
+	output: logs: record: {
+		description: "An individual MQTT message"
+		fields: {
+			message: {
+				description: "The payload of the MQTT message."
+				required:    true
+				type: string: {
+					examples: ["{\"temperature\": 21.5}"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp
+			topic: {
+				description: "The topic the message was published to."
+				required:    true
+				type: string: {
+					examples: ["sensors/kitchen/temperature"]
+					syntax: "literal"
+				}
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		connection_errors_total: components.sources.internal_metrics.output.metrics.connection_errors_total
+		processed_bytes_total:   components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:  components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+
+	how_it_works: components._mqtt.how_it_works & {
+		sessions: {
+			title: "Sessions"
+			body: """
+				Vector reconnects to the broker after connection failures. With
+				`clean_session` enabled, the subscriptions are made again after
+				each connection and the messages published while disconnected
+				are lost. Persistent sessions keep both on the broker, which
+				requires the same `client_id` on each connection.
+				"""
+		}
+	}
+}
@@ -0,0 +1,241 @@
+package metadata
+
+components: sources: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Receives logs and metrics from OpenTelemetry SDKs and collectors over
+		the OpenTelemetry protocol (OTLP), with gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		deployment_roles: ["aggregator", "daemon", "sidecar"]
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      false
+	}
+
+	features: {
+		multiline: enabled: false
+		receive: {
+			from: {
+				service: services.opentelemetry
+
+				interface: socket: {
+					api: {
+						title: "OpenTelemetry protocol"
+						url:   urls.opentelemetry_otlp
+					}
+					direction: "incoming"
+					port:      4317
+					protocols: ["http"]
+					ssl: "optional"
+				}
+			}
+			tls: enabled: false
+		}
+	}
+
+	support: {
+		targets: {
+			"aarch64-unknown-linux-gnu":      true
+			"aarch64-unknown-linux-musl":     true
+			"armv7-unknown-linux-gnueabihf":  true
+			"armv7-unknown-linux-musleabihf": true
+			"x86_64-apple-darwin":            true
+			"x86_64-pc-windows-msv":          true
+			"x86_64-unknown-linux-gnu":       true
+			"x86_64-unknown-linux-musl":      true
+		}
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		grpc: {
+			common:      true
+			description: "Configures the gRPC server, accepting the `Export` calls of the OTLP logs and metrics services. At least one of `grpc` or `http` must be configured."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to accept gRPC connections on. The address _must_ include a port."
+					required:    true
+					type: string: {
+						examples: ["0.0.0.0:4317"]
+						syntax: "literal"
+					}
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+		http: {
+			common:      true
+			description: "Configures the HTTP server, accepting protobuf encoded requests posted to `/v1/logs` and `/v1/metrics`. At least one of `grpc` or `http` must be configured."
+			required:    false
+			type: object: options: {
+				address: {
+					description: "The address to accept HTTP connections on. The address _must_ include a port."
+					required:    true
+					type: string: {
+						examples: ["0.0.0.0:4318"]
+						syntax: "literal"
+					}
+				}
+				tls: configuration._tls_accept & {_args: {
+					can_enable:             true
+					can_verify_certificate: true
+					enabled_default:        false
+				}}
+			}
+		}
+	}
+
+	output: {
+		logs: record: {
+			description: "An OpenTelemetry log record."
+			fields: {
+				message: {
+					description: "The body of the log record. Structured bodies are kept as objects or arrays."
+					required:    false
+					common:      true
+					type: "*": {}
+				}
+				timestamp: {
+					description: "The time the event occurred, or the time it was observed if that isn't set, or else the time the record was received."
+					required:    true
+					type: timestamp: {}
+				}
+				observed_timestamp: {
+					description: "The time the event was observed by the collection system."
+					required:    false
+					common:      false
+					type: timestamp: {}
+				}
+				severity_text: {
+					description: "The severity of the log record as text, as it's known at its source."
+					required:    false
+					common:      true
+					type: string: {
+						default: null
+						examples: ["INFO", "error"]
+						syntax: "literal"
+					}
+				}
+				severity_number: {
+					description: "The normalized severity of the log record, from 1 (`TRACE`) to 24 (`FATAL4`)."
+					required:    false
+					common:      true
+					type: uint: {
+						default: null
+						examples: [9, 17]
+						unit: null
+					}
+				}
+				attributes: {
+					description: "The attributes of the log record."
+					required:    false
+					common:      true
+					type: object: options: {}
+				}
+				resources: {
+					description: "The attributes of the resource, e.g. the service or host, that produced the log record."
+					required:    false
+					common:      true
+					type: object: options: {}
+				}
+				scope: {
+					description: "The instrumentation scope, e.g. the library, that produced the log record, with its `name`, `version` and `attributes`."
+					required:    false
+					common:      false
+					type: object: options: {}
+				}
+				trace_id: {
+					description: "The ID of the trace the log record belongs to, as hex."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["66346462623365323866633561646661"]
+						syntax: "literal"
+					}
+				}
+				span_id: {
+					description: "The ID of the span the log record belongs to, as hex."
+					required:    false
+					common:      false
+					type: string: {
+						default: null
+						examples: ["43222c2d51a7abe3"]
+						syntax: "literal"
+					}
+				}
+				flags: {
+					description: "The trace flags of the log record."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						unit:    null
+					}
+				}
+				dropped_attributes_count: {
+					description: "The number of attributes dropped by the SDK, e.g. because of limits."
+					required:    false
+					common:      false
+					type: uint: {
+						default: null
+						unit:    null
+					}
+				}
+			}
+		}
+		metrics: {
+			counter:   output._passthrough_counter
+			gauge:     output._passthrough_gauge
+			histogram: output._passthrough_histogram
+		}
+	}
+
+	how_it_works: {
+		metric_types: {
+			title: "Metric type interpretation"
+			body: """
+				Monotonic sums are received as counters and the other sums, which
+				can decrease, as gauges. Gauges and histograms keep their type.
+				Delta sums and histograms become incremental metrics and cumulative
+				ones absolute metrics. The attributes of the resource and of each
+				data point become the tags of the metric.
+
+				Exponential histograms and summaries aren't supported and are
+				dropped.
+				"""
+		}
+		transport: {
+			title: "gRPC and HTTP"
+			body: """
+				OTLP can be sent over gRPC or over HTTP, for which this source runs
+				a server each. Over HTTP, requests have to be encoded as protobuf
+				and may be compressed with gzip, as indicated by their
+				`Content-Encoding` header.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		events_discarded_total:       components.sources.internal_metrics.output.metrics.events_discarded_total
+		processed_bytes_total:        components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:       components.sources.internal_metrics.output.metrics.processed_events_total
+		protobuf_decode_errors_total: components.sources.internal_metrics.output.metrics.protobuf_decode_errors_total
+	}
+}
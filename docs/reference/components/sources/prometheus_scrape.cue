@@ -86,6 +86,7 @@ components: sources: prometheus_scrape: {
 			password_example: "${PROMETHEUS_PASSWORD}"
 			username_example: "${PROMETHEUS_USERNAME}"
 		}}
+		proxy: components._proxy
 	}
 
 	output: metrics: {
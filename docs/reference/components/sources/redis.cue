@@ -0,0 +1,149 @@
+package metadata
+
+components: sources: redis: {
+	title: "Redis"
+
+	description: """
+		Reads messages from a Redis list, Pub/Sub channel, or stream.
+		"""
+
+	features: {
+		collect: {
+			checkpoint: enabled: false
+			tls: enabled:        false
+			from: components._redis.features.collect.from
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: components._redis.support
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: {
+		acknowledgements: {
+			common:      false
+			description: "Only acknowledge a stream entry once its event has been delivered by all sinks. Entries whose event failed to be delivered stay pending. Only applies to the `stream` data type."
+			required:    false
+			type: bool: default: false
+		}
+		data_type: {
+			common:      true
+			description: "The Redis data type to read from."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "list"
+				enum: {
+					list:    "Pop messages from the head of a list with `BLPOP`."
+					channel: "Subscribe to a Pub/Sub channel. Messages published while Vector isn't subscribed are lost."
+					stream:  "Read entries from a stream in a consumer group with `XREADGROUP`, acknowledging them with `XACK`."
+				}
+				syntax: "literal"
+			}
+		}
+		endpoint: components._redis.configuration.endpoint
+		key: {
+			description: "The name of the list, channel, or stream to read from."
+			required:    true
+			warnings: []
+			type: string: {
+				examples: ["vector"]
+				syntax: "literal"
+			}
+		}
+		redis_key: {
+			common:      false
+			description: "The log field name to store the key the message was read from in."
+			required:    false
+			warnings: []
+			type: string: {
+				default: null
+				examples: ["redis_key"]
+				syntax: "literal"
+			}
+		}
+		stream: {
+			common:      false
+			description: "Options for the `stream` data type."
+			required:    false
+			warnings: []
+			type: object: options: {
+				consumer: {
+					common:      false
+					description: "The name of the consumer in the group."
+					required:    false
+					warnings: []
+					type: string: {
+						default: "vector"
+						syntax:  "literal"
+					}
+				}
+				group: {
+					common:      false
+					description: "The consumer group to read in, which is created if it doesn't exist."
+					required:    false
+					warnings: []
+					type: string: {
+						default: "vector"
+						syntax:  "literal"
+					}
+				}
+			}
+		}
+	}
+
+	output: logs: record: {
+		description: "An individual Redis message or stream entry"
+		fields: {
+			message: {
+				description: "The Redis message. Stream entries get one field per entry field instead."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+					syntax: "literal"
+				}
+			}
+			timestamp: fields._current_timestamp & {
+				description: "The time of the stream entry's ID, or the current time for other data types."
+			}
+		}
+	}
+
+	telemetry: metrics: {
+		acknowledgement_errors_total: components.sources.internal_metrics.output.metrics.acknowledgement_errors_total
+		connection_errors_total:      components.sources.internal_metrics.output.metrics.connection_errors_total
+		delivery_failed_total:        components.sources.internal_metrics.output.metrics.delivery_failed_total
+		processed_bytes_total:        components.sources.internal_metrics.output.metrics.processed_bytes_total
+		processed_events_total:       components.sources.internal_metrics.output.metrics.processed_events_total
+	}
+
+	how_it_works: {
+		streams: {
+			title: "Streams"
+			body: """
+				Stream entries are read in a [consumer group](\(urls.redis_streams)),
+				so several consumers can share a stream. Entries are acknowledged
+				once their event is sent to the next components, or once it is
+				delivered by all sinks if `acknowledgements` is enabled. Entries
+				left pending by a previous run of the consumer are read again
+				when the source starts.
+				"""
+		}
+	}
+}
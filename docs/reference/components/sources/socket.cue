@@ -16,6 +16,10 @@ components: sources: socket: {
 
 	features: {
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		receive: {
 			from: {
 				service: services.socket_client
@@ -67,7 +71,20 @@ components: sources: socket: {
 			required:      true
 			warnings: []
 			type: string: {
-				examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
+				how_it_works: {
+		gelf: {
+			title: "GELF"
+			body: """
+				With the `gelf` codec, [GELF](\(urls.gelf)) messages are null
+				delimited over TCP and Unix stream sockets. Over UDP, each
+				datagram holds a message, optionally compressed, or a chunk of
+				one, and chunked messages are reassembled. These are the default
+				framings of the codec when `framing` isn't set.
+				"""
+		}
+	}
+
+	examples: ["0.0.0.0:\(_port)", "systemd", "systemd#3"]
 				syntax: "literal"
 			}
 		}
@@ -84,7 +101,7 @@ components: sources: socket: {
 		}
 		max_length: {
 			common:      true
-			description: "The maximum bytes size of incoming messages before they are discarded."
+			description: "The maximum bytes size of incoming messages before they are discarded. Only used if `framing` isn't set."
 			required:    false
 			warnings: []
 			type: uint: {
@@ -14,6 +14,10 @@ components: sources: stdin: {
 
 	features: {
 		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "newline_delimited"
+		}
 		receive: {
 			from: {
 				service: services.stdin
@@ -58,7 +62,7 @@ components: sources: stdin: {
 		}
 		max_length: {
 			common:      false
-			description: "The maximum bytes size of a message before rest of it will be discarded."
+			description: "The maximum bytes size of a message before rest of it will be discarded. Only used if `framing` isn't set."
 			required:    false
 			warnings: []
 			type: uint: {
@@ -27,7 +27,7 @@ components: sources: vector: {
 				interface: socket: {
 					direction: "incoming"
 					port:      _port
-					protocols: ["tcp"]
+					protocols: ["tcp", "http"]
 					ssl: "optional"
 				}
 			}
@@ -63,6 +63,13 @@ components: sources: vector: {
 	}
 
 	configuration: {
+		acknowledgements: {
+			common:        false
+			description:   "Only acknowledge a batch once its events have been delivered by all sinks, so that the `vector` sink retries batches that failed to be delivered."
+			relevant_when: "version = \"2\""
+			required:      false
+			type: bool: default: false
+		}
 		address: {
 			description: "The TCP address to listen for connections on, or `systemd#N to use the Nth socket passed by systemd socket activation. If an address is used it _must_ include a port."
 			required:    true
@@ -72,6 +79,20 @@ components: sources: vector: {
 				syntax: "literal"
 			}
 		}
+		version: {
+			common:      true
+			description: "The version of the protocol to speak to the `vector` sink, which has to use the same version. Systemd sockets can only be used with version 1. Keepalive, the receive buffer size and the shutdown timeout only apply to version 1, and acknowledgements only to version 2. Setting an option of the other version is an error."
+			required:    false
+			warnings: []
+			type: string: {
+				default: "1"
+				enum: {
+					"1": "Receives length delimited events over TCP."
+					"2": "Receives batches of events over HTTP, acknowledging each of them."
+				}
+				syntax: "literal"
+			}
+		}
 		shutdown_timeout_secs: {
 			common:      false
 			description: "The timeout before a connection is forcefully closed during shutdown."
@@ -116,15 +137,20 @@ components: sources: vector: {
 			title: "Communication Protocol"
 			body: """
 				Upstream Vector instances forward data to downstream Vector
-				instances via the TCP protocol.
+				instances via the TCP protocol. With version 2 of the protocol,
+				batches of events are posted over HTTP instead, optionally gzip
+				compressed.
 				"""
 		}
 		message_acknowledgement: {
 			title: "Message Acknowledgement"
 			body: """
-				Currently, Vector does not perform any application level message
-				acknowledgement. While rare, this means the individual message
-				could be lost.
+				Version 1 of the protocol does not perform any application level
+				message acknowledgement. While rare, this means the individual
+				message could be lost. With version 2, the source acknowledges
+				each batch once its events are accepted, or once they are delivered
+				if `acknowledgements` is enabled. The sink retries batches that
+				aren't acknowledged.
 				"""
 		}
 
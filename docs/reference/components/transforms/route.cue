@@ -40,6 +40,8 @@ components: transforms: route: {
 			description: """
 				A table of route identifiers to logical conditions representing the filter of the route. Each route
 				can then be referenced as an input by other components with the name `<transform_name>.<route_id>`.
+				Events are sent to every route whose condition they match. Nothing is sent to the transform's own
+				name, so it can't be used as an input.
 				"""
 			required: true
 			warnings: []
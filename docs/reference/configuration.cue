@@ -23,6 +23,156 @@ configuration: {
 			}
 		}
 
+		reload_drain_timeout_secs: {
+			common: false
+			description: """
+				When the configuration is reloaded, the number of seconds sinks
+				that are changed or removed are given to send the events they
+				hold before they are shut down. Events still in the buffer of a
+				changed sink are handed over to its new instance if the buffer
+				configuration is unchanged.
+				"""
+			required: false
+			type: uint: {
+				default: 60
+				unit:    "seconds"
+			}
+		}
+
+		include: {
+			common: false
+			description: """
+				Other configuration files to load along with this one. Relative paths
+				are relative to the directory of this file, so configs that aren't
+				loaded from a file, such as remote configs, can only include absolute
+				paths. Wildcard paths are supported. Each file is only loaded once, and
+				included files are watched along with this one when `--watch-config`
+				is set.
+				"""
+			required: false
+			warnings: []
+			type: array: items: type: string: examples: ["tenants/*.toml", "/etc/vector/common.toml"]
+		}
+
+		templates: {
+			common: false
+			description: """
+				Component configurations that are defined once and instantiated any
+				number of times with different variables. Variables are referenced
+				within the configuration as `${var.<name>}`.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					kind: {
+						description: "The kind of component the template describes."
+						required:    true
+						warnings: []
+						type: string: enum: {
+							source:    "A source."
+							transform: "A transform."
+							sink:      "A sink."
+						}
+					}
+					config: {
+						description: "The configuration of the component, as for any component of that kind."
+						required:    true
+						warnings: []
+						type: object: {
+							examples: []
+							options: {}
+						}
+					}
+				}
+			}
+		}
+
+		instances: {
+			common: false
+			description: """
+				Components instantiated from a template, keyed by the name of the
+				component.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					template: {
+						description: "The name of the template to instantiate."
+						required:    true
+						warnings: []
+						type: string: examples: ["tenant_sink"]
+					}
+					vars: {
+						common:      true
+						description: "The values of the variables referenced by the template."
+						required:    false
+						warnings: []
+						type: object: {
+							examples: [{tenant: "acme"}]
+							options: {}
+						}
+					}
+				}
+			}
+		}
+
+		secret: {
+			common: false
+			description: """
+				Configures the backends secrets are retrieved from, keyed by the
+				name they are referenced by as `SECRET[<backend>.<key>]`.
+				"""
+			required: false
+			warnings: []
+			type: object: {
+				examples: []
+				options: {
+					type: {
+						description: "The type of the secret backend."
+						required:    true
+						warnings: []
+						type: string: {
+							enum: {
+								exec: "Run a command that is passed the requested keys and returns the secrets, both as JSON."
+								file: "Read the secrets from a file holding a JSON object."
+							}
+						}
+					}
+					command: {
+						description:   "The command to run, as the program followed by its arguments."
+						relevant_when: "type = \"exec\""
+						required:      true
+						warnings: []
+						type: array: items: type: string: examples: ["/usr/bin/fetch-secrets", "--role=vector"]
+					}
+					timeout_secs: {
+						common:        false
+						description:   "How long to wait for the command to return the secrets."
+						relevant_when: "type = \"exec\""
+						required:      false
+						warnings: []
+						type: uint: {
+							default: 5
+							unit:    "seconds"
+						}
+					}
+					path: {
+						description:   "The path of the JSON file holding the secrets."
+						relevant_when: "type = \"file\""
+						required:      true
+						warnings: []
+						type: string: examples: ["/etc/vector/secrets.json"]
+					}
+				}
+			}
+		}
+
+		proxy: components._proxy
+
 		healthchecks: {
 			common: false
 			description: """
@@ -101,6 +251,79 @@ configuration: {
 				},
 			]
 		}
+		templates: {
+			title: "Templates"
+			body: """
+				Components that only differ in a few values can be defined once as a
+				template, and instantiated for each set of values:
+
+				```toml title="vector.toml"
+				include = ["tenants/*.toml"]
+
+				[templates.tenant_sink]
+				  kind = "sink"
+
+				  [templates.tenant_sink.config]
+				    type = "http"
+				    inputs = ["${var.tenant}_logs"]
+				    uri = "https://${var.tenant}.example.com/logs"
+				    encoding.codec = "json"
+
+				[instances.acme_sink]
+				  template = "tenant_sink"
+				  vars = { tenant = "acme" }
+				```
+
+				A string that consists of just one variable is replaced with the value
+				of the variable as is, so variables can also hold numbers or arrays.
+				Instances are expanded before the configuration is validated, and errors
+				in them name the template and the file it was defined in.
+				"""
+		}
+		secrets: {
+			title: "Secrets"
+			body: """
+				Secrets, like the tokens of sinks, can be retrieved from a secret backend
+				instead of being written into the configuration file:
+
+				```toml title="vector.toml"
+				[secret.vault]
+				  type = "exec"
+				  command = ["/usr/bin/fetch-secrets"]
+
+				[sinks.datadog]
+				  type = "datadog_logs"
+				  inputs = ["in"]
+				  api_key = "SECRET[vault.datadog_api_key]"
+				```
+
+				Secrets are retrieved each time the configuration is loaded or reloaded.
+				Their values are escaped as in a double-quoted string, so secrets should
+				be referenced from double-quoted strings. Values of at least four
+				characters are redacted from Vector's logs and from the output of
+				`vector validate`, wherever they aren't part of a longer word.
+				"""
+
+			sub_sections: [
+				{
+					title: "The exec backend"
+					body: """
+						The command is passed the keys to retrieve on stdin:
+
+						```json
+						{"version": "1.0", "secrets": ["datadog_api_key"]}
+						```
+
+						It has to print the secrets to stdout, with an error for the ones
+						it can't retrieve:
+
+						```json
+						{"datadog_api_key": {"value": "...", "error": null}}
+						```
+						"""
+				},
+			]
+		}
 		formats: {
 			title: "Formats"
 			body:  """
@@ -0,0 +1,8 @@
+package metadata
+
+services: fluent: {
+	name:     "Fluentd and Fluent Bit"
+	thing:    "a Fluentd or Fluent Bit instance"
+	url:      urls.fluentd
+	versions: null
+}
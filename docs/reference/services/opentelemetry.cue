@@ -0,0 +1,8 @@
+package metadata
+
+services: opentelemetry: {
+	name:     "OpenTelemetry"
+	thing:    "an \(name) SDK or collector"
+	url:      urls.opentelemetry
+	versions: null
+}
@@ -0,0 +1,10 @@
+package metadata
+
+services: redis: {
+	name:     "Redis"
+	thing:    "a \(name) server"
+	url:      urls.redis
+	versions: null
+
+	description: "[Redis](\(urls.redis)) is an open source in-memory data store, whose lists, [Pub/Sub](\(urls.redis_pubsub)) channels and [streams](\(urls.redis_streams)) are commonly used as message queues."
+}
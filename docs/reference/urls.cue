@@ -4,6 +4,8 @@ urls: {
 	affine_type_system:                                       "\(wikipedia)/wiki/Substructural_type_system#Affine_type_systems"
 	adaptive_request_concurrency_post:                        "/blog/adaptive-request-concurrency/"
 	amazon_linux:                                             "https://aws.amazon.com/amazon-linux-ami/"
+	amqp:                                                     "https://www.amqp.org/"
+	amqp_091:                                                 "https://www.rabbitmq.com/amqp-0-9-1-reference.html"
 	ansi_escape_codes:                                        "\(wikipedia)/wiki/ANSI_escape_code"
 	apache:                                                   "https://httpd.apache.org"
 	apache_common:                                            "\(apache)/docs/1.3/logs.html#common"
@@ -157,7 +159,12 @@ urls: {
 	ffi:                                                      "https://en.wikipedia.org/wiki/Foreign_function_interface"
 	file:                                                     "\(wikipedia)/wiki/Computer_file"
 	file_system:                                              "\(wikipedia)/wiki/File_system"
+	fluent_bit:                                               "https://fluentbit.io/"
+	fluent_forward_protocol:                                  "https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1"
+	fluentd:                                                  "https://www.fluentd.org/"
 	freebsd:                                                  "https://www.freebsd.org/"
+	gelf:                                                     "https://go2docs.graylog.org/current/getting_in_log_data/gelf.html"
+	gelf_chunking:                                            "https://go2docs.graylog.org/current/getting_in_log_data/gelf.html#GELFviaUDP"
 	gcp_authentication:                                       "https://cloud.google.com/docs/authentication/"
 	gcp_authentication_api_key:                               "https://cloud.google.com/docs/authentication/api-keys"
 	gcp_authentication_server_to_server:                      "https://cloud.google.com/docs/authentication/production"
@@ -288,6 +295,8 @@ urls: {
 	mongodb:                                                  "https://www.mongodb.com"
 	mongodb_command_server_status:                            "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:                     "https://docs.mongodb.com/manual/reference/connection-string/"
+	mosquitto:                                                "https://mosquitto.org/"
+	mqtt:                                                     "https://mqtt.org/"
 	musl_builder_docker_image:                                "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	nats:                                                     "https://nats.io/"
 	new_bug_report:                                           "\(vector_repo)/issues/new?labels=type%3A+bug"
@@ -305,6 +314,8 @@ urls: {
 	nixos:                                                    "https://nixos.org/"
 	nixpkgs_9682:                                             "\(github)/NixOS/nixpkgs/issues/9682"
 	openssl:                                                  "https://www.openssl.org/"
+	opentelemetry:                                            "https://opentelemetry.io/"
+	opentelemetry_otlp:                                       "https://opentelemetry.io/docs/specs/otlp/"
 	order_of_ops:                                             "\(wikipedia)/wiki/Order_of_operations"
 	papertrail:                                               "https://www.papertrail.com/"
 	papertrail_syslog:                                        "https://help.papertrailapp.com/kb/how-it-works/http-api/#submitting-log-messages"
@@ -327,8 +338,14 @@ urls: {
 	protobuf:                                                 "https://developers.google.com/protocol-buffers"
 	pulsar:                                                   "https://pulsar.apache.org/"
 	pulsar_protocol:                                          "https://pulsar.apache.org/docs/en/develop-binary-protocol/"
+	rabbitmq:                                                 "https://www.rabbitmq.com/"
+	rabbitmq_confirms:                                        "https://www.rabbitmq.com/confirms.html"
 	raspbian:                                                 "https://www.raspbian.org/"
 	rdkafka:                                                  "\(github)/edenhill/librdkafka"
+	redis:                                                    "https://redis.io/"
+	redis_protocol:                                           "https://redis.io/topics/protocol"
+	redis_pubsub:                                             "https://redis.io/topics/pubsub"
+	redis_streams:                                            "https://redis.io/topics/streams-intro"
 	regex:                                                    "\(wikipedia)/wiki/Regular_expression"
 	regex_grouping_and_flags:                                 "https://docs.rs/regex/latest/regex/#grouping-and-flags"
 	regex_tester:                                             "https://rustexp.lpil.uk/"
@@ -337,6 +354,7 @@ urls: {
 	rfc_2822:                                                 "https://tools.ietf.org/html/rfc2822#section-3.3"
 	rfc_3339:                                                 "https://tools.ietf.org/html/rfc3339"
 	rfc_4180:                                                 "https://tools.ietf.org/html/rfc4180"
+	rfc_6587:                                                 "https://tools.ietf.org/html/rfc6587"
 	rhel:                                                     "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rlua:                                                     "\(github)/kyren/rlua"
 	rpm:                                                      "https://rpm.org/"
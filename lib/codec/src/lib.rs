@@ -36,6 +36,13 @@ impl BytesDelimitedCodec {
     pub fn max_length(&self) -> usize {
         self.max_length
     }
+
+    /// Forgets any partially searched or discarded frame, so that the next
+    /// call to `decode` starts afresh.
+    pub fn reset(&mut self) {
+        self.is_discarding = false;
+        self.next_index = 0;
+    }
 }
 
 impl Decoder for BytesDelimitedCodec {
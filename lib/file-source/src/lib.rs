@@ -11,11 +11,12 @@ mod internal_events;
 mod metadata_ext;
 pub mod paths_provider;
 
-pub use self::file_server::{FileServer, Shutdown as FileServerShutdown};
-pub use self::fingerprinter::{FingerprintStrategy, Fingerprinter};
+pub use self::checkpointer::{Checkpointer, CheckpointsView};
+pub use self::file_server::{FileServer, Line, Shutdown as FileServerShutdown};
+pub use self::fingerprinter::{FileFingerprint, FingerprintStrategy, Fingerprinter};
 pub use self::internal_events::FileSourceInternalEvents;
 
-type FilePosition = u64;
+pub type FilePosition = u64;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum ReadFrom {
@@ -113,6 +113,17 @@ impl components_query::ComponentsQueryComponentsEdgesNodeOn {
                 .unwrap_or(0),
         }
     }
+
+    /// Number of events and bytes held in the buffer, for sinks that report buffer usage
+    pub fn buffer_usage(&self) -> Option<(i64, i64)> {
+        match self {
+            components_query::ComponentsQueryComponentsEdgesNodeOn::Sink(s) => s
+                .buffer
+                .as_ref()
+                .map(|b| (b.events as i64, b.byte_size as i64)),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for components_query::ComponentsQueryComponentsEdgesNodeOn {
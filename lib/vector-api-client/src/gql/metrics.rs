@@ -83,6 +83,16 @@ pub struct ComponentProcessedBytesThroughputsSubscription;
 )]
 pub struct ComponentProcessedBytesTotalsSubscription;
 
+/// ComponentBufferMetricsSubscription contains the number of events and bytes held in
+/// the buffer of each sink
+#[derive(GraphQLQuery, Debug, Copy, Clone)]
+#[graphql(
+    schema_path = "graphql/schema.json",
+    query_path = "graphql/subscriptions/component_buffer_metrics.graphql",
+    response_derives = "Debug"
+)]
+pub struct ComponentBufferMetricsSubscription;
+
 /// Extension methods for metrics subscriptions
 pub trait MetricsSubscriptionExt {
     /// Executes an uptime metrics subscription
@@ -129,6 +139,12 @@ pub trait MetricsSubscriptionExt {
         &self,
         interval: i64,
     ) -> crate::BoxedSubscription<ComponentProcessedBytesThroughputsSubscription>;
+
+    /// Executes a component buffer metrics subscription
+    fn component_buffer_metrics_subscription(
+        &self,
+        interval: i64,
+    ) -> crate::BoxedSubscription<ComponentBufferMetricsSubscription>;
 }
 
 impl MetricsSubscriptionExt for crate::SubscriptionClient {
@@ -222,4 +238,16 @@ impl MetricsSubscriptionExt for crate::SubscriptionClient {
 
         self.start::<ComponentProcessedBytesThroughputsSubscription>(&request_body)
     }
+
+    /// Executes an all component buffer metrics subscription
+    fn component_buffer_metrics_subscription(
+        &self,
+        interval: i64,
+    ) -> BoxedSubscription<ComponentBufferMetricsSubscription> {
+        let request_body = ComponentBufferMetricsSubscription::build_query(
+            component_buffer_metrics_subscription::Variables { interval },
+        );
+
+        self.start::<ComponentBufferMetricsSubscription>(&request_body)
+    }
 }
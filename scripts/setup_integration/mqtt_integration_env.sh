@@ -0,0 +1,46 @@
+#!/usr/bin/env bash
+set -o pipefail
+
+# mqtt_integration_env.sh
+#
+# SUMMARY
+#
+#   Builds and pulls down the Vector MQTT Integration test environment
+
+if [ $# -ne 1 ]
+then
+    echo "Usage: $0 {stop|start}" 1>&2; exit 1;
+    exit 1
+fi
+ACTION=$1
+
+#
+# Functions
+#
+
+start_podman () {
+  podman pod create --replace --name vector-test-integration-mqtt -p 1883:1883
+  podman run -d --pod=vector-test-integration-mqtt  --name vector_mqtt \
+	 eclipse-mosquitto:2.0 mosquitto -c /mosquitto-no-auth.conf
+}
+
+start_docker () {
+  docker network create vector-test-integration-mqtt
+  docker run -d --network=vector-test-integration-mqtt -p 1883:1883 --name vector_mqtt \
+	 eclipse-mosquitto:2.0 mosquitto -c /mosquitto-no-auth.conf
+}
+
+stop_podman () {
+  podman rm --force vector_mqtt 2>/dev/null; true
+  podman pod stop vector-test-integration-mqtt 2>/dev/null; true
+  podman pod rm --force vector-test-integration-mqtt 2>/dev/null; true
+}
+
+stop_docker () {
+  docker rm --force vector_mqtt 2>/dev/null; true
+  docker network rm vector-test-integration-mqtt 2>/dev/null; true
+}
+
+echo "Running $ACTION action for MQTT integration tests environment"
+
+"${ACTION}"_"${CONTAINER_TOOL}"
@@ -111,6 +111,11 @@ impl Sink {
     pub async fn metrics(&self) -> metrics::SinkMetrics {
         metrics::by_component_name(self.get_name()).to_sink_metrics(self.get_component_type())
     }
+
+    /// Buffer usage. Empty until the sink's buffer reports its first metrics
+    pub async fn buffer(&self) -> Option<metrics::BufferMetrics> {
+        metrics::BufferMetrics::from_metrics(&metrics::by_component_name(self.get_name()))
+    }
 }
 
 #[cfg(test)]
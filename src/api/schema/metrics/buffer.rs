@@ -0,0 +1,102 @@
+use crate::event::{Metric, MetricValue};
+use async_graphql::Object;
+use std::collections::BTreeMap;
+
+/// Usage of a sink's buffer, taken from the `buffer_*` internal metrics.
+#[derive(Debug, Clone)]
+pub struct BufferMetrics(Vec<Metric>);
+
+impl BufferMetrics {
+    /// Returns `None` when the metrics don't belong to a buffered sink.
+    pub fn from_metrics(metrics: &[Metric]) -> Option<Self> {
+        let metrics = metrics
+            .iter()
+            .filter(|m| m.name().starts_with("buffer_"))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if metrics.is_empty() {
+            None
+        } else {
+            Some(Self(metrics))
+        }
+    }
+
+    fn value(&self, name: &str) -> f64 {
+        self.0
+            .iter()
+            .filter(|m| m.name() == name)
+            .map(|m| match m.data.value {
+                MetricValue::Counter { value } | MetricValue::Gauge { value } => value,
+                _ => 0.00,
+            })
+            .sum()
+    }
+}
+
+#[Object]
+impl BufferMetrics {
+    /// Number of events currently held in the buffer
+    pub async fn events(&self) -> f64 {
+        self.value("buffer_events")
+    }
+
+    /// Estimated size in bytes of the events currently held in the buffer
+    pub async fn byte_size(&self) -> f64 {
+        self.value("buffer_byte_size")
+    }
+
+    /// Total number of events written into the buffer
+    pub async fn received_events_total(&self) -> f64 {
+        self.value("buffer_received_events_total")
+    }
+
+    /// Total number of events read from the buffer by the sink
+    pub async fn sent_events_total(&self) -> f64 {
+        self.value("buffer_sent_events_total")
+    }
+
+    /// Total number of events dropped because the buffer was full
+    pub async fn discarded_events_total(&self) -> f64 {
+        self.value("buffer_discarded_events_total")
+    }
+}
+
+pub struct ComponentBufferMetrics {
+    name: String,
+    buffer: BufferMetrics,
+}
+
+impl ComponentBufferMetrics {
+    /// Groups the `buffer_*` metrics by their `component_name` tag, returning the usage of
+    /// each buffered sink
+    pub fn from_metrics(metrics: Vec<Metric>) -> Vec<Self> {
+        metrics
+            .into_iter()
+            .filter(|m| m.name().starts_with("buffer_"))
+            .filter_map(|m| Some((m.tag_value("component_name")?, m)))
+            .fold(BTreeMap::new(), |mut map, (name, m)| {
+                map.entry(name).or_insert_with(Vec::new).push(m);
+                map
+            })
+            .into_iter()
+            .map(|(name, metrics)| Self {
+                name,
+                buffer: BufferMetrics(metrics),
+            })
+            .collect()
+    }
+}
+
+#[Object]
+impl ComponentBufferMetrics {
+    /// Component name
+    async fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Buffer usage metrics
+    async fn buffer(&self) -> BufferMetrics {
+        self.buffer.clone()
+    }
+}
@@ -1,3 +1,4 @@
+mod buffer;
 mod errors;
 pub mod filter;
 mod host;
@@ -12,6 +13,7 @@ use async_graphql::{validators::IntRange, Interface, Object, Subscription};
 use chrono::{DateTime, Utc};
 use tokio::stream::{Stream, StreamExt};
 
+pub use buffer::{BufferMetrics, ComponentBufferMetrics};
 pub use errors::{ComponentErrorsTotal, ErrorsTotal};
 pub use filter::*;
 pub use host::HostMetrics;
@@ -161,6 +163,14 @@ impl MetricsSubscription {
         })
     }
 
+    /// Component buffer usage metrics over `interval`.
+    async fn component_buffer_metrics(
+        &self,
+        #[graphql(default = 1000, validator(IntRange(min = "10", max = "60_000")))] interval: i32,
+    ) -> impl Stream<Item = Vec<ComponentBufferMetrics>> {
+        get_all_metrics(interval).map(ComponentBufferMetrics::from_metrics)
+    }
+
     /// Total error metrics.
     async fn errors_total(
         &self,
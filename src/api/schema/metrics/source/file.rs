@@ -4,6 +4,7 @@ use crate::{
         metrics::{self, MetricsFilter},
         relay, sort,
     },
+    config::secret,
     event::Metric,
     filter_check,
 };
@@ -17,8 +18,10 @@ pub struct FileSourceMetricFile<'a> {
 }
 
 impl<'a> FileSourceMetricFile<'a> {
-    /// Returns a new FileSourceMetricFile from a (name, Vec<&Metric>) tuple
+    /// Returns a new FileSourceMetricFile from a (name, Vec<&Metric>) tuple. The
+    /// name is redacted here, as subscriptions don't pass through the query handler
     fn from_tuple((name, metrics): (String, Vec<&'a Metric>)) -> Self {
+        let name = secret::redact(&name);
         Self { name, metrics }
     }
 
@@ -1,8 +1,8 @@
 use super::{handler, schema};
-use crate::config;
+use crate::config::{self, secret};
 use async_graphql::{
     http::{playground_source, GraphQLPlaygroundConfig},
-    Request, Schema,
+    Request, Response as GQLResult, Schema,
 };
 use async_graphql_warp::{graphql_subscription, Response as GQLResponse};
 use std::{convert::Infallible, net::SocketAddr};
@@ -67,7 +67,9 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
     let graphql_handler = warp::path("graphql").and(graphql_subscription(schema.clone()).or(
         async_graphql_warp::graphql(schema).and_then(
             |(schema, request): (Schema<_, _, _>, Request)| async move {
-                Ok::<_, Infallible>(GQLResponse::from(schema.execute(request).await))
+                let mut response = schema.execute(request).await;
+                redact(&mut response);
+                Ok::<_, Infallible>(GQLResponse::from(response))
             },
         ),
     ));
@@ -113,3 +115,17 @@ fn make_routes(playground: bool) -> BoxedFilter<(impl Reply,)> {
         )
         .boxed()
 }
+
+/// Redacts resolved secrets from a query response, as its data and errors can
+/// hold values taken from the config.
+fn redact(response: &mut GQLResult) {
+    if let Ok(mut data) = serde_json::to_value(&response.data) {
+        secret::redact_json(&mut data);
+        if let Ok(data) = serde_json::from_value(data) {
+            response.data = data;
+        }
+    }
+    for error in response.errors.iter_mut() {
+        error.message = secret::redact(&error.message);
+    }
+}
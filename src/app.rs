@@ -1,4 +1,5 @@
 use crate::cli::{handle_config_errors, Color, LogFormat, Opts, RootOpts, SubCommand};
+use crate::config::remote::{self, RemoteConfig};
 use crate::signal::SignalTo;
 use crate::topology::RunningTopology;
 use crate::{
@@ -7,9 +8,10 @@ use crate::{
 use std::cmp::max;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use futures::StreamExt;
-use tokio::sync::mpsc;
+use tokio::{sync::mpsc, time};
 
 #[cfg(feature = "sources-host_metrics")]
 use crate::sources::host_metrics;
@@ -30,6 +32,7 @@ use tokio::runtime::Runtime;
 
 pub struct ApplicationConfig {
     pub config_paths: Vec<(PathBuf, config::FormatHint)>,
+    pub remote_configs: Vec<RemoteConfig>,
     pub topology: RunningTopology,
     pub graceful_crash: mpsc::UnboundedReceiver<()>,
     #[cfg(feature = "api")]
@@ -104,6 +107,7 @@ impl Application {
 
         let config = {
             let config_paths = root_opts.config_paths_with_formats();
+            let config_urls = root_opts.config_urls.clone();
             let watch_config = root_opts.watch_config;
             let require_healthy = root_opts.require_healthy;
 
@@ -130,7 +134,8 @@ impl Application {
                 #[cfg(feature = "sources-host_metrics")]
                 host_metrics::init_roots();
 
-                let config_paths = config::process_paths(&config_paths).ok_or(exitcode::CONFIG)?;
+                let config_paths = process_paths(&config_paths, !config_urls.is_empty())
+                    .ok_or(exitcode::CONFIG)?;
 
                 if watch_config {
                     // Start listening for config changes immediately.
@@ -146,8 +151,25 @@ impl Application {
                     path = ?config_paths
                 );
 
+                let mut remote_configs = config_urls
+                    .into_iter()
+                    .map(RemoteConfig::new)
+                    .collect::<Vec<_>>();
+                if !remote_configs.is_empty() {
+                    info!(
+                        message = "Fetching remote configs.",
+                        urls = ?remote_configs.iter().map(RemoteConfig::url).collect::<Vec<_>>()
+                    );
+                }
+                let (_, errors) = remote::fetch_all(&mut remote_configs).await;
+                if !errors.is_empty() {
+                    return Err(handle_config_errors(errors));
+                }
+
                 let mut config =
-                    config::load_from_paths(&config_paths, false).map_err(handle_config_errors)?;
+                    config::load_from_paths_and_remotes(&config_paths, &remote_configs, false)
+                        .map_err(handle_config_errors)?;
+                remote::loaded(&mut remote_configs, true);
 
                 config::LOG_SCHEMA
                     .set(config.global.log_schema.clone())
@@ -171,6 +193,7 @@ impl Application {
 
                 Ok(ApplicationConfig {
                     config_paths,
+                    remote_configs,
                     topology,
                     graceful_crash,
                     #[cfg(feature = "api")]
@@ -193,6 +216,7 @@ impl Application {
         let mut topology = self.config.topology;
 
         let mut config_paths = self.config.config_paths;
+        let mut remote_configs = self.config.remote_configs;
 
         let opts = self.opts;
 
@@ -221,54 +245,94 @@ impl Application {
                 None
             };
 
+            // Changes of remote configs trigger reloads like SIGHUP does.
+            let polling = !remote_configs.is_empty();
+            let interval = Duration::from_secs(max(opts.config_url_interval_secs, 1));
+            let mut remote_poll = time::interval_at(time::Instant::now() + interval, interval);
+            if polling {
+                info!(
+                    message = "Polling remote configs.",
+                    interval_secs = interval.as_secs()
+                );
+            }
             let signals = signal::signals();
             tokio::pin!(signals);
             let mut sources_finished = topology.sources_finished();
 
             let signal = loop {
-                tokio::select! {
-                Some(signal) = signals.next() => {
-                    if signal == SignalTo::Reload {
-                        // Reload paths
-                        config_paths = config::process_paths(&opts.config_paths_with_formats()).unwrap_or(config_paths);
-                        // Reload config
-                        let new_config = config::load_from_paths(&config_paths, false).map_err(handle_config_errors).ok();
-
-                        if let Some(mut new_config) = new_config {
-                            new_config.healthchecks.set_require_healthy(opts.require_healthy);
-                            match topology
-                                .reload_config_and_respawn(new_config)
-                                .await
-                            {
-                                Ok(true) => {
-                                    #[cfg(feature="api")]
-                                    if let Some(ref api_server) = api_server {
-                                        api_server.update_config(topology.config())
-                                    }
-
-                                    emit!(VectorReloaded { config_paths: &config_paths })
-                                },
-                                Ok(false) => emit!(VectorReloadFailed),
-                                // Trigger graceful shutdown for what remains of the topology
-                                Err(()) => {
-                                    emit!(VectorReloadFailed);
-                                    emit!(VectorRecoveryFailed);
-                                    break SignalTo::Shutdown;
-                                }
+                let reload = tokio::select! {
+                    Some(signal) = signals.next() => {
+                        if signal == SignalTo::Reload {
+                            // Reload paths
+                            config_paths = process_paths(&opts.config_paths_with_formats(), polling).unwrap_or(config_paths);
+                            // Remote configs that can't be fetched keep their last content
+                            let (_, errors) = remote::fetch_all(&mut remote_configs).await;
+                            for error in errors {
+                                warn!(message = "Failed to fetch remote config, using the one fetched last.", %error);
                             }
-                            sources_finished = topology.sources_finished();
+                            true
                         } else {
-                            emit!(VectorConfigLoadFailed);
+                            break signal;
+                        }
+                    }
+                    _ = remote_poll.tick(), if polling => {
+                        let (changed, errors) = remote::fetch_all(&mut remote_configs).await;
+                        for error in errors {
+                            warn!(message = "Failed to poll remote config.", %error);
                         }
-                    } else {
-                        break signal;
+                        if changed {
+                            info!("Remote config changed.");
+                        }
+                        changed
                     }
+                    // Trigger graceful shutdown if a component crashed, or all sources have ended.
+                    _ = graceful_crash.next() => break SignalTo::Shutdown,
+                    _ = &mut sources_finished => break SignalTo::Shutdown,
+                    else => unreachable!("Signal streams never end"),
+                };
+                if !reload {
+                    continue;
+                }
+
+                // Reload config
+                let new_config =
+                    config::load_from_paths_and_remotes(&config_paths, &remote_configs, false)
+                        .map_err(handle_config_errors)
+                        .ok();
+
+                if let Some(mut new_config) = new_config {
+                    new_config
+                        .healthchecks
+                        .set_require_healthy(opts.require_healthy);
+                    match topology.reload_config_and_respawn(new_config).await {
+                        Ok(true) => {
+                            remote::loaded(&mut remote_configs, true);
+
+                            #[cfg(feature = "api")]
+                            if let Some(ref api_server) = api_server {
+                                api_server.update_config(topology.config())
+                            }
+
+                            emit!(VectorReloaded {
+                                config_paths: &config_paths
+                            })
+                        }
+                        Ok(false) => {
+                            remote::loaded(&mut remote_configs, false);
+                            emit!(VectorReloadFailed);
+                        }
+                        // Trigger graceful shutdown for what remains of the topology
+                        Err(()) => {
+                            emit!(VectorReloadFailed);
+                            emit!(VectorRecoveryFailed);
+                            break SignalTo::Shutdown;
+                        }
+                    }
+                    sources_finished = topology.sources_finished();
+                } else {
+                    remote::loaded(&mut remote_configs, false);
+                    emit!(VectorConfigLoadFailed);
                 }
-                // Trigger graceful shutdown if a component crashed, or all sources have ended.
-                _ = graceful_crash.next() => break SignalTo::Shutdown,
-                _ = &mut sources_finished => break SignalTo::Shutdown,
-                else => unreachable!("Signal streams never end"),
-            }
             };
 
             match signal {
@@ -293,3 +357,15 @@ impl Application {
         });
     }
 }
+
+/// Config files are optional when configs are fetched from URLs.
+fn process_paths(
+    config_paths: &[(PathBuf, config::FormatHint)],
+    with_remotes: bool,
+) -> Option<Vec<(PathBuf, config::FormatHint)>> {
+    if config_paths.is_empty() && with_remotes {
+        Some(Vec::new())
+    } else {
+        config::process_paths(config_paths)
+    }
+}
@@ -0,0 +1,325 @@
+//! Holds on to the finalizers of the events a sink has read until the sink
+//! acks them, so that sources learn about their delivery.
+
+use super::dead_letter::{DeadLetter, Rejection};
+use crate::event::{Event, EventFinalizers, EventStatus};
+use futures::Stream;
+use pin_project::pin_project;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+#[derive(Debug)]
+enum Entry {
+    /// A run of consecutive events without finalizers.
+    Untracked(usize),
+    Tracked {
+        finalizers: EventFinalizers,
+        /// A copy of the event as read, kept for the dead letter output.
+        event: Option<Event>,
+    },
+    /// An event the sink rejected before it was part of any ack, e.g.
+    /// because it couldn't be encoded.
+    Rejected,
+}
+
+/// The finalizers of the events read by a sink, in the order they were read.
+#[derive(Debug, Default)]
+pub struct PendingFinalizers {
+    entries: Mutex<VecDeque<Entry>>,
+    dead_letter: Mutex<Option<DeadLetter>>,
+}
+
+impl PendingFinalizers {
+    pub(super) fn set_dead_letter(&self, dead_letter: Option<DeadLetter>) {
+        *self.dead_letter.lock().unwrap() = dead_letter;
+    }
+
+    fn keeps_events(&self) -> bool {
+        self.dead_letter.lock().unwrap().is_some()
+    }
+
+    fn push(&self, finalizers: EventFinalizers, event: Option<Event>) {
+        let mut entries = self.entries.lock().unwrap();
+        if finalizers.is_empty() && event.is_none() {
+            if let Some(Entry::Untracked(count)) = entries.back_mut() {
+                *count += 1;
+            } else {
+                entries.push_back(Entry::Untracked(1));
+            }
+        } else {
+            entries.push_back(Entry::Tracked { finalizers, event });
+        }
+    }
+
+    /// Finalizes the next `num` events. Returns how many events, including
+    /// the rejected ones in between, the buffer can forget about.
+    pub(super) fn ack(
+        &self,
+        mut num: usize,
+        status: EventStatus,
+        rejection: Option<&Rejection>,
+    ) -> usize {
+        let mut acked = num;
+        let mut finalized = Vec::new();
+        {
+            let mut entries = self.entries.lock().unwrap();
+            loop {
+                match entries.front_mut() {
+                    Some(Entry::Rejected) => {
+                        entries.pop_front();
+                        acked += 1;
+                    }
+                    _ if num == 0 => break,
+                    Some(Entry::Untracked(count)) => {
+                        let skipped = num.min(*count);
+                        *count -= skipped;
+                        num -= skipped;
+                        if *count == 0 {
+                            entries.pop_front();
+                        }
+                    }
+                    Some(Entry::Tracked { .. }) => {
+                        if let Some(Entry::Tracked { finalizers, event }) = entries.pop_front() {
+                            finalized.push((finalizers, event));
+                        }
+                        num -= 1;
+                    }
+                    None => break,
+                }
+            }
+        }
+
+        // Finalizers notify the sources when dropped, which is done
+        // outside of the lock.
+        for (finalizers, event) in finalized {
+            finalizers.update_status(status);
+            if let (Some(event), Some(rejection)) = (event, rejection) {
+                self.send_dead_letter(event, rejection);
+            }
+        }
+
+        acked
+    }
+
+    /// Rejects the event read last. Returns how many events the buffer can
+    /// forget about right away.
+    pub(super) fn reject_last(&self, rejection: &Rejection) -> usize {
+        let mut entries = self.entries.lock().unwrap();
+        let rejected = match entries.pop_back() {
+            Some(Entry::Untracked(count)) => {
+                if count > 1 {
+                    entries.push_back(Entry::Untracked(count - 1));
+                }
+                None
+            }
+            Some(Entry::Tracked { finalizers, event }) => Some((finalizers, event)),
+            Some(Entry::Rejected) | None => {
+                error!(message = "Tried to reject an event that wasn't read.");
+                return 0;
+            }
+        };
+        entries.push_back(Entry::Rejected);
+
+        // Nothing waits for an ack before it, so it's done with immediately.
+        let mut done = 0;
+        while let Some(Entry::Rejected) = entries.front() {
+            entries.pop_front();
+            done += 1;
+        }
+        drop(entries);
+
+        if let Some((finalizers, event)) = rejected {
+            finalizers.update_status(EventStatus::Failed);
+            if let Some(event) = event {
+                self.send_dead_letter(event, rejection);
+            }
+        }
+
+        done
+    }
+
+    fn send_dead_letter(&self, event: Event, rejection: &Rejection) {
+        if let Some(dead_letter) = self.dead_letter.lock().unwrap().as_mut() {
+            dead_letter.send(event, rejection);
+        }
+    }
+
+    fn poll_dead_letter(&self, cx: &mut Context<'_>) -> Poll<()> {
+        match self.dead_letter.lock().unwrap().as_mut() {
+            Some(dead_letter) => dead_letter.poll_flush(cx),
+            None => Poll::Ready(()),
+        }
+    }
+
+    /// Gives up on all events which haven't been acked yet, e.g. because the
+    /// sink reading them has stopped. This also closes the dead letter output.
+    pub(super) fn abandon(&self) {
+        self.set_dead_letter(None);
+        let abandoned = std::mem::take(&mut *self.entries.lock().unwrap());
+        for entry in abandoned {
+            if let Entry::Tracked { finalizers, .. } = entry {
+                finalizers.update_status(EventStatus::Errored);
+            }
+        }
+    }
+}
+
+/// Takes the finalizers off the events read by the sink, if they are tracked.
+#[pin_project]
+pub struct FinalizingStream<S> {
+    #[pin]
+    inner: S,
+    pending: Option<Arc<PendingFinalizers>>,
+    keep_events: bool,
+}
+
+impl<S> FinalizingStream<S> {
+    pub(super) fn new(inner: S, pending: Option<Arc<PendingFinalizers>>) -> Self {
+        let keep_events = pending
+            .as_ref()
+            .map_or(false, |pending| pending.keeps_events());
+        Self {
+            inner,
+            pending,
+            keep_events,
+        }
+    }
+}
+
+impl<S: Stream<Item = Event>> Stream for FinalizingStream<S> {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let pending = this.pending;
+        let keep_events = *this.keep_events;
+        // Rejected events have to be sent to the dead letter output before
+        // further events are read.
+        if let (Some(pending), true) = (pending.as_ref(), keep_events) {
+            if pending.poll_dead_letter(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+        this.inner.poll_next(cx).map(|event| {
+            event.map(|mut event| {
+                if let Some(pending) = pending {
+                    let finalizers = event.take_finalizers();
+                    let kept = if keep_events {
+                        Some(event.clone())
+                    } else {
+                        None
+                    };
+                    pending.push(finalizers, kept);
+                }
+                event
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Entry, FinalizingStream, PendingFinalizers};
+    use crate::{
+        buffers::{DeadLetter, Rejection},
+        config::log_schema,
+        event::{BatchNotifier, BatchStatus, Event, EventStatus, Value},
+    };
+    use futures::{channel::mpsc, stream, FutureExt, StreamExt};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn finalizes_events_when_acked() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let events = vec![
+            Event::from("untracked"),
+            Event::from("tracked").with_batch_notifier(&batch),
+        ];
+        drop(batch);
+
+        let pending = Arc::new(PendingFinalizers::default());
+        let read = FinalizingStream::new(stream::iter(events), Some(Arc::clone(&pending)))
+            .collect::<Vec<_>>()
+            .await;
+        drop(read);
+        // Without a dead letter output no copies of the events are kept.
+        assert!(pending
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|entry| !matches!(entry, Entry::Tracked { event: Some(_), .. })));
+
+        pending.ack(1, EventStatus::Delivered, None);
+        assert_eq!((&mut receiver).now_or_never(), None);
+
+        pending.ack(1, EventStatus::Errored, None);
+        assert_eq!(receiver.now_or_never(), Some(BatchStatus::Errored));
+    }
+
+    #[tokio::test]
+    async fn sends_rejected_events_to_dead_letter() {
+        let (tx, mut rx) = mpsc::channel(10);
+        let pending = Arc::new(PendingFinalizers::default());
+        pending.set_dead_letter(Some(DeadLetter::new("out", tx)));
+
+        let events = vec!["first", "second", "third"]
+            .into_iter()
+            .map(Event::from)
+            .collect::<Vec<_>>();
+        let mut read = FinalizingStream::new(stream::iter(events), Some(Arc::clone(&pending)));
+
+        read.next().await.unwrap();
+        read.next().await.unwrap();
+        assert_eq!(pending.reject_last(&Rejection::new("can't encode", 0)), 0);
+        read.next().await.unwrap();
+
+        // The rejected event in between is passed on to the buffer as well.
+        let rejection = Rejection::new("bad request", 3);
+        assert_eq!(pending.ack(2, EventStatus::Failed, Some(&rejection)), 3);
+
+        pending.abandon();
+        let rejected = rx.collect::<Vec<_>>().await;
+        assert_eq!(rejected.len(), 3);
+        let log = rejected[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "second".into());
+        assert_eq!(log["dead_letter.error"], "can't encode".into());
+        assert_eq!(log["dead_letter.sink"], "out".into());
+        assert_eq!(log["dead_letter.attempts"], Value::Integer(0));
+        let log = rejected[2].as_log();
+        assert_eq!(log[log_schema().message_key()], "third".into());
+        assert_eq!(log["dead_letter.error"], "bad request".into());
+        assert_eq!(log["dead_letter.attempts"], Value::Integer(3));
+    }
+
+    #[tokio::test]
+    async fn holds_back_reads_while_dead_letters_wait() {
+        // The channel only has room for one event.
+        let (tx, mut rx) = mpsc::channel(0);
+        let pending = Arc::new(PendingFinalizers::default());
+        pending.set_dead_letter(Some(DeadLetter::new("out", tx)));
+
+        let events = vec!["first", "second", "third"]
+            .into_iter()
+            .map(Event::from)
+            .collect::<Vec<_>>();
+        let mut read = FinalizingStream::new(stream::iter(events), Some(Arc::clone(&pending)));
+
+        read.next().await.unwrap();
+        read.next().await.unwrap();
+        let rejection = Rejection::new("bad request", 1);
+        assert_eq!(pending.ack(2, EventStatus::Failed, Some(&rejection)), 2);
+        assert!(read.next().now_or_never().is_none());
+
+        let first = rx.next().await.unwrap();
+        assert_eq!(first.as_log()[log_schema().message_key()], "first".into());
+        let third = read.next().await.unwrap();
+        assert_eq!(third.as_log()[log_schema().message_key()], "third".into());
+        let second = rx.next().await.unwrap();
+        assert_eq!(second.as_log()[log_schema().message_key()], "second".into());
+    }
+}
@@ -0,0 +1,122 @@
+//! Events permanently rejected by a sink can be routed to another component
+//! instead of being dropped. They are annotated with why they were rejected,
+//! so they can be inspected and replayed.
+
+use crate::{
+    event::Event,
+    internal_events::{DeadLetterEventDropped, DeadLetterEventSent},
+};
+use futures::channel::mpsc;
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+/// Why a sink gave up on a batch of events.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rejection {
+    pub error: String,
+    /// How often delivering the events was attempted, zero if they couldn't
+    /// even be encoded.
+    pub attempts: usize,
+}
+
+impl Rejection {
+    pub fn new(error: impl Into<String>, attempts: usize) -> Self {
+        Self {
+            error: error.into(),
+            attempts,
+        }
+    }
+}
+
+/// The sending end of a sink's dead letter output.
+#[derive(Clone, Debug)]
+pub struct DeadLetter {
+    sink: Arc<str>,
+    sender: mpsc::Sender<Event>,
+    /// Rejected events waiting for room in the channel.
+    waiting: VecDeque<Event>,
+}
+
+impl DeadLetter {
+    pub fn new(sink: &str, sender: mpsc::Sender<Event>) -> Self {
+        Self {
+            sink: sink.into(),
+            sender,
+            waiting: VecDeque::new(),
+        }
+    }
+
+    /// Rejections are reported while acking, which can't wait for the
+    /// components downstream, so events that don't fit in the channel wait
+    /// until `poll_flush` makes room for them.
+    pub(super) fn send(&mut self, mut event: Event, rejection: &Rejection) {
+        match &mut event {
+            Event::Log(log) => {
+                log.insert("dead_letter.error", rejection.error.clone());
+                log.insert("dead_letter.sink", self.sink.to_string());
+                log.insert("dead_letter.attempts", rejection.attempts as i64);
+            }
+            Event::Metric(metric) => {
+                metric.set_tag_value("dead_letter_error".into(), rejection.error.clone());
+                metric.set_tag_value("dead_letter_sink".into(), self.sink.to_string());
+                metric.set_tag_value(
+                    "dead_letter_attempts".into(),
+                    rejection.attempts.to_string(),
+                );
+            }
+        }
+
+        self.waiting.push_back(event);
+        self.try_flush();
+    }
+
+    /// Sends the waiting events for as long as there's room in the channel.
+    fn try_flush(&mut self) {
+        while let Some(event) = self.waiting.pop_front() {
+            match self.sender.try_send(event) {
+                Ok(()) => emit!(DeadLetterEventSent),
+                Err(error) if error.is_full() => {
+                    self.waiting.push_front(error.into_inner());
+                    break;
+                }
+                Err(_) => emit!(DeadLetterEventDropped),
+            }
+        }
+    }
+
+    /// Sends the waiting events, returning `Pending` until all of them are
+    /// sent. The sink reads no further events until then, so that it is
+    /// held back by the components downstream of its dead letter output.
+    pub(super) fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        while !self.waiting.is_empty() {
+            match self.sender.poll_ready(cx) {
+                Poll::Ready(Ok(())) => {
+                    let event = self.waiting.pop_front().expect("Event is waiting.");
+                    match self.sender.start_send(event) {
+                        Ok(()) => emit!(DeadLetterEventSent),
+                        Err(_) => emit!(DeadLetterEventDropped),
+                    }
+                }
+                Poll::Ready(Err(_)) => {
+                    for _ in self.waiting.drain(..) {
+                        emit!(DeadLetterEventDropped);
+                    }
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+        Poll::Ready(())
+    }
+}
+
+impl Drop for DeadLetter {
+    fn drop(&mut self) {
+        self.try_flush();
+        for _ in self.waiting.drain(..) {
+            emit!(DeadLetterEventDropped);
+        }
+    }
+}
@@ -0,0 +1,797 @@
+//! A disk buffer built on append-only segment files.
+//!
+//! Every event is stored as a record of `[length: u32][crc32: u32][payload]`,
+//! where the payload is the protobuf encoded event. Records are appended to
+//! the newest segment file and a new segment is started once the current one
+//! reaches the segment size. The position of the last acknowledged record is
+//! persisted in a small ledger file, so after a restart reading resumes from
+//! the first unacknowledged record. Segments which only contain acknowledged
+//! records are deleted.
+
+use super::{BufferIoError, Error};
+use crate::{
+    buffers::Acker,
+    event::{proto, Event},
+};
+use bytes::Bytes;
+use futures01::{
+    task::{self, AtomicTask, Task},
+    Async, AsyncSink, Poll, Sink, Stream,
+};
+use prost::Message;
+use snafu::ResultExt;
+use std::{
+    collections::VecDeque,
+    convert::TryInto,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+/// Upper bound on the size of a single segment file.
+const MAX_SEGMENT_SIZE: u64 = 64 * 1024 * 1024;
+/// The buffer is split into at least this many segments, so that space is
+/// reclaimed well before the whole buffer has been acknowledged.
+const MIN_SEGMENTS: u64 = 8;
+/// Number of records written before the writer flushes on its own.
+const WRITE_BATCH_SIZE: usize = 100;
+
+const RECORD_HEADER_SIZE: usize = 8;
+const LEDGER_FILE: &str = "ledger";
+const LEDGER_TMP_FILE: &str = "ledger.tmp";
+const SEGMENT_PREFIX: &str = "segment_";
+const SEGMENT_SUFFIX: &str = ".dat";
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct Position {
+    segment: u64,
+    offset: u64,
+}
+
+impl Position {
+    fn encode(self) -> [u8; 20] {
+        let mut buf = [0; 20];
+        buf[..8].copy_from_slice(&self.segment.to_be_bytes());
+        buf[8..16].copy_from_slice(&self.offset.to_be_bytes());
+        let checksum = crc32fast::hash(&buf[..16]);
+        buf[16..].copy_from_slice(&checksum.to_be_bytes());
+        buf
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        if buf.len() != 20 {
+            return None;
+        }
+        let checksum = u32::from_be_bytes(buf[16..].try_into().unwrap());
+        if crc32fast::hash(&buf[..16]) != checksum {
+            return None;
+        }
+        Some(Self {
+            segment: u64::from_be_bytes(buf[..8].try_into().unwrap()),
+            offset: u64::from_be_bytes(buf[8..16].try_into().unwrap()),
+        })
+    }
+}
+
+enum Record {
+    Valid(Vec<u8>),
+    /// The record is truncated, longer than the data available for it, or its
+    /// checksum doesn't match.
+    Corrupted,
+    /// There are no more records in the segment.
+    End,
+}
+
+fn segment_path(dir: &Path, segment: u64) -> PathBuf {
+    dir.join(format!(
+        "{}{:020}{}",
+        SEGMENT_PREFIX, segment, SEGMENT_SUFFIX
+    ))
+}
+
+fn parse_segment_name(name: &str) -> Option<u64> {
+    name.strip_prefix(SEGMENT_PREFIX)?
+        .strip_suffix(SEGMENT_SUFFIX)?
+        .parse()
+        .ok()
+}
+
+/// Reads the next record, which can't extend past the `available` bytes left
+/// in the segment.
+fn read_record(reader: &mut impl Read, available: u64) -> io::Result<Record> {
+    let mut header = [0; RECORD_HEADER_SIZE];
+    match read_exact_or_eof(reader, &mut header)? {
+        0 => return Ok(Record::End),
+        n if n < RECORD_HEADER_SIZE => return Ok(Record::Corrupted),
+        _ => {}
+    }
+
+    let len = u32::from_be_bytes(header[..4].try_into().unwrap()) as usize;
+    let checksum = u32::from_be_bytes(header[4..].try_into().unwrap());
+
+    // The header isn't covered by the checksum, so a damaged length is caught
+    // here before it's used to size the payload.
+    if (RECORD_HEADER_SIZE + len) as u64 > available {
+        return Ok(Record::Corrupted);
+    }
+
+    let mut payload = vec![0; len];
+    if read_exact_or_eof(reader, &mut payload)? < len || crc32fast::hash(&payload) != checksum {
+        return Ok(Record::Corrupted);
+    }
+
+    Ok(Record::Valid(payload))
+}
+
+/// Like `read_exact`, but returns the number of bytes read instead of failing
+/// when the end of the file is reached.
+fn read_exact_or_eof(reader: &mut impl Read, mut buf: &mut [u8]) -> io::Result<usize> {
+    let mut total = 0;
+    while !buf.is_empty() {
+        match reader.read(buf) {
+            Ok(0) => break,
+            Ok(n) => {
+                total += n;
+                buf = &mut buf[n..];
+            }
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(total)
+}
+
+fn read_ledger(dir: &Path) -> io::Result<Option<Position>> {
+    match fs::read(dir.join(LEDGER_FILE)) {
+        Ok(buf) => Ok(Position::decode(&buf)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Atomically replaces the ledger, so that a crash leaves either the old or
+/// the new acknowledged position behind.
+fn write_ledger(dir: &Path, position: Position) -> io::Result<()> {
+    let tmp_path = dir.join(LEDGER_TMP_FILE);
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(&position.encode())?;
+    file.sync_data()?;
+    fs::rename(tmp_path, dir.join(LEDGER_FILE))
+}
+
+struct WriteState {
+    file: BufWriter<File>,
+    /// End of the last appended record, which may not be flushed yet.
+    position: Position,
+    unflushed: usize,
+}
+
+impl WriteState {
+    fn append(&mut self, shared: &Shared, payload: &[u8]) -> io::Result<()> {
+        let record_size = (RECORD_HEADER_SIZE + payload.len()) as u64;
+        if self.position.offset > 0 && self.position.offset + record_size > shared.segment_size {
+            self.roll(shared)?;
+        }
+
+        self.file.write_all(&(payload.len() as u32).to_be_bytes())?;
+        self.file
+            .write_all(&crc32fast::hash(payload).to_be_bytes())?;
+        self.file.write_all(payload)?;
+
+        self.position.offset += record_size;
+        self.unflushed += 1;
+        Ok(())
+    }
+
+    fn flush(&mut self, shared: &Shared) -> io::Result<()> {
+        self.file.flush()?;
+        *shared.flushed.lock().unwrap() = self.position;
+        self.unflushed = 0;
+        Ok(())
+    }
+
+    fn roll(&mut self, shared: &Shared) -> io::Result<()> {
+        self.file.flush()?;
+
+        let segment = self.position.segment + 1;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(&shared.path, segment))?;
+        self.file = BufWriter::new(file);
+        self.position = Position { segment, offset: 0 };
+
+        // The new segment exists, so the reader can move past the old one.
+        *shared.flushed.lock().unwrap() = self.position;
+        self.unflushed = 0;
+        Ok(())
+    }
+}
+
+struct Shared {
+    path: PathBuf,
+    segment_size: u64,
+    write: Mutex<WriteState>,
+    /// Position up to which records are visible to the reader.
+    flushed: Mutex<Position>,
+}
+
+#[derive(Clone)]
+pub struct Writer {
+    shared: Arc<Shared>,
+    write_notifier: Arc<AtomicTask>,
+    blocked_write_tasks: Arc<Mutex<Vec<Task>>>,
+    max_size: usize,
+    current_size: Arc<AtomicUsize>,
+}
+
+impl Sink for Writer {
+    type SinkItem = Event;
+    type SinkError = ();
+
+    fn start_send(
+        &mut self,
+        event: Self::SinkItem,
+    ) -> Result<AsyncSink<Self::SinkItem>, Self::SinkError> {
+        let mut value = vec![];
+        proto::EventWrapper::from(event).encode(&mut value).unwrap(); // This will not error when writing to a Vec
+        let record_size = RECORD_HEADER_SIZE + value.len();
+
+        if !self.try_reserve(record_size) {
+            self.blocked_write_tasks
+                .lock()
+                .unwrap()
+                .push(task::current());
+
+            // The reader could have freed up space before the task was registered.
+            if !self.try_reserve(record_size) {
+                self.poll_complete()?;
+
+                let buf = Bytes::from(value);
+                let event = proto::EventWrapper::decode(buf).unwrap().into();
+                return Ok(AsyncSink::NotReady(event));
+            }
+        }
+
+        let mut state = self.shared.write.lock().unwrap();
+        state.append(&self.shared, &value).map_err(|error| {
+            error!(message = "Error writing to disk buffer.", %error);
+        })?;
+
+        if state.unflushed >= WRITE_BATCH_SIZE {
+            state.flush(&self.shared).map_err(|error| {
+                error!(message = "Error flushing disk buffer.", %error);
+            })?;
+            self.write_notifier.notify();
+        }
+
+        Ok(AsyncSink::Ready)
+    }
+
+    fn poll_complete(&mut self) -> Result<Async<()>, Self::SinkError> {
+        // Flushing hands the data to the OS, which is enough to survive a
+        // process crash. Anything torn by a host crash is truncated on open.
+        let mut state = self.shared.write.lock().unwrap();
+        if state.unflushed > 0 {
+            state.flush(&self.shared).map_err(|error| {
+                error!(message = "Error flushing disk buffer.", %error);
+            })?;
+            self.write_notifier.notify();
+        }
+
+        Ok(Async::Ready(()))
+    }
+}
+
+impl Writer {
+    fn try_reserve(&self, size: usize) -> bool {
+        let previous = self.current_size.fetch_add(size, Ordering::Relaxed);
+        // A single event larger than the buffer is still let through on its own,
+        // otherwise it would block the writer forever.
+        if previous == 0 || previous + size <= self.max_size {
+            true
+        } else {
+            self.current_size.fetch_sub(size, Ordering::Relaxed);
+            false
+        }
+    }
+}
+
+impl Drop for Writer {
+    fn drop(&mut self) {
+        let _ = self.poll_complete();
+
+        // We need to wake up the reader so it can return None if there are no more writers
+        self.write_notifier.notify();
+    }
+}
+
+pub struct Reader {
+    shared: Arc<Shared>,
+    file: Option<BufReader<File>>,
+    /// Length of the segment being read, once it's no longer written to.
+    segment_end: Option<u64>,
+    read_position: Position,
+    ack_position: Position,
+    write_notifier: Arc<AtomicTask>,
+    blocked_write_tasks: Arc<Mutex<Vec<Task>>>,
+    current_size: Arc<AtomicUsize>,
+    ack_counter: Arc<AtomicUsize>,
+    /// End position and size of every record handed out, but not yet acked.
+    unacked: VecDeque<(Position, usize)>,
+}
+
+impl Stream for Reader {
+    type Item = Event;
+    type Error = ();
+
+    fn poll(&mut self) -> Poll<Option<Self::Item>, Self::Error> {
+        self.delete_acked();
+
+        // If there's nothing left to read, we return NotReady and rely on Writer
+        // using write_notifier to wake this task up after the next flush.
+        self.write_notifier.register();
+
+        loop {
+            let flushed = *self.shared.flushed.lock().unwrap();
+            if self.read_position >= flushed {
+                return if Arc::strong_count(&self.shared) == 1 {
+                    // There are no writers left
+                    Ok(Async::Ready(None))
+                } else {
+                    Ok(Async::NotReady)
+                };
+            }
+
+            let record = self.read_next(flushed).map_err(|error| {
+                error!(message = "Error reading from disk buffer.", %error);
+            })?;
+
+            match record {
+                Record::Valid(value) => {
+                    let record_size = RECORD_HEADER_SIZE + value.len();
+                    self.read_position.offset += record_size as u64;
+
+                    match proto::EventWrapper::decode(Bytes::from(value)) {
+                        Ok(event) => {
+                            self.unacked.push_back((self.read_position, record_size));
+                            return Ok(Async::Ready(Some(Event::from(event))));
+                        }
+                        Err(error) => {
+                            error!(message = "Error deserializing proto.", %error);
+                            self.current_size.fetch_sub(record_size, Ordering::Relaxed);
+                        }
+                    }
+                }
+                Record::End if self.read_position.segment < flushed.segment => {
+                    self.next_segment();
+                }
+                Record::End => {
+                    // The flushed position is ahead of what can be read, the
+                    // file was modified outside of this buffer.
+                    error!(message = "Disk buffer segment ended unexpectedly.");
+                    self.skip_to(flushed);
+                }
+                Record::Corrupted => {
+                    error!(
+                        message =
+                            "Corrupted record in disk buffer; skipping the rest of the segment.",
+                        segment = self.read_position.segment
+                    );
+                    if self.read_position.segment < flushed.segment {
+                        let len = fs::metadata(segment_path(
+                            &self.shared.path,
+                            self.read_position.segment,
+                        ))
+                        .map(|metadata| metadata.len())
+                        .unwrap_or(self.read_position.offset);
+                        self.release(len.saturating_sub(self.read_position.offset));
+                        self.next_segment();
+                    } else {
+                        self.skip_to(flushed);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Reader {
+    fn drop(&mut self) {
+        self.delete_acked();
+    }
+}
+
+impl Reader {
+    fn read_next(&mut self, flushed: Position) -> io::Result<Record> {
+        if self.file.is_none() {
+            let path = segment_path(&self.shared.path, self.read_position.segment);
+            let mut file = match File::open(path) {
+                Ok(file) => file,
+                // Treat a missing segment like an empty one.
+                Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Record::End),
+                Err(error) => return Err(error),
+            };
+            file.seek(SeekFrom::Start(self.read_position.offset))?;
+            self.file = Some(BufReader::new(file));
+        }
+
+        let file = self.file.as_mut().expect("file was opened above");
+
+        // Records in the segment being written only become readable once
+        // flushed, while older segments are complete.
+        let end = match self.segment_end {
+            Some(end) => end,
+            None if self.read_position.segment < flushed.segment => {
+                let end = file.get_ref().metadata()?.len();
+                self.segment_end = Some(end);
+                end
+            }
+            None => flushed.offset,
+        };
+
+        read_record(file, end.saturating_sub(self.read_position.offset))
+    }
+
+    fn next_segment(&mut self) {
+        self.file = None;
+        self.segment_end = None;
+        self.read_position = Position {
+            segment: self.read_position.segment + 1,
+            offset: 0,
+        };
+    }
+
+    fn skip_to(&mut self, position: Position) {
+        if position.segment == self.read_position.segment {
+            self.release(position.offset.saturating_sub(self.read_position.offset));
+        }
+        self.file = None;
+        self.segment_end = None;
+        self.read_position = position;
+    }
+
+    /// Accounts for bytes which were skipped instead of being read and acked.
+    fn release(&self, size: u64) {
+        self.current_size
+            .fetch_sub(size as usize, Ordering::Relaxed);
+    }
+
+    fn delete_acked(&mut self) {
+        let num_to_delete = self.ack_counter.swap(0, Ordering::Relaxed);
+
+        if num_to_delete > 0 {
+            assert!(
+                num_to_delete <= self.unacked.len(),
+                "Tried to ack beyond read offset"
+            );
+
+            let previous = self.ack_position;
+            let mut size_deleted = 0;
+            for (position, size) in self.unacked.drain(..num_to_delete) {
+                self.ack_position = position;
+                size_deleted += size;
+            }
+            self.current_size.fetch_sub(size_deleted, Ordering::Relaxed);
+
+            if let Err(error) = write_ledger(&self.shared.path, self.ack_position) {
+                error!(message = "Error writing disk buffer ledger.", %error);
+            }
+
+            for segment in previous.segment..self.ack_position.segment {
+                if let Err(error) = fs::remove_file(segment_path(&self.shared.path, segment)) {
+                    if error.kind() != io::ErrorKind::NotFound {
+                        error!(message = "Error deleting disk buffer segment.", %error, segment);
+                    }
+                }
+            }
+        }
+
+        for task in self.blocked_write_tasks.lock().unwrap().drain(..) {
+            task.notify();
+        }
+    }
+}
+
+/// Checks all records after `start` in a segment and truncates the segment at
+/// the first damaged one. Returns the size of the valid records and the
+/// length of the segment.
+fn recover_segment(path: &Path, start: u64) -> io::Result<(u64, u64)> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let len = file.metadata()?.len();
+    file.seek(SeekFrom::Start(start))?;
+
+    let mut reader = BufReader::new(&mut file);
+    let mut offset = start;
+    loop {
+        match read_record(&mut reader, len.saturating_sub(offset))? {
+            Record::Valid(value) => offset += (RECORD_HEADER_SIZE + value.len()) as u64,
+            Record::End => break,
+            Record::Corrupted => {
+                warn!(
+                    message = "Truncating damaged disk buffer segment.",
+                    path = ?path,
+                    offset
+                );
+                drop(reader);
+                file.set_len(offset)?;
+                file.sync_all()?;
+                break;
+            }
+        }
+    }
+
+    Ok((offset - start, offset))
+}
+
+fn open_buffer(path: &Path, max_size: usize) -> io::Result<(Arc<Shared>, Position, usize)> {
+    fs::create_dir_all(path)?;
+
+    let mut segments = fs::read_dir(path)?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            parse_segment_name(entry.file_name().to_str()?)
+        })
+        .collect::<Vec<_>>();
+    segments.sort_unstable();
+
+    let mut ack_position = read_ledger(path)?.unwrap_or(Position {
+        segment: segments.first().copied().unwrap_or(0),
+        offset: 0,
+    });
+
+    // Segments that were fully acked before a crash, but not yet deleted.
+    for &segment in segments.iter().filter(|&&s| s < ack_position.segment) {
+        fs::remove_file(segment_path(path, segment))?;
+    }
+    segments.retain(|&s| s >= ack_position.segment);
+
+    let mut initial_size = 0;
+    let mut write_position = ack_position;
+    for &segment in &segments {
+        let start = if segment == ack_position.segment {
+            ack_position.offset
+        } else {
+            0
+        };
+        let (size, len) = recover_segment(&segment_path(path, segment), start)?;
+        initial_size += size;
+        write_position = Position {
+            segment,
+            offset: len,
+        };
+    }
+
+    if segments.is_empty() || write_position < ack_position {
+        // The ledger points past all remaining data, so start over from there.
+        ack_position.offset = 0;
+        write_position = ack_position;
+    }
+
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(segment_path(path, write_position.segment))?;
+
+    let segment_size = (max_size as u64 / MIN_SEGMENTS).min(MAX_SEGMENT_SIZE);
+    let shared = Arc::new(Shared {
+        path: path.to_path_buf(),
+        segment_size,
+        write: Mutex::new(WriteState {
+            file: BufWriter::new(file),
+            position: write_position,
+            unflushed: 0,
+        }),
+        flushed: Mutex::new(write_position),
+    });
+
+    Ok((shared, ack_position, initial_size as usize))
+}
+
+pub struct Buffer;
+
+impl super::DiskBuffer for Buffer {
+    type Writer = Writer;
+    type Reader = Reader;
+
+    fn build(path: PathBuf, max_size: usize) -> Result<(Self::Writer, Self::Reader, Acker), Error> {
+        let (shared, ack_position, initial_size) =
+            open_buffer(&path, max_size).with_context(|| BufferIoError { path: path.clone() })?;
+
+        let current_size = Arc::new(AtomicUsize::new(initial_size));
+
+        let write_notifier = Arc::new(AtomicTask::new());
+
+        let blocked_write_tasks = Arc::new(Mutex::new(Vec::new()));
+
+        let ack_counter = Arc::new(AtomicUsize::new(0));
+        let acker = Acker::Disk(Arc::clone(&ack_counter), Arc::clone(&write_notifier));
+
+        let writer = Writer {
+            shared: Arc::clone(&shared),
+            write_notifier: Arc::clone(&write_notifier),
+            blocked_write_tasks: Arc::clone(&blocked_write_tasks),
+            max_size,
+            current_size: Arc::clone(&current_size),
+        };
+
+        let reader = Reader {
+            shared,
+            file: None,
+            segment_end: None,
+            read_position: ack_position,
+            ack_position,
+            write_notifier,
+            blocked_write_tasks,
+            current_size,
+            ack_counter,
+            unacked: VecDeque::new(),
+        };
+
+        Ok((writer, reader, acker))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::DiskBuffer, segment_path, Buffer, Reader, Writer};
+    use crate::{buffers::Acker, config::log_schema, event::Event, test_util::temp_dir};
+    use futures01::{Async, AsyncSink, Sink, Stream};
+    use std::{fs::OpenOptions, io::Write, path::Path};
+    use tokio01_test::task::MockTask;
+
+    fn open(path: &Path, max_size: usize) -> (Writer, Reader, Acker) {
+        Buffer::build(path.to_path_buf(), max_size).unwrap()
+    }
+
+    fn write(writer: &mut Writer, messages: &[&str]) {
+        for message in messages {
+            let result = writer.start_send(Event::from(*message)).unwrap();
+            assert!(matches!(result, AsyncSink::Ready));
+        }
+        writer.poll_complete().unwrap();
+    }
+
+    fn read(reader: &mut Reader, count: usize) -> Vec<String> {
+        (0..count)
+            .map(|_| match reader.poll().unwrap() {
+                Async::Ready(Some(event)) => {
+                    event.as_log()[log_schema().message_key()].to_string_lossy()
+                }
+                other => panic!("Expected an event, got {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reads_what_was_written() {
+        let dir = temp_dir();
+        let (mut writer, mut reader, _acker) = open(&dir, 1_000_000);
+
+        MockTask::new().enter(|| {
+            write(&mut writer, &["one", "two", "three"]);
+            let events = read(&mut reader, 3);
+            assert_eq!(events[0], "one");
+            assert_eq!(events[2], "three");
+            assert_eq!(reader.poll(), Ok(Async::NotReady));
+
+            drop(writer);
+            assert_eq!(reader.poll(), Ok(Async::Ready(None)));
+        });
+    }
+
+    #[test]
+    fn resumes_from_acked_position() {
+        let dir = temp_dir();
+
+        MockTask::new().enter(|| {
+            let (mut writer, mut reader, acker) = open(&dir, 1_000_000);
+            write(&mut writer, &["one", "two", "three"]);
+            read(&mut reader, 3);
+            acker.ack(1);
+            assert_eq!(reader.poll(), Ok(Async::NotReady));
+        });
+
+        MockTask::new().enter(|| {
+            let (writer, mut reader, _acker) = open(&dir, 1_000_000);
+            let events = read(&mut reader, 2);
+            assert_eq!(events[0], "two");
+            assert_eq!(events[1], "three");
+            drop(writer);
+            assert_eq!(reader.poll(), Ok(Async::Ready(None)));
+        });
+    }
+
+    #[test]
+    fn rolls_and_deletes_segments() {
+        let dir = temp_dir();
+        // Small enough that every record gets its own segment.
+        let (mut writer, mut reader, acker) = open(&dir, 8);
+
+        MockTask::new().enter(|| {
+            for message in &["one", "two", "three"] {
+                write(&mut writer, &[*message]);
+                read(&mut reader, 1);
+                acker.ack(1);
+                assert_eq!(reader.poll(), Ok(Async::NotReady));
+            }
+        });
+
+        assert!(!segment_path(&dir, 0).exists());
+        assert!(!segment_path(&dir, 1).exists());
+        assert!(segment_path(&dir, 2).exists());
+    }
+
+    #[test]
+    fn blocks_when_full() {
+        let dir = temp_dir();
+        let (mut writer, mut reader, acker) = open(&dir, 30);
+
+        MockTask::new().enter(|| {
+            write(&mut writer, &["one"]);
+            let result = writer.start_send(Event::from("two")).unwrap();
+            assert!(matches!(result, AsyncSink::NotReady(_)));
+
+            read(&mut reader, 1);
+            acker.ack(1);
+            assert_eq!(reader.poll(), Ok(Async::NotReady));
+
+            write(&mut writer, &["two"]);
+        });
+    }
+
+    #[test]
+    fn rejects_damaged_record_length() {
+        let dir = temp_dir();
+
+        MockTask::new().enter(|| {
+            let (mut writer, _reader, _acker) = open(&dir, 1_000_000);
+            write(&mut writer, &["one", "two"]);
+        });
+
+        // Damage the length in the header of the first record.
+        let path = segment_path(&dir, 0);
+        let mut file = OpenOptions::new().write(true).open(&path).unwrap();
+        file.write_all(&u32::MAX.to_be_bytes()).unwrap();
+        drop(file);
+
+        MockTask::new().enter(|| {
+            let (mut writer, mut reader, _acker) = open(&dir, 1_000_000);
+            write(&mut writer, &["three"]);
+            let events = read(&mut reader, 1);
+            assert_eq!(events[0], "three");
+        });
+    }
+
+    #[test]
+    fn truncates_torn_write() {
+        let dir = temp_dir();
+
+        MockTask::new().enter(|| {
+            let (mut writer, _reader, _acker) = open(&dir, 1_000_000);
+            write(&mut writer, &["one", "two"]);
+        });
+
+        // Simulate a partially written record at the end of the segment.
+        let path = segment_path(&dir, 0);
+        let len = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_len(len - 2)
+            .unwrap();
+
+        MockTask::new().enter(|| {
+            let (mut writer, mut reader, _acker) = open(&dir, 1_000_000);
+            write(&mut writer, &["three"]);
+            let events = read(&mut reader, 2);
+            assert_eq!(events[0], "one");
+            assert_eq!(events[1], "three");
+        });
+    }
+}
@@ -1,5 +1,8 @@
-use crate::{config::Resource, Event};
-#[cfg(feature = "leveldb")]
+use crate::{
+    config::Resource,
+    event::{Event, EventStatus},
+    internal_events::BufferEventDropped,
+};
 use futures::compat::{Sink01CompatExt, Stream01CompatExt};
 use futures::{channel::mpsc, Sink, SinkExt, Stream};
 use futures01::task::AtomicTask;
@@ -14,11 +17,19 @@ use std::{
     },
     task::{Context, Poll},
 };
-#[cfg(feature = "leveldb")]
 use tokio::stream::StreamExt;
 
-#[cfg(feature = "leveldb")]
+mod acknowledgements;
+mod dead_letter;
 pub mod disk;
+mod overflow;
+mod usage;
+
+pub use acknowledgements::FinalizingStream;
+use acknowledgements::PendingFinalizers;
+pub use dead_letter::{DeadLetter, Rejection};
+pub use usage::BufferUsage;
+use usage::{UsageSink, UsageStream};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type")]
@@ -29,6 +40,9 @@ pub enum BufferConfig {
         max_events: usize,
         #[serde(default)]
         when_full: WhenFull,
+        /// Buffer that events spill into when `when_full` is `overflow`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        overflow: Option<Box<BufferConfig>>,
     },
     #[cfg(feature = "leveldb")]
     Disk {
@@ -36,6 +50,11 @@ pub enum BufferConfig {
         #[serde(default)]
         when_full: WhenFull,
     },
+    DiskV2 {
+        max_size: usize,
+        #[serde(default)]
+        when_full: WhenFull,
+    },
 }
 
 impl Default for BufferConfig {
@@ -43,6 +62,7 @@ impl Default for BufferConfig {
         BufferConfig::Memory {
             max_events: BufferConfig::memory_max_events(),
             when_full: Default::default(),
+            overflow: None,
         }
     }
 }
@@ -52,6 +72,7 @@ impl Default for BufferConfig {
 pub enum WhenFull {
     Block,
     DropNewest,
+    Overflow,
 }
 
 impl Default for WhenFull {
@@ -63,44 +84,106 @@ impl Default for WhenFull {
 #[derive(Clone)]
 pub enum BufferInputCloner {
     Memory(mpsc::Sender<Event>, WhenFull),
-    #[cfg(feature = "leveldb")]
     Disk(disk::Writer, WhenFull),
+    Overflow(
+        mpsc::Sender<Event>,
+        Box<BufferInputCloner>,
+        Arc<AtomicUsize>,
+    ),
+    Instrumented(Box<BufferInputCloner>, Arc<BufferUsage>),
 }
 
 impl BufferInputCloner {
     pub fn get(&self) -> Box<dyn Sink<Event, Error = ()> + Send> {
+        self.get_with_usage(None)
+    }
+
+    fn get_with_usage(
+        &self,
+        usage: Option<&Arc<BufferUsage>>,
+    ) -> Box<dyn Sink<Event, Error = ()> + Send> {
         match self {
             BufferInputCloner::Memory(tx, when_full) => {
                 let inner = tx
                     .clone()
                     .sink_map_err(|error| error!(message = "Sender error.", %error));
-                if when_full == &WhenFull::DropNewest {
-                    Box::new(DropWhenFull::new(inner))
-                } else {
-                    Box::new(inner)
-                }
+                track_usage(inner, *when_full, usage)
             }
 
-            #[cfg(feature = "leveldb")]
             BufferInputCloner::Disk(writer, when_full) => {
                 let inner = writer.clone().sink_compat();
-                if when_full == &WhenFull::DropNewest {
-                    Box::new(DropWhenFull::new(inner))
-                } else {
-                    Box::new(inner)
-                }
+                track_usage(inner, *when_full, usage)
             }
+
+            BufferInputCloner::Overflow(tx, overflow, pending) => {
+                let memory = tx
+                    .clone()
+                    .sink_map_err(|error| error!(message = "Sender error.", %error));
+                Box::new(overflow::OverflowSink::new(
+                    Pin::from(track_usage(memory, WhenFull::Block, usage)),
+                    Pin::from(overflow.get_with_usage(usage)),
+                    Arc::clone(pending),
+                ))
+            }
+
+            BufferInputCloner::Instrumented(inner, usage) => inner.get_with_usage(Some(usage)),
         }
     }
 }
 
+fn track_usage<S>(
+    inner: S,
+    when_full: WhenFull,
+    usage: Option<&Arc<BufferUsage>>,
+) -> Box<dyn Sink<Event, Error = ()> + Send>
+where
+    S: Sink<Event, Error = ()> + Send + Unpin + 'static,
+{
+    match usage {
+        Some(usage) => apply_when_full(
+            UsageSink::new(inner, Arc::clone(usage)),
+            when_full,
+            Some(usage),
+        ),
+        None => apply_when_full(inner, when_full, None),
+    }
+}
+
+fn apply_when_full<S>(
+    inner: S,
+    when_full: WhenFull,
+    usage: Option<&Arc<BufferUsage>>,
+) -> Box<dyn Sink<Event, Error = ()> + Send>
+where
+    S: Sink<Event, Error = ()> + Send + Unpin + 'static,
+{
+    if when_full == WhenFull::DropNewest {
+        Box::new(DropWhenFull::new(inner).with_usage(usage.cloned()))
+    } else {
+        Box::new(inner)
+    }
+}
+
+/// Reports the usage of a sink's buffer as internal metrics emitted in `span`.
+pub fn instrument(
+    tx: BufferInputCloner,
+    rx: Box<dyn Stream<Item = Event> + Send>,
+    span: tracing::Span,
+) -> (BufferInputCloner, Box<dyn Stream<Item = Event> + Send>) {
+    let usage = Arc::new(BufferUsage::new(span));
+    usage::report(Arc::downgrade(&usage));
+    let rx = Box::new(UsageStream::new(Pin::from(rx), Arc::clone(&usage)));
+    (BufferInputCloner::Instrumented(Box::new(tx), usage), rx)
+}
+
+const OVERFLOW_FROM_DISK: &str = "Only memory buffers can overflow.";
+
 impl BufferConfig {
     #[inline]
     const fn memory_max_events() -> usize {
         500
     }
 
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
     pub fn build(
         &self,
         data_dir: &Option<PathBuf>,
@@ -114,16 +197,58 @@ impl BufferConfig {
         String,
     > {
         match &self {
+            BufferConfig::Memory {
+                max_events,
+                when_full: WhenFull::Overflow,
+                overflow,
+            } => {
+                let overflow = overflow.as_ref().ok_or_else(|| {
+                    "Must set an overflow buffer to use `when_full = \"overflow\"`.".to_string()
+                })?;
+                if let BufferConfig::Memory { .. } = **overflow {
+                    return Err("Memory buffers can only overflow into a disk buffer.".into());
+                }
+
+                let (tx, rx) = mpsc::channel(*max_events);
+                let (overflow_tx, overflow_rx, overflow_acker) =
+                    overflow.build(data_dir, sink_name)?;
+                Ok(overflow::build(
+                    tx,
+                    rx,
+                    overflow_tx,
+                    overflow_rx,
+                    overflow_acker,
+                ))
+            }
+
             BufferConfig::Memory {
                 max_events,
                 when_full,
+                overflow,
             } => {
+                if overflow.is_some() {
+                    return Err(
+                        "The overflow buffer is only used with `when_full = \"overflow\"`.".into(),
+                    );
+                }
+
                 let (tx, rx) = mpsc::channel(*max_events);
                 let tx = BufferInputCloner::Memory(tx, *when_full);
                 let rx = Box::new(rx);
                 Ok((tx, rx, Acker::Null))
             }
 
+            #[cfg(feature = "leveldb")]
+            BufferConfig::Disk {
+                when_full: WhenFull::Overflow,
+                ..
+            } => Err(OVERFLOW_FROM_DISK.into()),
+
+            BufferConfig::DiskV2 {
+                when_full: WhenFull::Overflow,
+                ..
+            } => Err(OVERFLOW_FROM_DISK.into()),
+
             #[cfg(feature = "leveldb")]
             BufferConfig::Disk {
                 max_size,
@@ -144,16 +269,40 @@ impl BufferConfig {
                 );
                 Ok((tx, rx, acker))
             }
+
+            BufferConfig::DiskV2 {
+                max_size,
+                when_full,
+            } => {
+                let data_dir = data_dir
+                    .as_ref()
+                    .ok_or_else(|| "Must set data_dir to use on-disk buffering.".to_string())?;
+                let buffer_dir = format!("{}_buffer_v2", sink_name);
+
+                let (tx, rx, acker) =
+                    disk::open_segmented(&data_dir, buffer_dir.as_ref(), *max_size)
+                        .map_err(|error| error.to_string())?;
+                let tx = BufferInputCloner::Disk(tx, *when_full);
+                let rx = Box::new(
+                    rx.compat()
+                        .take_while(|event| event.is_ok())
+                        .map(|event| event.unwrap()),
+                );
+                Ok((tx, rx, acker))
+            }
         }
     }
 
     /// Resources that the sink is using.
-    #[cfg_attr(not(feature = "leveldb"), allow(unused))]
     pub fn resources(&self, sink_name: &str) -> Vec<Resource> {
         match self {
-            BufferConfig::Memory { .. } => Vec::new(),
+            BufferConfig::Memory { overflow, .. } => overflow
+                .as_ref()
+                .map(|overflow| overflow.resources(sink_name))
+                .unwrap_or_default(),
             #[cfg(feature = "leveldb")]
             BufferConfig::Disk { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
+            BufferConfig::DiskV2 { .. } => vec![Resource::DiskBuffer(sink_name.to_string())],
         }
     }
 }
@@ -161,6 +310,10 @@ impl BufferConfig {
 #[derive(Debug, Clone)]
 pub enum Acker {
     Disk(Arc<AtomicUsize>, Arc<AtomicTask>),
+    Overflow(Arc<overflow::OverflowAcker>),
+    /// Finalizes the events read by the sink as they are acked, before
+    /// passing the acks on to the buffer.
+    Finalizing(Arc<PendingFinalizers>, Box<Acker>),
     Null,
 }
 
@@ -172,6 +325,13 @@ impl Acker {
     // This is primary used by the on-disk buffer to know which events are okay to
     // delete from disk.
     pub fn ack(&self, num: usize) {
+        self.ack_with_status(num, EventStatus::Delivered)
+    }
+
+    // Like `ack`, but for events the sink is done with without having delivered
+    // them. The buffer treats them the same, but the sources waiting for
+    // end-to-end acknowledgements are told about the failure.
+    pub fn ack_with_status(&self, num: usize, status: EventStatus) {
         // Only ack items if the amount to ack is larger than zero.
         if num > 0 {
             match self {
@@ -180,7 +340,64 @@ impl Acker {
                     counter.fetch_add(num, Ordering::Relaxed);
                     notifier.notify();
                 }
+                Acker::Overflow(acker) => acker.ack(num),
+                Acker::Finalizing(pending, inner) => {
+                    inner.ack(pending.ack(num, status, None));
+                }
+            }
+        }
+    }
+
+    // Like `ack`, but for events the sink permanently rejected. They are sent
+    // to the sink's dead letter output, if it has one.
+    pub fn reject(&self, num: usize, rejection: Rejection) {
+        match self {
+            Acker::Finalizing(pending, inner) if num > 0 => {
+                inner.ack(pending.ack(num, EventStatus::Failed, Some(&rejection)));
             }
+            _ => self.ack_with_status(num, EventStatus::Failed),
+        }
+    }
+
+    // Rejects the event the sink read last, before it became part of any
+    // batch, e.g. because it couldn't be encoded. Has to be called before the
+    // sink reads its next event.
+    pub fn reject_last(&self, rejection: Rejection) {
+        if let Acker::Finalizing(pending, inner) = self {
+            inner.ack(pending.reject_last(&rejection));
+        }
+    }
+
+    /// Wraps this acker to finalize events once they're acked. The sink's
+    /// input has to be passed through `finalizing` for this to take effect.
+    pub fn with_finalization(self) -> Self {
+        Acker::Finalizing(Arc::new(PendingFinalizers::default()), Box::new(self))
+    }
+
+    /// Takes the finalizers off the events in `input`, to be finalized once
+    /// the sink acks them. Copies of the events are kept for `dead_letter`,
+    /// if given.
+    pub fn finalizing<S: Stream<Item = Event>>(
+        &self,
+        input: S,
+        dead_letter: Option<DeadLetter>,
+    ) -> FinalizingStream<S> {
+        let pending = match self {
+            Acker::Finalizing(pending, _) => {
+                pending.set_dead_letter(dead_letter);
+                Some(Arc::clone(pending))
+            }
+            _ => None,
+        };
+        FinalizingStream::new(input, pending)
+    }
+
+    /// Reports all events read but not acked by a sink that has stopped as
+    /// errored, so that the acks of the next sink reading from this buffer
+    /// line up with its own events. Closes the dead letter output.
+    pub fn abandon_pending(&self) {
+        if let Acker::Finalizing(pending, _) = self {
+            pending.abandon();
         }
     }
 
@@ -198,11 +415,20 @@ pub struct DropWhenFull<S> {
     #[pin]
     inner: S,
     drop: bool,
+    usage: Option<Arc<BufferUsage>>,
 }
 
 impl<S> DropWhenFull<S> {
     pub fn new(inner: S) -> Self {
-        Self { inner, drop: false }
+        Self {
+            inner,
+            drop: false,
+            usage: None,
+        }
+    }
+
+    pub fn with_usage(self, usage: Option<Arc<BufferUsage>>) -> Self {
+        Self { usage, ..self }
     }
 }
 
@@ -226,10 +452,10 @@ impl<T, S: Sink<T> + Unpin> Sink<T> for DropWhenFull<S> {
 
     fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
         if self.drop {
-            debug!(
-                message = "Shedding load; dropping event.",
-                internal_log_rate_secs = 10
-            );
+            match &self.usage {
+                Some(usage) => usage.dropped(),
+                None => emit!(BufferEventDropped),
+            }
             Ok(())
         } else {
             self.project().inner.start_send(item)
@@ -315,6 +541,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 500,
                 when_full: WhenFull::Block,
+                overflow: None,
             },
         );
 
@@ -326,6 +553,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 100,
                 when_full: WhenFull::Block,
+                overflow: None,
             },
         );
 
@@ -337,6 +565,7 @@ mod test {
             BufferConfig::Memory {
                 max_events: 500,
                 when_full: WhenFull::DropNewest,
+                overflow: None,
             },
         );
 
@@ -351,5 +580,53 @@ mod test {
                 when_full: WhenFull::Block,
             },
         );
+
+        check(
+            r#"
+          type = "disk_v2"
+          max_size = 1024
+          when_full = "drop_newest"
+          "#,
+            BufferConfig::DiskV2 {
+                max_size: 1024,
+                when_full: WhenFull::DropNewest,
+            },
+        );
+
+        check(
+            r#"
+          type = "memory"
+          when_full = "overflow"
+
+          [overflow]
+          type = "disk_v2"
+          max_size = 1024
+          "#,
+            BufferConfig::Memory {
+                max_events: 500,
+                when_full: WhenFull::Overflow,
+                overflow: Some(Box::new(BufferConfig::DiskV2 {
+                    max_size: 1024,
+                    when_full: WhenFull::Block,
+                })),
+            },
+        );
+    }
+
+    #[test]
+    fn overflow_requires_disk_buffer() {
+        let missing = BufferConfig::Memory {
+            max_events: 10,
+            when_full: WhenFull::Overflow,
+            overflow: None,
+        };
+        assert!(missing.build(&None, "out").is_err());
+
+        let memory = BufferConfig::Memory {
+            max_events: 10,
+            when_full: WhenFull::Overflow,
+            overflow: Some(Box::new(BufferConfig::default())),
+        };
+        assert!(memory.build(&None, "out").is_err());
     }
 }
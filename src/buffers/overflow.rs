@@ -0,0 +1,310 @@
+//! Tiered buffering: events are kept in the in-memory channel until it is
+//! full and then spill over into a secondary (disk) buffer.
+//!
+//! To preserve ordering, once anything has spilled over every following event
+//! goes to the overflow buffer as well, until the reader has drained it again.
+//! The reader always prefers the in-memory channel, which at that point only
+//! holds events older than the ones in the overflow buffer.
+
+use super::{Acker, BufferInputCloner};
+use crate::Event;
+use futures::{channel::mpsc, ready, Sink, Stream};
+use pin_project::pin_project;
+use std::{
+    collections::VecDeque,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+};
+
+pub fn build(
+    memory_tx: mpsc::Sender<Event>,
+    memory_rx: mpsc::Receiver<Event>,
+    overflow_tx: BufferInputCloner,
+    overflow_rx: Box<dyn Stream<Item = Event> + Send>,
+    overflow_acker: Acker,
+) -> (
+    BufferInputCloner,
+    Box<dyn Stream<Item = Event> + Send>,
+    Acker,
+) {
+    let pending = Arc::new(AtomicUsize::new(0));
+    let acker = Arc::new(OverflowAcker {
+        delivered: Mutex::new(VecDeque::new()),
+        overflow: overflow_acker,
+    });
+
+    let tx = BufferInputCloner::Overflow(memory_tx, Box::new(overflow_tx), Arc::clone(&pending));
+    let rx = Box::new(OverflowStream {
+        memory: memory_rx,
+        memory_done: false,
+        overflow: overflow_rx.into(),
+        pending,
+        acker: Arc::clone(&acker),
+    });
+
+    (tx, rx, Acker::Overflow(acker))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Tier {
+    Memory,
+    Overflow,
+}
+
+#[pin_project]
+pub struct OverflowSink<M, O> {
+    #[pin]
+    memory: M,
+    #[pin]
+    overflow: O,
+    /// Number of events written to the overflow buffer which haven't been read yet.
+    pending: Arc<AtomicUsize>,
+    target: Option<Tier>,
+}
+
+impl<M, O> OverflowSink<M, O> {
+    pub fn new(memory: M, overflow: O, pending: Arc<AtomicUsize>) -> Self {
+        Self {
+            memory,
+            overflow,
+            pending,
+            target: None,
+        }
+    }
+}
+
+impl<M, O> Sink<Event> for OverflowSink<M, O>
+where
+    M: Sink<Event, Error = ()>,
+    O: Sink<Event, Error = ()>,
+{
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+
+        if this.pending.load(Ordering::Acquire) == 0 {
+            if let Poll::Ready(result) = this.memory.poll_ready(cx) {
+                *this.target = Some(Tier::Memory);
+                return Poll::Ready(result);
+            }
+        }
+
+        ready!(this.overflow.poll_ready(cx))?;
+        *this.target = Some(Tier::Overflow);
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
+        let this = self.project();
+        match this.target.take() {
+            Some(Tier::Memory) => this.memory.start_send(item),
+            Some(Tier::Overflow) => {
+                // Counted before the write, so that nothing can overtake this
+                // event through the in-memory channel.
+                this.pending.fetch_add(1, Ordering::AcqRel);
+                this.overflow.start_send(item)
+            }
+            None => panic!("start_send called without poll_ready being called first"),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let memory = this.memory.poll_flush(cx)?;
+        let overflow = this.overflow.poll_flush(cx)?;
+        if memory.is_ready() && overflow.is_ready() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        let this = self.project();
+        let memory = this.memory.poll_close(cx)?;
+        let overflow = this.overflow.poll_close(cx)?;
+        if memory.is_ready() && overflow.is_ready() {
+            Poll::Ready(Ok(()))
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+struct OverflowStream {
+    memory: mpsc::Receiver<Event>,
+    memory_done: bool,
+    overflow: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    pending: Arc<AtomicUsize>,
+    acker: Arc<OverflowAcker>,
+}
+
+impl Stream for OverflowStream {
+    type Item = Event;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if !self.memory_done {
+            match Pin::new(&mut self.memory).poll_next(cx) {
+                Poll::Ready(Some(event)) => {
+                    self.acker.delivered(Tier::Memory);
+                    return Poll::Ready(Some(event));
+                }
+                Poll::Ready(None) => self.memory_done = true,
+                Poll::Pending => {}
+            }
+        }
+
+        match self.overflow.as_mut().poll_next(cx) {
+            Poll::Ready(Some(event)) => {
+                // Events persisted by a previous run aren't counted as pending.
+                let _ = self
+                    .pending
+                    .fetch_update(Ordering::AcqRel, Ordering::Acquire, |pending| {
+                        pending.checked_sub(1)
+                    });
+                self.acker.delivered(Tier::Overflow);
+                Poll::Ready(Some(event))
+            }
+            Poll::Ready(None) if self.memory_done => Poll::Ready(None),
+            Poll::Ready(None) | Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Routes acks to the overflow buffer for the events that were read from it.
+#[derive(Debug)]
+pub struct OverflowAcker {
+    /// Runs of consecutive events delivered from the same tier, in order.
+    delivered: Mutex<VecDeque<(Tier, usize)>>,
+    overflow: Acker,
+}
+
+impl OverflowAcker {
+    fn delivered(&self, tier: Tier) {
+        let mut delivered = self.delivered.lock().unwrap();
+        match delivered.back_mut() {
+            Some((last, count)) if *last == tier => *count += 1,
+            _ => delivered.push_back((tier, 1)),
+        }
+    }
+
+    pub(super) fn ack(&self, mut num: usize) {
+        let mut overflow_acks = 0;
+        {
+            let mut delivered = self.delivered.lock().unwrap();
+            while num > 0 {
+                let (tier, count) = match delivered.front_mut() {
+                    Some(front) => front,
+                    None => break,
+                };
+                let acked = num.min(*count);
+                if *tier == Tier::Overflow {
+                    overflow_acks += acked;
+                }
+                *count -= acked;
+                num -= acked;
+                if *count == 0 {
+                    delivered.pop_front();
+                }
+            }
+        }
+        self.overflow.ack(overflow_acks);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::build;
+    use crate::{
+        buffers::{Acker, BufferInputCloner},
+        config::log_schema,
+        Event,
+    };
+    use futures::{channel::mpsc, future, Sink, Stream};
+    use std::{
+        pin::Pin,
+        sync::{atomic::Ordering, Arc},
+        task::Poll,
+    };
+
+    #[tokio::test]
+    async fn spills_over_and_drains_in_order() {
+        future::lazy(|cx| {
+            let (memory_tx, memory_rx) = mpsc::channel(0);
+            let (overflow_tx, overflow_rx) = mpsc::channel(10);
+            let (acker, ack_counter) = Acker::new_for_testing();
+            let (tx, rx, acker) = build(
+                memory_tx,
+                memory_rx,
+                BufferInputCloner::Memory(overflow_tx, Default::default()),
+                Box::new(overflow_rx),
+                acker,
+            );
+
+            let mut tx = Pin::from(tx.get());
+            for i in 0..4 {
+                assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+                assert_eq!(tx.as_mut().start_send(Event::from(i.to_string())), Ok(()));
+            }
+
+            let mut rx = Pin::from(rx);
+            for i in 0..4 {
+                match rx.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(event)) => assert_eq!(
+                        event.as_log()[log_schema().message_key()],
+                        i.to_string().into()
+                    ),
+                    other => panic!("Expected an event, got {:?}", other),
+                }
+            }
+            assert_eq!(rx.as_mut().poll_next(cx), Poll::Pending);
+
+            // Only the events read from the overflow buffer are acked there.
+            acker.ack(4);
+            assert_eq!(ack_counter.load(Ordering::Relaxed), 3);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn returns_to_memory_once_drained() {
+        future::lazy(|cx| {
+            let (memory_tx, memory_rx) = mpsc::channel(0);
+            let (overflow_tx, overflow_rx) = mpsc::channel(10);
+            let (tx, rx, _acker) = build(
+                memory_tx,
+                memory_rx,
+                BufferInputCloner::Memory(overflow_tx, Default::default()),
+                Box::new(overflow_rx),
+                Acker::Null,
+            );
+            let pending = match &tx {
+                BufferInputCloner::Overflow(_, _, pending) => Arc::clone(pending),
+                _ => unreachable!(),
+            };
+
+            let mut tx = Pin::from(tx.get());
+            let mut rx = Pin::from(rx);
+            for i in 0..2 {
+                assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+                assert_eq!(tx.as_mut().start_send(Event::from(i.to_string())), Ok(()));
+            }
+            assert_eq!(pending.load(Ordering::Relaxed), 1);
+
+            for _ in 0..2 {
+                assert!(matches!(rx.as_mut().poll_next(cx), Poll::Ready(Some(_))));
+            }
+            assert_eq!(pending.load(Ordering::Relaxed), 0);
+
+            assert_eq!(tx.as_mut().poll_ready(cx), Poll::Ready(Ok(())));
+            assert_eq!(tx.as_mut().start_send(Event::from("2")), Ok(()));
+            assert_eq!(pending.load(Ordering::Relaxed), 0);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    event::{Event, MetricValue, Value},
+    internal_events::{
+        BufferEventDropped, BufferEventReceived, BufferEventSent, BufferUsageUpdated,
+    },
+};
+use futures::{Sink, Stream};
+use pin_project::pin_project;
+use std::{
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Weak,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tracing::Span;
+
+/// How often the gauges of the events and bytes held in a buffer are updated.
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Tracks how many events, and roughly how many bytes, a sink's buffer holds.
+///
+/// Writes to the buffer happen on the tasks of the upstream components, so
+/// all metrics are emitted inside the span of the sink owning the buffer.
+#[derive(Debug)]
+pub struct BufferUsage {
+    span: Span,
+    events: AtomicUsize,
+    byte_size: AtomicUsize,
+}
+
+impl BufferUsage {
+    pub fn new(span: Span) -> Self {
+        Self {
+            span,
+            events: AtomicUsize::new(0),
+            byte_size: AtomicUsize::new(0),
+        }
+    }
+
+    fn received(&self, byte_size: usize) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+        self.byte_size.fetch_add(byte_size, Ordering::Relaxed);
+        self.span
+            .in_scope(|| emit!(BufferEventReceived { byte_size }));
+    }
+
+    fn sent(&self, byte_size: usize) {
+        // Disk buffers can hold events written before this instance started,
+        // so the counts saturate instead of wrapping around.
+        saturating_sub(&self.events, 1);
+        saturating_sub(&self.byte_size, byte_size);
+        self.span.in_scope(|| emit!(BufferEventSent { byte_size }));
+    }
+
+    pub(super) fn dropped(&self) {
+        self.span.in_scope(|| emit!(BufferEventDropped));
+    }
+
+    fn current(&self) -> (usize, usize) {
+        (
+            self.events.load(Ordering::Relaxed),
+            self.byte_size.load(Ordering::Relaxed),
+        )
+    }
+}
+
+/// Updates the usage gauges every `REPORT_INTERVAL`, rather than on every event, for as
+/// long as the buffer is in use. Unchanged usage isn't emitted again.
+pub fn report(usage: Weak<BufferUsage>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(REPORT_INTERVAL);
+        let mut last = None;
+        loop {
+            interval.tick().await;
+            let usage = match usage.upgrade() {
+                Some(usage) => usage,
+                None => break,
+            };
+
+            let current = usage.current();
+            if last != Some(current) {
+                let (events, byte_size) = current;
+                usage
+                    .span
+                    .in_scope(|| emit!(BufferUsageUpdated { events, byte_size }));
+                last = Some(current);
+            }
+        }
+    });
+}
+
+fn saturating_sub(value: &AtomicUsize, amount: usize) {
+    let _ = value.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(amount))
+    });
+}
+
+/// Counts the events accepted by the inner sink as received by the buffer.
+#[pin_project]
+pub struct UsageSink<S> {
+    #[pin]
+    inner: S,
+    usage: Arc<BufferUsage>,
+}
+
+impl<S> UsageSink<S> {
+    pub fn new(inner: S, usage: Arc<BufferUsage>) -> Self {
+        Self { inner, usage }
+    }
+}
+
+impl<S: Sink<Event>> Sink<Event> for UsageSink<S> {
+    type Error = S::Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_ready(cx)
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
+        let this = self.project();
+        let byte_size = event_byte_size(&item);
+        this.inner.start_send(item)?;
+        this.usage.received(byte_size);
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.project().inner.poll_close(cx)
+    }
+}
+
+/// Counts the events read out of the buffer as sent.
+#[pin_project]
+pub struct UsageStream<S> {
+    #[pin]
+    inner: S,
+    usage: Arc<BufferUsage>,
+}
+
+impl<S> UsageStream<S> {
+    pub fn new(inner: S, usage: Arc<BufferUsage>) -> Self {
+        Self { inner, usage }
+    }
+}
+
+impl<S: Stream<Item = Event>> Stream for UsageStream<S> {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let poll = this.inner.poll_next(cx);
+        if let Poll::Ready(Some(event)) = &poll {
+            this.usage.sent(event_byte_size(event));
+        }
+        poll
+    }
+}
+
+/// An estimate of the in-memory size of an event's data.
+pub fn event_byte_size(event: &Event) -> usize {
+    match event {
+        Event::Log(log) => log
+            .as_map()
+            .iter()
+            .map(|(key, value)| key.len() + value_byte_size(value))
+            .sum(),
+        Event::Metric(metric) => {
+            let series = metric.name().len()
+                + metric.namespace().map(str::len).unwrap_or(0)
+                + metric
+                    .tags()
+                    .map(|tags| tags.iter().map(|(k, v)| k.len() + v.len()).sum::<usize>())
+                    .unwrap_or(0);
+            let value = match &metric.data.value {
+                MetricValue::Counter { .. } | MetricValue::Gauge { .. } => 8,
+                MetricValue::Set { values } => values.iter().map(String::len).sum::<usize>(),
+                MetricValue::Distribution { samples, .. } => samples.len() * 12,
+                MetricValue::AggregatedHistogram { buckets, .. } => buckets.len() * 12 + 12,
+                MetricValue::AggregatedSummary { quantiles, .. } => quantiles.len() * 16 + 12,
+            };
+            series + value
+        }
+    }
+}
+
+fn value_byte_size(value: &Value) -> usize {
+    match value {
+        Value::Bytes(bytes) => bytes.len(),
+        Value::Map(map) => map
+            .iter()
+            .map(|(key, value)| key.len() + value_byte_size(value))
+            .sum(),
+        Value::Array(array) => array.iter().map(value_byte_size).sum(),
+        Value::Integer(_) | Value::Float(_) | Value::Timestamp(_) => 8,
+        Value::Boolean(_) => 1,
+        Value::Null => 0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{event_byte_size, BufferUsage, UsageSink, UsageStream};
+    use crate::event::{Event, LogEvent};
+    use futures::{channel::mpsc, SinkExt, StreamExt};
+    use std::sync::{atomic::Ordering, Arc};
+    use tracing::Span;
+
+    #[test]
+    fn log_byte_size() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("flag", true);
+        assert_eq!(event_byte_size(&Event::from(log)), 7 + 5 + 4 + 1);
+    }
+
+    #[tokio::test]
+    async fn tracks_held_events() {
+        let usage = Arc::new(BufferUsage::new(Span::none()));
+        let (tx, rx) = mpsc::channel(10);
+        let mut tx = UsageSink::new(tx, Arc::clone(&usage));
+        let mut rx = UsageStream::new(rx, Arc::clone(&usage));
+
+        tx.send(Event::from("one")).await.unwrap();
+        tx.send(Event::from("two")).await.unwrap();
+        assert_eq!(usage.events.load(Ordering::Relaxed), 2);
+
+        rx.next().await.unwrap();
+        assert_eq!(usage.events.load(Ordering::Relaxed), 1);
+        rx.next().await.unwrap();
+        assert_eq!(usage.events.load(Ordering::Relaxed), 0);
+        assert_eq!(usage.byte_size.load(Ordering::Relaxed), 0);
+    }
+}
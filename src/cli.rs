@@ -80,6 +80,16 @@ pub struct RootOpts {
     #[structopt(name = "config-yaml", long, env = "VECTOR_CONFIG_YAML")]
     pub config_paths_yaml: Vec<PathBuf>,
 
+    /// Read configuration from one or more HTTP(S) URLs, in addition to any
+    /// files. File format is detected from the URL path, falling back to TOML.
+    /// The configs are fetched again on reload, and polled for changes.
+    #[structopt(name = "config-url", long, env = "VECTOR_CONFIG_URL")]
+    pub config_urls: Vec<http::Uri>,
+
+    /// How often, in seconds, configs given with `--config-url` are polled for changes.
+    #[structopt(long, default_value = "60", env = "VECTOR_CONFIG_URL_INTERVAL_SECS")]
+    pub config_url_interval_secs: u64,
+
     /// Exit on startup if any sinks fail healthchecks
     #[structopt(short, long, env = "VECTOR_REQUIRE_HEALTHY")]
     pub require_healthy: Option<bool>,
@@ -0,0 +1,291 @@
+//! Parsing the frames read by sources into events.
+
+use super::FramingConfig;
+use crate::{
+    config::log_schema,
+    event::{proto, Event, Value},
+};
+use bytes::Bytes;
+use chrono::{Datelike, Utc};
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use snafu::{ResultExt, Snafu};
+use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
+
+#[derive(Debug, Snafu)]
+pub enum DecodingError {
+    #[snafu(display("Invalid JSON: {}", source))]
+    InvalidJson { source: serde_json::Error },
+    #[snafu(display("Expected a JSON object, got {}", found))]
+    NotAnObject { found: &'static str },
+    #[snafu(display("Invalid UTF-8: {}", source))]
+    InvalidUtf8 { source: std::str::Utf8Error },
+    #[snafu(display("Invalid protobuf: {}", source))]
+    InvalidProtobuf { source: prost::DecodeError },
+    #[snafu(display("Failed to decompress: {}", source))]
+    Decompression { source: std::io::Error },
+    #[snafu(display("Decompressed message is longer than {} bytes", max_length))]
+    DecompressedTooLong { max_length: usize },
+    #[snafu(display("GELF messages require a string `{}` field", field))]
+    MissingGelfField { field: &'static str },
+    #[snafu(display("Invalid GELF timestamp {}", timestamp))]
+    InvalidGelfTimestamp { timestamp: f64 },
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "codec", rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum DecodingConfig {
+    /// The frame is used as the message as is.
+    #[derivative(Default)]
+    Bytes,
+    /// The frame holds a JSON object, or an array of them, whose fields
+    /// become the fields of the events.
+    Json,
+    /// The frame holds a syslog message in RFC 5424 or RFC 3164 format.
+    Syslog,
+    /// The frame holds an event as encoded by the `vector` sink.
+    Protobuf,
+    /// The frame holds a GELF message, which may be compressed with gzip or
+    /// zlib.
+    Gelf,
+}
+
+impl DecodingConfig {
+    /// The framing used by stream based sources when none is configured:
+    /// null delimited frames for GELF, as sent over TCP, and lines otherwise.
+    pub fn default_stream_framing(&self, max_length: usize) -> FramingConfig {
+        match self {
+            Self::Gelf => FramingConfig::CharacterDelimited {
+                delimiter: '\0',
+                max_length: Some(max_length),
+            },
+            _ => FramingConfig::newline_delimited(max_length),
+        }
+    }
+
+    /// The framing used by datagram based sources when none is configured:
+    /// datagrams holding GELF messages or their chunks, and lines otherwise.
+    pub fn default_datagram_framing(&self, max_length: usize) -> FramingConfig {
+        match self {
+            Self::Gelf => FramingConfig::ChunkedGelf {
+                max_length: Some(max_length),
+            },
+            _ => FramingConfig::newline_delimited(max_length),
+        }
+    }
+
+    /// Builds the deserializer, with GELF messages limited to `max_length`
+    /// bytes once decompressed.
+    pub fn build(&self, max_length: usize) -> Deserializer {
+        match self {
+            Self::Bytes => Deserializer::Bytes,
+            Self::Json => Deserializer::Json,
+            Self::Syslog => Deserializer::Syslog,
+            Self::Protobuf => Deserializer::Protobuf,
+            Self::Gelf => Deserializer::Gelf { max_length },
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Deserializer {
+    Bytes,
+    Json,
+    Syslog,
+    Protobuf,
+    Gelf { max_length: usize },
+}
+
+impl Deserializer {
+    pub fn parse(&self, frame: Bytes) -> Result<Vec<Event>, DecodingError> {
+        match self {
+            Self::Bytes => Ok(vec![Event::from(frame)]),
+            Self::Json => parse_json(&frame),
+            Self::Syslog => {
+                let line = std::str::from_utf8(&frame).context(InvalidUtf8)?;
+                Ok(vec![event_from_syslog(line)])
+            }
+            Self::Protobuf => proto::EventWrapper::decode(frame)
+                .map(|wrapper| vec![Event::from(wrapper)])
+                .context(InvalidProtobuf),
+            Self::Gelf { max_length } => Ok(vec![super::gelf::parse_gelf(&frame, *max_length)?]),
+        }
+    }
+}
+
+fn parse_json(frame: &[u8]) -> Result<Vec<Event>, DecodingError> {
+    let objects = match serde_json::from_slice(frame).context(InvalidJson)? {
+        JsonValue::Array(values) => values,
+        value => vec![value],
+    };
+    objects
+        .into_iter()
+        .map(|value| match value {
+            JsonValue::Object(map) => {
+                let mut event = Event::new_empty_log();
+                let log = event.as_mut_log();
+                for (key, value) in map {
+                    log.insert_flat(key, value);
+                }
+                if !log.contains(log_schema().timestamp_key()) {
+                    log.insert(log_schema().timestamp_key(), Utc::now());
+                }
+                Ok(event)
+            }
+            value => Err(DecodingError::NotAnObject {
+                found: json_type_name(&value),
+            }),
+        })
+        .collect()
+}
+
+pub(super) fn json_type_name(value: &JsonValue) -> &'static str {
+    match value {
+        JsonValue::Object(_) => "an object",
+        JsonValue::Array(_) => "an array",
+        JsonValue::String(_) => "a string",
+        JsonValue::Number(_) => "a number",
+        JsonValue::Bool(_) => "a boolean",
+        JsonValue::Null => "null",
+    }
+}
+
+/// Parses a syslog message into an event holding its message, timestamp and
+/// the other syslog fields, such as `hostname` and `severity`.
+pub fn event_from_syslog(line: &str) -> Event {
+    let parsed = syslog_loose::parse_message_with_year(line.trim(), resolve_year);
+    let mut event = Event::from(&parsed.msg[..]);
+
+    let timestamp = parsed
+        .timestamp
+        .map(|ts| ts.into())
+        .unwrap_or_else(Utc::now);
+    event
+        .as_mut_log()
+        .insert(log_schema().timestamp_key(), timestamp);
+
+    insert_fields_from_syslog(&mut event, parsed);
+
+    event
+}
+
+/// Function used to resolve the year for syslog messages that don't include the year.
+/// If the current month is January, and the syslog message is for December, it will take the previous year.
+/// Otherwise, take the current year.
+fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
+    let now = Utc::now();
+    if now.month() == 1 && month == 12 {
+        now.year() - 1
+    } else {
+        now.year()
+    }
+}
+
+fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
+    let log = event.as_mut_log();
+
+    if let Some(host) = parsed.hostname {
+        log.insert("hostname", host.to_string());
+    }
+    if let Some(severity) = parsed.severity {
+        log.insert("severity", severity.as_str().to_owned());
+    }
+    if let Some(facility) = parsed.facility {
+        log.insert("facility", facility.as_str().to_owned());
+    }
+    if let Protocol::RFC5424(version) = parsed.protocol {
+        log.insert("version", version as i64);
+    }
+    if let Some(app_name) = parsed.appname {
+        log.insert("appname", app_name.to_owned());
+    }
+    if let Some(msg_id) = parsed.msgid {
+        log.insert("msgid", msg_id.to_owned());
+    }
+    if let Some(procid) = parsed.procid {
+        let value: Value = match procid {
+            ProcId::PID(pid) => pid.into(),
+            ProcId::Name(name) => name.to_string().into(),
+        };
+        log.insert("procid", value);
+    }
+
+    for element in parsed.structured_data.into_iter() {
+        for (name, value) in element.params.into_iter() {
+            let key = format!("{}.{}", element.id, name);
+            log.insert(key, value.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DecodingConfig, DecodingError};
+    use crate::{
+        config::log_schema,
+        event::{Event, Value},
+    };
+    use bytes::Bytes;
+    use prost::Message;
+
+    #[test]
+    fn parses_json_objects_and_arrays() {
+        let deserializer = DecodingConfig::Json.build(1024);
+
+        let events = deserializer
+            .parse(Bytes::from(r#"{"message": "one", "count": 1}"#))
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "one".into());
+        assert_eq!(log["count"], Value::Integer(1));
+        assert!(log.contains(log_schema().timestamp_key()));
+
+        let events = deserializer
+            .parse(Bytes::from(r#"[{"message": "one"}, {"message": "two"}]"#))
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+
+        assert!(matches!(
+            deserializer.parse(Bytes::from("[1]")),
+            Err(DecodingError::NotAnObject { found: "a number" })
+        ));
+        assert!(matches!(
+            deserializer.parse(Bytes::from("{")),
+            Err(DecodingError::InvalidJson { .. })
+        ));
+    }
+
+    #[test]
+    fn parses_syslog() {
+        let events = DecodingConfig::Syslog
+            .build(1024)
+            .parse(Bytes::from(
+                "<34>1 2003-10-11T22:14:15.003Z mymachine.example.com su - ID47 - 'su root' failed",
+            ))
+            .unwrap();
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "'su root' failed".into());
+        assert_eq!(log["hostname"], "mymachine.example.com".into());
+        assert_eq!(log["appname"], "su".into());
+        assert_eq!(log["severity"], "crit".into());
+    }
+
+    #[test]
+    fn parses_protobuf() {
+        let event = Event::from("hello");
+        let mut frame = Vec::new();
+        crate::event::proto::EventWrapper::from(event.clone())
+            .encode(&mut frame)
+            .unwrap();
+
+        let events = DecodingConfig::Protobuf
+            .build(1024)
+            .parse(Bytes::from(frame))
+            .unwrap();
+        assert_eq!(events, vec![event]);
+    }
+}
@@ -0,0 +1,302 @@
+//! Serializing the events written by sinks.
+
+use crate::{
+    config::log_schema,
+    event::{
+        proto,
+        util::log::{get_value, PathComponent},
+        Event, LogEvent,
+    },
+    sinks::util::encoding::EncodingConfiguration,
+};
+use prost::Message as _;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Avro requires a schema, specify one with `encoding.schema`."))]
+    MissingAvroSchema,
+    #[snafu(display("Invalid Avro schema: {}", source))]
+    InvalidAvroSchema { source: avro_rs::Error },
+    #[snafu(display("CSV requires the columns to be listed in `encoding.only_fields`."))]
+    MissingCsvColumns,
+}
+
+#[derive(Debug, Snafu)]
+pub enum EncodingError {
+    #[snafu(display("Failed to encode as JSON: {}", source))]
+    Json { source: serde_json::Error },
+    #[snafu(display("Failed to encode as Avro: {}", source))]
+    Avro { source: avro_rs::Error },
+    #[snafu(display("Metrics can't be encoded as {:?}", codec))]
+    MetricNotSupported { codec: Encoding },
+    #[snafu(display("{}", source))]
+    Framing { source: super::FramingError },
+    #[snafu(display("GELF messages require a `{}` field", field))]
+    MissingGelfField { field: &'static str },
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Encoding {
+    /// The `message` field of logs, or metrics as text.
+    Text,
+    /// The event as a JSON object.
+    Json,
+    /// The same as `json`, kept for the sinks writing newline delimited JSON
+    /// by default.
+    Ndjson,
+    /// The fields of logs as `key=value` pairs.
+    Logfmt,
+    /// The fields of logs listed in `only_fields` as a CSV row.
+    Csv,
+    /// Logs as an Avro datum of the schema given in `schema`.
+    Avro,
+    /// The event as encoded by the `vector` sink.
+    Protobuf,
+    /// Logs as an uncompressed GELF message.
+    Gelf,
+}
+
+impl Encoding {
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Text | Self::Logfmt => "text/plain",
+            Self::Json | Self::Gelf => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Csv => "text/csv",
+            Self::Avro => "avro/binary",
+            Self::Protobuf => "application/octet-stream",
+        }
+    }
+
+    pub fn file_extension(self) -> &'static str {
+        match self {
+            Self::Text | Self::Logfmt => "log",
+            Self::Json | Self::Gelf => "json",
+            Self::Ndjson => "ndjson",
+            Self::Csv => "csv",
+            Self::Avro => "avro",
+            Self::Protobuf => "pb",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Serializer {
+    Text,
+    Json,
+    Logfmt,
+    Csv { columns: Vec<Vec<PathComponent>> },
+    Avro { schema: avro_rs::Schema },
+    Protobuf,
+    Gelf,
+}
+
+impl Serializer {
+    pub fn new(encoding: &impl EncodingConfiguration<Encoding>) -> crate::Result<Self> {
+        Ok(match encoding.codec() {
+            Encoding::Text => Self::Text,
+            Encoding::Json | Encoding::Ndjson => Self::Json,
+            Encoding::Logfmt => Self::Logfmt,
+            Encoding::Csv => Self::Csv {
+                columns: encoding
+                    .only_fields()
+                    .clone()
+                    .ok_or(BuildError::MissingCsvColumns)?,
+            },
+            Encoding::Avro => {
+                let schema = encoding
+                    .schema()
+                    .as_ref()
+                    .ok_or(BuildError::MissingAvroSchema)?;
+                Self::Avro {
+                    schema: avro_rs::Schema::parse_str(schema).context(InvalidAvroSchema)?,
+                }
+            }
+            Encoding::Protobuf => Self::Protobuf,
+            Encoding::Gelf => Self::Gelf,
+        })
+    }
+
+    pub fn serialize(&self, event: Event) -> Result<Vec<u8>, EncodingError> {
+        let log = match event {
+            Event::Log(log) => log,
+            Event::Metric(metric) => {
+                return match self {
+                    Self::Text => Ok(metric.to_string().into_bytes()),
+                    Self::Json => serde_json::to_vec(&metric).context(Json),
+                    Self::Protobuf => Ok(encode_protobuf(Event::Metric(metric))),
+                    _ => Err(EncodingError::MetricNotSupported {
+                        codec: self.codec(),
+                    }),
+                }
+            }
+        };
+
+        match self {
+            Self::Text => Ok(log
+                .get(log_schema().message_key())
+                .map(|value| value.as_bytes().to_vec())
+                .unwrap_or_default()),
+            Self::Json => serde_json::to_vec(&log).context(Json),
+            Self::Logfmt => Ok(encode_logfmt(&log)),
+            Self::Csv { columns } => Ok(encode_csv(&log, columns)),
+            Self::Avro { schema } => {
+                let value = avro_rs::to_value(log).context(Avro)?;
+                let value = value.resolve(schema).context(Avro)?;
+                avro_rs::to_avro_datum(schema, value).context(Avro)
+            }
+            Self::Protobuf => Ok(encode_protobuf(Event::Log(log))),
+            Self::Gelf => super::gelf::encode_gelf(&log),
+        }
+    }
+
+    fn codec(&self) -> Encoding {
+        match self {
+            Self::Text => Encoding::Text,
+            Self::Json => Encoding::Json,
+            Self::Logfmt => Encoding::Logfmt,
+            Self::Csv { .. } => Encoding::Csv,
+            Self::Avro { .. } => Encoding::Avro,
+            Self::Protobuf => Encoding::Protobuf,
+            Self::Gelf => Encoding::Gelf,
+        }
+    }
+}
+
+fn encode_protobuf(event: Event) -> Vec<u8> {
+    let event = proto::EventWrapper::from(event);
+    let mut buf = Vec::with_capacity(event.encoded_len());
+    event
+        .encode(&mut buf)
+        .expect("Encoding into a Vec can't fail.");
+    buf
+}
+
+fn encode_logfmt(log: &LogEvent) -> Vec<u8> {
+    let mut line = String::new();
+    for (key, value) in log.all_fields() {
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&key);
+        line.push('=');
+
+        let value = value.to_string_lossy();
+        if value.is_empty()
+            || value.contains(|c: char| c == ' ' || c == '=' || c == '"' || c.is_control())
+        {
+            line.push('"');
+            for c in value.chars() {
+                match c {
+                    '"' | '\\' => {
+                        line.push('\\');
+                        line.push(c);
+                    }
+                    '\n' => line.push_str("\\n"),
+                    _ => line.push(c),
+                }
+            }
+            line.push('"');
+        } else {
+            line.push_str(&value);
+        }
+    }
+    line.into_bytes()
+}
+
+fn encode_csv(log: &LogEvent, columns: &[Vec<PathComponent>]) -> Vec<u8> {
+    columns
+        .iter()
+        .map(|path| {
+            let value = match path.split_first() {
+                Some((PathComponent::Key(key), rest)) => log
+                    .as_map()
+                    .get(key)
+                    .and_then(|value| get_value(value, rest.iter().cloned())),
+                _ => None,
+            };
+            let value = value
+                .map(|value| value.to_string_lossy())
+                .unwrap_or_default();
+            if value.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            } else {
+                value
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+        .into_bytes()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Encoding, Serializer};
+    use crate::{
+        config::log_schema,
+        event::{Event, Metric, MetricKind, MetricValue},
+        sinks::util::encoding::EncodingConfig,
+    };
+
+    fn serialize(encoding: EncodingConfig<Encoding>, event: Event) -> String {
+        let serializer = Serializer::new(&encoding).unwrap();
+        String::from_utf8(serializer.serialize(event).unwrap()).unwrap()
+    }
+
+    fn event() -> Event {
+        let mut event = Event::from("hello \"world\"");
+        event.as_mut_log().insert("code", 200);
+        event.as_mut_log().insert("path", "/a,b");
+        event.as_mut_log().remove(log_schema().timestamp_key());
+        event
+    }
+
+    #[test]
+    fn serializes_logs() {
+        assert_eq!(serialize(Encoding::Text.into(), event()), "hello \"world\"");
+        assert_eq!(
+            serialize(Encoding::Json.into(), event()),
+            r#"{"code":200,"message":"hello \"world\"","path":"/a,b"}"#
+        );
+        assert_eq!(
+            serialize(Encoding::Logfmt.into(), event()),
+            r#"code=200 message="hello \"world\"" path=/a,b"#
+        );
+
+        let encoding: EncodingConfig<Encoding> = toml::from_str(
+            r#"
+            codec = "csv"
+            only_fields = ["path", "missing", "message"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(serialize(encoding, event()), r#""/a,b",,"hello ""world""""#);
+    }
+
+    #[test]
+    fn requires_csv_columns_and_avro_schema() {
+        assert!(Serializer::new(&EncodingConfig::from(Encoding::Csv)).is_err());
+        assert!(Serializer::new(&EncodingConfig::from(Encoding::Avro)).is_err());
+    }
+
+    #[test]
+    fn serializes_metrics_only_where_supported() {
+        let metric = Event::Metric(Metric::new(
+            "hits",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 1.0 },
+        ));
+
+        assert!(Serializer::new(&EncodingConfig::from(Encoding::Json))
+            .unwrap()
+            .serialize(metric.clone())
+            .is_ok());
+        assert!(Serializer::new(&EncodingConfig::from(Encoding::Logfmt))
+            .unwrap()
+            .serialize(metric)
+            .is_err());
+    }
+}
@@ -0,0 +1,587 @@
+//! Splitting the bytes read by sources into frames, and framing the events
+//! written by sinks.
+
+use super::ChunkedGelfDecoder;
+use bytes::{Buf, Bytes, BytesMut};
+use codec::BytesDelimitedCodec;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{convert::TryInto, io};
+use tokio_util::codec::Decoder;
+
+#[derive(Debug, Snafu)]
+pub enum FramingError {
+    #[snafu(display("Delimiter {:?} is not an ASCII character", delimiter))]
+    NonAsciiDelimiter { delimiter: char },
+    #[snafu(display("Frame of {} bytes is longer than {} bytes", length, max_length))]
+    FrameTooLong { length: usize, max_length: usize },
+    #[snafu(display("Framing method {:?} can only be used by sources", method))]
+    SourceOnly { method: &'static str },
+}
+
+#[derive(Clone, Debug, Derivative, Deserialize, Serialize, PartialEq)]
+#[serde(tag = "method", rename_all = "snake_case")]
+#[derivative(Default)]
+pub enum FramingConfig {
+    /// All bytes received form a single frame, e.g. a whole datagram or
+    /// message.
+    #[derivative(Default)]
+    Bytes,
+    NewlineDelimited {
+        max_length: Option<usize>,
+    },
+    CharacterDelimited {
+        delimiter: char,
+        max_length: Option<usize>,
+    },
+    /// Octet counting as in RFC 6587, falling back to newline delimited
+    /// frames for those not starting with a length.
+    OctetCounting {
+        max_length: Option<usize>,
+    },
+    /// Frames prefixed with their length as a 4 byte big-endian integer.
+    LengthDelimited {
+        max_length: Option<usize>,
+    },
+    /// Each datagram is a frame, like with `bytes`, except for the chunks of
+    /// chunked GELF messages, which are reassembled into a frame.
+    ChunkedGelf {
+        max_length: Option<usize>,
+    },
+}
+
+impl FramingConfig {
+    pub fn newline_delimited(max_length: usize) -> Self {
+        Self::NewlineDelimited {
+            max_length: Some(max_length),
+        }
+    }
+
+    /// Uses `max_length` for the frames where the config doesn't set a
+    /// maximum length of its own, for sources with a `max_length` option.
+    pub fn with_default_max_length(self, max_length: usize) -> Self {
+        let default = |configured: Option<usize>| Some(configured.unwrap_or(max_length));
+        match self {
+            Self::Bytes => Self::Bytes,
+            Self::NewlineDelimited { max_length } => Self::NewlineDelimited {
+                max_length: default(max_length),
+            },
+            Self::CharacterDelimited {
+                delimiter,
+                max_length,
+            } => Self::CharacterDelimited {
+                delimiter,
+                max_length: default(max_length),
+            },
+            Self::OctetCounting { max_length } => Self::OctetCounting {
+                max_length: default(max_length),
+            },
+            Self::LengthDelimited { max_length } => Self::LengthDelimited {
+                max_length: default(max_length),
+            },
+            Self::ChunkedGelf { max_length } => Self::ChunkedGelf {
+                max_length: default(max_length),
+            },
+        }
+    }
+
+    /// The maximum length of the frames read, which is also the limit for
+    /// frames decompressed when parsed. For `bytes` framing this is the
+    /// default maximum, as it doesn't limit frames itself.
+    pub fn max_length(&self) -> usize {
+        match *self {
+            Self::Bytes => bounded(None),
+            Self::NewlineDelimited { max_length }
+            | Self::CharacterDelimited { max_length, .. }
+            | Self::OctetCounting { max_length }
+            | Self::LengthDelimited { max_length }
+            | Self::ChunkedGelf { max_length } => bounded(max_length),
+        }
+    }
+
+    pub fn build(&self) -> Result<Framer, FramingError> {
+        Ok(match *self {
+            Self::Bytes => Framer::Bytes(BytesDecoder),
+            Self::NewlineDelimited { max_length } => Framer::NewlineDelimited(
+                NewlineDelimitedDecoder::new_with_max_length(bounded(max_length)),
+            ),
+            Self::CharacterDelimited {
+                delimiter,
+                max_length,
+            } => {
+                if !delimiter.is_ascii() {
+                    return Err(FramingError::NonAsciiDelimiter { delimiter });
+                }
+                Framer::CharacterDelimited(BytesDelimitedCodec::new_with_max_length(
+                    delimiter as u8,
+                    bounded(max_length),
+                ))
+            }
+            Self::OctetCounting { max_length } => Framer::OctetCounting(
+                OctetCountingDecoder::new_with_max_length(bounded(max_length)),
+            ),
+            Self::LengthDelimited { max_length } => Framer::LengthDelimited(
+                LengthDelimitedDecoder::new_with_max_length(bounded(max_length)),
+            ),
+            Self::ChunkedGelf { max_length } => {
+                Framer::ChunkedGelf(ChunkedGelfDecoder::new_with_max_length(bounded(max_length)))
+            }
+        })
+    }
+
+    /// Builds the counterpart of `build` used by sinks, which frames the
+    /// serialized events so that they can be split up again by a source
+    /// using the same framing.
+    pub fn build_encoder(&self) -> Result<FrameEncoder, FramingError> {
+        Ok(match *self {
+            Self::Bytes => FrameEncoder::Bytes,
+            Self::NewlineDelimited { max_length } => FrameEncoder::CharacterDelimited {
+                delimiter: b'\n',
+                max_length: unlimited(max_length),
+            },
+            Self::CharacterDelimited {
+                delimiter,
+                max_length,
+            } => {
+                if !delimiter.is_ascii() {
+                    return Err(FramingError::NonAsciiDelimiter { delimiter });
+                }
+                FrameEncoder::CharacterDelimited {
+                    delimiter: delimiter as u8,
+                    max_length: unlimited(max_length),
+                }
+            }
+            Self::OctetCounting { max_length } => FrameEncoder::OctetCounting {
+                max_length: unlimited(max_length),
+            },
+            Self::LengthDelimited { max_length } => FrameEncoder::LengthDelimited {
+                max_length: unlimited(max_length).min(u32::MAX as usize),
+            },
+            Self::ChunkedGelf { .. } => {
+                return Err(FramingError::SourceOnly {
+                    method: "chunked_gelf",
+                })
+            }
+        })
+    }
+}
+
+/// Frames read are limited to 1 MiB unless configured otherwise, so that a
+/// corrupt or malicious length can't make a source buffer without bound.
+fn bounded(max_length: Option<usize>) -> usize {
+    max_length.unwrap_or(bytesize::mib(1u64) as usize)
+}
+
+fn unlimited(max_length: Option<usize>) -> usize {
+    max_length.unwrap_or(usize::MAX)
+}
+
+#[derive(Clone, Debug)]
+pub enum Framer {
+    Bytes(BytesDecoder),
+    NewlineDelimited(NewlineDelimitedDecoder),
+    CharacterDelimited(BytesDelimitedCodec),
+    OctetCounting(OctetCountingDecoder),
+    LengthDelimited(LengthDelimitedDecoder),
+    ChunkedGelf(ChunkedGelfDecoder),
+}
+
+impl Framer {
+    /// Forgets the state left by previous input, such as a frame being
+    /// discarded for exceeding `max_length`, so that independent inputs are
+    /// framed independently. Chunks of GELF messages are kept, as those span
+    /// datagrams.
+    pub fn reset(&mut self) {
+        match self {
+            Self::NewlineDelimited(decoder) => decoder.0.reset(),
+            Self::CharacterDelimited(decoder) => decoder.reset(),
+            Self::OctetCounting(decoder) => decoder.other.reset(),
+            Self::Bytes(_) | Self::LengthDelimited(_) | Self::ChunkedGelf(_) => {}
+        }
+    }
+}
+
+impl Decoder for Framer {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            Self::Bytes(decoder) => decoder.decode(src),
+            Self::NewlineDelimited(decoder) => decoder.decode(src),
+            Self::CharacterDelimited(decoder) => decoder.decode(src),
+            Self::OctetCounting(decoder) => decoder.decode(src),
+            Self::LengthDelimited(decoder) => decoder.decode(src),
+            Self::ChunkedGelf(decoder) => decoder.decode(src),
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        match self {
+            Self::Bytes(decoder) => decoder.decode_eof(src),
+            Self::NewlineDelimited(decoder) => decoder.decode_eof(src),
+            Self::CharacterDelimited(decoder) => decoder.decode_eof(src),
+            Self::OctetCounting(decoder) => decoder.decode_eof(src),
+            Self::LengthDelimited(decoder) => decoder.decode_eof(src),
+            Self::ChunkedGelf(decoder) => decoder.decode_eof(src),
+        }
+    }
+}
+
+/// Passes on everything up to the end of the input as one frame.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BytesDecoder;
+
+impl Decoder for BytesDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        if src.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(src.split().freeze()))
+        }
+    }
+}
+
+/// Splits frames on newlines, also trimming a `\r` before the newline so that
+/// CRLF terminated lines decode the same.
+#[derive(Clone, Debug)]
+pub struct NewlineDelimitedDecoder(BytesDelimitedCodec);
+
+impl NewlineDelimitedDecoder {
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self(BytesDelimitedCodec::new_with_max_length(b'\n', max_length))
+    }
+}
+
+impl Decoder for NewlineDelimitedDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        Ok(self.0.decode(src)?.map(trim_carriage_return))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        Ok(self.0.decode_eof(src)?.map(trim_carriage_return))
+    }
+}
+
+fn trim_carriage_return(mut frame: Bytes) -> Bytes {
+    if frame.ends_with(b"\r") {
+        frame.truncate(frame.len() - 1);
+    }
+    frame
+}
+
+/// Decodes according to `Octet Counting` in https://tools.ietf.org/html/rfc6587
+#[derive(Clone, Debug)]
+pub struct OctetCountingDecoder {
+    other: BytesDelimitedCodec,
+}
+
+impl OctetCountingDecoder {
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            other: BytesDelimitedCodec::new_with_max_length(b'\n', max_length),
+        }
+    }
+
+    fn octet_decode(&self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        // Encoding scheme:
+        //
+        // len ' ' data
+        // |    |  | len number of bytes that contain syslog message
+        // |    |
+        // |    | Separating whitespace
+        // |
+        // | ASCII decimal number of unknown length
+
+        if let Some(i) = src.iter().position(|&b| b == b' ') {
+            let len: usize = std::str::from_utf8(&src[..i])
+                .map_err(|_| ())
+                .and_then(|num| num.parse().map_err(|_| ()))
+                .map_err(|_| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Unable to decode message len as number",
+                    )
+                })?;
+
+            let from = i + 1;
+            if len > self.other.max_length() {
+                src.advance(src.len().min(from + len));
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Frame length limit exceeded",
+                ));
+            }
+            if src.len() >= from + len {
+                src.advance(from);
+                Ok(Some(src.split_to(len).freeze()))
+            } else {
+                Ok(None)
+            }
+        } else if src.len() < self.other.max_length() {
+            Ok(None)
+        } else {
+            // This is certainly malformed, and there is no recovering from this.
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Frame length limit exceeded",
+            ))
+        }
+    }
+
+    /// None if this is not octet counting encoded
+    fn checked_decode(&self, src: &mut BytesMut) -> Option<Result<Option<Bytes>, io::Error>> {
+        if let Some(&first_byte) = src.get(0) {
+            if (49..=57).contains(&first_byte) {
+                // First character is non zero number so we can assume that
+                // octet count framing is used.
+                trace!("Octet counting encoded event detected.");
+                return Some(self.octet_decode(src));
+            }
+        }
+        None
+    }
+}
+
+impl Decoder for OctetCountingDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        if let Some(ret) = self.checked_decode(src) {
+            ret
+        } else {
+            // Octet counting isn't used so fallback to newline codec.
+            self.other.decode(src)
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        if let Some(ret) = self.checked_decode(src) {
+            ret
+        } else {
+            // Octet counting isn't used so fallback to newline codec.
+            self.other.decode_eof(src)
+        }
+    }
+}
+
+/// Decodes frames prefixed with their length as a 4 byte big-endian integer.
+#[derive(Clone, Debug)]
+pub struct LengthDelimitedDecoder {
+    max_length: usize,
+}
+
+impl LengthDelimitedDecoder {
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self { max_length }
+    }
+}
+
+impl Decoder for LengthDelimitedDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        if src.len() < 4 {
+            return Ok(None);
+        }
+        let len = u32::from_be_bytes(src[..4].try_into().expect("slice has 4 bytes")) as usize;
+        if len > self.max_length {
+            src.advance(src.len().min(4 + len));
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frame length limit exceeded",
+            ));
+        }
+
+        if src.len() < 4 + len {
+            src.reserve(4 + len - src.len());
+            Ok(None)
+        } else {
+            src.advance(4);
+            Ok(Some(src.split_to(len).freeze()))
+        }
+    }
+}
+
+/// Frames serialized events, the counterpart of `Framer`.
+#[derive(Clone, Debug)]
+pub enum FrameEncoder {
+    Bytes,
+    CharacterDelimited { delimiter: u8, max_length: usize },
+    OctetCounting { max_length: usize },
+    LengthDelimited { max_length: usize },
+}
+
+impl FrameEncoder {
+    pub fn frame(&self, mut payload: Vec<u8>) -> Result<Vec<u8>, FramingError> {
+        let max_length = match *self {
+            Self::Bytes => return Ok(payload),
+            Self::CharacterDelimited { max_length, .. }
+            | Self::OctetCounting { max_length }
+            | Self::LengthDelimited { max_length } => max_length,
+        };
+        if payload.len() > max_length {
+            return Err(FramingError::FrameTooLong {
+                length: payload.len(),
+                max_length,
+            });
+        }
+
+        Ok(match *self {
+            Self::Bytes => payload,
+            Self::CharacterDelimited { delimiter, .. } => {
+                payload.push(delimiter);
+                payload
+            }
+            Self::OctetCounting { .. } => {
+                let mut frame = format!("{} ", payload.len()).into_bytes();
+                frame.append(&mut payload);
+                frame
+            }
+            Self::LengthDelimited { .. } => {
+                let mut frame = (payload.len() as u32).to_be_bytes().to_vec();
+                frame.append(&mut payload);
+                frame
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FramingConfig;
+    use bytes::{BufMut, Bytes, BytesMut};
+    use tokio_util::codec::Decoder;
+
+    fn frames(config: FramingConfig, input: &[u8]) -> Vec<Bytes> {
+        let mut framer = config.build().unwrap();
+        let mut buf = BytesMut::from(input);
+        let mut frames = Vec::new();
+        while let Some(frame) = framer.decode_eof(&mut buf).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    #[test]
+    fn frames_by_method() {
+        assert_eq!(
+            frames(FramingConfig::Bytes, b"one\ntwo"),
+            vec![Bytes::from("one\ntwo")]
+        );
+        assert_eq!(
+            frames(FramingConfig::newline_delimited(100), b"one\ntwo"),
+            vec![Bytes::from("one"), Bytes::from("two")]
+        );
+        assert_eq!(
+            frames(FramingConfig::newline_delimited(100), b"one\r\ntwo\r"),
+            vec![Bytes::from("one"), Bytes::from("two")]
+        );
+        assert_eq!(
+            frames(
+                FramingConfig::CharacterDelimited {
+                    delimiter: '\0',
+                    max_length: None
+                },
+                b"one\0two\0"
+            ),
+            vec![Bytes::from("one"), Bytes::from("two")]
+        );
+        assert_eq!(
+            frames(
+                FramingConfig::OctetCounting { max_length: None },
+                b"3 one4 two\nthree\n"
+            ),
+            vec![
+                Bytes::from("one"),
+                Bytes::from("two\n"),
+                Bytes::from("three")
+            ]
+        );
+
+        let mut input = BytesMut::new();
+        for frame in &["one", "three"] {
+            input.put_u32(frame.len() as u32);
+            input.put_slice(frame.as_bytes());
+        }
+        assert_eq!(
+            frames(FramingConfig::LengthDelimited { max_length: None }, &input),
+            vec![Bytes::from("one"), Bytes::from("three")]
+        );
+    }
+
+    #[test]
+    fn rejects_lengths_over_max_length() {
+        let mut framer = FramingConfig::LengthDelimited { max_length: None }
+            .build()
+            .unwrap();
+        let mut buf = BytesMut::new();
+        buf.put_u32(u32::MAX);
+        assert!(framer.decode(&mut buf).is_err());
+        assert!(buf.capacity() < 1024 * 1024);
+
+        let mut framer = FramingConfig::OctetCounting {
+            max_length: Some(4),
+        }
+        .build()
+        .unwrap();
+        let mut buf = BytesMut::from(&b"5 hello3 one"[..]);
+        assert!(framer.decode_eof(&mut buf).is_err());
+        assert_eq!(
+            framer.decode_eof(&mut buf).unwrap(),
+            Some(Bytes::from("one"))
+        );
+    }
+
+    #[test]
+    fn rejects_non_ascii_delimiters() {
+        let config: FramingConfig = toml::from_str(
+            r#"
+            method = "character_delimited"
+            delimiter = "é"
+            "#,
+        )
+        .unwrap();
+        assert!(config.build().is_err());
+        assert!(config.build_encoder().is_err());
+    }
+
+    #[test]
+    fn encoded_frames_decode_again() {
+        let configs = vec![
+            FramingConfig::newline_delimited(100),
+            FramingConfig::CharacterDelimited {
+                delimiter: ';',
+                max_length: None,
+            },
+            FramingConfig::OctetCounting { max_length: None },
+            FramingConfig::LengthDelimited { max_length: None },
+        ];
+        for config in configs {
+            let encoder = config.build_encoder().unwrap();
+            let mut input = Vec::new();
+            for payload in &["one", "three"] {
+                input.extend(encoder.frame(payload.as_bytes().to_vec()).unwrap());
+            }
+            assert_eq!(
+                frames(config, &input),
+                vec![Bytes::from("one"), Bytes::from("three")]
+            );
+        }
+
+        let encoder = FramingConfig::newline_delimited(2).build_encoder().unwrap();
+        assert!(encoder.frame(b"one".to_vec()).is_err());
+    }
+}
@@ -0,0 +1,528 @@
+//! Graylog Extended Log Format (GELF) messages, as received and sent by
+//! Graylog.
+//!
+//! https://go2docs.graylog.org/current/getting_in_log_data/gelf.html
+//!
+//! The `short_message` of a message becomes the message of the event, and its
+//! additional fields, prefixed with an underscore, become fields without the
+//! prefix. As sources set the `host` field to the address of the peer, the
+//! `host` of the message is kept in the `hostname` field, like the syslog
+//! codec does.
+
+use super::{decoding::json_type_name, DecodingError, EncodingError};
+use crate::{
+    config::log_schema,
+    event::{Event, LogEvent, Value},
+};
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use serde_json::{Map, Value as JsonValue};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+    io::{self, Read},
+    time::{Duration, Instant},
+};
+use tokio_util::codec::Decoder;
+
+const VERSION: &str = "1.1";
+const HOSTNAME: &str = "hostname";
+
+/// The fields defined by GELF besides `version`, `host`, `short_message` and
+/// `timestamp`, which are kept as they are.
+const STANDARD_FIELDS: &[&str] = &["full_message", "level", "facility", "line", "file"];
+
+/// Chunks of a message not reassembled within this time are dropped.
+const CHUNK_TIMEOUT: Duration = Duration::from_secs(5);
+const CHUNK_MAGIC: &[u8] = &[0x1e, 0x0f];
+const CHUNK_HEADER_LENGTH: usize = 12;
+const MAX_CHUNKS: u8 = 128;
+/// Chunks of further messages are dropped while this many are being
+/// reassembled.
+const MAX_PENDING_MESSAGES: usize = 1000;
+
+/// Parses a GELF message, which may be compressed with gzip or zlib, in which
+/// case it may be up to `max_length` bytes once decompressed.
+pub fn parse_gelf(frame: &[u8], max_length: usize) -> Result<Event, DecodingError> {
+    let frame = decompress(frame, max_length)?;
+    let object =
+        serde_json::from_slice(&frame).map_err(|source| DecodingError::InvalidJson { source })?;
+    let object = match object {
+        JsonValue::Object(object) => object,
+        value => {
+            return Err(DecodingError::NotAnObject {
+                found: json_type_name(&value),
+            })
+        }
+    };
+    for &field in &["version", "host", "short_message"] {
+        if !matches!(object.get(field), Some(JsonValue::String(_))) {
+            return Err(DecodingError::MissingGelfField { field });
+        }
+    }
+
+    let mut log = LogEvent::default();
+    let mut timestamp = None;
+    for (key, value) in object {
+        match key.as_str() {
+            "version" | "_id" => {}
+            "short_message" => {
+                log.insert(log_schema().message_key(), value);
+            }
+            "host" => {
+                log.insert_flat(HOSTNAME, value);
+            }
+            "timestamp" => {
+                if let Some(seconds) = value.as_f64() {
+                    timestamp = Some(
+                        timestamp_from_seconds(seconds)
+                            .ok_or(DecodingError::InvalidGelfTimestamp { timestamp: seconds })?,
+                    );
+                }
+            }
+            field => {
+                let field = field.strip_prefix('_').unwrap_or(field).to_owned();
+                log.insert_flat(field, value);
+            }
+        }
+    }
+    log.insert(
+        log_schema().timestamp_key(),
+        timestamp.unwrap_or_else(Utc::now),
+    );
+    Ok(Event::from(log))
+}
+
+/// Converts fractional seconds since the epoch, or `None` if they are out of
+/// the range of timestamps.
+fn timestamp_from_seconds(seconds: f64) -> Option<DateTime<Utc>> {
+    let whole = seconds.floor();
+    if !whole.is_finite() || whole.abs() >= i64::MAX as f64 {
+        return None;
+    }
+    let nanoseconds = ((seconds - whole) * 1e9) as u32;
+    Utc.timestamp_opt(whole as i64, nanoseconds.min(999_999_999))
+        .single()
+}
+
+fn decompress(frame: &[u8], max_length: usize) -> Result<Cow<'_, [u8]>, DecodingError> {
+    let mut buf = Vec::new();
+    let limit = max_length as u64 + 1;
+    match frame {
+        [0x1f, 0x8b, ..] => MultiGzDecoder::new(frame).take(limit).read_to_end(&mut buf),
+        [0x78, ..] => ZlibDecoder::new(frame).take(limit).read_to_end(&mut buf),
+        _ => return Ok(Cow::Borrowed(frame)),
+    }
+    .map_err(|source| DecodingError::Decompression { source })?;
+    if buf.len() > max_length {
+        return Err(DecodingError::DecompressedTooLong { max_length });
+    }
+    Ok(Cow::Owned(buf))
+}
+
+/// Encodes a log as an uncompressed GELF message. Fields that aren't defined
+/// by GELF are sent as additional fields, with their names prefixed with an
+/// underscore and characters other than letters, digits, `_`, `-` and `.`
+/// replaced by `_`.
+pub fn encode_gelf(log: &LogEvent) -> Result<Vec<u8>, EncodingError> {
+    let message_key = log_schema().message_key();
+    let host_key = log_schema().host_key();
+    let timestamp_key = log_schema().timestamp_key();
+
+    let host = log
+        .get(HOSTNAME)
+        .or_else(|| log.get(host_key))
+        .ok_or(EncodingError::MissingGelfField { field: "host" })?;
+    let message = log
+        .get(message_key)
+        .ok_or(EncodingError::MissingGelfField {
+            field: "short_message",
+        })?;
+
+    let mut object = Map::new();
+    object.insert("version".into(), VERSION.into());
+    object.insert("host".into(), host.to_string_lossy().into());
+    object.insert("short_message".into(), message.to_string_lossy().into());
+    if let Some(Value::Timestamp(timestamp)) = log.get(timestamp_key) {
+        let seconds = timestamp.timestamp_millis() as f64 / 1000.0;
+        object.insert("timestamp".into(), seconds.into());
+    }
+
+    for (key, value) in log.all_fields() {
+        if key == message_key || key == timestamp_key || key == host_key || key == HOSTNAME {
+            continue;
+        }
+        let key = if STANDARD_FIELDS.contains(&key.as_str()) {
+            key
+        } else {
+            additional_field_name(&key)
+        };
+        // `_id` is reserved by Graylog.
+        if key == "_id" {
+            continue;
+        }
+        let value = match value {
+            Value::Integer(integer) => (*integer).into(),
+            Value::Float(float) => (*float).into(),
+            Value::Null => continue,
+            value => value.to_string_lossy().into(),
+        };
+        object.insert(key, value);
+    }
+
+    serde_json::to_vec(&object).map_err(|source| EncodingError::Json { source })
+}
+
+fn additional_field_name(key: &str) -> String {
+    let mut name = String::with_capacity(key.len() + 1);
+    name.push('_');
+    name.extend(key.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' {
+            c
+        } else {
+            '_'
+        }
+    }));
+    name
+}
+
+/// Passes on each datagram as a frame, like `BytesDecoder`, but buffers the
+/// chunks of chunked GELF messages until all chunks of a message are
+/// received, and then passes on the reassembled message.
+#[derive(Clone, Debug)]
+pub struct ChunkedGelfDecoder {
+    max_length: usize,
+    pending: HashMap<[u8; 8], PendingMessage>,
+    /// The pending messages in the order they were started, to expire them
+    /// without going over all of them. Messages that were completed since
+    /// are skipped once their turn comes.
+    started: VecDeque<(Instant, [u8; 8])>,
+}
+
+#[derive(Clone, Debug)]
+struct PendingMessage {
+    chunks: Vec<Option<Bytes>>,
+    received: usize,
+    length: usize,
+    started: Instant,
+}
+
+impl ChunkedGelfDecoder {
+    pub fn new_with_max_length(max_length: usize) -> Self {
+        Self {
+            max_length,
+            pending: HashMap::new(),
+            started: VecDeque::new(),
+        }
+    }
+
+    fn expire(&mut self, now: Instant) {
+        while let Some(&(started, id)) = self.started.front() {
+            if now.duration_since(started) < CHUNK_TIMEOUT {
+                break;
+            }
+            self.started.pop_front();
+            if self.pending.get(&id).map(|message| message.started) == Some(started) {
+                self.pending.remove(&id);
+            }
+        }
+    }
+
+    fn handle_chunk(&mut self, mut chunk: Bytes) -> Result<Option<Bytes>, io::Error> {
+        if chunk.len() < CHUNK_HEADER_LENGTH {
+            return Err(invalid_chunk("Chunk is shorter than its header"));
+        }
+        let header = chunk.split_to(CHUNK_HEADER_LENGTH);
+        let mut id = [0; 8];
+        id.copy_from_slice(&header[2..10]);
+        let (sequence, count) = (header[10], header[11]);
+        if count == 0 || count > MAX_CHUNKS || sequence >= count {
+            return Err(invalid_chunk("Invalid chunk sequence"));
+        }
+
+        let now = Instant::now();
+        self.expire(now);
+        if !self.pending.contains_key(&id) {
+            if self.pending.len() >= MAX_PENDING_MESSAGES {
+                return Err(invalid_chunk("Too many messages being reassembled"));
+            }
+            self.started.push_back((now, id));
+        }
+
+        let message = self.pending.entry(id).or_insert_with(|| PendingMessage {
+            chunks: vec![None; count as usize],
+            received: 0,
+            length: 0,
+            started: now,
+        });
+        if message.chunks.len() != count as usize {
+            self.pending.remove(&id);
+            return Err(invalid_chunk("Chunks disagree on their count"));
+        }
+        let slot = &mut message.chunks[sequence as usize];
+        if slot.is_none() {
+            message.received += 1;
+            message.length += chunk.len();
+            *slot = Some(chunk);
+        }
+        if message.length > self.max_length {
+            self.pending.remove(&id);
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frame length limit exceeded",
+            ));
+        }
+        if message.received < message.chunks.len() {
+            return Ok(None);
+        }
+
+        let message = self.pending.remove(&id).expect("Message is pending.");
+        let mut frame = BytesMut::with_capacity(message.length);
+        for chunk in message.chunks.into_iter().flatten() {
+            frame.extend_from_slice(&chunk);
+        }
+        Ok(Some(frame.freeze()))
+    }
+}
+
+fn invalid_chunk(reason: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, reason)
+}
+
+impl Decoder for ChunkedGelfDecoder {
+    type Item = Bytes;
+    type Error = io::Error;
+
+    fn decode(&mut self, _src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        Ok(None)
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Bytes>, io::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+        let frame = src.split().freeze();
+        if frame.starts_with(CHUNK_MAGIC) {
+            self.handle_chunk(frame)
+        } else if frame.len() > self.max_length {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Frame length limit exceeded",
+            ))
+        } else {
+            Ok(Some(frame))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use flate2::{
+        write::{GzEncoder, ZlibEncoder},
+        Compression,
+    };
+    use std::io::Write;
+
+    const MAX_LENGTH: usize = 1024;
+
+    const MESSAGE: &str = r#"{
+        "version": "1.1",
+        "host": "example.org",
+        "short_message": "A short message",
+        "timestamp": 1385053862.3072,
+        "level": 1,
+        "_user_id": 9001,
+        "_some_info": "foo"
+    }"#;
+
+    #[test]
+    fn parses_messages() {
+        let log = parse_gelf(MESSAGE.as_bytes(), MAX_LENGTH)
+            .unwrap()
+            .into_log();
+        assert_eq!(log[log_schema().message_key()], "A short message".into());
+        assert_eq!(log["hostname"], "example.org".into());
+        let timestamp = log[log_schema().timestamp_key()].as_timestamp().unwrap();
+        assert_eq!(timestamp.timestamp_millis(), 1_385_053_862_307);
+        assert_eq!(log["level"], 1.into());
+        assert_eq!(log["user_id"], 9001.into());
+        assert_eq!(log["some_info"], "foo".into());
+        assert!(!log.contains("version"));
+    }
+
+    #[test]
+    fn parses_compressed_messages() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(MESSAGE.as_bytes()).unwrap();
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(MESSAGE.as_bytes()).unwrap();
+
+        for frame in vec![gzip.finish().unwrap(), zlib.finish().unwrap()] {
+            let log = parse_gelf(&frame, MAX_LENGTH).unwrap().into_log();
+            assert_eq!(log[log_schema().message_key()], "A short message".into());
+        }
+    }
+
+    #[test]
+    fn requires_standard_fields() {
+        assert!(matches!(
+            parse_gelf(br#"{"version": "1.1", "host": "example.org"}"#, MAX_LENGTH),
+            Err(DecodingError::MissingGelfField {
+                field: "short_message"
+            })
+        ));
+        assert!(matches!(
+            parse_gelf(
+                br#"{"version": "1.1", "host": 1, "short_message": "hello"}"#,
+                MAX_LENGTH
+            ),
+            Err(DecodingError::MissingGelfField { field: "host" })
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_timestamps() {
+        for timestamp in &["1e300", "-1e300", "1e17"] {
+            let message = format!(
+                r#"{{"version": "1.1", "host": "example.org", "short_message": "hello", "timestamp": {}}}"#,
+                timestamp
+            );
+            assert!(matches!(
+                parse_gelf(message.as_bytes(), MAX_LENGTH),
+                Err(DecodingError::InvalidGelfTimestamp { .. })
+            ));
+        }
+
+        let message = br#"{"version": "1.1", "host": "example.org", "short_message": "hello", "timestamp": -1.5}"#;
+        let log = parse_gelf(message, MAX_LENGTH).unwrap().into_log();
+        let timestamp = log[log_schema().timestamp_key()].as_timestamp().unwrap();
+        assert_eq!(timestamp.timestamp_millis(), -1500);
+    }
+
+    #[test]
+    fn encodes_additional_fields_with_prefix() {
+        let mut log = parse_gelf(MESSAGE.as_bytes(), MAX_LENGTH)
+            .unwrap()
+            .into_log();
+        log.insert("request.path", "/");
+        log.insert("id", "reserved");
+        log.insert_flat("with space", true);
+
+        let encoded = encode_gelf(&log).unwrap();
+        let object: JsonValue = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(
+            object,
+            serde_json::json!({
+                "version": "1.1",
+                "host": "example.org",
+                "short_message": "A short message",
+                "timestamp": 1385053862.307,
+                "level": 1,
+                "_user_id": 9001,
+                "_some_info": "foo",
+                "_request.path": "/",
+                "_with_space": "true",
+            })
+        );
+
+        let decoded = parse_gelf(&encoded, MAX_LENGTH).unwrap().into_log();
+        assert_eq!(decoded["user_id"], 9001.into());
+        assert_eq!(decoded["hostname"], "example.org".into());
+    }
+
+    #[test]
+    fn encoding_requires_a_message() {
+        let mut log = LogEvent::default();
+        log.insert("host", "example.org");
+        assert!(matches!(
+            encode_gelf(&log),
+            Err(EncodingError::MissingGelfField {
+                field: "short_message"
+            })
+        ));
+    }
+
+    fn chunk(id: u8, sequence: u8, count: u8, payload: &[u8]) -> BytesMut {
+        let mut chunk = BytesMut::from(CHUNK_MAGIC);
+        chunk.extend_from_slice(&[id; 8]);
+        chunk.extend_from_slice(&[sequence, count]);
+        chunk.extend_from_slice(payload);
+        chunk
+    }
+
+    #[test]
+    fn reassembles_chunks() {
+        let mut decoder = ChunkedGelfDecoder::new_with_max_length(1024);
+
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 1, 2, b"world")).unwrap(),
+            None
+        );
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(2, 0, 2, b"other")).unwrap(),
+            None
+        );
+        assert_eq!(
+            decoder
+                .decode_eof(&mut BytesMut::from(&b"unchunked"[..]))
+                .unwrap(),
+            Some(Bytes::from("unchunked"))
+        );
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 0, 2, b"hello ")).unwrap(),
+            Some(Bytes::from("hello world"))
+        );
+        assert_eq!(decoder.pending.len(), 1);
+
+        assert!(decoder.decode_eof(&mut chunk(3, 2, 2, b"")).is_err());
+        assert!(decoder.decode_eof(&mut chunk(3, 0, 129, b"")).is_err());
+    }
+
+    #[test]
+    fn limits_reassembled_length() {
+        let mut decoder = ChunkedGelfDecoder::new_with_max_length(8);
+
+        assert_eq!(
+            decoder.decode_eof(&mut chunk(1, 0, 2, b"hello")).unwrap(),
+            None
+        );
+        assert!(decoder.decode_eof(&mut chunk(1, 1, 2, b"world")).is_err());
+        assert!(decoder.pending.is_empty());
+    }
+
+    #[test]
+    fn limits_pending_messages() {
+        let mut decoder = ChunkedGelfDecoder::new_with_max_length(1024);
+        for id in 0..MAX_PENDING_MESSAGES {
+            let mut chunk = chunk(0, 0, 2, b"hello");
+            chunk[2..10].copy_from_slice(&(id as u64).to_be_bytes());
+            assert_eq!(decoder.decode_eof(&mut chunk).unwrap(), None);
+        }
+        assert!(decoder
+            .decode_eof(&mut chunk(0xff, 0, 2, b"hello"))
+            .is_err());
+
+        // Expired messages make room for new ones.
+        decoder.expire(Instant::now() + CHUNK_TIMEOUT);
+        assert!(decoder.pending.is_empty());
+        assert!(decoder.started.is_empty());
+        assert_eq!(
+            decoder
+                .decode_eof(&mut chunk(0xff, 0, 2, b"hello"))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn limits_decompressed_length() {
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(&[b' '; MAX_LENGTH + 1]).unwrap();
+        assert!(matches!(
+            parse_gelf(&gzip.finish().unwrap(), MAX_LENGTH),
+            Err(DecodingError::DecompressedTooLong { .. })
+        ));
+    }
+}
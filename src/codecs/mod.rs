@@ -0,0 +1,242 @@
+//! Framing, decoding and encoding shared by the sources reading bytes and the
+//! sinks writing them, configured with their `framing` and `decoding` or
+//! `encoding` options.
+//!
+//! For sources, the framer splits the bytes read into frames, each of which
+//! is parsed into events by the deserializer. For sinks, each event is
+//! serialized and then framed.
+
+mod decoding;
+mod encoding;
+mod framing;
+mod gelf;
+
+pub use decoding::{event_from_syslog, DecodingConfig, DecodingError, Deserializer};
+pub use encoding::{Encoding, EncodingError, Serializer};
+pub use framing::{
+    BytesDecoder, FrameEncoder, Framer, FramingConfig, FramingError, LengthDelimitedDecoder,
+    OctetCountingDecoder,
+};
+pub use gelf::ChunkedGelfDecoder;
+
+use crate::{
+    event::Event,
+    internal_events::{DecoderParseFailed, EncoderEncodeFailed},
+    sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
+};
+use bytes::{Bytes, BytesMut};
+use std::{collections::VecDeque, io};
+
+/// Splits bytes into frames and parses them into events. Frames that can't
+/// be parsed are dropped.
+///
+/// Each event is decoded along with the size of the frame it was parsed from,
+/// which is only counted for the first event of each frame.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    framer: Framer,
+    deserializer: Deserializer,
+    pending: VecDeque<(Event, usize)>,
+}
+
+impl Decoder {
+    pub fn new(framer: Framer, deserializer: Deserializer) -> Self {
+        Self {
+            framer,
+            deserializer,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn from_config(
+        framing: &FramingConfig,
+        decoding: &DecodingConfig,
+    ) -> Result<Self, FramingError> {
+        Ok(Self::new(
+            framing.build()?,
+            decoding.build(framing.max_length()),
+        ))
+    }
+
+    fn handle_frame(&mut self, frame: Bytes) {
+        let mut byte_size = frame.len();
+        match self.deserializer.parse(frame) {
+            Ok(events) => {
+                for event in events {
+                    self.pending.push_back((event, byte_size));
+                    byte_size = 0;
+                }
+            }
+            Err(error) => emit!(DecoderParseFailed { error: &error }),
+        }
+    }
+
+    /// Decodes all events in `bytes`, e.g. a datagram or message, framed
+    /// independently of any previous ones. Frames that can't be split off are
+    /// skipped where the framer can tell where they end, otherwise the rest
+    /// of `bytes` is dropped.
+    pub fn decode_all(&mut self, bytes: &[u8]) -> Vec<(Event, usize)> {
+        self.framer.reset();
+        let mut buf = BytesMut::from(bytes);
+        let mut events = Vec::new();
+        // Discarding a frame exceeding `max_length` starts with a call
+        // consuming nothing, so only stop once two calls in a row didn't.
+        let mut stalled = false;
+        loop {
+            let remaining = buf.len();
+            match tokio_util::codec::Decoder::decode_eof(self, &mut buf) {
+                Ok(Some(event)) => {
+                    events.push(event);
+                    stalled = false;
+                }
+                Ok(None) => {
+                    if buf.is_empty() || (stalled && buf.len() == remaining) {
+                        break;
+                    }
+                    stalled = buf.len() == remaining;
+                }
+                Err(error) if buf.len() < remaining => {
+                    warn!(message = "Failed framing data, skipping frame.", %error, internal_log_rate_secs = 10);
+                    self.framer.reset();
+                    stalled = false;
+                }
+                Err(error) => {
+                    warn!(message = "Failed framing data, dropping the rest.", %error, internal_log_rate_secs = 10);
+                    break;
+                }
+            }
+        }
+        events
+    }
+}
+
+impl tokio_util::codec::Decoder for Decoder {
+    type Item = (Event, usize);
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some(event));
+            }
+            match self.framer.decode(src)? {
+                Some(frame) => self.handle_frame(frame),
+                None => return Ok(None),
+            }
+        }
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, io::Error> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some(event));
+            }
+            match self.framer.decode_eof(src)? {
+                Some(frame) => self.handle_frame(frame),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Serializes events and frames them, after applying the rules of the
+/// sink's `encoding` option, such as `except_fields`. Events that can't be
+/// encoded are dropped.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    encoding: EncodingConfig<Encoding>,
+    serializer: Serializer,
+    framer: FrameEncoder,
+}
+
+impl Encoder {
+    pub fn new(encoding: EncodingConfig<Encoding>, framing: &FramingConfig) -> crate::Result<Self> {
+        Ok(Self {
+            serializer: Serializer::new(&encoding)?,
+            framer: framing.build_encoder()?,
+            encoding,
+        })
+    }
+
+    pub fn codec(&self) -> Encoding {
+        *self.encoding.codec()
+    }
+
+    pub fn try_encode(&self, mut event: Event) -> Result<Vec<u8>, EncodingError> {
+        self.encoding.apply_rules(&mut event);
+        let payload = self.serializer.serialize(event)?;
+        self.framer
+            .frame(payload)
+            .map_err(|source| EncodingError::Framing { source })
+    }
+
+    pub fn encode(&self, event: Event) -> Option<Vec<u8>> {
+        self.try_encode(event)
+            .map_err(|error| emit!(EncoderEncodeFailed { error: &error }))
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Decoder, DecodingConfig, Encoder, Encoding, FramingConfig};
+    use crate::{config::log_schema, event::Event};
+
+    #[test]
+    fn decodes_frames_into_events() {
+        let mut decoder = Decoder::from_config(
+            &FramingConfig::newline_delimited(1024),
+            &DecodingConfig::Json,
+        )
+        .unwrap();
+
+        let events = decoder.decode_all(
+            b"{\"message\": \"one\"}\nnot json\n[{\"message\": \"two\"}, {\"message\": \"three\"}]",
+        );
+        let messages = events
+            .iter()
+            .map(|(event, _)| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["one", "two", "three"]);
+
+        let sizes = events.iter().map(|(_, size)| *size).collect::<Vec<_>>();
+        assert_eq!(sizes, vec![18, 42, 0]);
+    }
+
+    #[test]
+    fn frames_each_payload_independently() {
+        let mut decoder =
+            Decoder::from_config(&FramingConfig::newline_delimited(4), &DecodingConfig::Bytes)
+                .unwrap();
+        let mut messages = |bytes: &[u8]| {
+            decoder
+                .decode_all(bytes)
+                .iter()
+                .map(|(event, _)| event.as_log()[log_schema().message_key()].to_string_lossy())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(messages(b"one\ntoo long\ntwo"), vec!["one", "two"]);
+        assert_eq!(messages(b"cut off at the end"), Vec::<String>::new());
+        assert_eq!(messages(b"new\n"), vec!["new"]);
+    }
+
+    #[test]
+    fn encoded_events_decode_again() {
+        let framing = FramingConfig::LengthDelimited { max_length: None };
+        let encoder = Encoder::new(Encoding::Protobuf.into(), &framing).unwrap();
+        let mut decoder = Decoder::from_config(&framing, &DecodingConfig::Protobuf).unwrap();
+
+        let events = vec![Event::from("one"), Event::from("two")];
+        let mut bytes = Vec::new();
+        for event in events.clone() {
+            bytes.extend(encoder.encode(event).unwrap());
+        }
+        let decoded = decoder
+            .decode_all(&bytes)
+            .into_iter()
+            .map(|(event, _)| event)
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, events);
+    }
+}
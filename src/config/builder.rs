@@ -1,8 +1,11 @@
 #[cfg(feature = "api")]
 use super::api;
 use super::{
-    compiler, default_data_dir, Config, GlobalOptions, HealthcheckOptions, SinkConfig, SinkOuter,
-    SourceConfig, TestDefinition, TransformConfig, TransformOuter,
+    compiler, default_data_dir,
+    secret::SecretBackend,
+    template::{TemplateDefinition, TemplateInstance},
+    Config, GlobalOptions, HealthcheckOptions, ProxyConfig, SinkConfig, SinkOuter, SourceConfig,
+    TestDefinition, TransformConfig, TransformOuter,
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
@@ -25,6 +28,16 @@ pub struct ConfigBuilder {
     pub transforms: IndexMap<String, TransformOuter>,
     #[serde(default)]
     pub tests: Vec<TestDefinition>,
+    /// Already resolved while loading, they are only kept for merging.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub secret: IndexMap<String, SecretBackend>,
+    /// Already loaded along with the config that includes them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub templates: IndexMap<String, TemplateDefinition>,
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub instances: IndexMap<String, TemplateInstance>,
 }
 
 impl Clone for ConfigBuilder {
@@ -50,6 +63,10 @@ impl From<Config> for ConfigBuilder {
             sinks: c.sinks,
             transforms: c.transforms,
             tests: c.tests,
+            secret: IndexMap::new(),
+            include: Vec::new(),
+            templates: IndexMap::new(),
+            instances: IndexMap::new(),
         }
     }
 }
@@ -112,6 +129,22 @@ impl ConfigBuilder {
             errors.push("conflicting values for 'data_dir' found".to_owned());
         }
 
+        if self.global.reload_drain_timeout_secs.is_none() {
+            self.global.reload_drain_timeout_secs = with.global.reload_drain_timeout_secs;
+        } else if with.global.reload_drain_timeout_secs.is_some()
+            && self.global.reload_drain_timeout_secs != with.global.reload_drain_timeout_secs
+        {
+            errors.push("conflicting values for 'reload_drain_timeout_secs' found".to_owned());
+        }
+
+        if self.global.proxy == ProxyConfig::default() {
+            self.global.proxy = with.global.proxy;
+        } else if with.global.proxy != ProxyConfig::default()
+            && self.global.proxy != with.global.proxy
+        {
+            errors.push("conflicting values for 'proxy' found".to_owned());
+        }
+
         // If the user has multiple config files, we must *merge* log schemas until we meet a
         // conflict, then we are allowed to error.
         if let Err(merge_errors) = self.global.log_schema.merge(with.global.log_schema) {
@@ -135,6 +168,21 @@ impl ConfigBuilder {
                 errors.push(format!("duplicate transform name found: {}", k));
             }
         });
+        with.secret.keys().for_each(|k| {
+            if self.secret.contains_key(k) {
+                errors.push(format!("duplicate secret backend name found: {}", k));
+            }
+        });
+        with.templates.keys().for_each(|k| {
+            if self.templates.contains_key(k) {
+                errors.push(format!("duplicate template name found: {}", k));
+            }
+        });
+        with.instances.keys().for_each(|k| {
+            if self.instances.contains_key(k) {
+                errors.push(format!("duplicate instance name found: {}", k));
+            }
+        });
         with.tests.iter().for_each(|wt| {
             if self.tests.iter().any(|t| t.name == wt.name) {
                 errors.push(format!("duplicate test name found: {}", wt.name));
@@ -148,6 +196,10 @@ impl ConfigBuilder {
         self.sinks.extend(with.sinks);
         self.transforms.extend(with.transforms);
         self.tests.extend(with.tests);
+        self.secret.extend(with.secret);
+        self.include.extend(with.include);
+        self.templates.extend(with.templates);
+        self.instances.extend(with.instances);
 
         Ok(())
     }
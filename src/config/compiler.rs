@@ -1,11 +1,21 @@
-use super::{builder::ConfigBuilder, handle_warnings, validation, Config, TransformOuter};
+use super::{
+    builder::ConfigBuilder,
+    handle_warnings,
+    template::{ComponentKind, Location},
+    validation, Config, TransformOuter,
+};
 use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
 
 pub fn compile(mut builder: ConfigBuilder, deny_warnings: bool) -> Result<Config, Vec<String>> {
     let mut errors = Vec::new();
 
+    expand_templates(&mut builder)?;
+
     expand_wildcards(&mut builder);
 
+    wire_dead_letters(&mut builder);
+
     let expansions = expand_macros(&mut builder)?;
 
     if let Err(warn) = handle_warnings(validation::warnings(&builder), deny_warnings) {
@@ -41,6 +51,69 @@ pub fn compile(mut builder: ConfigBuilder, deny_warnings: bool) -> Result<Config
     }
 }
 
+/// Replaces each template instance with the component it describes.
+pub(super) fn expand_templates(config: &mut ConfigBuilder) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+
+    for (name, instance) in std::mem::take(&mut config.instances) {
+        let template = match config.templates.get(&instance.template) {
+            Some(template) => template,
+            None => {
+                errors.push(format!(
+                    "instance {:?} refers to unknown template {:?}",
+                    name, instance.template
+                ));
+                continue;
+            }
+        };
+        let location = Location::new(&instance.template, template);
+
+        let value =
+            match template.instantiate(&instance) {
+                Ok(value) => value,
+                Err(errs) => {
+                    errors.extend(errs.into_iter().map(|error| {
+                        format!("{}, instantiated as {:?}: {}", location, name, error)
+                    }));
+                    continue;
+                }
+            };
+        let result = match template.kind {
+            ComponentKind::Source => instantiate(&mut config.sources, &name, value, "source"),
+            ComponentKind::Transform => {
+                instantiate(&mut config.transforms, &name, value, "transform")
+            }
+            ComponentKind::Sink => instantiate(&mut config.sinks, &name, value, "sink"),
+        };
+        if let Err(error) = result {
+            errors.push(format!(
+                "{}, instantiated as {:?}: {}",
+                location, name, error
+            ));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn instantiate<T: DeserializeOwned>(
+    components: &mut IndexMap<String, T>,
+    name: &str,
+    value: serde_json::Value,
+    kind: &str,
+) -> Result<(), String> {
+    if components.contains_key(name) {
+        return Err(format!("duplicate {} name found: {}", kind, name));
+    }
+    let component = serde_json::from_value(value).map_err(|error| error.to_string())?;
+    components.insert(name.to_owned(), component);
+    Ok(())
+}
+
 /// Some component configs can act like macros and expand themselves into multiple replacement
 /// configs. Performs those expansions and records the relevant metadata.
 pub(super) fn expand_macros(
@@ -102,6 +175,31 @@ fn expand_wildcards(config: &mut ConfigBuilder) {
     }
 }
 
+/// Adds the dead letter outputs of sinks to the inputs of the components they
+/// name. Unknown components are reported by validation.
+fn wire_dead_letters(config: &mut ConfigBuilder) {
+    let dead_letters = config
+        .sinks
+        .iter()
+        .filter_map(|(name, sink)| {
+            Some((sink.dead_letter.clone()?, sink.dead_letter_output(name)?))
+        })
+        .collect::<Vec<_>>();
+
+    for (target, output) in dead_letters {
+        let inputs = if let Some(sink) = config.sinks.get_mut(&target) {
+            &mut sink.inputs
+        } else if let Some(transform) = config.transforms.get_mut(&target) {
+            &mut transform.inputs
+        } else {
+            continue;
+        };
+        if !inputs.contains(&output) {
+            inputs.push(output);
+        }
+    }
+}
+
 fn expand_wildcards_inner(inputs: &mut Vec<String>, name: &str, candidates: &[String]) {
     let raw_inputs = std::mem::take(inputs);
     for raw_input in raw_inputs {
@@ -218,4 +316,110 @@ mod test {
             vec!["foo1", "foo2", "bar", "foos"]
         );
     }
+
+    #[test]
+    fn dead_letter_wiring() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        builder.add_sink("out", &["in"], MockSinkConfig);
+        builder.add_sink("dlq", &["in"], MockSinkConfig);
+        builder.sinks["out"].dead_letter = Some("dlq".into());
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.sinks["dlq"].inputs, vec!["in", "out.dead_letter"]);
+    }
+
+    #[test]
+    fn dead_letter_must_exist() {
+        let mut builder = ConfigBuilder::default();
+        builder.add_source("in", MockSourceConfig);
+        builder.add_sink("out", &["in"], MockSinkConfig);
+        builder.sinks["out"].dead_letter = Some("dlq".into());
+
+        let errors = builder.build().expect_err("build should fail");
+
+        assert_eq!(
+            errors,
+            vec![r#"Dead letter "dlq" for sink "out" doesn't exist."#.to_owned()]
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct MockUriSinkConfig {
+        uri: String,
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_uri")]
+    impl SinkConfig for MockUriSinkConfig {
+        async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+            unimplemented!()
+        }
+
+        fn sink_type(&self) -> &'static str {
+            "mock_uri"
+        }
+
+        fn input_type(&self) -> DataType {
+            DataType::Any
+        }
+    }
+
+    #[test]
+    fn template_expansion() {
+        let mut builder: ConfigBuilder = crate::config::format::deserialize(
+            r#"
+            [templates.tenant]
+            kind = "sink"
+            config.type = "mock_uri"
+            config.inputs = ["${var.input}"]
+            config.uri = "https://${var.tenant}.example.com"
+
+            [instances.acme]
+            template = "tenant"
+            vars = { tenant = "acme", input = "in" }
+            "#,
+            Some(crate::config::Format::TOML),
+        )
+        .unwrap();
+        builder.add_source("in", MockSourceConfig);
+
+        let config = builder.build().expect("build should succeed");
+
+        assert_eq!(config.sinks["acme"].inputs, vec!["in"]);
+        let sink = serde_json::to_value(&config.sinks["acme"].inner).unwrap();
+        assert_eq!(sink["uri"], "https://acme.example.com");
+    }
+
+    #[test]
+    fn template_errors_name_the_template() {
+        let mut builder: ConfigBuilder = crate::config::format::deserialize(
+            r#"
+            [templates.tenant]
+            kind = "sink"
+            config.type = "mock_uri"
+            config.inputs = ["in"]
+
+            [instances.acme]
+            template = "tenant"
+
+            [instances.other]
+            template = "missing"
+            "#,
+            Some(crate::config::Format::TOML),
+        )
+        .unwrap();
+        builder.templates["tenant"].origin = Some("tenants.toml".into());
+
+        let errors = builder.build().expect_err("build should fail");
+
+        assert_eq!(
+            errors,
+            vec![
+                r#"template "tenant" defined in "tenants.toml", instantiated as "acme": missing field `uri`"#.to_owned(),
+                r#"instance "other" refers to unknown template "missing""#.to_owned(),
+            ]
+        );
+    }
 }
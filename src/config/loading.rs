@@ -1,8 +1,12 @@
-use super::{builder::ConfigBuilder, format, handle_warnings, vars, Config, Format, FormatHint};
+use super::{
+    builder::ConfigBuilder, format, handle_warnings, remote::RemoteConfig, secret, vars, Config,
+    Format, FormatHint,
+};
 use glob::glob;
 use lazy_static::lazy_static;
+use serde::Deserialize;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fs::File,
     path::{Path, PathBuf},
     sync::Mutex,
@@ -18,6 +22,9 @@ lazy_static! {
         vec![(PathBuf::from(config_path), Some(Format::TOML))]
     };
     pub static ref CONFIG_PATHS: Mutex<Vec<(PathBuf, FormatHint)>> = Mutex::default();
+    /// Every file loaded through `include` so far, so that the config watcher
+    /// can watch them too.
+    pub static ref INCLUDED_PATHS: Mutex<HashSet<PathBuf>> = Mutex::default();
 }
 
 /// Merge the paths coming from different cli flags with different formats into
@@ -83,6 +90,22 @@ pub fn load_from_paths(
     load_builder_from_paths(config_paths, deny_warnings)?.build_with(deny_warnings)
 }
 
+/// Loads the configs at `config_paths` along with the last fetched content of
+/// `remotes`.
+pub fn load_from_paths_and_remotes(
+    config_paths: &[(PathBuf, FormatHint)],
+    remotes: &[RemoteConfig],
+    deny_warnings: bool,
+) -> Result<Config, Vec<String>> {
+    let mut builder = load_builder_from_paths(config_paths, deny_warnings)?;
+    let inputs = remotes
+        .iter()
+        .filter_map(|remote| remote.content())
+        .map(|(content, format)| (content.as_bytes(), format, None));
+    builder.append(load_from_inputs(inputs, deny_warnings)?)?;
+    builder.build_with(deny_warnings)
+}
+
 pub fn load_builder_from_paths(
     config_paths: &[(PathBuf, FormatHint)],
     deny_warnings: bool,
@@ -92,7 +115,8 @@ pub fn load_builder_from_paths(
 
     for (path, format) in config_paths {
         if let Some(file) = open_config(&path) {
-            inputs.push((file, format.or_else(move || Format::from_path(&path).ok())));
+            let format = format.or_else(|| Format::from_path(&path).ok());
+            inputs.push((file, format, Some(path.clone())));
         } else {
             errors.push(format!("Config file not found in path: {:?}.", path));
         };
@@ -106,18 +130,47 @@ pub fn load_builder_from_paths(
 }
 
 pub fn load_from_str(input: &str, format: FormatHint) -> Result<Config, Vec<String>> {
-    load_from_inputs(std::iter::once((input.as_bytes(), format)), false)?.build()
+    load_from_inputs(std::iter::once((input.as_bytes(), format, None)), false)?.build()
 }
 
 fn load_from_inputs(
-    inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint)>,
+    inputs: impl IntoIterator<Item = (impl std::io::Read, FormatHint, Option<PathBuf>)>,
     deny_warnings: bool,
 ) -> Result<ConfigBuilder, Vec<String>> {
     let mut config = Config::builder();
     let mut errors = Vec::new();
 
-    for (input, format) in inputs {
-        if let Err(errs) = load(input, format, deny_warnings).and_then(|n| config.append(n)) {
+    let mut contents = Vec::new();
+    let mut paths = Vec::new();
+    for (input, format, path) in inputs {
+        match load(input, deny_warnings) {
+            Ok(content) => {
+                contents.push((content, format));
+                paths.push(path);
+            }
+            Err(errs) => errors.extend(errs),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    load_includes(&mut contents, &mut paths, deny_warnings)?;
+
+    // Secrets can be referenced across files, so they are resolved for all
+    // of them at once.
+    secret::interpolate(&mut contents)?;
+
+    for ((content, format), path) in contents.into_iter().zip(paths) {
+        let loaded = format::deserialize::<ConfigBuilder>(&content, format)
+            .map(|mut loaded| {
+                for template in loaded.templates.values_mut() {
+                    template.origin = path.clone();
+                }
+                loaded
+            })
+            .map_err(|errs| errs.iter().map(|e| secret::redact(e)).collect());
+        if let Err(errs) = loaded.and_then(|n| config.append(n)) {
             // TODO: add back paths
             errors.extend(errs.iter().map(|e| e.to_string()));
         }
@@ -130,6 +183,101 @@ fn load_from_inputs(
     }
 }
 
+/// Only the part of a config needed to find the files it includes.
+#[derive(Deserialize)]
+struct IncludesOnly {
+    #[serde(default)]
+    include: Vec<String>,
+}
+
+/// Loads the files included by `contents`, and the ones included by those in
+/// turn, appending them to `contents`. Relative paths are relative to the
+/// directory of the including file, so configs that aren't read from a file,
+/// such as remote configs, can only include absolute paths. Each file is only
+/// loaded once.
+fn load_includes(
+    contents: &mut Vec<(String, FormatHint)>,
+    paths: &mut Vec<Option<PathBuf>>,
+    deny_warnings: bool,
+) -> Result<(), Vec<String>> {
+    let mut loaded = paths
+        .iter()
+        .flatten()
+        .filter_map(|path| path.canonicalize().ok())
+        .collect::<HashSet<_>>();
+    let mut errors = Vec::new();
+
+    let mut index = 0;
+    while index < contents.len() {
+        let (content, format) = &contents[index];
+        let includes = format::deserialize::<IncludesOnly>(content, *format)?.include;
+        let base = paths[index]
+            .as_ref()
+            .map(|path| path.parent().map(Path::to_path_buf).unwrap_or_default());
+        index += 1;
+
+        for include in includes {
+            let pattern = match &base {
+                Some(base) => base.join(&include),
+                None if Path::new(&include).is_absolute() => PathBuf::from(&include),
+                None => {
+                    errors.push(format!(
+                        "Relative include {:?} can only be used in config files, use an absolute path instead.",
+                        include
+                    ));
+                    continue;
+                }
+            };
+            let matches = match glob(&pattern.to_string_lossy()) {
+                Ok(matches) => matches.filter_map(Result::ok).collect::<Vec<_>>(),
+                Err(error) => {
+                    errors.push(format!("Invalid include pattern {:?}: {}", include, error));
+                    continue;
+                }
+            };
+            if matches.is_empty() {
+                errors.push(format!(
+                    "Included config file not found in path: {:?}.",
+                    pattern
+                ));
+            }
+
+            for path in matches {
+                let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+                if !loaded.insert(canonical) {
+                    continue;
+                }
+                let file = match open_config(&path) {
+                    Some(file) => file,
+                    None => {
+                        errors.push(format!("Config file not found in path: {:?}.", path));
+                        continue;
+                    }
+                };
+                match load(file, deny_warnings) {
+                    Ok(content) => {
+                        // Ignore poison error, the path is only needed for watching.
+                        std::mem::drop(
+                            INCLUDED_PATHS
+                                .lock()
+                                .map(|mut guard| guard.insert(path.clone())),
+                        );
+                        contents.push((content, Format::from_path(&path).ok()));
+                        paths.push(Some(path));
+                    }
+                    Err(errs) => errors.extend(errs),
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
 fn open_config(path: &Path) -> Option<File> {
     match File::open(path) {
         Ok(f) => Some(f),
@@ -145,11 +293,8 @@ fn open_config(path: &Path) -> Option<File> {
     }
 }
 
-fn load(
-    mut input: impl std::io::Read,
-    format: FormatHint,
-    deny_warnings: bool,
-) -> Result<ConfigBuilder, Vec<String>> {
+/// Reads `input`, interpolating environment variables.
+fn load(mut input: impl std::io::Read, deny_warnings: bool) -> Result<String, Vec<String>> {
     let mut source_string = String::new();
     input
         .read_to_string(&mut source_string)
@@ -164,5 +309,5 @@ fn load(
     let (with_vars, warnings) = vars::interpolate(&source_string, &vars);
     handle_warnings(warnings, deny_warnings)?;
 
-    format::deserialize(&with_vars, format)
+    Ok(with_vars)
 }
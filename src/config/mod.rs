@@ -17,6 +17,7 @@ use std::fs::DirBuilder;
 use std::hash::Hash;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::time::Duration;
 
 pub mod api;
 mod builder;
@@ -26,6 +27,10 @@ mod diff;
 pub mod format;
 mod loading;
 mod log_schema;
+mod proxy;
+pub mod remote;
+pub mod secret;
+pub mod template;
 mod unit_test;
 mod validation;
 mod vars;
@@ -34,8 +39,12 @@ pub mod watcher;
 pub use builder::ConfigBuilder;
 pub use diff::ConfigDiff;
 pub use format::{Format, FormatHint};
-pub use loading::{load_from_paths, load_from_str, merge_path_lists, process_paths, CONFIG_PATHS};
+pub use loading::{
+    load_from_paths, load_from_paths_and_remotes, load_from_str, merge_path_lists, process_paths,
+    CONFIG_PATHS, INCLUDED_PATHS,
+};
 pub use log_schema::{log_schema, LogSchema, LOG_SCHEMA};
+pub use proxy::ProxyConfig;
 pub use unit_test::build_unit_tests_main as build_unit_tests;
 pub use validation::warnings;
 
@@ -61,6 +70,13 @@ pub struct GlobalOptions {
         default
     )]
     pub log_schema: LogSchema,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reload_drain_timeout_secs: Option<u64>,
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    pub proxy: ProxyConfig,
 }
 
 pub fn default_data_dir() -> Option<PathBuf> {
@@ -89,6 +105,12 @@ pub enum DataDirError {
 }
 
 impl GlobalOptions {
+    /// How long sinks that are changed or removed on reload are given to
+    /// send the events they hold before they are shut down.
+    pub fn reload_drain_timeout(&self) -> Duration {
+        Duration::from_secs(self.reload_drain_timeout_secs.unwrap_or(60))
+    }
+
     /// Resolve the `data_dir` option in either the global or local
     /// config, and validate that it exists and is writable.
     pub fn resolve_and_validate_data_dir(
@@ -210,6 +232,17 @@ pub type SourceDescription = ComponentDescription<Box<dyn SourceConfig>>;
 
 inventory::collect!(SourceDescription);
 
+/// The name other components use to consume the output `output` of
+/// `component`.
+pub fn output_name(component: &str, output: &str) -> String {
+    format!("{}.{}", component, output)
+}
+
+/// The name of the output a sink sends the events it rejects on.
+pub fn dead_letter_output(sink: &str) -> String {
+    output_name(sink, "dead_letter")
+}
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct SinkOuter {
     pub inputs: Vec<String>,
@@ -225,6 +258,16 @@ pub struct SinkOuter {
     #[serde(default)]
     pub buffer: crate::buffers::BufferConfig,
 
+    /// Sink or transform that events this sink permanently rejects are sent to.
+    #[serde(default)]
+    pub dead_letter: Option<String>,
+
+    #[serde(
+        skip_serializing_if = "crate::serde::skip_serializing_if_default",
+        default
+    )]
+    pub proxy: ProxyConfig,
+
     #[serde(flatten)]
     pub inner: Box<dyn SinkConfig>,
 }
@@ -235,11 +278,19 @@ impl SinkOuter {
             buffer: Default::default(),
             healthcheck: SinkHealthcheckOptions::default(),
             healthcheck_uri: None,
+            dead_letter: None,
+            proxy: Default::default(),
             inner,
             inputs,
         }
     }
 
+    /// The name of the output the rejected events are sent on, which the
+    /// `dead_letter` component has as an input.
+    pub fn dead_letter_output(&self, name: &str) -> Option<String> {
+        self.dead_letter.as_ref().map(|_| dead_letter_output(name))
+    }
+
     pub fn resources(&self, name: &str) -> Vec<Resource> {
         let mut resources = self.inner.resources();
         resources.append(&mut self.buffer.resources(name));
@@ -316,6 +367,7 @@ pub trait SinkConfig: core::fmt::Debug + Send + Sync {
 pub struct SinkContext {
     pub(super) acker: Acker,
     pub(super) healthcheck: SinkHealthcheckOptions,
+    pub(super) proxy: ProxyConfig,
 }
 
 impl SinkContext {
@@ -324,12 +376,19 @@ impl SinkContext {
         Self {
             acker: Acker::Null,
             healthcheck: SinkHealthcheckOptions::default(),
+            proxy: ProxyConfig::default(),
         }
     }
 
     pub fn acker(&self) -> Acker {
         self.acker.clone()
     }
+
+    /// The proxy options of the sink, merged with the global ones and the
+    /// environment variables.
+    pub fn proxy(&self) -> &ProxyConfig {
+        &self.proxy
+    }
 }
 
 pub type SinkDescription = ComponentDescription<Box<dyn SinkConfig>>;
@@ -343,6 +402,18 @@ pub struct TransformOuter {
     pub inner: Box<dyn TransformConfig>,
 }
 
+impl TransformOuter {
+    /// The names of the transform's named outputs, as they are referred to
+    /// in `inputs`.
+    pub fn named_outputs(&self, name: &str) -> Vec<String> {
+        self.inner
+            .named_outputs()
+            .iter()
+            .map(|output| output_name(name, output))
+            .collect()
+    }
+}
+
 #[async_trait]
 #[typetag::serde(tag = "type")]
 pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone {
@@ -354,6 +425,18 @@ pub trait TransformConfig: core::fmt::Debug + Send + Sync + dyn_clone::DynClone
 
     fn transform_type(&self) -> &'static str;
 
+    /// Outputs the transform has in addition to its default one, consumed as
+    /// `<transform name>.<output name>`. Events on them are of `output_type`.
+    fn named_outputs(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Whether anything is emitted on the default output. Transforms that
+    /// only emit on their named outputs can't be consumed by name.
+    fn has_default_output(&self) -> bool {
+        true
+    }
+
     /// Allows a transform configuration to expand itself into multiple "child"
     /// transformations to replace it. This allows a transform to act as a macro
     /// for various patterns.
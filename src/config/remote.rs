@@ -0,0 +1,286 @@
+//! Configs fetched from HTTP(S) endpoints, such as a central config service.
+
+use super::{Format, FormatHint, ProxyConfig};
+use crate::http::{HttpClient, HttpError};
+use http::{
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    HeaderValue, Request, StatusCode, Uri,
+};
+use hyper::{body::to_bytes as body_to_bytes, Body};
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+pub enum RemoteConfigError {
+    #[snafu(display("Failed to fetch config from {}: {}", url, source))]
+    Request { url: Uri, source: HttpError },
+    #[snafu(display("Failed to fetch config from {}: {}", url, status))]
+    Status { url: Uri, status: StatusCode },
+    #[snafu(display("Failed to read config from {}: {}", url, source))]
+    ReadBody { url: Uri, source: hyper::Error },
+    #[snafu(display("Config from {} is not valid UTF-8: {}", url, source))]
+    NotUtf8 {
+        url: Uri,
+        source: std::string::FromUtf8Error,
+    },
+}
+
+/// A config fetched over HTTP(S). The validators of the last response are
+/// sent along with the next request, so that unchanged configs aren't
+/// downloaded again.
+///
+/// Changed content is only kept once the config loaded from it, so that a
+/// broken config doesn't replace the one that's running, see `loaded`.
+#[derive(Clone, Debug)]
+pub struct RemoteConfig {
+    url: Uri,
+    format: FormatHint,
+    etag: Option<HeaderValue>,
+    last_modified: Option<HeaderValue>,
+    /// The content of the last response, to tell whether it changed.
+    fetched: Option<String>,
+    /// Changed content that the config wasn't loaded from yet.
+    pending: Option<String>,
+    /// The content the config was last loaded from.
+    content: Option<String>,
+}
+
+impl RemoteConfig {
+    /// The format is detected from the path of the URL.
+    pub fn new(url: Uri) -> Self {
+        let format = Format::from_path(url.path()).ok();
+        Self {
+            url,
+            format,
+            etag: None,
+            last_modified: None,
+            fetched: None,
+            pending: None,
+            content: None,
+        }
+    }
+
+    pub fn url(&self) -> &Uri {
+        &self.url
+    }
+
+    /// The content to load the config from, the one that changed if any.
+    pub fn content(&self) -> Option<(&str, FormatHint)> {
+        self.pending
+            .as_deref()
+            .or_else(|| self.content.as_deref())
+            .map(|content| (content, self.format))
+    }
+
+    /// Keeps the changed content if the config loaded from it, or drops it
+    /// otherwise. It isn't loaded again until it changes once more.
+    pub fn loaded(&mut self, success: bool) {
+        if let Some(pending) = self.pending.take() {
+            if success {
+                self.content = Some(pending);
+            }
+        }
+    }
+
+    /// Fetches the config, returning whether its content changed. The
+    /// previous content is kept if fetching fails.
+    pub async fn fetch(&mut self, client: &HttpClient) -> Result<bool, RemoteConfigError> {
+        let mut request = Request::get(&self.url);
+        if self.fetched.is_some() {
+            if let Some(etag) = &self.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &self.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let request = request
+            .body(Body::empty())
+            .expect("Building the request can't fail");
+
+        let response = client.send(request).await.context(Request {
+            url: self.url.clone(),
+        })?;
+        match response.status() {
+            StatusCode::NOT_MODIFIED if self.fetched.is_some() => return Ok(false),
+            status if status.is_success() => (),
+            status => {
+                return Err(RemoteConfigError::Status {
+                    url: self.url.clone(),
+                    status,
+                })
+            }
+        }
+
+        let etag = response.headers().get(ETAG).cloned();
+        let last_modified = response.headers().get(LAST_MODIFIED).cloned();
+        let body = body_to_bytes(response.into_body())
+            .await
+            .context(ReadBody {
+                url: self.url.clone(),
+            })?;
+        let content = String::from_utf8(body.to_vec()).context(NotUtf8 {
+            url: self.url.clone(),
+        })?;
+
+        self.etag = etag;
+        self.last_modified = last_modified;
+        if self.fetched.as_ref() == Some(&content) {
+            return Ok(false);
+        }
+        self.fetched = Some(content.clone());
+        self.pending = Some(content);
+        Ok(true)
+    }
+}
+
+/// Tells each of `remotes` whether the config loaded from them.
+pub fn loaded(remotes: &mut [RemoteConfig], success: bool) {
+    for remote in remotes {
+        remote.loaded(success);
+    }
+}
+
+/// Fetches all `remotes`, returning whether any of them changed along with
+/// the errors of the ones that failed.
+pub async fn fetch_all(remotes: &mut [RemoteConfig]) -> (bool, Vec<String>) {
+    if remotes.is_empty() {
+        return (false, Vec::new());
+    }
+
+    // Remotes are fetched before the global options are loaded.
+    let client = match HttpClient::new(None, &ProxyConfig::from_env()) {
+        Ok(client) => client,
+        Err(error) => return (false, vec![error.to_string()]),
+    };
+
+    let mut changed = false;
+    let mut errors = Vec::new();
+    for remote in remotes.iter_mut() {
+        match remote.fetch(&client).await {
+            Ok(remote_changed) => changed |= remote_changed,
+            Err(error) => errors.push(error.to_string()),
+        }
+    }
+    (changed, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RemoteConfig;
+    use crate::{
+        config::{Format, ProxyConfig},
+        http::HttpClient,
+        test_util::next_addr,
+    };
+    use http::Uri;
+    use hyper::{
+        header::{ETAG, IF_NONE_MATCH},
+        service::{make_service_fn, service_fn},
+        Body, Response, Server, StatusCode,
+    };
+    use std::{
+        convert::Infallible,
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc,
+        },
+    };
+
+    /// Serves `version = <version>`, with the version as its ETag.
+    fn serve_versions() -> (Uri, Arc<AtomicUsize>) {
+        let addr = next_addr();
+        let version = Arc::new(AtomicUsize::new(1));
+        let server_version = Arc::clone(&version);
+        let make_service = make_service_fn(move |_| {
+            let version = Arc::clone(&server_version);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request| {
+                    let version = version.load(Ordering::SeqCst).to_string();
+                    let response = match request.headers().get(IF_NONE_MATCH) {
+                        Some(etag) if etag == version.as_str() => Response::builder()
+                            .status(StatusCode::NOT_MODIFIED)
+                            .body(Body::empty()),
+                        _ => Response::builder()
+                            .header(ETAG, version.as_str())
+                            .body(Body::from(format!("version = {}", version))),
+                    };
+                    async move { Ok::<_, Infallible>(response.unwrap()) }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+
+        let url = format!("http://{}/vector.toml", addr).parse().unwrap();
+        (url, version)
+    }
+
+    #[tokio::test]
+    async fn fetches_only_changed_configs() {
+        let (url, version) = serve_versions();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+        let mut remote = RemoteConfig::new(url);
+
+        assert!(remote.fetch(&client).await.unwrap());
+        assert_eq!(remote.content(), Some(("version = 1", Some(Format::TOML))));
+        assert!(!remote.fetch(&client).await.unwrap());
+
+        version.store(2, Ordering::SeqCst);
+        assert!(remote.fetch(&client).await.unwrap());
+        assert_eq!(remote.content(), Some(("version = 2", Some(Format::TOML))));
+    }
+
+    #[tokio::test]
+    async fn keeps_content_until_it_loads() {
+        let (url, version) = serve_versions();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+        let mut remote = RemoteConfig::new(url);
+        assert!(remote.fetch(&client).await.unwrap());
+        remote.loaded(true);
+
+        version.store(2, Ordering::SeqCst);
+        assert!(remote.fetch(&client).await.unwrap());
+        assert_eq!(remote.content(), Some(("version = 2", Some(Format::TOML))));
+        remote.loaded(false);
+        assert_eq!(remote.content(), Some(("version = 1", Some(Format::TOML))));
+
+        // Content that failed to load isn't loaded again until it changes.
+        assert!(!remote.fetch(&client).await.unwrap());
+        assert_eq!(remote.content(), Some(("version = 1", Some(Format::TOML))));
+        version.store(3, Ordering::SeqCst);
+        assert!(remote.fetch(&client).await.unwrap());
+        remote.loaded(true);
+        assert_eq!(remote.content(), Some(("version = 3", Some(Format::TOML))));
+    }
+
+    #[tokio::test]
+    async fn keeps_content_when_fetching_fails() {
+        let addr = next_addr();
+        let failing = Arc::new(AtomicBool::new(false));
+        let server_failing = Arc::clone(&failing);
+        let make_service = make_service_fn(move |_| {
+            let failing = Arc::clone(&server_failing);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |_| {
+                    let response = if failing.load(Ordering::SeqCst) {
+                        Response::builder()
+                            .status(StatusCode::INTERNAL_SERVER_ERROR)
+                            .body(Body::empty())
+                    } else {
+                        Response::builder().body(Body::from("version = 1"))
+                    };
+                    async move { Ok::<_, Infallible>(response.unwrap()) }
+                }))
+            }
+        });
+        tokio::spawn(Server::bind(&addr).serve(make_service));
+
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
+        let url = format!("http://{}/vector.toml", addr).parse().unwrap();
+        let mut remote = RemoteConfig::new(url);
+        assert!(remote.fetch(&client).await.unwrap());
+
+        failing.store(true, Ordering::SeqCst);
+        assert!(remote.fetch(&client).await.is_err());
+        assert_eq!(remote.content(), Some(("version = 1", Some(Format::TOML))));
+    }
+}
@@ -0,0 +1,531 @@
+//! Secrets referenced in configs as `SECRET[backend.key]`.
+//!
+//! The backends are configured in the `secret` table. Each time the configs
+//! are loaded, every backend is asked for the keys referenced from them. The
+//! values resolved by the last two loads, which covers the running config and
+//! the one replacing it, are remembered so they can be redacted from logs,
+//! error messages and the API. Only values of at least four characters are
+//! redacted, and only where they aren't part of a longer word, so that
+//! secrets like `1` or `true` don't redact unrelated text.
+
+use super::{format, FormatHint};
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet, VecDeque},
+    io::{self, Read, Write},
+    path::PathBuf,
+    process::{Command, ExitStatus, Stdio},
+    sync::{mpsc, RwLock},
+    thread,
+    time::Duration,
+};
+
+const REDACTED: &str = "**REDACTED**";
+
+lazy_static! {
+    /// Matches either a whole commented out line, which has no capture groups,
+    /// or a secret reference.
+    static ref SECRET_REGEX: Regex =
+        Regex::new(r"(?m)^[ \t]*#.*$|SECRET\[([[:word:]]+)\.([[:word:].-]+)\]").unwrap();
+    static ref RESOLVED: RwLock<VecDeque<HashSet<String>>> = RwLock::default();
+}
+
+/// How many loads the resolved secrets are remembered for.
+const RESOLVED_GENERATIONS: usize = 2;
+
+/// The length below which resolved secrets aren't redacted.
+const MIN_REDACTED_LENGTH: usize = 4;
+
+#[derive(Debug, Snafu)]
+enum SecretError {
+    #[snafu(display("Command is empty"))]
+    EmptyCommand,
+    #[snafu(display("Failed to run {:?}: {}", command, source))]
+    Run { command: String, source: io::Error },
+    #[snafu(display("{:?} didn't exit within {} seconds", command, timeout_secs))]
+    Timeout { command: String, timeout_secs: u64 },
+    #[snafu(display("{:?} exited with {}", command, status))]
+    Exit { command: String, status: ExitStatus },
+    #[snafu(display("Failed to read {:?}: {}", path, source))]
+    ReadFile { path: PathBuf, source: io::Error },
+    #[snafu(display("Failed to parse secrets: {}", source))]
+    Parse { source: serde_json::Error },
+    #[snafu(display("Secret {:?} not found", key))]
+    NotFound { key: String },
+    #[snafu(display("Failed to retrieve secret {:?}: {}", key, error))]
+    Retrieve { key: String, error: String },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SecretBackend {
+    /// Runs a command, which is passed the requested keys as JSON on stdin,
+    /// and reads the secrets as JSON from its stdout.
+    Exec(ExecBackend),
+    /// Reads the secrets from a file holding a JSON object.
+    File(FileBackend),
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExecBackend {
+    command: Vec<String>,
+    #[serde(default = "default_timeout_secs")]
+    timeout_secs: u64,
+}
+
+fn default_timeout_secs() -> u64 {
+    5
+}
+
+#[derive(Deserialize)]
+struct ExecResponse {
+    value: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FileBackend {
+    path: PathBuf,
+}
+
+impl SecretBackend {
+    fn retrieve(&self, keys: &BTreeSet<String>) -> Result<HashMap<String, String>, SecretError> {
+        match self {
+            Self::Exec(backend) => backend.retrieve(keys),
+            Self::File(backend) => backend.retrieve(keys),
+        }
+    }
+}
+
+impl ExecBackend {
+    fn retrieve(&self, keys: &BTreeSet<String>) -> Result<HashMap<String, String>, SecretError> {
+        let (program, args) = self
+            .command
+            .split_first()
+            .ok_or(SecretError::EmptyCommand)?;
+        let command = self.command.join(" ");
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| Run {
+                command: command.clone(),
+            })?;
+
+        let request = serde_json::json!({ "version": "1.0", "secrets": keys }).to_string();
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let result = stdin.write_all(request.as_bytes()).and_then(|()| {
+                drop(stdin);
+                let mut output = Vec::new();
+                stdout.read_to_end(&mut output).map(|_| output)
+            });
+            let _ = tx.send(result);
+        });
+
+        let output = match rx.recv_timeout(Duration::from_secs(self.timeout_secs)) {
+            Ok(output) => output.with_context(|| Run {
+                command: command.clone(),
+            })?,
+            Err(_) => {
+                // The reader thread isn't waited for, as a process started by
+                // the command may keep its stdout open after it is killed.
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(SecretError::Timeout {
+                    command,
+                    timeout_secs: self.timeout_secs,
+                });
+            }
+        };
+        let status = child.wait().with_context(|| Run {
+            command: command.clone(),
+        })?;
+        if !status.success() {
+            return Err(SecretError::Exit { command, status });
+        }
+
+        let mut response: HashMap<String, ExecResponse> =
+            serde_json::from_slice(&output).context(Parse)?;
+        keys.iter()
+            .map(|key| match response.remove(key) {
+                Some(ExecResponse {
+                    error: Some(error), ..
+                }) => Err(SecretError::Retrieve {
+                    key: key.clone(),
+                    error,
+                }),
+                Some(ExecResponse {
+                    value: Some(value), ..
+                }) => Ok((key.clone(), value)),
+                _ => Err(SecretError::NotFound { key: key.clone() }),
+            })
+            .collect()
+    }
+}
+
+impl FileBackend {
+    fn retrieve(&self, keys: &BTreeSet<String>) -> Result<HashMap<String, String>, SecretError> {
+        let content = std::fs::read(&self.path).with_context(|| ReadFile {
+            path: self.path.clone(),
+        })?;
+        let mut secrets: HashMap<String, String> =
+            serde_json::from_slice(&content).context(Parse)?;
+        keys.iter()
+            .map(|key| {
+                secrets
+                    .remove(key)
+                    .map(|value| (key.clone(), value))
+                    .ok_or_else(|| SecretError::NotFound { key: key.clone() })
+            })
+            .collect()
+    }
+}
+
+/// Only the part of a config needed to resolve its secrets.
+#[derive(Deserialize)]
+struct SecretsOnly {
+    #[serde(default)]
+    secret: IndexMap<String, SecretBackend>,
+}
+
+/// Replaces the secrets referenced in `contents` with their values, which
+/// are retrieved from the backends configured in any of the `contents`.
+pub(super) fn interpolate(contents: &mut [(String, FormatHint)]) -> Result<(), Vec<String>> {
+    if contents
+        .iter()
+        .all(|(content, _)| references(content).next().is_none())
+    {
+        return Ok(());
+    }
+
+    let mut backends = IndexMap::new();
+    let mut errors = Vec::new();
+    for (content, format) in contents.iter() {
+        let secrets = format::deserialize::<SecretsOnly>(content, *format)?;
+        for (name, backend) in secrets.secret {
+            if backends.contains_key(&name) {
+                errors.push(format!("duplicate secret backend name found: {}", name));
+            } else {
+                backends.insert(name, backend);
+            }
+        }
+    }
+
+    let mut keys = HashMap::<_, BTreeSet<_>>::new();
+    for (content, _) in contents.iter() {
+        for (backend, key) in references(content) {
+            if backends.contains_key(backend) {
+                keys.entry(backend.to_owned())
+                    .or_default()
+                    .insert(key.to_owned());
+            } else {
+                errors.push(format!(
+                    "Secret backend {:?} referenced by SECRET[{}.{}] is not configured.",
+                    backend, backend, key
+                ));
+            }
+        }
+    }
+    if !errors.is_empty() {
+        errors.sort();
+        errors.dedup();
+        return Err(errors);
+    }
+
+    let mut secrets = HashMap::new();
+    for (backend, keys) in keys {
+        match backends[&backend].retrieve(&keys) {
+            Ok(values) => {
+                secrets.insert(backend, values);
+            }
+            Err(error) => errors.push(format!(
+                "Failed to retrieve secrets from backend {:?}: {}",
+                backend, error
+            )),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    remember(
+        secrets
+            .values()
+            .flat_map(|values| values.values())
+            .filter(|value| value.chars().count() >= MIN_REDACTED_LENGTH)
+            .cloned()
+            .collect(),
+    );
+
+    for (content, _) in contents.iter_mut() {
+        *content = SECRET_REGEX
+            .replace_all(content, |captures: &Captures<'_>| match captures.get(1) {
+                Some(backend) => escape(&secrets[backend.as_str()][&captures[2]]),
+                None => captures[0].to_owned(),
+            })
+            .into_owned();
+    }
+    Ok(())
+}
+
+/// Escapes `value` as inside a double-quoted string, which is the same for
+/// TOML, YAML and JSON, so that a secret can't end the string it is
+/// referenced from. Values without quotes, backslashes or control characters
+/// are unchanged, so those can also be referenced outside of strings.
+fn escape(value: &str) -> String {
+    let quoted = serde_json::to_string(value).expect("Strings always serialize.");
+    quoted[1..quoted.len() - 1].to_owned()
+}
+
+/// Returns the `(backend, key)` of each secret referenced outside of comments.
+fn references(content: &str) -> impl Iterator<Item = (&str, &str)> {
+    SECRET_REGEX.captures_iter(content).filter_map(|captures| {
+        let backend = captures.get(1)?.as_str();
+        let key = captures.get(2)?.as_str();
+        Some((backend, key))
+    })
+}
+
+/// Remembers the secrets resolved by a load, forgetting those of the oldest
+/// one. Loading the same secrets again doesn't count as a new load.
+fn remember(secrets: HashSet<String>) {
+    let mut resolved = RESOLVED.write().unwrap();
+    if resolved.back() != Some(&secrets) {
+        resolved.push_back(secrets);
+        while resolved.len() > RESOLVED_GENERATIONS {
+            resolved.pop_front();
+        }
+    }
+}
+
+fn is_resolved_empty() -> bool {
+    RESOLVED.read().unwrap().iter().all(HashSet::is_empty)
+}
+
+/// Replaces all remembered secrets in `message`.
+pub fn redact(message: &str) -> String {
+    let resolved = RESOLVED.read().unwrap();
+    resolved
+        .iter()
+        .flatten()
+        .fold(message.to_owned(), |message, secret| {
+            redact_secret(&message, secret)
+        })
+}
+
+/// Replaces the occurrences of `secret` in `message` that aren't part of a
+/// longer word.
+fn redact_secret(message: &str, secret: &str) -> String {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let starts_word = secret.chars().next().map_or(false, is_word);
+    let ends_word = secret.chars().next_back().map_or(false, is_word);
+
+    let mut redacted = String::with_capacity(message.len());
+    let mut last = 0;
+    for (start, _) in message.match_indices(secret) {
+        let end = start + secret.len();
+        if (starts_word && message[..start].chars().next_back().map_or(false, is_word))
+            || (ends_word && message[end..].chars().next().map_or(false, is_word))
+        {
+            continue;
+        }
+        redacted.push_str(&message[last..start]);
+        redacted.push_str(REDACTED);
+        last = end;
+    }
+    redacted.push_str(&message[last..]);
+    redacted
+}
+
+/// Replaces all remembered secrets in the strings of `value`.
+pub fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(string) => *string = redact(string),
+        serde_json::Value::Array(values) => values.iter_mut().for_each(redact_json),
+        serde_json::Value::Object(map) => map.values_mut().for_each(redact_json),
+        _ => (),
+    }
+}
+
+/// Redacts all remembered secrets from what is written to the inner writer.
+/// Output is held back until a whole line is written, so a secret split
+/// across writes is still redacted. The rest is written on flush or drop.
+#[derive(Debug)]
+pub struct Redacting<W: Write> {
+    inner: W,
+    buffer: Vec<u8>,
+}
+
+impl<W: Write> Redacting<W> {
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buffer: Vec::new(),
+        }
+    }
+
+    fn write_redacted(&mut self, len: usize) -> io::Result<()> {
+        let bytes = self.buffer.drain(..len).collect::<Vec<_>>();
+        match std::str::from_utf8(&bytes) {
+            Ok(text) => self.inner.write_all(redact(text).as_bytes()),
+            Err(_) => self.inner.write_all(&bytes),
+        }
+    }
+}
+
+impl<W: Write> Write for Redacting<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.buffer.is_empty() && is_resolved_empty() {
+            return self.inner.write(buf);
+        }
+        self.buffer.extend_from_slice(buf);
+        if let Some(end) = self.buffer.iter().rposition(|&byte| byte == b'\n') {
+            self.write_redacted(end + 1)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            self.write_redacted(self.buffer.len())?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<W: Write> Drop for Redacting<W> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{interpolate, redact, redact_secret, Redacting};
+    use crate::config::Format;
+    use std::io::Write;
+
+    #[test]
+    fn interpolates_secrets_from_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            file,
+            r#"{{"token": "file-secret-token", "quoted": "a\"b\\c\nd"}}"#
+        )
+        .unwrap();
+
+        let mut contents = vec![
+            (
+                format!(
+                    "[secret.files]\ntype = \"file\"\npath = {:?}\n",
+                    file.path()
+                ),
+                Some(Format::TOML),
+            ),
+            (
+                "token = \"SECRET[files.token]\"".to_owned(),
+                Some(Format::TOML),
+            ),
+            (
+                "[sinks.out]\ntoken = \"SECRET[files.quoted]\"".to_owned(),
+                Some(Format::TOML),
+            ),
+        ];
+        interpolate(&mut contents).unwrap();
+        assert_eq!(contents[1].0, "token = \"file-secret-token\"");
+        let value: toml::Value = toml::from_str(&contents[2].0).unwrap();
+        assert_eq!(value["sinks"]["out"]["token"].as_str(), Some("a\"b\\c\nd"));
+
+        assert_eq!(
+            redact("Invalid token file-secret-token."),
+            "Invalid token **REDACTED**."
+        );
+
+        let mut output = Vec::new();
+        {
+            let mut writer = Redacting::new(&mut output);
+            writer.write_all(b"Invalid token file-sec").unwrap();
+            writer
+                .write_all(b"ret-token.\nTrailing file-secret")
+                .unwrap();
+            writer.write_all(b"-token").unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Invalid token **REDACTED**.\nTrailing **REDACTED**"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn interpolates_secrets_from_exec() {
+        let script = r#"cat > /dev/null; echo '{"first": {"value": "exec-secret-first", "error": null}, "second": {"value": null, "error": "forbidden"}}'"#;
+        let backend = format!(
+            "[secret.exec]\ntype = \"exec\"\ncommand = [\"sh\", \"-c\", {:?}]\n",
+            script
+        );
+
+        let mut contents = vec![(
+            format!("{}token = \"SECRET[exec.first]\"", backend),
+            Some(Format::TOML),
+        )];
+        interpolate(&mut contents).unwrap();
+        assert!(contents[0].0.ends_with("token = \"exec-secret-first\""));
+
+        let mut contents = vec![(
+            format!("{}token = \"SECRET[exec.second]\"", backend),
+            Some(Format::TOML),
+        )];
+        let errors = interpolate(&mut contents).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "Failed to retrieve secrets from backend \"exec\": Failed to retrieve secret \"second\": forbidden"
+            ]
+        );
+    }
+
+    #[test]
+    fn redacts_whole_words_only() {
+        assert_eq!(
+            redact_secret("secret, secrets and my_secret", "secret"),
+            "**REDACTED**, secrets and my_secret"
+        );
+        assert_eq!(
+            redact_secret("key=-secret-, key=a-secret-b", "-secret-"),
+            "key=**REDACTED**, key=a**REDACTED**b"
+        );
+    }
+
+    #[test]
+    fn ignores_commented_out_secrets() {
+        let content =
+            "# token = \"SECRET[missing.token]\"\n  #SECRET[missing.other]\ntoken = \"none\"";
+        let mut contents = vec![(content.to_owned(), Some(Format::TOML))];
+        interpolate(&mut contents).unwrap();
+        assert_eq!(contents[0].0, content);
+    }
+
+    #[test]
+    fn errors_on_unknown_backend() {
+        let mut contents = vec![(
+            "token = \"SECRET[missing.token]\"".to_owned(),
+            Some(Format::TOML),
+        )];
+        assert_eq!(
+            interpolate(&mut contents).unwrap_err(),
+            vec![
+                "Secret backend \"missing\" referenced by SECRET[missing.token] is not configured."
+            ]
+        );
+    }
+}
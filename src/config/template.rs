@@ -0,0 +1,182 @@
+//! Component templates, which are defined once and instantiated as any number
+//! of components by filling in their variables.
+
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fmt, path::PathBuf};
+
+static NULL: Value = Value::Null;
+
+lazy_static! {
+    static ref VAR_REGEX: Regex = Regex::new(r"\$\{var\.(\w+)\}").unwrap();
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentKind {
+    Source,
+    Transform,
+    Sink,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateDefinition {
+    pub kind: ComponentKind,
+    /// The component config, in which `${var.<name>}` is replaced with the
+    /// variables of each instance.
+    pub config: Value,
+    /// The file the template was loaded from, for error messages.
+    #[serde(skip)]
+    pub origin: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateInstance {
+    pub template: String,
+    #[serde(default)]
+    pub vars: IndexMap<String, Value>,
+}
+
+/// Where a template was defined, for error messages.
+pub struct Location<'a> {
+    name: &'a str,
+    template: &'a TemplateDefinition,
+}
+
+impl<'a> Location<'a> {
+    pub fn new(name: &'a str, template: &'a TemplateDefinition) -> Self {
+        Self { name, template }
+    }
+}
+
+impl<'a> fmt::Display for Location<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.template.origin {
+            Some(path) => write!(f, "template {:?} defined in {:?}", self.name, path),
+            None => write!(f, "template {:?}", self.name),
+        }
+    }
+}
+
+impl TemplateDefinition {
+    /// Fills in the variables of `instance`. A string that consists of just
+    /// one variable is replaced with its value, so that variables can also
+    /// hold numbers, arrays, and so on.
+    pub fn instantiate(&self, instance: &TemplateInstance) -> Result<Value, Vec<String>> {
+        let mut errors = Vec::new();
+        let config = substitute(&self.config, &instance.vars, &mut errors);
+        if errors.is_empty() {
+            Ok(config)
+        } else {
+            errors.sort();
+            errors.dedup();
+            Err(errors)
+        }
+    }
+}
+
+fn substitute(value: &Value, vars: &IndexMap<String, Value>, errors: &mut Vec<String>) -> Value {
+    match value {
+        Value::String(string) => {
+            if let Some(captures) = VAR_REGEX.captures(string) {
+                if captures[0].len() == string.len() {
+                    return lookup(&captures[1], vars, errors).clone();
+                }
+            }
+            let substituted =
+                VAR_REGEX.replace_all(string, |captures: &Captures<'_>| {
+                    match lookup(&captures[1], vars, errors) {
+                        Value::String(string) => string.clone(),
+                        value => value.to_string(),
+                    }
+                });
+            Value::String(substituted.into_owned())
+        }
+        Value::Array(values) => Value::Array(
+            values
+                .iter()
+                .map(|value| substitute(value, vars, errors))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), substitute(value, vars, errors)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
+
+fn lookup<'a>(
+    name: &str,
+    vars: &'a IndexMap<String, Value>,
+    errors: &mut Vec<String>,
+) -> &'a Value {
+    vars.get(name).unwrap_or_else(|| {
+        errors.push(format!("undefined variable {:?}", name));
+        &NULL
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ComponentKind, TemplateDefinition, TemplateInstance};
+    use serde_json::json;
+
+    #[test]
+    fn instantiate_fills_in_variables() {
+        let template = TemplateDefinition {
+            kind: ComponentKind::Sink,
+            config: json!({
+                "type": "http",
+                "inputs": "${var.inputs}",
+                "uri": "https://${var.tenant}.example.com:${var.port}",
+                "encoding": "json"
+            }),
+            origin: None,
+        };
+        let instance = TemplateInstance {
+            template: "tenant".into(),
+            vars: vec![
+                ("tenant".to_owned(), json!("acme")),
+                ("port".to_owned(), json!(8080)),
+                ("inputs".to_owned(), json!(["acme_logs"])),
+            ]
+            .into_iter()
+            .collect(),
+        };
+
+        assert_eq!(
+            template.instantiate(&instance).unwrap(),
+            json!({
+                "type": "http",
+                "inputs": ["acme_logs"],
+                "uri": "https://acme.example.com:8080",
+                "encoding": "json"
+            })
+        );
+    }
+
+    #[test]
+    fn instantiate_reports_undefined_variables() {
+        let template = TemplateDefinition {
+            kind: ComponentKind::Source,
+            config: json!({ "type": "stdin", "host_key": "${var.host}-${var.host}" }),
+            origin: None,
+        };
+        let instance = TemplateInstance {
+            template: "stdin".into(),
+            vars: Default::default(),
+        };
+
+        assert_eq!(
+            template.instantiate(&instance).unwrap_err(),
+            vec!["undefined variable \"host\""]
+        );
+    }
+}
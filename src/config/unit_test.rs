@@ -3,7 +3,7 @@ use crate::config::{self, TransformConfig};
 use crate::{
     conditions::Condition,
     event::{Event, Value},
-    transforms::Transform,
+    transforms::{Transform, TransformOutputs},
 };
 use indexmap::IndexMap;
 use std::{collections::HashMap, path::PathBuf};
@@ -25,6 +25,7 @@ async fn build_unit_tests(mut builder: ConfigBuilder) -> Result<Vec<UnitTest>, V
     let mut tests = vec![];
     let mut errors = vec![];
 
+    super::compiler::expand_templates(&mut builder)?;
     let expansions = super::compiler::expand_macros(&mut builder)?;
 
     // Don't let this escape since it's not validated
@@ -72,6 +73,8 @@ struct UnitTestTransform {
     transform: Transform,
     config: Box<dyn TransformConfig>,
     next: Vec<String>,
+    /// The named outputs of the transform along with their targets.
+    named_outputs: Vec<(String, Vec<String>)>,
 }
 
 struct UnitTestCheck {
@@ -113,10 +116,27 @@ fn walk(
 ) {
     let mut results = Vec::new();
     let mut targets = Vec::new();
+    let mut named_results = Vec::new();
 
     // Use `remove` to take ownership.
     if let Some((key, mut target)) = transforms.remove_entry(node) {
         match target.transform {
+            Transform::Function(ref mut t) if !target.named_outputs.is_empty() => {
+                let mut outputs = TransformOutputs::default();
+                for input in inputs.clone() {
+                    t.transform_outputs(&mut outputs, input)
+                }
+                let mut named = vec![Vec::new(); target.named_outputs.len()];
+                for (index, event) in outputs.into_events() {
+                    match index {
+                        0 => results.push(event),
+                        index => named[index - 1].push(event),
+                    }
+                }
+                named_results = target.named_outputs.iter().cloned().zip(named).collect();
+                targets = target.next.clone();
+                transforms.insert(key, target);
+            }
             Transform::Function(ref mut t) => {
                 for input in inputs.clone() {
                     t.transform(&mut results, input)
@@ -138,7 +158,8 @@ fn walk(
                     transform:  futures::executor::block_on(target.config.clone().build())
                         .expect("Failed to build a known valid transform config. Things may have changed during runtime."),
                     config: target.config,
-                    next: target.next
+                    next: target.next,
+                    named_outputs: target.named_outputs,
                 });
             }
         }
@@ -148,6 +169,24 @@ fn walk(
         walk(&child, results.clone(), transforms, aggregated_results);
     }
 
+    // Named outputs are checked like transforms that receive the inputs of
+    // their transform.
+    for ((output, next), events) in named_results {
+        for child in next {
+            walk(&child, events.clone(), transforms, aggregated_results);
+        }
+        aggregate(&output, inputs.clone(), events, aggregated_results);
+    }
+
+    aggregate(node, inputs, results, aggregated_results);
+}
+
+fn aggregate(
+    node: &str,
+    mut inputs: Vec<Event>,
+    mut results: Vec<Event>,
+    aggregated_results: &mut HashMap<String, (Vec<Event>, Vec<Event>)>,
+) {
     if let Some((mut e_inputs, mut e_results)) = aggregated_results.remove(node) {
         inputs.append(&mut e_inputs);
         results.append(&mut e_results);
@@ -399,6 +438,15 @@ async fn build_unit_test(
         .map(|(k, _)| (k.clone(), IndexMap::new()))
         .collect();
 
+    // Named outputs are linked to their transforms like transforms of their
+    // own.
+    config.transforms.iter().for_each(|(k, t)| {
+        for output in t.named_outputs(k) {
+            transform_outputs.insert(output.clone(), IndexMap::new());
+            transform_outputs[k].insert(output, ());
+        }
+    });
+
     config.transforms.iter().for_each(|(k, t)| {
         t.inputs.iter().for_each(|i| {
             if let Some(outputs) = transform_outputs.get_mut(i) {
@@ -446,6 +494,22 @@ async fn build_unit_test(
     let mut transforms: IndexMap<String, UnitTestTransform> = IndexMap::new();
     for (name, transform_config) in &config.transforms {
         if let Some(outputs) = transform_outputs.remove(name) {
+            let named_outputs = transform_config
+                .named_outputs(name)
+                .into_iter()
+                .map(|output| {
+                    let next = transform_outputs
+                        .remove(&output)
+                        .map(|outputs| outputs.into_iter().map(|(k, _)| k).collect())
+                        .unwrap_or_default();
+                    (output, next)
+                })
+                .collect::<Vec<_>>();
+            let next = outputs
+                .into_iter()
+                .map(|(k, _)| k)
+                .filter(|k| !named_outputs.iter().any(|(output, _)| output == k))
+                .collect();
             match transform_config.inner.build().await {
                 Ok(transform) => {
                     transforms.insert(
@@ -453,7 +517,8 @@ async fn build_unit_test(
                         UnitTestTransform {
                             transform,
                             config: transform_config.inner.clone(),
-                            next: outputs.into_iter().map(|(k, _)| k).collect(),
+                            next,
+                            named_outputs,
                         },
                     );
                 }
@@ -473,7 +538,13 @@ async fn build_unit_test(
     }
 
     definition.outputs.iter().for_each(|o| {
-        if !transforms.contains_key(&o.extract_from) {
+        if !transforms.contains_key(&o.extract_from)
+            && !transforms.values().any(|t| {
+                t.named_outputs
+                    .iter()
+                    .any(|(output, _)| output == &o.extract_from)
+            })
+        {
             let targets = inputs.iter().map(|(i, _)| i).flatten().collect::<Vec<_>>();
             if targets.len() == 1 {
                 errors.push(format!(
@@ -38,6 +38,31 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         ));
     }
 
+    for (name, sink) in config.sinks.iter() {
+        if let Some(target) = &sink.dead_letter {
+            if target == name {
+                errors.push(format!("Sink {:?} can't be its own dead letter.", name));
+            } else if !config.sinks.contains_key(target) && !config.transforms.contains_key(target)
+            {
+                errors.push(format!(
+                    "Dead letter {:?} for sink {:?} doesn't exist.",
+                    target, name
+                ));
+            }
+        }
+    }
+
+    let dead_letter_outputs = config
+        .sinks
+        .iter()
+        .filter_map(|(name, sink)| sink.dead_letter_output(name))
+        .collect::<Vec<_>>();
+    let named_outputs = config
+        .transforms
+        .iter()
+        .flat_map(|(name, transform)| transform.named_outputs(name))
+        .collect::<Vec<_>>();
+
     // Warnings and errors
     let sink_inputs = config
         .sinks
@@ -57,7 +82,20 @@ pub fn check_shape(config: &ConfigBuilder) -> Result<(), Vec<String>> {
         }
 
         for input in inputs {
-            if !config.sources.contains_key(&input) && !config.transforms.contains_key(&input) {
+            if let Some(transform) = config.transforms.get(&input) {
+                if !transform.inner.has_default_output() {
+                    errors.push(format!(
+                        "Input {:?} for {} {:?} has no default output, use one of {:?} instead.",
+                        input,
+                        output_type,
+                        name,
+                        transform.named_outputs(&input)
+                    ));
+                }
+            } else if !config.sources.contains_key(&input)
+                && !dead_letter_outputs.contains(&input)
+                && !named_outputs.contains(&input)
+            {
                 errors.push(format!(
                     "Input {:?} for {} {:?} doesn't exist.",
                     input, output_type, name
@@ -103,20 +141,22 @@ pub fn check_resources(config: &ConfigBuilder) -> Result<(), Vec<String>> {
 pub fn warnings(config: &ConfigBuilder) -> Vec<String> {
     let mut warnings = vec![];
 
-    let source_names = config.sources.keys().map(|name| ("source", name.clone()));
-    let transform_names = config
-        .transforms
+    let source_names = config
+        .sources
         .keys()
-        .map(|name| ("transform", name.clone()));
-    for (input_type, name) in transform_names.chain(source_names) {
+        .map(|name| ("source", name.clone(), vec![name.clone()]));
+    let transform_names = config.transforms.iter().map(|(name, transform)| {
+        let mut outputs = transform.named_outputs(name);
+        outputs.push(name.clone());
+        ("transform", name.clone(), outputs)
+    });
+    for (input_type, name, outputs) in transform_names.chain(source_names) {
+        let consumed = |inputs: &Vec<String>| outputs.iter().any(|output| inputs.contains(output));
         if !config
             .transforms
             .iter()
-            .any(|(_, transform)| transform.inputs.contains(&name))
-            && !config
-                .sinks
-                .iter()
-                .any(|(_, sink)| sink.inputs.contains(&name))
+            .any(|(_, transform)| consumed(&transform.inputs))
+            && !config.sinks.iter().any(|(_, sink)| consumed(&sink.inputs))
         {
             warnings.push(format!(
                 "{} {:?} has no consumers",
@@ -266,10 +306,23 @@ impl From<&ConfigBuilder> for Graph {
                 config.inner.output_type(),
                 config.inputs.clone(),
             );
+
+            // Named outputs carry the same type as the default one.
+            let ty = config.inner.output_type();
+            for output in config.named_outputs(name) {
+                graph.add_transform(&output, ty, ty, vec![name.clone()]);
+            }
         }
 
         for (name, config) in config.sinks.iter() {
             graph.add_sink(name, config.inner.input_type(), config.inputs.clone());
+
+            // Rejected events pass through the sink unchanged, so its dead
+            // letter output is checked like a transform.
+            if let Some(output) = config.dead_letter_output(name) {
+                let ty = config.inner.input_type();
+                graph.add_transform(&output, ty, ty, config.inputs.clone());
+            }
         }
 
         graph
@@ -1,10 +1,12 @@
+#[cfg(unix)]
+use super::INCLUDED_PATHS;
 use crate::Error;
 #[cfg(unix)]
 use notify::{raw_watcher, Op, RawEvent, RecommendedWatcher, RecursiveMode, Watcher};
 use std::{path::PathBuf, time::Duration};
 #[cfg(unix)]
 use std::{
-    sync::mpsc::{channel, Receiver},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
     thread,
 };
 
@@ -21,7 +23,9 @@ const CONFIG_WATCH_DELAY: std::time::Duration = std::time::Duration::from_secs(1
 #[cfg(unix)]
 const RETRY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
 
-/// Triggers SIGHUP when file on config_path changes.
+/// Triggers SIGHUP when file on config_path, or a file included by the loaded
+/// configs, changes. Files included by later loads are picked up within
+/// CONFIG_WATCH_DELAY.
 /// Accumulates file changes until no change for given duration has occurred.
 /// Has best effort guarantee of detecting all file changes from the end of
 /// this function until the main thread stops.
@@ -41,7 +45,22 @@ pub fn spawn_thread<'a>(
 
     thread::spawn(move || loop {
         if let Some((mut watcher, receiver)) = watcher.take() {
-            while let Ok(RawEvent { op: Ok(event), .. }) = receiver.recv() {
+            let mut included = included_count();
+            loop {
+                let event = match receiver.recv_timeout(CONFIG_WATCH_DELAY) {
+                    Ok(RawEvent { op: Ok(event), .. }) => event,
+                    Ok(_) | Err(RecvTimeoutError::Disconnected) => break,
+                    Err(RecvTimeoutError::Timeout) => {
+                        if included != included_count() {
+                            included = included_count();
+                            if let Err(error) = add_paths(&mut watcher, &config_paths) {
+                                error!(message = "Failed to read files to watch.", %error);
+                                break;
+                            }
+                        }
+                        continue;
+                    }
+                };
                 if event.intersects(Op::CREATE | Op::REMOVE | Op::WRITE | Op::CLOSE_WRITE) {
                     debug!(message = "Configuration file change detected.", event = ?event);
 
@@ -114,9 +133,24 @@ fn add_paths(watcher: &mut RecommendedWatcher, config_paths: &[PathBuf]) -> Resu
     for path in config_paths {
         watcher.watch(path, RecursiveMode::NonRecursive)?;
     }
+    let included = INCLUDED_PATHS
+        .lock()
+        .map(|guard| guard.iter().cloned().collect::<Vec<_>>())
+        .unwrap_or_default();
+    for path in included {
+        // Included files may have been removed since they were loaded.
+        if let Err(error) = watcher.watch(&path, RecursiveMode::NonRecursive) {
+            debug!(message = "Failed to watch included file.", path = ?path, %error);
+        }
+    }
     Ok(())
 }
 
+#[cfg(unix)]
+fn included_count() -> usize {
+    INCLUDED_PATHS.lock().map(|guard| guard.len()).unwrap_or(0)
+}
+
 #[cfg(unix)]
 #[cfg(test)]
 mod tests {
@@ -151,6 +185,25 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn included_file_update() {
+        trace_init();
+
+        let delay = Duration::from_secs(3);
+        let file_path = temp_file();
+        let included_path = temp_file();
+        File::create(&file_path).unwrap();
+        let mut file = File::create(&included_path).unwrap();
+
+        let _ = spawn_thread(&[file_path], delay).unwrap();
+        INCLUDED_PATHS.lock().unwrap().insert(included_path);
+        tokio::time::delay_for(CONFIG_WATCH_DELAY * 2).await;
+
+        if !test(&mut file, delay * 5).await {
+            panic!("Test timed out");
+        }
+    }
+
     #[tokio::test]
     async fn sym_file_update() {
         trace_init();
@@ -0,0 +1,242 @@
+//! End-to-end acknowledgements.
+//!
+//! A source that wants to know when its events have been delivered attaches a
+//! `BatchNotifier` to them. Each event then carries an `EventFinalizer` that is
+//! shared by all of its clones, so it is only dropped once every sink the
+//! event was routed to is done with it. Once all the finalizers of a batch are
+//! dropped, the source receives the combined `BatchStatus` of the batch.
+
+use futures::channel::oneshot;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+/// The status of a single event, as reported by a sink.
+///
+/// Variants are ordered by precedence, an event that was both delivered by one
+/// sink and errored in another has errored.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum EventStatus {
+    /// The event was dropped without being delivered anywhere, e.g. by a filter.
+    Dropped,
+    /// The event was delivered.
+    Delivered,
+    /// Delivering the event failed, but could succeed if retried.
+    Errored,
+    /// The event was permanently rejected.
+    Failed,
+}
+
+impl EventStatus {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Dropped,
+            1 => Self::Delivered,
+            2 => Self::Errored,
+            _ => Self::Failed,
+        }
+    }
+}
+
+/// The combined status of all the events in a batch.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[repr(u8)]
+pub enum BatchStatus {
+    /// All events were either delivered or intentionally dropped.
+    Delivered,
+    /// At least one event errored.
+    Errored,
+    /// At least one event was permanently rejected.
+    Failed,
+}
+
+impl BatchStatus {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            0 => Self::Delivered,
+            1 => Self::Errored,
+            _ => Self::Failed,
+        }
+    }
+}
+
+impl From<EventStatus> for BatchStatus {
+    fn from(status: EventStatus) -> Self {
+        match status {
+            EventStatus::Dropped | EventStatus::Delivered => Self::Delivered,
+            EventStatus::Errored => Self::Errored,
+            EventStatus::Failed => Self::Failed,
+        }
+    }
+}
+
+/// Notifies the source of the status of a batch of events once all of them
+/// have been finalized.
+#[derive(Debug)]
+pub struct BatchNotifier {
+    status: AtomicU8,
+    notifier: Option<oneshot::Sender<BatchStatus>>,
+}
+
+impl BatchNotifier {
+    pub fn new_with_receiver() -> (Arc<Self>, BatchStatusReceiver) {
+        let (sender, receiver) = oneshot::channel();
+        let notifier = Self {
+            status: AtomicU8::new(BatchStatus::Delivered as u8),
+            notifier: Some(sender),
+        };
+        (Arc::new(notifier), BatchStatusReceiver(receiver))
+    }
+
+    /// Creates a notifier only if acknowledgements are `enabled`.
+    pub fn maybe_new_with_receiver(
+        enabled: bool,
+    ) -> (Option<Arc<Self>>, Option<BatchStatusReceiver>) {
+        if enabled {
+            let (notifier, receiver) = Self::new_with_receiver();
+            (Some(notifier), Some(receiver))
+        } else {
+            (None, None)
+        }
+    }
+
+    fn update_status(&self, status: EventStatus) {
+        let status = BatchStatus::from(status);
+        if status != BatchStatus::Delivered {
+            self.status.fetch_max(status as u8, Ordering::AcqRel);
+        }
+    }
+}
+
+impl Drop for BatchNotifier {
+    fn drop(&mut self) {
+        if let Some(notifier) = self.notifier.take() {
+            let status = BatchStatus::from_u8(self.status.load(Ordering::Acquire));
+            // The source may not be interested in the status anymore.
+            let _ = notifier.send(status);
+        }
+    }
+}
+
+/// Resolves to the status of a batch once all of its events are finalized.
+#[derive(Debug)]
+pub struct BatchStatusReceiver(oneshot::Receiver<BatchStatus>);
+
+impl Future for BatchStatusReceiver {
+    type Output = BatchStatus;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // The notifier always sends its status when dropped, so the sender
+        // can only be gone without a status if that panicked.
+        Pin::new(&mut self.0)
+            .poll(cx)
+            .map(|status| status.unwrap_or(BatchStatus::Errored))
+    }
+}
+
+/// Tracks the status of one event, across all of its clones.
+#[derive(Debug)]
+pub struct EventFinalizer {
+    status: AtomicU8,
+    batch: Arc<BatchNotifier>,
+}
+
+impl EventFinalizer {
+    pub fn new(batch: Arc<BatchNotifier>) -> Self {
+        Self {
+            status: AtomicU8::new(EventStatus::Dropped as u8),
+            batch,
+        }
+    }
+
+    fn update_status(&self, status: EventStatus) {
+        self.status.fetch_max(status as u8, Ordering::AcqRel);
+    }
+}
+
+impl Drop for EventFinalizer {
+    fn drop(&mut self) {
+        let status = EventStatus::from_u8(self.status.load(Ordering::Acquire));
+        self.batch.update_status(status);
+    }
+}
+
+/// The finalizers attached to an event. Events created by merging others
+/// carry the finalizers of all of them.
+#[derive(Clone, Debug, Default)]
+pub struct EventFinalizers(Vec<Arc<EventFinalizer>>);
+
+impl EventFinalizers {
+    pub fn new(finalizer: EventFinalizer) -> Self {
+        Self(vec![Arc::new(finalizer)])
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn add(&mut self, finalizer: EventFinalizer) {
+        self.0.push(Arc::new(finalizer));
+    }
+
+    pub fn merge(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+
+    /// Records the outcome of delivering the event. Drop the finalizers
+    /// afterwards to report it to the sources.
+    pub fn update_status(&self, status: EventStatus) {
+        for finalizer in &self.0 {
+            finalizer.update_status(status);
+        }
+    }
+}
+
+// Finalizers are bookkeeping, not data, and never make two events differ.
+impl PartialEq for EventFinalizers {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use futures::FutureExt;
+
+    #[test]
+    fn notifies_once_all_finalizers_are_dropped() {
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let first = EventFinalizers::new(EventFinalizer::new(Arc::clone(&batch)));
+        let second = EventFinalizers::new(EventFinalizer::new(batch));
+        let clone = first.clone();
+
+        first.update_status(EventStatus::Delivered);
+        drop(first);
+        drop(second);
+        assert_eq!((&mut receiver).now_or_never(), None);
+
+        drop(clone);
+        assert_eq!(receiver.now_or_never(), Some(BatchStatus::Delivered));
+    }
+
+    #[test]
+    fn errors_take_precedence() {
+        let (batch, receiver) = BatchNotifier::new_with_receiver();
+        let finalizers = EventFinalizers::new(EventFinalizer::new(batch));
+        let clone = finalizers.clone();
+
+        finalizers.update_status(EventStatus::Errored);
+        clone.update_status(EventStatus::Delivered);
+        drop(finalizers);
+        drop(clone);
+        assert_eq!(receiver.now_or_never(), Some(BatchStatus::Errored));
+    }
+}
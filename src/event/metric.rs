@@ -1,3 +1,4 @@
+use super::finalization::{BatchNotifier, EventFinalizer, EventFinalizers};
 use chrono::{DateTime, Utc};
 use derive_is_enum_variant::is_enum_variant;
 use remap::{Object, Segment};
@@ -8,6 +9,7 @@ use std::{
     convert::TryFrom,
     fmt::{self, Display, Formatter},
     iter::FromIterator,
+    sync::Arc,
 };
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -16,6 +18,8 @@ pub struct Metric {
     pub series: MetricSeries,
     #[serde(flatten)]
     pub data: MetricData,
+    #[serde(skip)]
+    finalizers: EventFinalizers,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -239,9 +243,22 @@ impl Metric {
                 kind,
                 value,
             },
+            finalizers: Default::default(),
         }
     }
 
+    pub fn add_batch_notifier(&mut self, batch: Arc<BatchNotifier>) {
+        self.finalizers.add(EventFinalizer::new(batch));
+    }
+
+    pub fn add_finalizers(&mut self, finalizers: EventFinalizers) {
+        self.finalizers.merge(finalizers);
+    }
+
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+
     pub fn with_namespace<T: Into<String>>(mut self, namespace: Option<T>) -> Self {
         self.series.name.namespace = namespace.map(Into::into);
         self
@@ -260,16 +277,16 @@ impl Metric {
     /// Rewrite this into a Metric with the data marked as absolute.
     pub fn into_absolute(self) -> Self {
         Self {
-            series: self.series,
             data: self.data.into_absolute(),
+            ..self
         }
     }
 
     /// Rewrite this into a Metric with the data marked as incremental.
     pub fn into_incremental(self) -> Self {
         Self {
-            series: self.series,
             data: self.data.into_incremental(),
+            ..self
         }
     }
 
@@ -361,6 +378,7 @@ impl Metric {
         Self {
             series: self.series.clone(),
             data: self.data.zero(),
+            finalizers: Default::default(),
         }
     }
 }
@@ -2,9 +2,13 @@ use self::proto::{event_wrapper::Event as EventProto, metric::Value as MetricPro
 use crate::config::log_schema;
 use bytes::Bytes;
 use chrono::{DateTime, SecondsFormat, TimeZone, Utc};
-use std::collections::{BTreeMap, HashMap};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
 pub mod discriminant;
+pub mod finalization;
 pub mod merge;
 pub mod merge_state;
 pub mod metric;
@@ -14,6 +18,9 @@ mod log_event;
 mod lookup;
 mod value;
 
+pub use finalization::{
+    BatchNotifier, BatchStatus, BatchStatusReceiver, EventFinalizers, EventStatus,
+};
 pub use log_event::LogEvent;
 pub use lookup::Lookup;
 pub use metric::{Metric, MetricKind, MetricValue, StatisticKind};
@@ -80,6 +87,39 @@ impl Event {
             _ => panic!("Failed type coercion, {:?} is not a metric", self),
         }
     }
+
+    pub fn add_batch_notifier(&mut self, batch: Arc<BatchNotifier>) {
+        match self {
+            Event::Log(log) => log.add_batch_notifier(batch),
+            Event::Metric(metric) => metric.add_batch_notifier(batch),
+        }
+    }
+
+    pub fn with_batch_notifier(mut self, batch: &Arc<BatchNotifier>) -> Self {
+        self.add_batch_notifier(Arc::clone(batch));
+        self
+    }
+
+    pub fn with_batch_notifier_option(self, batch: &Option<Arc<BatchNotifier>>) -> Self {
+        match batch {
+            Some(batch) => self.with_batch_notifier(batch),
+            None => self,
+        }
+    }
+
+    pub fn add_finalizers(&mut self, finalizers: EventFinalizers) {
+        match self {
+            Event::Log(log) => log.add_finalizers(finalizers),
+            Event::Metric(metric) => metric.add_finalizers(finalizers),
+        }
+    }
+
+    pub fn take_finalizers(&mut self) -> EventFinalizers {
+        match self {
+            Event::Log(log) => log.take_finalizers(),
+            Event::Metric(metric) => metric.take_finalizers(),
+        }
+    }
 }
 
 fn timestamp_to_string(timestamp: &DateTime<Utc>) -> String {
@@ -294,7 +334,7 @@ impl From<Event> for proto::EventWrapper {
 
                 proto::EventWrapper { event: Some(event) }
             }
-            Event::Metric(Metric { series, data }) => {
+            Event::Metric(Metric { series, data, .. }) => {
                 let name = series.name.name;
                 let namespace = series.name.namespace.unwrap_or_default();
 
@@ -1,4 +1,5 @@
 use crate::{
+    config::ProxyConfig,
     dns::Resolver,
     internal_events::http_client,
     tls::{tls_connector_builder, MaybeTlsSettings, TlsError},
@@ -14,6 +15,7 @@ use hyper::{
     client::{Client, HttpConnector},
 };
 use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
@@ -30,6 +32,8 @@ pub enum HttpError {
     BuildTlsConnector { source: TlsError },
     #[snafu(display("Failed to build HTTPS connector"))]
     MakeHttpsConnector { source: openssl::error::ErrorStack },
+    #[snafu(display("Failed to build proxy connector: {}", source))]
+    MakeProxyConnector { source: http::uri::InvalidUri },
     #[snafu(display("Failed to make HTTP(S) request"))]
     CallRequest { source: hyper::Error },
 }
@@ -37,7 +41,8 @@ pub enum HttpError {
 pub type HttpClientFuture = <HttpClient as Service<http::Request<Body>>>::Future;
 
 pub struct HttpClient<B = Body> {
-    client: Client<HttpsConnector<HttpConnector<Resolver>>, B>,
+    client: Client<ProxyConnector<HttpsConnector<HttpConnector<Resolver>>>, B>,
+    proxy_connector: ProxyConnector<HttpsConnector<HttpConnector<Resolver>>>,
     span: Span,
     user_agent: HeaderValue,
 }
@@ -48,12 +53,19 @@ where
     B::Data: Send,
     B::Error: Into<crate::Error>,
 {
-    pub fn new(tls_settings: impl Into<MaybeTlsSettings>) -> Result<HttpClient<B>, HttpError> {
+    pub fn new(
+        tls_settings: impl Into<MaybeTlsSettings>,
+        proxy_config: &ProxyConfig,
+    ) -> Result<HttpClient<B>, HttpError> {
         let mut http = HttpConnector::new_with_resolver(Resolver);
         http.enforce_http(false);
 
         let settings = tls_settings.into();
         let tls = tls_connector_builder(&settings).context(BuildTlsConnector)?;
+        let mut proxy_tls = tls_connector_builder(&settings).context(BuildTlsConnector)?;
+        if let Some(settings) = settings.tls() {
+            settings.apply_connector_configuration(&mut proxy_tls);
+        }
         let mut https = HttpsConnector::with_connector(http, tls).context(MakeHttpsConnector)?;
 
         let settings = settings.tls().cloned();
@@ -65,7 +77,15 @@ where
             Ok(())
         });
 
-        let client = Client::builder().build(https);
+        // Requests to HTTPS endpoints are tunneled through the proxy, with
+        // the same TLS settings as without one.
+        let mut proxy_connector = ProxyConnector::unsecured(https);
+        proxy_connector.set_tls(Some(proxy_tls.build()));
+        proxy_config
+            .configure(&mut proxy_connector)
+            .context(MakeProxyConnector)?;
+
+        let client = Client::builder().build(proxy_connector.clone());
 
         let version = crate::get_version();
         let user_agent = HeaderValue::from_str(&format!("Vector/{}", version))
@@ -75,6 +95,7 @@ where
 
         Ok(HttpClient {
             client,
+            proxy_connector,
             span,
             user_agent,
         })
@@ -87,6 +108,11 @@ where
         let _enter = self.span.enter();
 
         default_request_headers(&mut request, &self.user_agent);
+        // Requests to HTTP endpoints are sent to the proxy as they are, so
+        // they carry its credentials.
+        if let Some(headers) = self.proxy_connector.http_headers(request.uri()) {
+            request.headers_mut().extend(headers.clone());
+        }
 
         emit!(http_client::AboutToSendHTTPRequest { request: &request });
 
@@ -168,6 +194,7 @@ impl<B> Clone for HttpClient<B> {
     fn clone(&self) -> Self {
         Self {
             client: self.client.clone(),
+            proxy_connector: self.proxy_connector.clone(),
             span: self.span.clone(),
             user_agent: self.user_agent.clone(),
         }
@@ -0,0 +1,155 @@
+use super::InternalEvent;
+#[cfg(feature = "sources-amqp")]
+use crate::event::BatchStatus;
+use metrics::counter;
+
+#[cfg(feature = "sources-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpEventReceived {
+    pub byte_size: usize,
+}
+
+#[cfg(feature = "sources-amqp")]
+impl InternalEvent for AmqpEventReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received one message.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sources-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpAcknowledgementFailed {
+    pub error: lapin::Error,
+    pub delivery_tag: u64,
+}
+
+#[cfg(feature = "sources-amqp")]
+impl InternalEvent for AmqpAcknowledgementFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Failed to acknowledge message.",
+            delivery_tag = %self.delivery_tag,
+            error = %self.error,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("acknowledgement_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sources-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpEventDeliveryFailed {
+    pub delivery_tag: u64,
+    pub status: BatchStatus,
+}
+
+#[cfg(feature = "sources-amqp")]
+impl InternalEvent for AmqpEventDeliveryFailed {
+    fn emit_logs(&self) {
+        error!(
+            message = "Message was not delivered, returning it to the broker.",
+            delivery_tag = %self.delivery_tag,
+            status = ?self.status,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("delivery_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct AmqpConnectionFailed {
+    pub error: lapin::Error,
+}
+
+impl InternalEvent for AmqpConnectionFailed {
+    fn emit_logs(&self) {
+        error!(message = "AMQP connection failed; reconnecting.", error = %self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpEventSendSuccess {
+    pub byte_size: usize,
+}
+
+#[cfg(feature = "sinks-amqp")]
+impl InternalEvent for AmqpEventSendSuccess {
+    fn emit_logs(&self) {
+        trace!(message = "Processed one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sinks-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpEventSendFail {
+    pub error: lapin::Error,
+}
+
+#[cfg(feature = "sinks-amqp")]
+impl InternalEvent for AmqpEventSendFail {
+    fn emit_logs(&self) {
+        error!(message = "Failed to send message.", error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpEventRejected;
+
+#[cfg(feature = "sinks-amqp")]
+impl InternalEvent for AmqpEventRejected {
+    fn emit_logs(&self) {
+        error!(message = "Message was rejected by the broker.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-amqp")]
+#[derive(Debug)]
+pub(crate) struct AmqpEventMissingKeys<'a> {
+    pub keys: &'a [String],
+}
+
+#[cfg(feature = "sinks-amqp")]
+impl<'a> InternalEvent for AmqpEventMissingKeys<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Keys do not exist on the event; dropping event.",
+            missing_keys = ?self.keys,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("missing_keys_total", 1);
+    }
+}
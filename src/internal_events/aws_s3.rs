@@ -1,10 +1,11 @@
 #[cfg(feature = "sources-aws_s3")]
 pub mod source {
+    use crate::event::BatchStatus;
     use crate::internal_events::InternalEvent;
     use crate::sources::aws_s3::sqs::ProcessingError;
     use metrics::counter;
     use rusoto_core::RusotoError;
-    use rusoto_sqs::{DeleteMessageError, ReceiveMessageError};
+    use rusoto_sqs::{ChangeMessageVisibilityError, DeleteMessageError, ReceiveMessageError};
 
     #[derive(Debug)]
     pub(crate) struct SqsMessageReceiveFailed<'a> {
@@ -68,6 +69,38 @@ pub mod source {
         }
     }
 
+    #[derive(Debug)]
+    pub(crate) struct SqsMessageDeliveryFailed<'a> {
+        pub message_id: &'a str,
+        pub status: BatchStatus,
+    }
+
+    impl<'a> InternalEvent for SqsMessageDeliveryFailed<'a> {
+        fn emit_logs(&self) {
+            warn!(message = "Events from SQS message were not delivered, not deleting it.", %self.message_id, status = ?self.status);
+        }
+
+        fn emit_metrics(&self) {
+            counter!("sqs_message_delivery_failed_total", 1);
+        }
+    }
+
+    #[derive(Debug)]
+    pub(crate) struct SqsMessageVisibilityExtendFailed<'a> {
+        pub message_id: &'a str,
+        pub error: &'a RusotoError<ChangeMessageVisibilityError>,
+    }
+
+    impl<'a> InternalEvent for SqsMessageVisibilityExtendFailed<'a> {
+        fn emit_logs(&self) {
+            warn!(message = "Failed to extend the visibility timeout of SQS message.", %self.message_id, %self.error);
+        }
+
+        fn emit_metrics(&self) {
+            counter!("sqs_message_visibility_extend_failed_total", 1);
+        }
+    }
+
     #[derive(Debug)]
     pub(crate) struct SqsMessageDeleteSucceeded<'a> {
         pub message_id: &'a str,
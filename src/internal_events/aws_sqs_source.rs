@@ -0,0 +1,122 @@
+use super::InternalEvent;
+use crate::event::BatchStatus;
+use metrics::counter;
+use rusoto_core::RusotoError;
+use rusoto_sqs::{ChangeMessageVisibilityError, DeleteMessageError, ReceiveMessageError};
+
+#[derive(Debug)]
+pub struct AwsSqsMessageReceiveFailed<'a> {
+    pub error: &'a RusotoError<ReceiveMessageError>,
+}
+
+impl<'a> InternalEvent for AwsSqsMessageReceiveFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Failed to fetch SQS messages.", %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_receive_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageReceiveSucceeded {
+    pub count: usize,
+}
+
+impl InternalEvent for AwsSqsMessageReceiveSucceeded {
+    fn emit_logs(&self) {
+        trace!(message = "Received SQS messages.", %self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_receive_succeeded_total", 1);
+        counter!("sqs_message_received_messages_total", self.count as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsEventsReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub message_id: &'a str,
+}
+
+impl<'a> InternalEvent for AwsSqsEventsReceived<'a> {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+            message_id = %self.message_id,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageDeliveryFailed<'a> {
+    pub message_id: &'a str,
+    pub status: BatchStatus,
+}
+
+impl<'a> InternalEvent for AwsSqsMessageDeliveryFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Events from SQS message were not delivered, not deleting it.", %self.message_id, status = ?self.status);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_delivery_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageVisibilityExtendFailed<'a> {
+    pub message_id: &'a str,
+    pub error: &'a RusotoError<ChangeMessageVisibilityError>,
+}
+
+impl<'a> InternalEvent for AwsSqsMessageVisibilityExtendFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Failed to extend the visibility timeout of SQS message.", %self.message_id, %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_visibility_extend_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageDeleteSucceeded<'a> {
+    pub message_id: &'a str,
+}
+
+impl<'a> InternalEvent for AwsSqsMessageDeleteSucceeded<'a> {
+    fn emit_logs(&self) {
+        trace!(message = "Deleted SQS message.", %self.message_id);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_delete_succeeded_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct AwsSqsMessageDeleteFailed<'a> {
+    pub message_id: &'a str,
+    pub error: &'a RusotoError<DeleteMessageError>,
+}
+
+impl<'a> InternalEvent for AwsSqsMessageDeleteFailed<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Deletion of SQS message failed.", %self.message_id, %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("sqs_message_delete_failed_total", 1);
+    }
+}
@@ -0,0 +1,80 @@
+use super::InternalEvent;
+use metrics::{counter, gauge};
+
+#[derive(Debug)]
+pub struct BufferEventReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for BufferEventReceived {
+    fn emit_metrics(&self) {
+        counter!("buffer_received_events_total", 1);
+        counter!("buffer_received_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferEventSent {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for BufferEventSent {
+    fn emit_metrics(&self) {
+        counter!("buffer_sent_events_total", 1);
+        counter!("buffer_sent_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferEventDropped;
+
+impl InternalEvent for BufferEventDropped {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Shedding load; dropping event.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("buffer_discarded_events_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct BufferUsageUpdated {
+    pub events: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for BufferUsageUpdated {
+    fn emit_metrics(&self) {
+        gauge!("buffer_events", self.events as f64);
+        gauge!("buffer_byte_size", self.byte_size as f64);
+    }
+}
+
+#[derive(Debug)]
+pub struct DeadLetterEventSent;
+
+impl InternalEvent for DeadLetterEventSent {
+    fn emit_metrics(&self) {
+        counter!("dead_letter_events_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct DeadLetterEventDropped;
+
+impl InternalEvent for DeadLetterEventDropped {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Dead letter output is closed; dropping rejected event.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("dead_letter_events_dropped_total", 1);
+    }
+}
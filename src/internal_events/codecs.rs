@@ -0,0 +1,33 @@
+use super::InternalEvent;
+use crate::codecs::{DecodingError, EncodingError};
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct DecoderParseFailed<'a> {
+    pub error: &'a DecodingError,
+}
+
+impl InternalEvent for DecoderParseFailed<'_> {
+    fn emit_logs(&self) {
+        warn!(message = "Failed parsing frame, dropping it.", error = %self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("decoder_parse_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct EncoderEncodeFailed<'a> {
+    pub error: &'a EncodingError,
+}
+
+impl InternalEvent for EncoderEncodeFailed<'_> {
+    fn emit_logs(&self) {
+        error!(message = "Failed encoding event, dropping it.", error = %self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("encoder_encode_errors_total", 1);
+    }
+}
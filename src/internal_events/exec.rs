@@ -0,0 +1,106 @@
+use super::InternalEvent;
+use metrics::{counter, histogram};
+use std::time::Duration;
+
+#[derive(Debug)]
+pub struct ExecEventReceived<'a> {
+    pub command: &'a str,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for ExecEventReceived<'_> {
+    fn emit_logs(&self) {
+        trace!(message = "Received one event.", command = %self.command);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecFailed<'a> {
+    pub command: &'a str,
+    pub error: std::io::Error,
+}
+
+impl InternalEvent for ExecFailed<'_> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Unable to run command.",
+            command = %self.command,
+            error = ?self.error,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("command_executions_failed_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecCommandExited<'a> {
+    pub command: &'a str,
+    pub exit_code: Option<i32>,
+    pub elapsed: Duration,
+}
+
+impl InternalEvent for ExecCommandExited<'_> {
+    fn emit_logs(&self) {
+        debug!(
+            message = "Command exited.",
+            command = %self.command,
+            exit_code = ?self.exit_code,
+            elapsed_ms = %self.elapsed.as_millis(),
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("command_executed_total", 1);
+        histogram!("command_execution_duration_nanoseconds", self.elapsed);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecTimedOut<'a> {
+    pub command: &'a str,
+    pub elapsed: Duration,
+}
+
+impl InternalEvent for ExecTimedOut<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Command timed out, killing it.",
+            command = %self.command,
+            elapsed_ms = %self.elapsed.as_millis(),
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("command_timeouts_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct ExecBufferFull<'a> {
+    pub command: &'a str,
+    pub max_bytes: usize,
+}
+
+impl InternalEvent for ExecBufferFull<'_> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Command output exceeds the buffer limit, killing it.",
+            command = %self.command,
+            max_bytes = %self.max_bytes,
+            internal_log_rate_secs = 10,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("buffer_overflows_total", 1);
+    }
+}
@@ -0,0 +1,34 @@
+use super::InternalEvent;
+use crate::sources::fluent::DecodeError;
+use metrics::counter;
+
+#[derive(Debug)]
+pub struct FluentMessageReceived {
+    pub byte_size: usize,
+}
+
+impl InternalEvent for FluentMessageReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received fluent message.", byte_size = %self.byte_size);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct FluentMessageDecodeError<'a> {
+    pub error: &'a DecodeError,
+}
+
+impl<'a> InternalEvent for FluentMessageDecodeError<'a> {
+    fn emit_logs(&self) {
+        warn!(message = "Error decoding fluent message; dropping message.", error = %self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("parse_errors_total", 1);
+    }
+}
@@ -0,0 +1,71 @@
+use super::InternalEvent;
+use http::StatusCode;
+use metrics::{counter, histogram};
+use std::time::Instant;
+
+#[derive(Debug)]
+pub struct HttpScrapeEventReceived<'a> {
+    pub byte_size: usize,
+    pub count: usize,
+    pub endpoint: &'a str,
+}
+
+impl InternalEvent for HttpScrapeEventReceived<'_> {
+    fn emit_logs(&self) {
+        debug!(message = "Scraped events.", endpoint = %self.endpoint, count = ?self.count);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.count as u64);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpScrapeRequestCompleted {
+    pub start: Instant,
+    pub end: Instant,
+}
+
+impl InternalEvent for HttpScrapeRequestCompleted {
+    fn emit_logs(&self) {
+        debug!(message = "Request completed.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("requests_completed_total", 1);
+        histogram!("request_duration_nanoseconds", self.end - self.start);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpScrapeErrorResponse<'a> {
+    pub code: StatusCode,
+    pub endpoint: &'a str,
+}
+
+impl InternalEvent for HttpScrapeErrorResponse<'_> {
+    fn emit_logs(&self) {
+        error!(message = "HTTP error response.", endpoint = %self.endpoint, code = %self.code);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("http_error_response_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct HttpScrapeRequestError<'a> {
+    pub error: crate::Error,
+    pub endpoint: &'a str,
+}
+
+impl InternalEvent for HttpScrapeRequestError<'_> {
+    fn emit_logs(&self) {
+        error!(message = "HTTP request processing error.", endpoint = %self.endpoint, error = ?self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("http_request_errors_total", 1);
+    }
+}
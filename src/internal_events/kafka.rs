@@ -1,4 +1,5 @@
 use super::InternalEvent;
+use crate::event::BatchStatus;
 use metrics::counter;
 
 #[derive(Debug)]
@@ -57,3 +58,31 @@ impl InternalEvent for KafkaKeyExtractionFailed<'_> {
         error!(message = "Failed to extract key.", key_field = %self.key_field);
     }
 }
+
+#[derive(Debug)]
+pub struct KafkaEventDeliveryFailed<'a> {
+    pub topic: &'a str,
+    pub partition: i32,
+    pub offset: i64,
+    pub status: BatchStatus,
+}
+
+impl InternalEvent for KafkaEventDeliveryFailed<'_> {
+    fn emit_logs(&self) {
+        let message = match self.status {
+            BatchStatus::Failed => "Message was rejected, skipping over it.",
+            _ => "Message was not delivered, no longer updating the consumer offset of its partition.",
+        };
+        error!(
+            message,
+            topic = %self.topic,
+            partition = %self.partition,
+            offset = %self.offset,
+            status = ?self.status,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("delivery_failed_total", 1);
+    }
+}
@@ -0,0 +1,111 @@
+use super::InternalEvent;
+use metrics::counter;
+use rumqttc::ConnectionError;
+
+#[cfg(feature = "sources-mqtt")]
+#[derive(Debug)]
+pub(crate) struct MqttEventReceived<'a> {
+    pub byte_size: usize,
+    pub topic: &'a str,
+}
+
+#[cfg(feature = "sources-mqtt")]
+impl<'a> InternalEvent for MqttEventReceived<'a> {
+    fn emit_logs(&self) {
+        trace!(message = "Received one message.", topic = %self.topic, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sources-mqtt")]
+#[derive(Debug)]
+pub(crate) struct MqttSubscribeFailed<'a> {
+    pub error: rumqttc::ClientError,
+    pub topic: &'a str,
+}
+
+#[cfg(feature = "sources-mqtt")]
+impl<'a> InternalEvent for MqttSubscribeFailed<'a> {
+    fn emit_logs(&self) {
+        error!(message = "Failed to subscribe.", topic = %self.topic, error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct MqttConnectionFailed {
+    pub error: ConnectionError,
+}
+
+impl InternalEvent for MqttConnectionFailed {
+    fn emit_logs(&self) {
+        error!(message = "MQTT connection failed; reconnecting.", error = %self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-mqtt")]
+#[derive(Debug)]
+pub(crate) struct MqttEventSendSuccess {
+    pub byte_size: usize,
+}
+
+#[cfg(feature = "sinks-mqtt")]
+impl InternalEvent for MqttEventSendSuccess {
+    fn emit_logs(&self) {
+        trace!(message = "Processed one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sinks-mqtt")]
+#[derive(Debug)]
+pub(crate) struct MqttEventSendFail {
+    pub error: rumqttc::ClientError,
+}
+
+#[cfg(feature = "sinks-mqtt")]
+impl InternalEvent for MqttEventSendFail {
+    fn emit_logs(&self) {
+        error!(message = "Failed to send message.", error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("send_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-mqtt")]
+#[derive(Debug)]
+pub(crate) struct MqttEventMissingKeys<'a> {
+    pub keys: &'a [String],
+}
+
+#[cfg(feature = "sinks-mqtt")]
+impl<'a> InternalEvent for MqttEventMissingKeys<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Keys do not exist on the event; dropping event.",
+            missing_keys = ?self.keys,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("missing_keys_total", 1);
+    }
+}
@@ -0,0 +1,60 @@
+use super::InternalEvent;
+use metrics::counter;
+#[cfg(feature = "sources-opentelemetry")]
+use prost::DecodeError;
+
+#[cfg(feature = "sources-opentelemetry")]
+#[derive(Debug)]
+pub struct OpentelemetryEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+    pub protocol: &'static str,
+}
+
+#[cfg(feature = "sources-opentelemetry")]
+impl InternalEvent for OpentelemetryEventsReceived {
+    fn emit_logs(&self) {
+        trace!(message = "Received events.", count = %self.count, protocol = %self.protocol);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", self.count as u64, "protocol" => self.protocol);
+        counter!("processed_bytes_total", self.byte_size as u64, "protocol" => self.protocol);
+    }
+}
+
+#[cfg(feature = "sources-opentelemetry")]
+#[derive(Debug)]
+pub struct OpentelemetryProtoDecodeError {
+    pub error: DecodeError,
+}
+
+#[cfg(feature = "sources-opentelemetry")]
+impl InternalEvent for OpentelemetryProtoDecodeError {
+    fn emit_logs(&self) {
+        error!(message = "Failed to decode protobuf message.", error = ?self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("protobuf_decode_errors_total", 1);
+    }
+}
+
+#[derive(Debug)]
+pub struct OpentelemetryMetricUnsupported<'a> {
+    pub name: &'a str,
+}
+
+impl<'a> InternalEvent for OpentelemetryMetricUnsupported<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Metric type is not supported; dropping metric.",
+            name = %self.name,
+            internal_log_rate_secs = 30,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("events_discarded_total", 1);
+    }
+}
@@ -0,0 +1,123 @@
+use super::InternalEvent;
+#[cfg(feature = "sources-redis")]
+use crate::event::BatchStatus;
+use metrics::counter;
+#[cfg(feature = "sources-redis")]
+use redis::RedisError;
+
+#[cfg(feature = "sources-redis")]
+#[derive(Debug)]
+pub(crate) struct RedisEventReceived {
+    pub byte_size: usize,
+}
+
+#[cfg(feature = "sources-redis")]
+impl InternalEvent for RedisEventReceived {
+    fn emit_logs(&self) {
+        trace!(
+            message = "Received one message.",
+            internal_log_rate_secs = 10
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sources-redis")]
+#[derive(Debug)]
+pub(crate) struct RedisAcknowledgementFailed<'a> {
+    pub error: RedisError,
+    pub id: &'a str,
+}
+
+#[cfg(feature = "sources-redis")]
+impl<'a> InternalEvent for RedisAcknowledgementFailed<'a> {
+    fn emit_logs(&self) {
+        error!(message = "Failed to acknowledge stream entry.", id = %self.id, error = %self.error);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("acknowledgement_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sources-redis")]
+#[derive(Debug)]
+pub(crate) struct RedisEventDeliveryFailed<'a> {
+    pub id: &'a str,
+    pub status: BatchStatus,
+}
+
+#[cfg(feature = "sources-redis")]
+impl<'a> InternalEvent for RedisEventDeliveryFailed<'a> {
+    fn emit_logs(&self) {
+        error!(
+            message = "Stream entry was not delivered, leaving it pending.",
+            id = %self.id,
+            status = ?self.status,
+        );
+    }
+
+    fn emit_metrics(&self) {
+        counter!("delivery_failed_total", 1);
+    }
+}
+
+#[cfg(feature = "sources-redis")]
+#[derive(Debug)]
+pub(crate) struct RedisConnectionFailed {
+    pub error: RedisError,
+}
+
+#[cfg(feature = "sources-redis")]
+impl InternalEvent for RedisConnectionFailed {
+    fn emit_logs(&self) {
+        error!(message = "Redis connection failed; reconnecting.", error = %self.error, internal_log_rate_secs = 10);
+    }
+
+    fn emit_metrics(&self) {
+        counter!("connection_errors_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-redis")]
+#[derive(Debug)]
+pub(crate) struct RedisEventSent {
+    pub byte_size: usize,
+}
+
+#[cfg(feature = "sinks-redis")]
+impl InternalEvent for RedisEventSent {
+    fn emit_logs(&self) {
+        trace!(message = "Processed one event.");
+    }
+
+    fn emit_metrics(&self) {
+        counter!("processed_events_total", 1);
+        counter!("processed_bytes_total", self.byte_size as u64);
+    }
+}
+
+#[cfg(feature = "sinks-redis")]
+#[derive(Debug)]
+pub(crate) struct RedisEventMissingKeys<'a> {
+    pub keys: &'a [String],
+}
+
+#[cfg(feature = "sinks-redis")]
+impl<'a> InternalEvent for RedisEventMissingKeys<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Keys do not exist on the event; dropping event.",
+            missing_keys = ?self.keys,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("missing_keys_total", 1);
+    }
+}
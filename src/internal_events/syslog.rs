@@ -46,3 +46,47 @@ impl InternalEvent for SyslogUdpUtf8Error {
         counter!("utf8_convert_errors_total", 1, "mode" => "udp");
     }
 }
+
+#[cfg(feature = "sinks-syslog")]
+#[derive(Debug)]
+pub struct SyslogEventMissingKeys<'a> {
+    pub keys: &'a [String],
+}
+
+#[cfg(feature = "sinks-syslog")]
+impl<'a> InternalEvent for SyslogEventMissingKeys<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Keys do not exist on the event; dropping event.",
+            missing_keys = ?self.keys,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("missing_keys_total", 1);
+    }
+}
+
+#[cfg(feature = "sinks-syslog")]
+#[derive(Debug)]
+pub struct SyslogInvalidPriority<'a> {
+    pub field: &'static str,
+    pub value: &'a str,
+}
+
+#[cfg(feature = "sinks-syslog")]
+impl<'a> InternalEvent for SyslogInvalidPriority<'a> {
+    fn emit_logs(&self) {
+        warn!(
+            message = "Invalid syslog priority value; using the default.",
+            field = %self.field,
+            value = %self.value,
+            internal_log_rate_secs = 30,
+        )
+    }
+
+    fn emit_metrics(&self) {
+        counter!("invalid_priority_total", 1);
+    }
+}
@@ -20,6 +20,7 @@
 //!
 
 use crate::{
+    config::ProxyConfig,
     http::{HttpClient, HttpError},
     tls::TlsSettings,
 };
@@ -60,7 +61,7 @@ impl Client {
         } = config;
 
         let tls_settings = TlsSettings::from_options(&Some(tls_options))?;
-        let inner = HttpClient::new(tls_settings)?;
+        let inner = HttpClient::new(tls_settings, &ProxyConfig::default())?;
 
         let uri::Parts {
             scheme, authority, ..
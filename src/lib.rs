@@ -28,6 +28,8 @@ static ALLOC: jemallocator::Jemalloc = jemallocator::Jemalloc;
 pub mod config;
 pub mod buffers;
 pub mod cli;
+#[cfg(feature = "codecs")]
+pub mod codecs;
 pub mod conditions;
 pub mod dns;
 pub mod event;
@@ -37,6 +39,8 @@ pub mod generate;
 pub mod wasm;
 #[macro_use]
 pub mod internal_events;
+#[cfg(feature = "lapin")]
+pub(crate) mod amqp;
 #[cfg(feature = "api")]
 pub mod api;
 pub mod app;
@@ -51,6 +55,10 @@ pub mod line_agg;
 pub mod list;
 pub mod mapping;
 pub mod metrics;
+#[cfg(feature = "rumqttc")]
+pub(crate) mod mqtt;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sinks-opentelemetry"))]
+pub(crate) mod opentelemetry;
 pub(crate) mod pipeline;
 #[cfg(any(feature = "sinks-prometheus", feature = "sources-prometheus"))]
 pub(crate) mod prometheus;
@@ -79,6 +87,8 @@ pub mod types;
 pub mod udp;
 pub mod unit_test;
 pub mod validate;
+#[cfg(any(feature = "sinks-vector", feature = "sources-vector"))]
+pub(crate) mod vector_protocol;
 #[cfg(windows)]
 pub mod vector_windows;
 
@@ -2,6 +2,7 @@
 
 #![deny(missing_docs)]
 
+use crate::event::LogEvent;
 use bytes::{Bytes, BytesMut};
 use futures::{Stream, StreamExt};
 use pin_project::pin_project;
@@ -48,6 +49,28 @@ pub enum Mode {
     HaltWith,
 }
 
+/// The context of a line. When lines are aggregated, the context of the
+/// first line is kept and the contexts of the following ones are merged into
+/// it.
+pub trait LineContext {
+    /// Merges the context of a following line in. Discards it by default.
+    fn merge_next(&mut self, _next: Self)
+    where
+        Self: Sized,
+    {
+    }
+}
+
+impl LineContext for () {}
+
+impl LineContext for LogEvent {
+    /// Keeps the finalizers of the following lines, so that the aggregated
+    /// line is only acknowledged once it's delivered.
+    fn merge_next(&mut self, mut next: Self) {
+        self.add_finalizers(next.take_finalizers());
+    }
+}
+
 /// Configuration parameters of the line aggregator.
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -139,6 +162,7 @@ impl<T, K, C> LineAgg<T, K, C>
 where
     T: Stream<Item = (K, Bytes, C)> + Unpin,
     K: Hash + Eq + Clone,
+    C: LineContext,
 {
     /// Create a new `LineAgg` using the specified `inner` stream and
     /// preconfigured `logic`.
@@ -157,6 +181,7 @@ impl<T, K, C> Stream for LineAgg<T, K, C>
 where
     T: Stream<Item = (K, Bytes, C)> + Unpin,
     K: Hash + Eq + Clone,
+    C: LineContext,
 {
     /// `K` - file name, or other line source,
     /// `Bytes` - the line data,
@@ -237,6 +262,7 @@ impl<T, K, C> LineAgg<T, K, C>
 where
     T: Stream<Item = (K, Bytes, C)> + Unpin,
     K: Hash + Eq + Clone,
+    C: LineContext,
 {
     /// Handle line and do stashing of extra emitted lines.
     /// Requires that the `stashed` item is empty (i.e. entry is vacant). This
@@ -281,6 +307,7 @@ pub enum Emit<T> {
 impl<K, C> Logic<K, C>
 where
     K: Hash + Eq + Clone,
+    C: LineContext,
 {
     /// Handle line, if we have something to output - return it.
     pub fn handle_line(
@@ -299,7 +326,7 @@ where
                     Mode::ContinueThrough => {
                         if condition_matched {
                             let buffered = entry.get_mut();
-                            buffered.add_next_line(line);
+                            buffered.add_next_line(line, context);
                             None
                         } else {
                             let (src, buffered) = entry.remove_entry();
@@ -311,11 +338,11 @@ where
                     Mode::ContinuePast => {
                         if condition_matched {
                             let buffered = entry.get_mut();
-                            buffered.add_next_line(line);
+                            buffered.add_next_line(line, context);
                             None
                         } else {
                             let (src, mut buffered) = entry.remove_entry();
-                            buffered.add_next_line(line);
+                            buffered.add_next_line(line, context);
                             Some((src, Emit::One(buffered.merge())))
                         }
                     }
@@ -327,7 +354,7 @@ where
                             Some((src, Emit::Two(buffered.merge(), (line, context))))
                         } else {
                             let buffered = entry.get_mut();
-                            buffered.add_next_line(line);
+                            buffered.add_next_line(line, context);
                             None
                         }
                     }
@@ -336,11 +363,11 @@ where
                     Mode::HaltWith => {
                         if condition_matched {
                             let (src, mut buffered) = entry.remove_entry();
-                            buffered.add_next_line(line);
+                            buffered.add_next_line(line, context);
                             Some((src, Emit::One(buffered.merge())))
                         } else {
                             let buffered = entry.get_mut();
-                            buffered.add_next_line(line);
+                            buffered.add_next_line(line, context);
                             None
                         }
                     }
@@ -369,7 +396,7 @@ struct Aggregate<C> {
     context: C,
 }
 
-impl<C> Aggregate<C> {
+impl<C: LineContext> Aggregate<C> {
     fn new(first_line: Bytes, context: C) -> Self {
         Self {
             lines: vec![first_line],
@@ -377,8 +404,9 @@ impl<C> Aggregate<C> {
         }
     }
 
-    fn add_next_line(&mut self, line: Bytes) {
+    fn add_next_line(&mut self, line: Bytes, context: C) {
         self.lines.push(line);
+        self.context.merge_next(context);
     }
 
     fn merge(self) -> (Bytes, C) {
@@ -0,0 +1,179 @@
+//! Connection options shared by the `mqtt` source and sink.
+
+use crate::tls::TlsConfig;
+use rand::{distributions::Alphanumeric, thread_rng, Rng};
+use rumqttc::{MqttOptions, QoS};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Snafu)]
+enum MqttError {
+    #[snafu(display("Could not read {:?}: {}", path, source))]
+    ReadFile {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[snafu(display("MQTT over TLS requires the CA certificate in `tls.ca_file`"))]
+    MissingCaFile,
+    #[snafu(display("Client authentication requires both `tls.crt_file` and `tls.key_file`"))]
+    IncompleteClientAuth,
+    #[snafu(display("The `tls.{}` option isn't supported by MQTT", option))]
+    UnsupportedTlsOption { option: &'static str },
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct MqttConnectionConfig {
+    pub host: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Generated for each connection if not set.
+    pub client_id: Option<String>,
+    #[serde(default = "default_keep_alive_secs")]
+    pub keep_alive_secs: u16,
+    pub user: Option<String>,
+    pub password: Option<String>,
+    pub tls: Option<TlsConfig>,
+}
+
+fn default_port() -> u16 {
+    1883
+}
+
+fn default_keep_alive_secs() -> u16 {
+    60
+}
+
+impl MqttConnectionConfig {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port: default_port(),
+            client_id: None,
+            keep_alive_secs: default_keep_alive_secs(),
+            user: None,
+            password: None,
+            tls: None,
+        }
+    }
+
+    pub fn options(&self) -> crate::Result<MqttOptions> {
+        let client_id = self.client_id.clone().unwrap_or_else(|| {
+            let suffix: String = thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(10)
+                .map(char::from)
+                .collect();
+            format!("vector-{}", suffix)
+        });
+        let mut options = MqttOptions::new(client_id, &self.host, self.port);
+        options.set_keep_alive(self.keep_alive_secs);
+        if let (Some(user), Some(password)) = (&self.user, &self.password) {
+            options.set_credentials(user, password);
+        }
+
+        let tls = match &self.tls {
+            Some(tls) if tls.enabled.unwrap_or(false) => &tls.options,
+            _ => return Ok(options),
+        };
+        // The client verifies certificates with rustls, which only takes the
+        // certificates and key, in PEM format.
+        if tls.verify_certificate == Some(false) {
+            return Err(MqttError::UnsupportedTlsOption {
+                option: "verify_certificate",
+            }
+            .into());
+        }
+        if tls.verify_hostname == Some(false) {
+            return Err(MqttError::UnsupportedTlsOption {
+                option: "verify_hostname",
+            }
+            .into());
+        }
+        if tls.key_pass.is_some() {
+            return Err(MqttError::UnsupportedTlsOption { option: "key_pass" }.into());
+        }
+
+        let ca_file = tls.ca_file.as_ref().ok_or(MqttError::MissingCaFile)?;
+        options.set_ca(read_file(ca_file)?);
+        match (&tls.crt_file, &tls.key_file) {
+            (Some(crt_file), Some(key_file)) => {
+                options.set_client_auth(read_file(crt_file)?, read_file(key_file)?);
+            }
+            (None, None) => {}
+            _ => return Err(MqttError::IncompleteClientAuth.into()),
+        }
+        Ok(options)
+    }
+}
+
+fn read_file(path: &Path) -> crate::Result<Vec<u8>> {
+    Ok(std::fs::read(path).context(ReadFile { path })?)
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MqttQos {
+    AtMostOnce,
+    #[derivative(Default)]
+    AtLeastOnce,
+    ExactlyOnce,
+}
+
+impl From<MqttQos> for QoS {
+    fn from(qos: MqttQos) -> Self {
+        match qos {
+            MqttQos::AtMostOnce => QoS::AtMostOnce,
+            MqttQos::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQos::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tls::{TlsOptions, TEST_PEM_CA_PATH, TEST_PEM_CRT_PATH, TEST_PEM_KEY_PATH};
+
+    fn tls(options: TlsOptions) -> MqttConnectionConfig {
+        MqttConnectionConfig {
+            tls: Some(TlsConfig {
+                enabled: Some(true),
+                options,
+            }),
+            ..MqttConnectionConfig::new("localhost")
+        }
+    }
+
+    #[test]
+    fn reads_tls_files() {
+        assert!(tls(TlsOptions {
+            ca_file: Some(TEST_PEM_CA_PATH.into()),
+            crt_file: Some(TEST_PEM_CRT_PATH.into()),
+            key_file: Some(TEST_PEM_KEY_PATH.into()),
+            ..TlsOptions::default()
+        })
+        .options()
+        .is_ok());
+    }
+
+    #[test]
+    fn rejects_unsupported_tls_options() {
+        assert!(tls(TlsOptions::default()).options().is_err());
+        assert!(tls(TlsOptions {
+            ca_file: Some(TEST_PEM_CA_PATH.into()),
+            crt_file: Some(TEST_PEM_CRT_PATH.into()),
+            ..TlsOptions::default()
+        })
+        .options()
+        .is_err());
+        assert!(tls(TlsOptions {
+            ca_file: Some(TEST_PEM_CA_PATH.into()),
+            verify_certificate: Some(false),
+            ..TlsOptions::default()
+        })
+        .options()
+        .is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! The OpenTelemetry protocol (OTLP), spoken by the `opentelemetry` source
+//! and sink.
+//!
+//! The protobuf definitions in `proto/opentelemetry` are the subset of the
+//! upstream ones covering logs and metrics. They keep the upstream field
+//! numbers, so fields left out are just skipped when decoding.
+
+pub mod proto {
+    pub mod collector {
+        pub mod logs {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.collector.logs.v1.rs"
+                ));
+            }
+        }
+
+        pub mod metrics {
+            pub mod v1 {
+                include!(concat!(
+                    env!("OUT_DIR"),
+                    "/opentelemetry.proto.collector.metrics.v1.rs"
+                ));
+            }
+        }
+    }
+
+    pub mod common {
+        pub mod v1 {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/opentelemetry.proto.common.v1.rs"
+            ));
+        }
+    }
+
+    pub mod logs {
+        pub mod v1 {
+            include!(concat!(env!("OUT_DIR"), "/opentelemetry.proto.logs.v1.rs"));
+        }
+    }
+
+    pub mod metrics {
+        pub mod v1 {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/opentelemetry.proto.metrics.v1.rs"
+            ));
+        }
+    }
+
+    pub mod resource {
+        pub mod v1 {
+            include!(concat!(
+                env!("OUT_DIR"),
+                "/opentelemetry.proto.resource.v1.rs"
+            ));
+        }
+    }
+}
+
+use crate::event::Value;
+use chrono::SecondsFormat;
+use prost::Message;
+use proto::common::v1::{any_value, AnyValue, ArrayValue, KeyValue, KeyValueList};
+use std::collections::BTreeMap;
+
+/// The path OTLP/HTTP exporters post logs to.
+pub const LOGS_PATH: &str = "/v1/logs";
+/// The path OTLP/HTTP exporters post metrics to.
+pub const METRICS_PATH: &str = "/v1/metrics";
+/// The content type of OTLP/HTTP requests and responses encoded as protobuf.
+pub const PROTOBUF_CONTENT_TYPE: &str = "application/x-protobuf";
+
+impl From<AnyValue> for Value {
+    fn from(value: AnyValue) -> Self {
+        match value.value {
+            Some(any_value::Value::StringValue(string)) => string.into(),
+            Some(any_value::Value::BoolValue(boolean)) => boolean.into(),
+            Some(any_value::Value::IntValue(integer)) => integer.into(),
+            Some(any_value::Value::DoubleValue(float)) => float.into(),
+            Some(any_value::Value::BytesValue(bytes)) => Value::Bytes(bytes.into()),
+            Some(any_value::Value::ArrayValue(array)) => {
+                Value::Array(array.values.into_iter().map(Into::into).collect())
+            }
+            Some(any_value::Value::KvlistValue(list)) => Value::Map(attributes_to_map(list.values)),
+            None => Value::Null,
+        }
+    }
+}
+
+impl From<Value> for AnyValue {
+    fn from(value: Value) -> Self {
+        let value = match value {
+            Value::Bytes(bytes) => {
+                any_value::Value::StringValue(String::from_utf8_lossy(&bytes).into_owned())
+            }
+            Value::Integer(integer) => any_value::Value::IntValue(integer),
+            Value::Float(float) => any_value::Value::DoubleValue(float),
+            Value::Boolean(boolean) => any_value::Value::BoolValue(boolean),
+            Value::Timestamp(timestamp) => any_value::Value::StringValue(
+                timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            ),
+            Value::Map(map) => any_value::Value::KvlistValue(KeyValueList {
+                values: map_to_attributes(map),
+            }),
+            Value::Array(array) => any_value::Value::ArrayValue(ArrayValue {
+                values: array.into_iter().map(Into::into).collect(),
+            }),
+            Value::Null => return AnyValue { value: None },
+        };
+        AnyValue { value: Some(value) }
+    }
+}
+
+/// Collects attributes into a map, keeping the last value of duplicate keys.
+pub fn attributes_to_map(attributes: Vec<KeyValue>) -> BTreeMap<String, Value> {
+    attributes
+        .into_iter()
+        .map(|attribute| {
+            let value = attribute.value.map(Into::into).unwrap_or(Value::Null);
+            (attribute.key, value)
+        })
+        .collect()
+}
+
+/// Encodes a request or response body.
+pub fn encode_message(message: &impl Message) -> Vec<u8> {
+    let mut buf = Vec::with_capacity(message.encoded_len());
+    message
+        .encode(&mut buf)
+        .expect("Encoding into a Vec can't fail.");
+    buf
+}
+
+/// Turns the fields of a map into attributes, the reverse of
+/// `attributes_to_map`.
+pub fn map_to_attributes(map: BTreeMap<String, Value>) -> Vec<KeyValue> {
+    map.into_iter()
+        .map(|(key, value)| KeyValue {
+            key,
+            value: Some(value.into()),
+        })
+        .collect()
+}
+
+/// Formats a trace or span ID the way OpenTelemetry SDKs print them, as
+/// lowercase hex.
+#[cfg(feature = "sources-opentelemetry")]
+pub fn id_to_hex(id: &[u8]) -> String {
+    id.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parses a trace or span ID formatted as lowercase hex.
+#[cfg(feature = "sinks-opentelemetry")]
+pub fn hex_to_id(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{attributes_to_map, map_to_attributes, proto::common::v1::*};
+    use crate::event::Value;
+
+    fn string_value(string: &str) -> AnyValue {
+        AnyValue {
+            value: Some(any_value::Value::StringValue(string.into())),
+        }
+    }
+
+    #[test]
+    fn converts_nested_attributes() {
+        let attributes = vec![
+            KeyValue {
+                key: "service.name".into(),
+                value: Some(string_value("checkout")),
+            },
+            KeyValue {
+                key: "labels".into(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::KvlistValue(KeyValueList {
+                        values: vec![KeyValue {
+                            key: "tier".into(),
+                            value: Some(AnyValue {
+                                value: Some(any_value::Value::ArrayValue(ArrayValue {
+                                    values: vec![
+                                        string_value("web"),
+                                        AnyValue {
+                                            value: Some(any_value::Value::IntValue(2)),
+                                        },
+                                    ],
+                                })),
+                            }),
+                        }],
+                    })),
+                }),
+            },
+            KeyValue {
+                key: "empty".into(),
+                value: None,
+            },
+        ];
+
+        let map = attributes_to_map(attributes);
+        assert_eq!(map["service.name"], Value::from("checkout"));
+        assert_eq!(
+            map["labels"],
+            Value::Map(
+                vec![(
+                    "tier".to_owned(),
+                    Value::Array(vec![Value::from("web"), Value::Integer(2)])
+                )]
+                .into_iter()
+                .collect()
+            )
+        );
+        assert_eq!(map["empty"], Value::Null);
+    }
+
+    #[test]
+    fn converts_maps_back_to_attributes() {
+        let attributes = vec![
+            KeyValue {
+                key: "count".into(),
+                value: Some(AnyValue {
+                    value: Some(any_value::Value::IntValue(3)),
+                }),
+            },
+            KeyValue {
+                key: "service.name".into(),
+                value: Some(string_value("checkout")),
+            },
+        ];
+        assert_eq!(
+            map_to_attributes(attributes_to_map(attributes.clone())),
+            attributes
+        );
+    }
+
+    #[cfg(feature = "sources-opentelemetry")]
+    #[test]
+    fn formats_ids_as_hex() {
+        assert_eq!(super::id_to_hex(&[0x0a, 0xff, 0x00, 0x42]), "0aff0042");
+    }
+
+    #[cfg(feature = "sinks-opentelemetry")]
+    #[test]
+    fn parses_ids_from_hex() {
+        assert_eq!(
+            super::hex_to_id("0aff0042"),
+            Some(vec![0x0a, 0xff, 0x00, 0x42])
+        );
+        assert_eq!(super::hex_to_id("0af"), None);
+        assert_eq!(super::hex_to_id("zz"), None);
+    }
+}
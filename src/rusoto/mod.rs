@@ -1,4 +1,4 @@
-use crate::{http::HttpError, tls::MaybeTlsSettings};
+use crate::{config::ProxyConfig, http::HttpError, tls::MaybeTlsSettings};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::StreamExt;
@@ -35,9 +35,9 @@ pub use region::{region_from_endpoint, RegionOrEndpoint};
 
 pub type Client = HttpClient<super::http::HttpClient<RusotoBody>>;
 
-pub fn client() -> crate::Result<Client> {
+pub fn client(proxy: &ProxyConfig) -> crate::Result<Client> {
     let settings = MaybeTlsSettings::enable_client()?;
-    let client = super::http::HttpClient::new(settings)?;
+    let client = super::http::HttpClient::new(settings, proxy)?;
     Ok(HttpClient { client })
 }
 
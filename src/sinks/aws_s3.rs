@@ -1,13 +1,13 @@
 use crate::{
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    buffers::Rejection,
+    codecs::{Encoder, Encoding, FramingConfig},
+    config::{DataType, GenerateConfig, ProxyConfig, SinkConfig, SinkContext, SinkDescription},
     rusoto::{self, AWSAuthentication, RegionOrEndpoint},
     serde::to_string,
     sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        retries::RetryLogic,
-        sink::Response,
-        BatchConfig, BatchSettings, Buffer, Compression, Concurrency, PartitionBatchSink,
-        PartitionBuffer, PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
+        encoding::EncodingConfig, retries::RetryLogic, sink::Response, BatchConfig, BatchSettings,
+        Buffer, Compression, Concurrency, PartitionBatchSink, PartitionBuffer,
+        PartitionInnerBuffer, ServiceBuilderExt, TowerRequestConfig,
     },
     template::Template,
     Event,
@@ -50,6 +50,7 @@ pub struct S3SinkConfig {
     #[serde(flatten)]
     pub region: RegionOrEndpoint,
     pub encoding: EncodingConfig<Encoding>,
+    pub framing: Option<FramingConfig>,
     #[serde(default = "Compression::gzip_default")]
     pub compression: Compression,
     #[serde(default)]
@@ -124,13 +125,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Derivative)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
 inventory::submit! {
     SinkDescription::new::<S3SinkConfig>("aws_s3")
 }
@@ -146,6 +140,7 @@ impl GenerateConfig for S3SinkConfig {
             options: S3Options::default(),
             region: RegionOrEndpoint::default(),
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Compression::gzip_default(),
             batch: BatchConfig::default(),
             request: TowerRequestConfig::default(),
@@ -163,7 +158,7 @@ impl SinkConfig for S3SinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let client = self.create_client()?;
+        let client = self.create_client(cx.proxy())?;
         let healthcheck = self.clone().healthcheck(client.clone()).boxed();
         let sink = self.new(client, cx)?;
         Ok((sink, healthcheck))
@@ -191,7 +186,11 @@ enum HealthcheckError {
 impl S3SinkConfig {
     pub fn new(&self, client: S3Client, cx: SinkContext) -> crate::Result<super::VectorSink> {
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
-        let encoding = self.encoding.clone();
+        let framing = self
+            .framing
+            .clone()
+            .unwrap_or(FramingConfig::NewlineDelimited { max_length: None });
+        let encoder = Encoder::new(self.encoding.clone(), &framing)?;
 
         let compression = self.compression;
         let filename_time_format = self
@@ -230,8 +229,15 @@ impl S3SinkConfig {
 
         let buffer = PartitionBuffer::new(Buffer::new(batch.size, self.compression));
 
-        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok))
+        let acker = cx.acker();
+        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, acker.clone())
+            .with_flat_map(move |e| {
+                let encoded = encode_event(e, &key_prefix, &encoder);
+                if encoded.is_none() {
+                    acker.reject_last(Rejection::new("Event could not be encoded.", 0));
+                }
+                stream::iter(encoded).map(Ok)
+            })
             .sink_map_err(|error| error!(message = "Sink failed to flush.", %error));
 
         Ok(super::VectorSink::Sink(Box::new(sink)))
@@ -258,9 +264,9 @@ impl S3SinkConfig {
         }
     }
 
-    pub fn create_client(&self) -> crate::Result<S3Client> {
+    pub fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<S3Client> {
         let region = (&self.region).try_into()?;
-        let client = rusoto::client()?;
+        let client = rusoto::client(proxy)?;
 
         let creds = self.auth.build(&region, self.assume_role.clone())?;
 
@@ -391,9 +397,9 @@ impl RetryLogic for S3RetryLogic {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &Encoder,
 ) -> Option<PartitionInnerBuffer<Vec<u8>, Bytes>> {
     let key = key_prefix
         .render_string(&event)
@@ -406,25 +412,7 @@ fn encode_event(
         })
         .ok()?;
 
-    encoding.apply_rules(&mut event);
-
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let bytes = encoder.encode(event)?;
 
     Some(PartitionInnerBuffer::new(bytes, key.into()))
 }
@@ -432,6 +420,15 @@ fn encode_event(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
+
+    fn encoder(encoding: EncodingConfig<Encoding>) -> Encoder {
+        Encoder::new(
+            encoding,
+            &FramingConfig::NewlineDelimited { max_length: None },
+        )
+        .unwrap()
+    }
 
     #[test]
     fn generate_config() {
@@ -445,7 +442,7 @@ mod tests {
         let bytes = encode_event(
             message.clone().into(),
             &batch_time_format,
-            &Encoding::Text.into(),
+            &encoder(Encoding::Text.into()),
         )
         .unwrap();
 
@@ -461,7 +458,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let bytes = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let bytes =
+            encode_event(event, &batch_time_format, &encoder(Encoding::Ndjson.into())).unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -486,7 +484,7 @@ mod tests {
             timestamp_format: None,
         };
 
-        let bytes = encode_event(event, &key_prefix, &encoding_config).unwrap();
+        let bytes = encode_event(event, &key_prefix, &encoder(encoding_config)).unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -587,7 +585,7 @@ mod integration_tests {
 
         let config = config(1000000).await;
         let prefix = config.key_prefix.clone();
-        let client = config.create_client().unwrap();
+        let client = config.create_client(&ProxyConfig::default()).unwrap();
         let sink = config.new(client, cx).unwrap();
 
         let (lines, events) = random_lines_with_stream(100, 10);
@@ -617,7 +615,7 @@ mod integration_tests {
             ..config(1010).await
         };
         let prefix = config.key_prefix.clone();
-        let client = config.create_client().unwrap();
+        let client = config.create_client(&ProxyConfig::default()).unwrap();
         let sink = config.new(client, cx).unwrap();
 
         let (lines, _events) = random_lines_with_stream(100, 30);
@@ -662,7 +660,7 @@ mod integration_tests {
         };
 
         let prefix = config.key_prefix.clone();
-        let client = config.create_client().unwrap();
+        let client = config.create_client(&ProxyConfig::default()).unwrap();
         let sink = config.new(client, cx).unwrap();
 
         let (lines, events) = random_lines_with_stream(100, 500);
@@ -687,7 +685,7 @@ mod integration_tests {
     #[tokio::test]
     async fn s3_healthchecks() {
         let config = config(1).await;
-        let client = config.create_client().unwrap();
+        let client = config.create_client(&ProxyConfig::default()).unwrap();
         config.healthcheck(client).await.unwrap();
     }
 
@@ -697,7 +695,7 @@ mod integration_tests {
             bucket: "asdflkjadskdaadsfadf".to_string(),
             ..config(1).await
         };
-        let client = config.create_client().unwrap();
+        let client = config.create_client(&ProxyConfig::default()).unwrap();
         assert_downcast_matches!(
             config.healthcheck(client).await.unwrap_err(),
             HealthcheckError,
@@ -732,6 +730,7 @@ mod integration_tests {
             options: S3Options::default(),
             region: RegionOrEndpoint::with_endpoint("http://localhost:4566".to_owned()),
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Compression::None,
             batch: BatchConfig {
                 max_bytes: Some(batch_size),
@@ -127,7 +127,7 @@ impl SinkConfig for ElasticSearchConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let common = ElasticSearchCommon::parse_config(&self)?;
-        let client = HttpClient::new(common.tls_settings.clone())?;
+        let client = HttpClient::new(common.tls_settings.clone(), cx.proxy())?;
 
         let healthcheck = healthcheck(client.clone(), common).boxed();
 
@@ -648,7 +648,7 @@ mod tests {
 mod integration_tests {
     use super::*;
     use crate::{
-        config::{SinkConfig, SinkContext},
+        config::{ProxyConfig, SinkConfig, SinkContext},
         http::HttpClient,
         test_util::{random_events_with_stream, random_string, trace_init},
         tls::{self, TlsOptions},
@@ -915,8 +915,8 @@ mod integration_tests {
         let uri = format!("{}/_flush", common.base_url);
         let request = Request::post(uri).body(Body::empty()).unwrap();
 
-        let client =
-            HttpClient::new(common.tls_settings.clone()).expect("Could not build client to flush");
+        let client = HttpClient::new(common.tls_settings.clone(), &ProxyConfig::default())
+            .expect("Could not build client to flush");
         let response = client.send(request).await?;
         match response.status() {
             StatusCode::OK => Ok(()),
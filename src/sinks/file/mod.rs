@@ -1,13 +1,11 @@
 use crate::expiring_hash_map::ExpiringHashMap;
 use crate::{
-    buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    buffers::{Acker, Rejection},
+    codecs::{Encoder, Encoding, FramingConfig},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::FileOpen,
-    sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        StreamSink,
-    },
+    sinks::util::{encoding::EncodingConfig, StreamSink},
     template::Template,
 };
 use async_compression::tokio_02::write::GzipEncoder;
@@ -35,6 +33,7 @@ pub struct FileSinkConfig {
     pub path: Template,
     pub idle_timeout_secs: Option<u64>,
     pub encoding: EncodingConfig<Encoding>,
+    pub framing: Option<FramingConfig>,
     #[serde(
         default,
         skip_serializing_if = "crate::serde::skip_serializing_if_default"
@@ -52,19 +51,13 @@ impl GenerateConfig for FileSinkConfig {
             path: Template::try_from("/tmp/vector-%Y-%m-%d.log").unwrap(),
             idle_timeout_secs: None,
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Default::default(),
         })
         .unwrap()
     }
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-}
-
 #[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "snake_case")]
 pub enum Compression {
@@ -127,7 +120,7 @@ impl SinkConfig for FileSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let sink = FileSink::new(&self, cx.acker());
+        let sink = FileSink::new(&self, cx.acker())?;
         Ok((
             super::VectorSink::Stream(Box::new(sink)),
             future::ok(()).boxed(),
@@ -147,22 +140,26 @@ impl SinkConfig for FileSinkConfig {
 pub struct FileSink {
     acker: Acker,
     path: Template,
-    encoding: EncodingConfig<Encoding>,
+    encoder: Encoder,
     idle_timeout: Duration,
     files: ExpiringHashMap<Bytes, OutFile>,
     compression: Compression,
 }
 
 impl FileSink {
-    pub fn new(config: &FileSinkConfig, acker: Acker) -> Self {
-        Self {
+    pub fn new(config: &FileSinkConfig, acker: Acker) -> crate::Result<Self> {
+        let framing = config
+            .framing
+            .clone()
+            .unwrap_or(FramingConfig::NewlineDelimited { max_length: None });
+        Ok(Self {
             acker,
             path: config.path.clone(),
-            encoding: config.encoding.clone(),
+            encoder: Encoder::new(config.encoding.clone(), &framing)?,
             idle_timeout: Duration::from_secs(config.idle_timeout_secs.unwrap_or(30)),
             files: ExpiringHashMap::default(),
             compression: config.compression,
-        }
+        })
     }
 
     /// Uses pass the `event` to `self.path` template to obtain the file path
@@ -193,9 +190,9 @@ impl FileSink {
             tokio::select! {
                 event = input.next() => {
                     match event {
-                        Some(event) => {
-                            self.process_event(event).await;
-                            self.acker.ack(1);
+                        Some(event) => match self.process_event(event).await {
+                            Ok(()) => self.acker.ack(1),
+                            Err(rejection) => self.acker.reject_last(rejection),
                         },
                         None => {
                             // If we got `None` - terminate the processing.
@@ -247,17 +244,23 @@ impl FileSink {
         Ok(())
     }
 
-    async fn process_event(&mut self, event: Event) {
+    /// Writes an event to its file, or rejects it if it has no path or can't
+    /// be encoded.
+    async fn process_event(&mut self, event: Event) -> Result<(), Rejection> {
         let path = match self.partition_event(&event) {
             Some(path) => path,
             None => {
                 // We weren't able to find the path to use for the
                 // file.
                 // This is already logged at `partition_event`, so
-                // here we just skip the event.
-                return;
+                // here we just reject the event.
+                return Err(Rejection::new("Path could not be rendered.", 0));
             }
         };
+        let buf = self
+            .encoder
+            .encode(event)
+            .ok_or_else(|| Rejection::new("Event could not be encoded.", 0))?;
 
         let next_deadline = self.deadline_at();
         trace!(message = "Computed next deadline.", next_deadline = ?next_deadline, path = ?path);
@@ -274,7 +277,7 @@ impl FileSink {
                     // Maybe other events will work though! Just log
                     // the error and skip this event.
                     error!(message = "Unable to open the file.", path = ?path, %error);
-                    return;
+                    return Ok(());
                 }
             };
 
@@ -288,9 +291,10 @@ impl FileSink {
         };
 
         trace!(message = "Writing an event to file.", path = ?path);
-        if let Err(error) = write_event_to_file(file, event, &self.encoding).await {
+        if let Err(error) = file.write_all(&buf[..]).await {
             error!(message = "Failed to write file.", path = ?path, %error);
         }
+        Ok(())
     }
 }
 
@@ -310,28 +314,6 @@ async fn open_file(path: impl AsRef<std::path::Path>) -> std::io::Result<File> {
         .await
 }
 
-pub fn encode_event(encoding: &EncodingConfig<Encoding>, mut event: Event) -> Vec<u8> {
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log).expect("Unable to encode event as JSON."),
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.to_string_lossy().into_bytes())
-            .unwrap_or_default(),
-    }
-}
-
-async fn write_event_to_file(
-    file: &mut OutFile,
-    event: Event,
-    encoding: &EncodingConfig<Encoding>,
-) -> Result<(), std::io::Error> {
-    let mut buf = encode_event(encoding, event);
-    buf.push(b'\n');
-    file.write_all(&buf[..]).await
-}
-
 #[async_trait]
 impl StreamSink for FileSink {
     async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -343,9 +325,12 @@ impl StreamSink for FileSink {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{
-        lines_from_file, lines_from_gzip_file, random_events_with_stream, random_lines_with_stream,
-        temp_dir, temp_file, trace_init,
+    use crate::{
+        config::log_schema,
+        test_util::{
+            lines_from_file, lines_from_gzip_file, random_events_with_stream,
+            random_lines_with_stream, temp_dir, temp_file, trace_init,
+        },
     };
     use futures::stream;
     use std::convert::TryInto;
@@ -365,10 +350,11 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (input, _events) = random_lines_with_stream(100, 64);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -390,10 +376,11 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: None,
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Compression::Gzip,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (input, _) = random_lines_with_stream(100, 64);
 
         let events = Box::pin(stream::iter(input.clone().into_iter().map(Event::from)));
@@ -420,10 +407,11 @@ mod tests {
             path: template.try_into().unwrap(),
             idle_timeout_secs: None,
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
 
         let (mut input, _events) = random_events_with_stream(32, 8);
         input[0].as_mut_log().insert("date", "2019-26-07");
@@ -501,10 +489,11 @@ mod tests {
             path: template.clone().try_into().unwrap(),
             idle_timeout_secs: Some(1),
             encoding: Encoding::Text.into(),
+            framing: None,
             compression: Compression::None,
         };
 
-        let mut sink = FileSink::new(&config, Acker::Null);
+        let mut sink = FileSink::new(&config, Acker::Null).unwrap();
         let (mut input, _events) = random_lines_with_stream(10, 64);
 
         let (mut tx, rx) = tokio::sync::mpsc::channel(1);
@@ -1,5 +1,7 @@
 use super::{healthcheck_response, GcpAuthConfig, GcpCredentials, Scope};
 use crate::{
+    buffers::Rejection,
+    codecs::{Encoder, Encoding, FramingConfig},
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     http::{HttpClient, HttpClientFuture, HttpError},
     serde::to_string,
@@ -61,6 +63,7 @@ pub struct GcsSinkConfig {
     filename_append_uuid: Option<bool>,
     filename_extension: Option<String>,
     encoding: EncodingConfig<Encoding>,
+    framing: Option<FramingConfig>,
     #[serde(default)]
     compression: Compression,
     #[serde(default)]
@@ -84,6 +87,7 @@ fn default_config(e: Encoding) -> GcsSinkConfig {
         filename_append_uuid: Default::default(),
         filename_extension: Default::default(),
         encoding: e.into(),
+        framing: None,
         compression: Compression::gzip_default(),
         batch: Default::default(),
         request: Default::default(),
@@ -124,22 +128,6 @@ lazy_static! {
     };
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone, Copy)]
-#[serde(rename_all = "snake_case")]
-enum Encoding {
-    Text,
-    Ndjson,
-}
-
-impl Encoding {
-    fn content_type(self) -> &'static str {
-        match self {
-            Self::Text => "text/plain",
-            Self::Ndjson => "application/x-ndjson",
-        }
-    }
-}
-
 inventory::submit! {
     SinkDescription::new::<GcsSinkConfig>(NAME)
 }
@@ -186,14 +174,14 @@ enum HealthcheckError {
 }
 
 impl GcsSink {
-    async fn new(config: &GcsSinkConfig, _cx: &SinkContext) -> crate::Result<Self> {
+    async fn new(config: &GcsSinkConfig, cx: &SinkContext) -> crate::Result<Self> {
         let creds = config
             .auth
             .make_credentials(Scope::DevStorageReadWrite)
             .await?;
         let settings = RequestSettings::new(config)?;
         let tls = TlsSettings::from_options(&config.tls)?;
-        let client = HttpClient::new(tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
         let base_url = format!("{}{}/", BASE_URL, config.bucket);
         let bucket = config.bucket.clone();
         Ok(GcsSink {
@@ -207,7 +195,11 @@ impl GcsSink {
 
     fn service(self, config: &GcsSinkConfig, cx: &SinkContext) -> crate::Result<VectorSink> {
         let request = config.request.unwrap_with(&REQUEST_DEFAULTS);
-        let encoding = config.encoding.clone();
+        let framing = config
+            .framing
+            .clone()
+            .unwrap_or(FramingConfig::NewlineDelimited { max_length: None });
+        let encoder = Encoder::new(config.encoding.clone(), &framing)?;
 
         let batch = BatchSettings::default()
             .bytes(bytesize::mib(10u64))
@@ -226,9 +218,16 @@ impl GcsSink {
 
         let buffer = PartitionBuffer::new(Buffer::new(batch.size, config.compression));
 
-        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, cx.acker())
+        let acker = cx.acker();
+        let sink = PartitionBatchSink::new(svc, buffer, batch.timeout, acker.clone())
             .sink_map_err(|error| error!(message = "Fatal gcp_cloud_storage error.", %error))
-            .with_flat_map(move |e| stream::iter(encode_event(e, &key_prefix, &encoding)).map(Ok));
+            .with_flat_map(move |e| {
+                let encoded = encode_event(e, &key_prefix, &encoder);
+                if encoded.is_none() {
+                    acker.reject_last(Rejection::new("Event could not be encoded.", 0));
+                }
+                stream::iter(encoded).map(Ok)
+            });
 
         Ok(VectorSink::Sink(Box::new(sink)))
     }
@@ -397,9 +396,9 @@ fn make_header((name, value): (&String, &String)) -> crate::Result<(HeaderName,
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_prefix: &Template,
-    encoding: &EncodingConfig<Encoding>,
+    encoder: &Encoder,
 ) -> Option<PartitionInnerBuffer<Vec<u8>, Bytes>> {
     let key = key_prefix
         .render_string(&event)
@@ -411,24 +410,7 @@ fn encode_event(
             );
         })
         .ok()?;
-    encoding.apply_rules(&mut event);
-    let log = event.into_log();
-    let bytes = match encoding.codec() {
-        Encoding::Ndjson => serde_json::to_vec(&log)
-            .map(|mut b| {
-                b.push(b'\n');
-                b
-            })
-            .expect("Failed to encode event as json, this is a bug!"),
-        Encoding::Text => {
-            let mut bytes = log
-                .get(crate::config::log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default();
-            bytes.push(b'\n');
-            bytes
-        }
-    };
+    let bytes = encoder.encode(event)?;
 
     Some(PartitionInnerBuffer::new(bytes, key.into()))
 }
@@ -464,6 +446,14 @@ impl RetryLogic for GcsRetryLogic {
 mod tests {
     use super::*;
 
+    fn encoder(encoding: Encoding) -> Encoder {
+        Encoder::new(
+            encoding.into(),
+            &FramingConfig::NewlineDelimited { max_length: None },
+        )
+        .unwrap()
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<GcsSinkConfig>();
@@ -476,7 +466,7 @@ mod tests {
         let bytes = encode_event(
             message.clone().into(),
             &batch_time_format,
-            &Encoding::Text.into(),
+            &encoder(Encoding::Text),
         )
         .unwrap();
 
@@ -492,7 +482,7 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let batch_time_format = Template::try_from("date=%F").unwrap();
-        let bytes = encode_event(event, &batch_time_format, &Encoding::Ndjson.into()).unwrap();
+        let bytes = encode_event(event, &batch_time_format, &encoder(Encoding::Ndjson)).unwrap();
 
         let (bytes, _) = bytes.into_parts();
         let map: HashMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
@@ -513,7 +503,7 @@ mod tests {
         event.as_mut_log().insert("key", "value");
 
         let key_format = Template::try_from("key: {{ key }}").unwrap();
-        let bytes = encode_event(event, &key_format, &Encoding::Text.into()).unwrap();
+        let bytes = encode_event(event, &key_format, &encoder(Encoding::Text)).unwrap();
 
         let (_, key) = bytes.into_parts();
         assert_eq!(key, "key: value");
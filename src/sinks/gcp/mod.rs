@@ -1,4 +1,5 @@
 use crate::{
+    config::ProxyConfig,
     http::{HttpClient, HttpError},
     sinks::HealthcheckError,
 };
@@ -78,7 +79,8 @@ async fn get_token_implicit() -> Result<Token, GcpError> {
         .body(hyper::Body::empty())
         .unwrap();
 
-    let res = HttpClient::new(None)
+    // The metadata server is link-local, so it's never proxied.
+    let res = HttpClient::new(None, &ProxyConfig::default())
         .context(BuildHttpClient)?
         .send(req)
         .await
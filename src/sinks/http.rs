@@ -1,5 +1,6 @@
 use crate::{
-    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    codecs::{Encoder, Encoding, FramingConfig},
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     http::{Auth, HttpClient, MaybeAuth},
     internal_events::{HTTPEventEncoded, HTTPEventMissingMessage},
@@ -49,6 +50,7 @@ pub struct HttpSinkConfig {
     #[serde(default)]
     pub compression: Compression,
     pub encoding: EncodingConfig<Encoding>,
+    pub framing: Option<FramingConfig>,
     #[serde(default)]
     pub batch: BatchConfig,
     #[serde(default)]
@@ -66,6 +68,7 @@ fn default_config(e: Encoding) -> HttpSinkConfig {
         compression: Default::default(),
         batch: Default::default(),
         encoding: e.into(),
+        framing: Default::default(),
         request: Default::default(),
         tls: Default::default(),
     }
@@ -89,14 +92,6 @@ pub enum HttpMethod {
     Put,
 }
 
-#[derive(Deserialize, Serialize, Debug, Eq, PartialEq, Clone)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Ndjson,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<HttpSinkConfig>("http")
 }
@@ -119,7 +114,7 @@ impl SinkConfig for HttpSinkConfig {
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
 
         let healthcheck = match cx.healthcheck.uri.clone() {
             Some(healthcheck_uri) => {
@@ -144,7 +139,7 @@ impl SinkConfig for HttpSinkConfig {
         let request = config.request.tower.unwrap_with(&REQUEST_DEFAULTS);
 
         let sink = BatchedHttpSink::new(
-            config,
+            HttpSinkService::new(config)?,
             Buffer::new(batch.size, Compression::None),
             request,
             batch.timeout,
@@ -167,43 +162,48 @@ impl SinkConfig for HttpSinkConfig {
     }
 }
 
+/// The sink's config along with the encoder built from it.
+struct HttpSinkService {
+    config: HttpSinkConfig,
+    encoder: Encoder,
+    /// Whether the events are sent as a JSON array, which is the default
+    /// for the `json` codec.
+    json_array: bool,
+}
+
+impl HttpSinkService {
+    fn new(config: HttpSinkConfig) -> crate::Result<Self> {
+        let json_array = config.framing.is_none() && *config.encoding.codec() == Encoding::Json;
+        let framing = match &config.framing {
+            Some(framing) => framing.clone(),
+            None if json_array => FramingConfig::CharacterDelimited {
+                delimiter: ',',
+                max_length: None,
+            },
+            None => FramingConfig::NewlineDelimited { max_length: None },
+        };
+        Ok(Self {
+            encoder: Encoder::new(config.encoding.clone(), &framing)?,
+            config,
+            json_array,
+        })
+    }
+}
+
 #[async_trait::async_trait]
-impl HttpSink for HttpSinkConfig {
+impl HttpSink for HttpSinkService {
     type Input = Vec<u8>;
     type Output = Vec<u8>;
 
-    fn encode_event(&self, mut event: Event) -> Option<Self::Input> {
-        self.encoding.apply_rules(&mut event);
-        let event = event.into_log();
-
-        let body = match &self.encoding.codec() {
-            Encoding::Text => {
-                if let Some(v) = event.get(crate::config::log_schema().message_key()) {
-                    let mut b = v.to_string_lossy().into_bytes();
-                    b.push(b'\n');
-                    b
-                } else {
-                    emit!(HTTPEventMissingMessage);
-                    return None;
-                }
-            }
-
-            Encoding::Ndjson => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
-                b.push(b'\n');
-                b
-            }
+    fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        if self.encoder.codec() == Encoding::Text
+            && !event.as_log().contains(log_schema().message_key())
+        {
+            emit!(HTTPEventMissingMessage);
+            return None;
+        }
 
-            Encoding::Json => {
-                let mut b = serde_json::to_vec(&event)
-                    .map_err(|error| panic!("Unable to encode into JSON: {}", error))
-                    .ok()?;
-                b.push(b',');
-                b
-            }
-        };
+        let body = self.encoder.encode(event)?;
 
         emit!(HTTPEventEncoded {
             byte_size: body.len(),
@@ -213,21 +213,19 @@ impl HttpSink for HttpSinkConfig {
     }
 
     async fn build_request(&self, mut body: Self::Output) -> crate::Result<http::Request<Vec<u8>>> {
-        let method = match &self.method.clone().unwrap_or(HttpMethod::Post) {
+        let method = match &self.config.method.clone().unwrap_or(HttpMethod::Post) {
             HttpMethod::Post => Method::POST,
             HttpMethod::Put => Method::PUT,
         };
-        let uri: Uri = self.uri.uri.clone();
-
-        let ct = match self.encoding.codec() {
-            Encoding::Text => "text/plain",
-            Encoding::Ndjson => "application/x-ndjson",
-            Encoding::Json => {
-                body.insert(0, b'[');
-                body.pop(); // remove trailing comma from last record
-                body.push(b']');
-                "application/json"
-            }
+        let uri: Uri = self.config.uri.uri.clone();
+
+        let ct = if self.json_array {
+            body.insert(0, b'[');
+            body.pop(); // remove trailing comma from last record
+            body.push(b']');
+            "application/json"
+        } else {
+            self.encoder.codec().content_type()
         };
 
         let mut builder = Request::builder()
@@ -235,7 +233,7 @@ impl HttpSink for HttpSinkConfig {
             .uri(uri)
             .header("Content-Type", ct);
 
-        match self.compression {
+        match self.config.compression {
             Compression::Gzip(level) => {
                 builder = builder.header("Content-Encoding", "gzip");
 
@@ -247,13 +245,13 @@ impl HttpSink for HttpSinkConfig {
             Compression::None => {}
         }
 
-        for (header, value) in self.request.headers.iter() {
+        for (header, value) in self.config.request.headers.iter() {
             builder = builder.header(header.as_str(), value.as_str());
         }
 
         let mut request = builder.body(body).unwrap();
 
-        if let Some(auth) = &self.auth {
+        if let Some(auth) = &self.config.auth {
             auth.apply(&mut request);
         }
 
@@ -318,24 +316,20 @@ mod tests {
 
     #[test]
     fn http_encode_event_text() {
-        let encoding = EncodingConfig::from(Encoding::Text);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Text);
-        config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap();
+        let sink = HttpSinkService::new(default_config(Encoding::Text)).unwrap();
+        let bytes = sink.encode_event(event).unwrap();
 
         assert_eq!(bytes, Vec::from(&"hello world\n"[..]));
     }
 
     #[test]
     fn http_encode_event_json() {
-        let encoding = EncodingConfig::from(Encoding::Ndjson);
         let event = Event::from("hello world");
 
-        let mut config = default_config(Encoding::Json);
-        config.encoding = encoding;
-        let bytes = config.encode_event(event).unwrap();
+        let sink = HttpSinkService::new(default_config(Encoding::Ndjson)).unwrap();
+        let bytes = sink.encode_event(event).unwrap();
 
         #[derive(Deserialize, Debug)]
         #[serde(deny_unknown_fields)]
@@ -797,6 +797,7 @@ mod tests {
 #[cfg(test)]
 mod integration_tests {
     use crate::{
+        config::ProxyConfig,
         http::HttpClient,
         sinks::influxdb::{
             healthcheck,
@@ -816,7 +817,7 @@ mod integration_tests {
             bucket: BUCKET.to_string(),
             token: TOKEN.to_string(),
         });
-        let client = HttpClient::new(None).unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
 
         healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
             .unwrap()
@@ -835,7 +836,7 @@ mod integration_tests {
             bucket: BUCKET.to_string(),
             token: TOKEN.to_string(),
         });
-        let client = HttpClient::new(None).unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
 
         healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
             .unwrap()
@@ -854,7 +855,7 @@ mod integration_tests {
             password: None,
         });
         let influxdb2_settings = None;
-        let client = HttpClient::new(None).unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
 
         healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
             .unwrap()
@@ -873,7 +874,7 @@ mod integration_tests {
             password: None,
         });
         let influxdb2_settings = None;
-        let client = HttpClient::new(None).unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
 
         healthcheck(endpoint, influxdb1_settings, influxdb2_settings, client)
             .unwrap()
@@ -1,12 +1,10 @@
 use crate::{
-    buffers::Acker,
+    buffers::{Acker, Rejection},
+    codecs::{Encoder, Encoding, FramingConfig},
     config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     kafka::{KafkaAuthConfig, KafkaCompression},
     serde::to_string,
-    sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        BatchConfig,
-    },
+    sinks::util::{encoding::EncodingConfig, BatchConfig},
     template::{Template, TemplateError},
     Event,
 };
@@ -48,6 +46,8 @@ pub struct KafkaSinkConfig {
     topic: String,
     key_field: Option<String>,
     encoding: EncodingConfig<Encoding>,
+    #[serde(default)]
+    framing: FramingConfig,
     /// These batching options will **not** override librdkafka_options values.
     #[serde(default)]
     batch: BatchConfig,
@@ -71,18 +71,11 @@ fn default_message_timeout_ms() -> u64 {
     300000 // default in librdkafka
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 pub struct KafkaSink {
     producer: Arc<FutureProducer>,
     topic: Template,
     key_field: Option<String>,
-    encoding: EncodingConfig<Encoding>,
+    encoder: Encoder,
     delivery_fut: FuturesUnordered<BoxFuture<'static, (usize, Result<DeliveryFuture, KafkaError>)>>,
     in_flight: FuturesUnordered<
         BoxFuture<'static, (usize, Result<Result<(i32, i64), KafkaError>, Canceled>)>,
@@ -230,7 +223,7 @@ impl KafkaSink {
             producer: Arc::new(producer),
             topic: Template::try_from(config.topic).context(TopicTemplate)?,
             key_field: config.key_field,
-            encoding: config.encoding,
+            encoder: Encoder::new(config.encoding, &config.framing)?,
             delivery_fut: FuturesUnordered::new(),
             in_flight: FuturesUnordered::new(),
             acker,
@@ -288,7 +281,16 @@ impl Sink<Event> for KafkaSink {
             Event::Metric(metric) => metric.data.timestamp.as_ref(),
         }
         .map(|ts| ts.timestamp_millis());
-        let (key, body) = encode_event(item, &self.key_field, &self.encoding);
+        // The encoder reports events it fails to encode, they are rejected
+        // without failing the whole sink.
+        let (key, body) = match encode_event(item, &self.key_field, &self.encoder) {
+            Some(encoded) => encoded,
+            None => {
+                self.acker
+                    .reject_last(Rejection::new("Event could not be encoded.", 0));
+                return Ok(());
+            }
+        };
 
         let seqno = self.seq_head;
         self.seq_head += 1;
@@ -394,10 +396,10 @@ async fn healthcheck(config: KafkaSinkConfig) -> crate::Result<()> {
 }
 
 fn encode_event(
-    mut event: Event,
+    event: Event,
     key_field: &Option<String>,
-    encoding: &EncodingConfig<Encoding>,
-) -> (Vec<u8>, Vec<u8>) {
+    encoder: &Encoder,
+) -> Option<(Vec<u8>, Vec<u8>)> {
     let key = key_field
         .as_ref()
         .and_then(|f| match &event {
@@ -409,30 +411,21 @@ fn encode_event(
         })
         .unwrap_or_default();
 
-    encoding.apply_rules(&mut event);
-
-    let body = match event {
-        Event::Log(log) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&log).unwrap(),
-            Encoding::Text => log
-                .get(log_schema().message_key())
-                .map(|v| v.as_bytes().to_vec())
-                .unwrap_or_default(),
-        },
-        Event::Metric(metric) => match encoding.codec() {
-            Encoding::Json => serde_json::to_vec(&metric).unwrap(),
-            Encoding::Text => metric.to_string().into_bytes(),
-        },
-    };
+    let body = encoder.encode(event)?;
 
-    (key, body)
+    Some((key, body))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::event::{Metric, MetricKind, MetricValue};
-    use std::collections::BTreeMap;
+    use futures::{stream, StreamExt};
+    use std::{collections::BTreeMap, sync::atomic::Ordering};
+
+    fn encoder(encoding: EncodingConfig<Encoding>) -> Encoder {
+        Encoder::new(encoding, &FramingConfig::Bytes).unwrap()
+    }
 
     #[test]
     fn generate_config() {
@@ -447,8 +440,9 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             message.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &encoder(Encoding::Text.into()),
+        )
+        .unwrap();
 
         assert_eq!(&key_bytes[..], key.as_bytes());
         assert_eq!(&bytes[..], message.as_bytes());
@@ -462,11 +456,8 @@ mod tests {
         event.as_mut_log().insert("key", "value");
         event.as_mut_log().insert("foo", "bar");
 
-        let (key, bytes) = encode_event(
-            event,
-            &Some("key".into()),
-            &EncodingConfig::from(Encoding::Json),
-        );
+        let (key, bytes) =
+            encode_event(event, &Some("key".into()), &encoder(Encoding::Json.into())).unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
@@ -486,8 +477,9 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Text),
-        );
+            &encoder(Encoding::Text.into()),
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(metric.to_string(), String::from_utf8_lossy(&bytes));
@@ -503,8 +495,9 @@ mod tests {
         let (key_bytes, bytes) = encode_event(
             metric.clone().into(),
             &None,
-            &EncodingConfig::from(Encoding::Json),
-        );
+            &encoder(Encoding::Json.into()),
+        )
+        .unwrap();
 
         assert_eq!("", String::from_utf8_lossy(&key_bytes));
         assert_eq!(
@@ -522,20 +515,45 @@ mod tests {
         let (key, bytes) = encode_event(
             event,
             &Some("key".into()),
-            &EncodingConfig {
+            &encoder(EncodingConfig {
                 codec: Encoding::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
-            },
-        );
+            }),
+        )
+        .unwrap();
 
         let map: BTreeMap<String, String> = serde_json::from_slice(&bytes[..]).unwrap();
 
         assert_eq!(&key[..], b"value");
         assert!(!map.contains_key("key"));
     }
+
+    #[tokio::test]
+    async fn rejects_unencodable_events() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"bootstrap_servers = "localhost:9091"
+            topic = "topic-1234"
+            encoding.codec = "gelf""#,
+        )
+        .unwrap();
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let acker = acker.with_finalization();
+        let sink = KafkaSink::new(config, acker.clone()).unwrap();
+
+        // GELF messages require a host and a message.
+        let events = stream::iter(vec![Event::new_empty_log()]);
+        acker
+            .finalizing(events, None)
+            .map(Ok)
+            .forward(sink)
+            .await
+            .unwrap();
+
+        assert_eq!(ack_counter.load(Ordering::Relaxed), 1);
+    }
 }
 
 #[cfg(feature = "kafka-integration-tests")]
@@ -565,6 +583,7 @@ mod integration_test {
             topic: topic.clone(),
             key_field: None,
             encoding: EncodingConfig::from(Encoding::Text),
+            framing: FramingConfig::Bytes,
             batch: BatchConfig::default(),
             compression: KafkaCompression::None,
             auth: KafkaAuthConfig::default(),
@@ -616,6 +635,7 @@ mod integration_test {
             topic: format!("{}-%Y%m%d", topic),
             compression: KafkaCompression::None,
             encoding: Encoding::Text.into(),
+            framing: FramingConfig::Bytes,
             key_field: None,
             auth: KafkaAuthConfig {
                 sasl: None,
@@ -770,6 +790,7 @@ mod integration_test {
             topic: format!("{}-%Y%m%d", topic),
             key_field: None,
             encoding: EncodingConfig::from(Encoding::Text),
+            framing: FramingConfig::Bytes,
             batch: BatchConfig::default(),
             compression,
             auth: kafka_auth.clone(),
@@ -0,0 +1,272 @@
+use crate::{
+    buffers::{Acker, Rejection},
+    codecs::{Encoder, Encoding, FramingConfig},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    emit,
+    internal_events::{
+        MqttConnectionFailed, MqttEventMissingKeys, MqttEventSendFail, MqttEventSendSuccess,
+    },
+    mqtt::{MqttConnectionConfig, MqttQos},
+    sinks::util::{encoding::EncodingConfig, StreamSink},
+    template::{Template, TemplateError},
+    Event,
+};
+use async_trait::async_trait;
+use futures::{stream::BoxStream, FutureExt, StreamExt};
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, Packet, QoS};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{convert::TryFrom, time::Duration};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid topic template: {}", source))]
+    TopicTemplate { source: TemplateError },
+}
+
+#[derive(Debug, Snafu)]
+enum HealthcheckError {
+    #[snafu(display("Connection was refused: {:?}", code))]
+    Refused { code: rumqttc::ConnectReturnCode },
+    #[snafu(display("Timed out connecting to the broker"))]
+    Timeout,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MqttSinkConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topic: String,
+    #[serde(default)]
+    qos: MqttQos,
+    #[serde(default)]
+    retain: bool,
+    encoding: EncodingConfig<Encoding>,
+    #[serde(default)]
+    framing: FramingConfig,
+}
+
+inventory::submit! {
+    SinkDescription::new::<MqttSinkConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            encoding.codec = "json"
+            host = "127.0.0.1"
+            topic = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let sink = MqttSink::new(self.clone(), cx.acker())?;
+        let healthcheck = healthcheck(self.connection.clone()).boxed();
+        Ok((super::VectorSink::Stream(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "mqtt"
+    }
+}
+
+/// Connects to the broker and waits for it to accept the connection.
+async fn healthcheck(connection: MqttConnectionConfig) -> crate::Result<()> {
+    let (_client, mut eventloop) = AsyncClient::new(connection.options()?, 1);
+    let connack = async {
+        loop {
+            if let MqttEvent::Incoming(Packet::ConnAck(connack)) = eventloop.poll().await? {
+                return Ok::<_, rumqttc::ConnectionError>(connack);
+            }
+        }
+    };
+    let connack = tokio::time::timeout(Duration::from_secs(10), connack)
+        .await
+        .map_err(|_| HealthcheckError::Timeout)??;
+    match connack.code {
+        rumqttc::ConnectReturnCode::Accepted => Ok(()),
+        code => Err(HealthcheckError::Refused { code }.into()),
+    }
+}
+
+pub struct MqttSink {
+    connection: MqttConnectionConfig,
+    encoder: Encoder,
+    topic: Template,
+    qos: QoS,
+    retain: bool,
+    acker: Acker,
+}
+
+impl MqttSink {
+    fn new(config: MqttSinkConfig, acker: Acker) -> crate::Result<Self> {
+        Ok(Self {
+            encoder: Encoder::new(config.encoding, &config.framing)?,
+            topic: Template::try_from(config.topic).context(TopicTemplate)?,
+            qos: config.qos.into(),
+            retain: config.retain,
+            connection: config.connection,
+            acker,
+        })
+    }
+}
+
+/// Drives the connection, which sends the published messages, until the
+/// client is dropped. Polling again after an error reconnects.
+async fn run_eventloop(mut eventloop: EventLoop) {
+    loop {
+        match eventloop.poll().await {
+            Ok(_) => {}
+            Err(rumqttc::ConnectionError::RequestsDone) => break,
+            Err(error) => {
+                emit!(MqttConnectionFailed { error });
+                tokio::time::delay_for(Duration::from_secs(1)).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink for MqttSink {
+    async fn run(&mut self, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let options = self.connection.options().map_err(|error| {
+            error!(message = "Invalid MQTT options.", %error);
+        })?;
+        let (client, eventloop) = AsyncClient::new(options, 100);
+        let eventloop = tokio::spawn(run_eventloop(eventloop));
+
+        while let Some(event) = input.next().await {
+            let topic = match self.topic.render_string(&event) {
+                Ok(topic) => topic,
+                Err(missing_keys) => {
+                    emit!(MqttEventMissingKeys {
+                        keys: &missing_keys
+                    });
+                    self.acker
+                        .reject_last(Rejection::new("Topic could not be rendered.", 0));
+                    continue;
+                }
+            };
+
+            let message = match self.encoder.encode(event) {
+                Some(message) => message,
+                None => {
+                    self.acker
+                        .reject_last(Rejection::new("Event could not be encoded.", 0));
+                    continue;
+                }
+            };
+            let message_len = message.len();
+
+            match client.publish(topic, self.qos, self.retain, message).await {
+                Ok(()) => emit!(MqttEventSendSuccess {
+                    byte_size: message_len,
+                }),
+                Err(error) => emit!(MqttEventSendFail { error }),
+            }
+
+            self.acker.ack(1);
+        }
+
+        // The event loop sends the messages still queued once the client is
+        // dropped, and then stops.
+        drop(client);
+        let _ = tokio::time::timeout(Duration::from_secs(5), eventloop).await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSinkConfig>();
+    }
+
+    #[test]
+    fn renders_topics_per_event() {
+        let template = Template::try_from("devices/{{ device }}").unwrap();
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("device", "sensor-1");
+        assert_eq!(template.render_string(&event).unwrap(), "devices/sensor-1");
+        assert!(template.render_string(&Event::from("hello")).is_err());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{random_lines_with_stream, random_string, trace_init};
+
+    #[tokio::test]
+    async fn mqtt_happy() {
+        trace_init();
+
+        let topic = format!("test/{}", random_string(10));
+        let config: MqttSinkConfig = toml::from_str(&format!(
+            r#"
+            encoding.codec = "text"
+            host = "127.0.0.1"
+            topic = "{}"
+            "#,
+            topic
+        ))
+        .unwrap();
+        healthcheck(config.connection.clone()).await.unwrap();
+
+        // Establish the consumer subscription.
+        let (client, mut eventloop) = AsyncClient::new(
+            MqttConnectionConfig::new("127.0.0.1").options().unwrap(),
+            10,
+        );
+        client
+            .subscribe(topic.clone(), QoS::AtLeastOnce)
+            .await
+            .unwrap();
+        loop {
+            if let MqttEvent::Incoming(Packet::SubAck(_)) = eventloop.poll().await.unwrap() {
+                break;
+            }
+        }
+
+        let (acker, ack_counter) = Acker::new_for_testing();
+        let mut sink = MqttSink::new(config, acker).unwrap();
+        let num_events = 100;
+        let (input, events) = random_lines_with_stream(100, num_events);
+        sink.run(Box::pin(events)).await.unwrap();
+
+        let mut output = Vec::new();
+        while output.len() < num_events {
+            let notification = tokio::time::timeout(Duration::from_secs(5), eventloop.poll())
+                .await
+                .expect("Timed out waiting for messages")
+                .unwrap();
+            if let MqttEvent::Incoming(Packet::Publish(publish)) = notification {
+                assert_eq!(publish.topic, topic);
+                output.push(String::from_utf8_lossy(&publish.payload).to_string());
+            }
+        }
+
+        assert_eq!(output, input);
+        assert_eq!(
+            ack_counter.load(std::sync::atomic::Ordering::Relaxed),
+            num_events
+        );
+    }
+}
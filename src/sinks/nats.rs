@@ -1,12 +1,10 @@
 use crate::{
-    buffers::Acker,
+    buffers::{Acker, Rejection},
+    codecs::{Encoder, Encoding, FramingConfig},
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     emit,
     internal_events::{NatsEventMissingKeys, NatsEventSendFail, NatsEventSendSuccess},
-    sinks::util::{
-        encoding::{EncodingConfig, EncodingConfiguration},
-        StreamSink,
-    },
+    sinks::util::{encoding::EncodingConfig, StreamSink},
     template::{Template, TemplateError},
     Event,
 };
@@ -29,6 +27,8 @@ enum BuildError {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct NatsSinkConfig {
     encoding: EncodingConfig<Encoding>,
+    #[serde(default)]
+    framing: FramingConfig,
     #[serde(default = "default_name")]
     name: String,
     subject: String,
@@ -39,13 +39,6 @@ fn default_name() -> String {
     String::from("vector")
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-}
-
 inventory::submit! {
     SinkDescription::new::<NatsSinkConfig>("nats")
 }
@@ -117,7 +110,7 @@ struct NatsOptions {
 }
 
 pub struct NatsSink {
-    encoding: EncodingConfig<Encoding>,
+    encoder: Encoder,
     options: NatsOptions,
     subject: Template,
     url: String,
@@ -128,7 +121,7 @@ impl NatsSink {
     fn new(config: NatsSinkConfig, acker: Acker) -> crate::Result<Self> {
         Ok(NatsSink {
             options: (&config).into(),
-            encoding: config.encoding,
+            encoder: Encoder::new(config.encoding, &config.framing)?,
             subject: Template::try_from(config.subject).context(SubjectTemplate)?,
             url: config.url,
             acker,
@@ -169,10 +162,17 @@ impl StreamSink for NatsSink {
                 });
             })?;
 
-            let log = encode_event(event, &self.encoding);
-            let message_len = log.len();
+            let message = match self.encoder.encode(event) {
+                Some(message) => message,
+                None => {
+                    self.acker
+                        .reject_last(Rejection::new("Event could not be encoded.", 0));
+                    continue;
+                }
+            };
+            let message_len = message.len();
 
-            match nc.publish(&subject, log).await {
+            match nc.publish(&subject, message).await {
                 Ok(_) => {
                     emit!(NatsEventSendSuccess {
                         byte_size: message_len,
@@ -190,25 +190,16 @@ impl StreamSink for NatsSink {
     }
 }
 
-fn encode_event(mut event: Event, encoding: &EncodingConfig<Encoding>) -> String {
-    encoding.apply_rules(&mut event);
-
-    match encoding.codec() {
-        Encoding::Json => serde_json::to_string(event.as_log()).unwrap(),
-        Encoding::Text => event
-            .as_log()
-            .get(crate::config::log_schema().message_key())
-            .map(|v| v.to_string_lossy())
-            .unwrap_or_default(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
-    use super::{encode_event, Encoding, EncodingConfig};
     use crate::event::{Event, Value};
 
+    fn encode_event(event: Event, encoding: EncodingConfig<Encoding>) -> String {
+        let encoder = Encoder::new(encoding, &FramingConfig::Bytes).unwrap();
+        String::from_utf8(encoder.encode(event).unwrap()).unwrap()
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<NatsSinkConfig>();
@@ -219,7 +210,7 @@ mod tests {
         let event = Event::from("foo");
         assert_eq!(
             "foo",
-            encode_event(event, &EncodingConfig::from(Encoding::Text))
+            encode_event(event, EncodingConfig::from(Encoding::Text))
         );
     }
 
@@ -231,7 +222,7 @@ mod tests {
         log.insert("z", Value::from(25));
         log.insert("a", Value::from("0"));
 
-        let encoded = encode_event(event, &EncodingConfig::from(Encoding::Json));
+        let encoded = encode_event(event, EncodingConfig::from(Encoding::Json));
         let expected = r#"{"a":"0","x":"23","z":25}"#;
         assert_eq!(encoded, expected);
     }
@@ -258,6 +249,7 @@ mod integration_tests {
 
         let cnf = NatsSinkConfig {
             encoding: EncodingConfig::from(Encoding::Text),
+            framing: FramingConfig::Bytes,
             name: "".to_owned(),
             subject: subject.clone(),
             url: "nats://127.0.0.1:4222".to_owned(),
@@ -69,10 +69,10 @@ pub enum Encoding {
     Json,
 }
 
-impl From<Encoding> for crate::sinks::http::Encoding {
-    fn from(v: Encoding) -> crate::sinks::http::Encoding {
+impl From<Encoding> for crate::codecs::Encoding {
+    fn from(v: Encoding) -> crate::codecs::Encoding {
         match v {
-            Encoding::Json => crate::sinks::http::Encoding::Json,
+            Encoding::Json => crate::codecs::Encoding::Json,
         }
     }
 }
@@ -154,6 +154,7 @@ impl NewRelicLogsConfig {
             headers: None,
             compression: self.compression,
             encoding: self.encoding.clone().into_encoding(),
+            framing: None,
 
             batch,
             request,
@@ -0,0 +1,725 @@
+//! OpenTelemetry sink
+//!
+//! Exports logs and metrics to an OpenTelemetry collector, or any other
+//! backend accepting the OpenTelemetry protocol (OTLP), as OTLP/HTTP
+//! protobuf requests posted to the `/v1/logs` and `/v1/metrics` paths of the
+//! endpoint.
+//!
+//! Logs and metrics are batched separately. Each event is converted into its
+//! own resource, and the consecutive ones of the same resource and scope are
+//! merged when building the request.
+
+use crate::{
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event, LogEvent, Value,
+    },
+    http::{Auth, HttpClient, MaybeAuth},
+    internal_events::OpentelemetryMetricUnsupported,
+    opentelemetry::{
+        encode_message, hex_to_id, map_to_attributes,
+        proto::{
+            collector::{
+                logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+            },
+            common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+            logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+            metrics::v1::{
+                metric, number_data_point, AggregationTemporality, Gauge, Histogram,
+                HistogramDataPoint, NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum,
+            },
+            resource::v1::Resource,
+        },
+        LOGS_PATH, METRICS_PATH, PROTOBUF_CONTENT_TYPE,
+    },
+    sinks::util::{
+        buffer::compression::GZIP_DEFAULT,
+        http::{HttpSink, PartitionHttpSink},
+        BatchConfig, BatchSettings, Compression, EncodedLength, PartitionBuffer,
+        PartitionInnerBuffer, TowerRequestConfig, UriSerde, VecBuffer,
+    },
+    tls::{TlsOptions, TlsSettings},
+};
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use futures::{future, FutureExt, SinkExt};
+use http::{Request, Uri};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, io::Write};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetrySinkConfig {
+    endpoint: UriSerde,
+    auth: Option<Auth>,
+    #[serde(default)]
+    compression: Compression,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
+    tls: Option<TlsOptions>,
+}
+
+inventory::submit! {
+    SinkDescription::new::<OpentelemetrySinkConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetrySinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"endpoint = "http://localhost:4318""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpentelemetrySinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+        let auth = self.auth.choose_one(&self.endpoint.auth)?;
+
+        // OTLP has no health endpoint, so only the one configured with
+        // `healthcheck.uri` is checked.
+        let healthcheck = match cx.healthcheck.uri.clone() {
+            Some(uri) => healthcheck(uri, auth.clone(), client.clone()).boxed(),
+            None => future::ok(()).boxed(),
+        };
+
+        let batch = BatchSettings::default()
+            .bytes(bytesize::mib(10u64))
+            .events(1_000)
+            .timeout(1)
+            .parse_config(self.batch)?;
+        let request = self.request.unwrap_with(&TowerRequestConfig::default());
+
+        let sink = OpentelemetrySink {
+            endpoint: self
+                .endpoint
+                .with_default_parts()
+                .uri
+                .to_string()
+                .trim_end_matches('/')
+                .to_owned(),
+            compression: self.compression,
+            auth,
+        };
+        let sink = PartitionHttpSink::new(
+            sink,
+            PartitionBuffer::new(VecBuffer::new(batch.size)),
+            request,
+            batch.timeout,
+            client,
+            cx.acker(),
+        )
+        .sink_map_err(|error| error!(message = "Fatal OpenTelemetry sink error.", %error));
+
+        Ok((super::VectorSink::Sink(Box::new(sink)), healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+}
+
+async fn healthcheck(uri: UriSerde, auth: Option<Auth>, client: HttpClient) -> crate::Result<()> {
+    let auth = auth.choose_one(&uri.auth)?;
+    let uri = uri.with_default_parts();
+    let mut request = Request::head(&uri.uri).body(hyper::Body::empty()).unwrap();
+
+    if let Some(auth) = auth {
+        auth.apply(&mut request);
+    }
+
+    let response = client.send(request).await?;
+
+    match response.status() {
+        status if status.is_success() => Ok(()),
+        status => Err(super::HealthcheckError::UnexpectedStatus { status }.into()),
+    }
+}
+
+/// The OTLP path events are exported to, by which they are batched.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+}
+
+/// A single event, as the resource holding it.
+#[derive(Clone, Debug)]
+enum Record {
+    Log(ResourceLogs),
+    Metric(ResourceMetrics),
+}
+
+impl Record {
+    fn signal(&self) -> Signal {
+        match self {
+            Self::Log(_) => Signal::Logs,
+            Self::Metric(_) => Signal::Metrics,
+        }
+    }
+}
+
+impl EncodedLength for Record {
+    fn encoded_length(&self) -> usize {
+        match self {
+            Self::Log(resource_logs) => resource_logs.encoded_len(),
+            Self::Metric(resource_metrics) => resource_metrics.encoded_len(),
+        }
+    }
+}
+
+struct OpentelemetrySink {
+    endpoint: String,
+    compression: Compression,
+    auth: Option<Auth>,
+}
+
+#[async_trait::async_trait]
+impl HttpSink for OpentelemetrySink {
+    type Input = PartitionInnerBuffer<Record, Signal>;
+    type Output = PartitionInnerBuffer<Vec<Record>, Signal>;
+
+    fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        let record = match event {
+            Event::Log(log) => Record::Log(log_to_resource_logs(log)),
+            Event::Metric(metric) => Record::Metric(metric_to_resource_metrics(metric)?),
+        };
+        let signal = record.signal();
+        Some(PartitionInnerBuffer::new(record, signal))
+    }
+
+    async fn build_request(&self, output: Self::Output) -> crate::Result<Request<Vec<u8>>> {
+        let (records, signal) = output.into_parts();
+        let (path, mut body) = match signal {
+            Signal::Logs => (
+                LOGS_PATH,
+                encode_message(&ExportLogsServiceRequest {
+                    resource_logs: merge_logs(records),
+                }),
+            ),
+            Signal::Metrics => (
+                METRICS_PATH,
+                encode_message(&ExportMetricsServiceRequest {
+                    resource_metrics: merge_metrics(records),
+                }),
+            ),
+        };
+
+        let uri: Uri = format!("{}{}", self.endpoint, path).parse()?;
+        let mut builder = Request::post(uri).header("Content-Type", PROTOBUF_CONTENT_TYPE);
+
+        match self.compression {
+            Compression::Gzip(level) => {
+                builder = builder.header("Content-Encoding", "gzip");
+
+                let level = level.unwrap_or(GZIP_DEFAULT) as u32;
+                let mut w = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+                w.write_all(&body).expect("Writing to Vec can't fail");
+                body = w.finish().expect("Writing to Vec can't fail");
+            }
+            Compression::None => {}
+        }
+
+        let mut request = builder.body(body).unwrap();
+
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        Ok(request)
+    }
+}
+
+fn timestamp_nanos(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos() as u64
+}
+
+/// Removes the field `key` if `convert` accepts its value. Fields that
+/// aren't accepted are kept, to be sent as attributes.
+fn take_field<T>(
+    log: &mut LogEvent,
+    key: &str,
+    convert: impl FnOnce(&Value) -> Option<T>,
+) -> Option<T> {
+    let converted = log.get(key).and_then(convert)?;
+    log.remove(key);
+    Some(converted)
+}
+
+fn as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::Bytes(bytes) => Some(String::from_utf8_lossy(bytes).into_owned()),
+        _ => None,
+    }
+}
+
+fn as_map(value: &Value) -> Option<BTreeMap<String, Value>> {
+    match value {
+        Value::Map(map) => Some(map.clone()),
+        _ => None,
+    }
+}
+
+fn as_integer(value: &Value) -> Option<i64> {
+    match value {
+        Value::Integer(integer) => Some(*integer),
+        _ => None,
+    }
+}
+
+fn as_id(value: &Value) -> Option<Vec<u8>> {
+    as_string(value).and_then(|hex| hex_to_id(&hex))
+}
+
+/// Converts a log into a record, taking its fields from those set by the
+/// `opentelemetry` source. The other fields are sent as attributes.
+fn log_to_resource_logs(mut log: LogEvent) -> ResourceLogs {
+    let mut record = LogRecord::default();
+    if let Some(message) = log.remove(log_schema().message_key()) {
+        record.body = Some(message.into());
+    }
+    if let Some(timestamp) = take_field(&mut log, log_schema().timestamp_key(), |value| {
+        value.as_timestamp().copied()
+    }) {
+        record.time_unix_nano = timestamp_nanos(timestamp);
+    }
+    if let Some(timestamp) = take_field(&mut log, "observed_timestamp", |value| {
+        value.as_timestamp().copied()
+    }) {
+        record.observed_time_unix_nano = timestamp_nanos(timestamp);
+    }
+    if let Some(text) = take_field(&mut log, "severity_text", as_string) {
+        record.severity_text = text;
+    }
+    if let Some(number) = take_field(&mut log, "severity_number", as_integer) {
+        record.severity_number = number as i32;
+    }
+    if let Some(id) = take_field(&mut log, "trace_id", as_id) {
+        record.trace_id = id;
+    }
+    if let Some(id) = take_field(&mut log, "span_id", as_id) {
+        record.span_id = id;
+    }
+    if let Some(flags) = take_field(&mut log, "flags", as_integer) {
+        record.flags = flags as u32;
+    }
+    if let Some(count) = take_field(&mut log, "dropped_attributes_count", as_integer) {
+        record.dropped_attributes_count = count as u32;
+    }
+
+    let resource = take_field(&mut log, "resources", as_map).map(|resources| Resource {
+        attributes: map_to_attributes(resources),
+        ..Default::default()
+    });
+    let scope = take_field(&mut log, "scope", as_map).map(scope_from_map);
+
+    let mut attributes = take_field(&mut log, "attributes", as_map).unwrap_or_default();
+    for (key, value) in log {
+        attributes.entry(key).or_insert(value);
+    }
+    record.attributes = map_to_attributes(attributes);
+
+    ResourceLogs {
+        resource,
+        scope_logs: vec![ScopeLogs {
+            scope,
+            log_records: vec![record],
+            ..Default::default()
+        }],
+        ..Default::default()
+    }
+}
+
+fn scope_from_map(mut map: BTreeMap<String, Value>) -> InstrumentationScope {
+    let mut take_string = |key| {
+        map.remove(key)
+            .map(|value| value.to_string_lossy())
+            .unwrap_or_default()
+    };
+    let name = take_string("name");
+    let version = take_string("version");
+    let attributes = match map.remove("attributes") {
+        Some(Value::Map(attributes)) => map_to_attributes(attributes),
+        _ => Vec::new(),
+    };
+    InstrumentationScope {
+        name,
+        version,
+        attributes,
+        ..Default::default()
+    }
+}
+
+/// Converts a metric into a single data point, with the tags as its
+/// attributes. Incremental metrics have delta temporality, and absolute ones
+/// cumulative temporality.
+fn metric_to_resource_metrics(metric: Metric) -> Option<ResourceMetrics> {
+    let name = match metric.namespace() {
+        Some(namespace) => format!("{}.{}", namespace, metric.name()),
+        None => metric.name().to_owned(),
+    };
+    let attributes = metric
+        .tags()
+        .map(|tags| {
+            tags.iter()
+                .map(|(key, value)| KeyValue {
+                    key: key.clone(),
+                    value: Some(AnyValue {
+                        value: Some(any_value::Value::StringValue(value.clone())),
+                    }),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let time_unix_nano = timestamp_nanos(metric.data.timestamp.unwrap_or_else(Utc::now));
+    let temporality = match metric.data.kind {
+        MetricKind::Incremental => AggregationTemporality::Delta,
+        MetricKind::Absolute => AggregationTemporality::Cumulative,
+    } as i32;
+    let number_point = |value| NumberDataPoint {
+        attributes: attributes.clone(),
+        time_unix_nano,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        ..Default::default()
+    };
+
+    let data = match metric.data.value {
+        MetricValue::Counter { value } => metric::Data::Sum(Sum {
+            data_points: vec![number_point(value)],
+            aggregation_temporality: temporality,
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => match metric.data.kind {
+            MetricKind::Absolute => metric::Data::Gauge(Gauge {
+                data_points: vec![number_point(value)],
+            }),
+            // OTLP gauges can't be incremental, but sums that can decrease
+            // can be.
+            MetricKind::Incremental => metric::Data::Sum(Sum {
+                data_points: vec![number_point(value)],
+                aggregation_temporality: temporality,
+                is_monotonic: false,
+            }),
+        },
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => {
+            let buckets = buckets
+                .into_iter()
+                .filter(|bucket| bucket.upper_limit.is_finite())
+                .collect::<Vec<_>>();
+            let mut bucket_counts = buckets
+                .iter()
+                .map(|bucket| bucket.count as u64)
+                .collect::<Vec<_>>();
+            // The counts of the values above the highest bound are only
+            // implied by the total count.
+            let overflow = (count as u64).saturating_sub(bucket_counts.iter().sum());
+            bucket_counts.push(overflow);
+            metric::Data::Histogram(Histogram {
+                data_points: vec![HistogramDataPoint {
+                    attributes,
+                    time_unix_nano,
+                    count: count as u64,
+                    sum,
+                    bucket_counts,
+                    explicit_bounds: buckets.iter().map(|bucket| bucket.upper_limit).collect(),
+                    ..Default::default()
+                }],
+                aggregation_temporality: temporality,
+            })
+        }
+        _ => {
+            emit!(OpentelemetryMetricUnsupported { name: &name });
+            return None;
+        }
+    };
+
+    Some(ResourceMetrics {
+        scope_metrics: vec![ScopeMetrics {
+            metrics: vec![crate::opentelemetry::proto::metrics::v1::Metric {
+                name,
+                data: Some(data),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        ..Default::default()
+    })
+}
+
+/// Merges the consecutive logs of the same resource and scope.
+fn merge_logs(records: Vec<Record>) -> Vec<ResourceLogs> {
+    let mut merged: Vec<ResourceLogs> = Vec::new();
+    for record in records {
+        let resource_logs = match record {
+            Record::Log(resource_logs) => resource_logs,
+            Record::Metric(_) => continue,
+        };
+        match merged.last_mut() {
+            Some(last)
+                if last.resource == resource_logs.resource
+                    && last.schema_url == resource_logs.schema_url =>
+            {
+                for scope_logs in resource_logs.scope_logs {
+                    match last.scope_logs.last_mut() {
+                        Some(last_scope) if last_scope.scope == scope_logs.scope => {
+                            last_scope.log_records.extend(scope_logs.log_records)
+                        }
+                        _ => last.scope_logs.push(scope_logs),
+                    }
+                }
+            }
+            _ => merged.push(resource_logs),
+        }
+    }
+    merged
+}
+
+/// Merges the consecutive metrics of the same resource and scope.
+fn merge_metrics(records: Vec<Record>) -> Vec<ResourceMetrics> {
+    let mut merged: Vec<ResourceMetrics> = Vec::new();
+    for record in records {
+        let resource_metrics = match record {
+            Record::Metric(resource_metrics) => resource_metrics,
+            Record::Log(_) => continue,
+        };
+        match merged.last_mut() {
+            Some(last)
+                if last.resource == resource_metrics.resource
+                    && last.schema_url == resource_metrics.schema_url =>
+            {
+                for scope_metrics in resource_metrics.scope_metrics {
+                    match last.scope_metrics.last_mut() {
+                        Some(last_scope) if last_scope.scope == scope_metrics.scope => {
+                            last_scope.metrics.extend(scope_metrics.metrics)
+                        }
+                        _ => last.scope_metrics.push(scope_metrics),
+                    }
+                }
+            }
+            _ => merged.push(resource_metrics),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::metric::Bucket,
+        sinks::util::test::{build_test_server, load_sink},
+        test_util::next_addr,
+    };
+    use bytes::buf::BufExt;
+    use flate2::read::GzDecoder;
+    use futures::{stream, StreamExt};
+    use std::io::Read;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetrySinkConfig>();
+    }
+
+    fn log_record(resource_logs: &ResourceLogs) -> &LogRecord {
+        &resource_logs.scope_logs[0].log_records[0]
+    }
+
+    #[test]
+    fn converts_logs_back_to_records() {
+        let mut log = LogEvent::default();
+        log.insert(log_schema().message_key(), "hello");
+        log.insert(log_schema().timestamp_key(), Utc::now());
+        log.insert("severity_text", "INFO");
+        log.insert("severity_number", 9);
+        log.insert("trace_id", "0aff0042");
+        log.insert("span_id", "not hex");
+        log.insert("attributes.user", "alice");
+        log.insert("resources.service\\.name", "checkout");
+        log.insert("scope.name", "db");
+        log.insert("host", "web-1");
+
+        let resource_logs = log_to_resource_logs(log);
+        let record = log_record(&resource_logs);
+        assert_eq!(record.body, Some(Value::from("hello").into()));
+        assert_ne!(record.time_unix_nano, 0);
+        assert_eq!(record.severity_text, "INFO");
+        assert_eq!(record.severity_number, 9);
+        assert_eq!(record.trace_id, vec![0x0a, 0xff, 0x00, 0x42]);
+        assert!(record.span_id.is_empty());
+
+        let attributes = record
+            .attributes
+            .iter()
+            .map(|attribute| attribute.key.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(attributes, vec!["host", "span_id", "user"]);
+
+        let resource = resource_logs.resource.as_ref().unwrap();
+        assert_eq!(resource.attributes[0].key, "service.name");
+        let scope = resource_logs.scope_logs[0].scope.as_ref().unwrap();
+        assert_eq!(scope.name, "db");
+    }
+
+    fn metric_data(metric: Metric) -> metric::Data {
+        metric_to_resource_metrics(metric).unwrap().scope_metrics[0].metrics[0]
+            .data
+            .clone()
+            .unwrap()
+    }
+
+    #[test]
+    fn converts_metric_kinds_to_temporality() {
+        let counter = Metric::new(
+            "requests",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 3.0 },
+        );
+        match metric_data(counter) {
+            metric::Data::Sum(sum) => {
+                assert!(sum.is_monotonic);
+                assert_eq!(sum.aggregation_temporality(), AggregationTemporality::Delta);
+            }
+            data => panic!("Unexpected data {:?}", data),
+        }
+
+        let gauge = Metric::new(
+            "connections",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 3.0 },
+        );
+        assert!(matches!(metric_data(gauge), metric::Data::Gauge(_)));
+
+        let histogram = Metric::new(
+            "latency",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.5,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 3,
+                    },
+                ],
+                count: 7,
+                sum: 6.5,
+            },
+        );
+        match metric_data(histogram) {
+            metric::Data::Histogram(histogram) => {
+                assert_eq!(
+                    histogram.aggregation_temporality(),
+                    AggregationTemporality::Cumulative
+                );
+                let point = &histogram.data_points[0];
+                assert_eq!(point.explicit_bounds, vec![0.5, 1.0]);
+                assert_eq!(point.bucket_counts, vec![2, 3, 2]);
+            }
+            data => panic!("Unexpected data {:?}", data),
+        }
+
+        let set = Metric::new(
+            "users",
+            MetricKind::Incremental,
+            MetricValue::Set {
+                values: vec!["alice".into()].into_iter().collect(),
+            },
+        );
+        assert!(metric_to_resource_metrics(set).is_none());
+    }
+
+    #[test]
+    fn merges_records_of_the_same_resource() {
+        let record = |message: &str, service: &str| {
+            let mut log = LogEvent::default();
+            log.insert(log_schema().message_key(), message);
+            log.insert("resources.service", service.to_owned());
+            Record::Log(log_to_resource_logs(log))
+        };
+
+        let merged = merge_logs(vec![
+            record("one", "a"),
+            record("two", "a"),
+            record("three", "b"),
+        ]);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].scope_logs.len(), 1);
+        assert_eq!(merged[0].scope_logs[0].log_records.len(), 2);
+        assert_eq!(merged[1].scope_logs[0].log_records.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn exports_logs_and_metrics() {
+        let addr = next_addr();
+        let config = format!(
+            r#"
+            endpoint = "http://{}"
+            compression = "gzip"
+            "#,
+            addr
+        );
+        let (config, cx) = load_sink::<OpentelemetrySinkConfig>(&config).unwrap();
+        let (sink, _) = config.build(cx).await.unwrap();
+
+        let (rx, trigger, server) = build_test_server(addr);
+        tokio::spawn(server);
+
+        let events = vec![
+            Event::from("one"),
+            Event::from("two"),
+            Event::Metric(Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 1.0 },
+            )),
+        ];
+        sink.run(stream::iter(events)).await.unwrap();
+        drop(trigger);
+
+        let mut requests = rx
+            .map(|(parts, body)| {
+                assert_eq!(parts.method, "POST");
+                assert_eq!(parts.headers["content-type"], PROTOBUF_CONTENT_TYPE);
+                assert_eq!(parts.headers["content-encoding"], "gzip");
+                let mut decoded = Vec::new();
+                GzDecoder::new(body.reader())
+                    .read_to_end(&mut decoded)
+                    .unwrap();
+                (parts.uri.path().to_owned(), decoded)
+            })
+            .collect::<Vec<_>>()
+            .await;
+        requests.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(requests.len(), 2);
+
+        assert_eq!(requests[0].0, LOGS_PATH);
+        let logs = ExportLogsServiceRequest::decode(&requests[0].1[..]).unwrap();
+        assert_eq!(logs.resource_logs.len(), 1);
+        assert_eq!(logs.resource_logs[0].scope_logs[0].log_records.len(), 2);
+
+        assert_eq!(requests[1].0, METRICS_PATH);
+        let metrics = ExportMetricsServiceRequest::decode(&requests[1].1[..]).unwrap();
+        assert_eq!(
+            metrics.resource_metrics[0].scope_metrics[0].metrics[0].name,
+            "requests"
+        );
+    }
+}
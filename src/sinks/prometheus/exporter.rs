@@ -337,7 +337,8 @@ impl StreamSink for PrometheusExporter {
 mod tests {
     use super::*;
     use crate::{
-        event::metric::{Metric, MetricData, MetricSeries, MetricValue},
+        config::ProxyConfig,
+        event::metric::{Metric, MetricValue},
         http::HttpClient,
         test_util::{random_string, trace_init},
         tls::MaybeTlsSettings,
@@ -378,7 +379,7 @@ mod tests {
             .body(Body::empty())
             .expect("Error creating request.");
         let settings = MaybeTlsSettings::from_config(&Some(tls_config), false).unwrap();
-        let result = HttpClient::new(settings)
+        let result = HttpClient::new(settings, &ProxyConfig::default())
             .unwrap()
             .send(request)
             .await
@@ -434,40 +435,22 @@ mod tests {
                 .collect(),
         ));
 
-        let m2 = Metric {
-            series: MetricSeries {
-                tags: Some(
-                    vec![("tag1".to_owned(), "value2".to_owned())]
-                        .into_iter()
-                        .collect(),
-                ),
-                ..m1.series.clone()
-            },
-            data: m1.data.clone(),
+        let m2 = m1.clone().with_tags(Some(
+            vec![("tag1".to_owned(), "value2".to_owned())]
+                .into_iter()
+                .collect(),
+        ));
+
+        let with_value = |metric: &Metric, value| {
+            let mut metric = metric.clone();
+            metric.data.value = MetricValue::Counter { value };
+            Event::Metric(metric)
         };
 
         let metrics = vec![
-            Event::Metric(Metric {
-                series: m1.series.clone(),
-                data: MetricData {
-                    value: MetricValue::Counter { value: 32. },
-                    ..m1.data.clone()
-                },
-            }),
-            Event::Metric(Metric {
-                series: m2.series.clone(),
-                data: MetricData {
-                    value: MetricValue::Counter { value: 33. },
-                    ..m2.data.clone()
-                },
-            }),
-            Event::Metric(Metric {
-                series: m1.series.clone(),
-                data: MetricData {
-                    value: MetricValue::Counter { value: 40. },
-                    ..m1.data.clone()
-                },
-            }),
+            with_value(&m1, 32.),
+            with_value(&m2, 33.),
+            with_value(&m1, 40.),
         ];
 
         sink.run(Box::pin(futures::stream::iter(metrics)))
@@ -491,7 +474,7 @@ mod tests {
 #[cfg(all(test, feature = "prometheus-integration-tests"))]
 mod integration_tests {
     use super::*;
-    use crate::{http::HttpClient, test_util::trace_init};
+    use crate::{config::ProxyConfig, http::HttpClient, test_util::trace_init};
     use chrono::Utc;
     use serde_json::Value;
     use tokio::{sync::mpsc, time};
@@ -600,7 +583,7 @@ mod integration_tests {
         let request = Request::post(url)
             .body(Body::empty())
             .expect("Error creating request.");
-        let result = HttpClient::new(None)
+        let result = HttpClient::new(None, &ProxyConfig::default())
             .unwrap()
             .send(request)
             .await
@@ -1,6 +1,7 @@
 use crate::{
-    buffers::Acker,
-    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    buffers::{Acker, Rejection},
+    codecs::{Encoder, Encoding, FramingConfig},
+    config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::Event,
     internal_events::PulsarEncodeEventFailed,
     sinks::util::encoding::{EncodingConfig, EncodingConfiguration},
@@ -31,6 +32,8 @@ pub struct PulsarSinkConfig {
     endpoint: String,
     topic: String,
     encoding: EncodingConfig<Encoding>,
+    #[serde(default)]
+    framing: FramingConfig,
     auth: Option<AuthConfig>,
 }
 
@@ -40,14 +43,6 @@ pub struct AuthConfig {
     token: String, // <jwt token>
 }
 
-#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
-#[serde(rename_all = "snake_case")]
-pub enum Encoding {
-    Text,
-    Json,
-    Avro,
-}
-
 type PulsarProducer = Producer<TokioExecutor>;
 type BoxedPulsarProducer = Box<PulsarProducer>;
 
@@ -58,8 +53,7 @@ enum PulsarSinkState {
 }
 
 struct PulsarSink {
-    encoding: EncodingConfig<Encoding>,
-    avro_schema: Option<avro_rs::Schema>,
+    encoder: Encoder,
     state: PulsarSinkState,
     in_flight:
         FuturesUnordered<BoxFuture<'static, (usize, Result<CommandSendReceipt, PulsarError>)>>,
@@ -80,6 +74,7 @@ impl GenerateConfig for PulsarSinkConfig {
             endpoint: "pulsar://127.0.0.1:6650".to_string(),
             topic: "topic-1234".to_string(),
             encoding: Encoding::Text.into(),
+            framing: FramingConfig::Bytes,
             auth: None,
         })
         .unwrap()
@@ -93,11 +88,12 @@ impl SinkConfig for PulsarSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let encoder = Encoder::new(self.encoding.clone(), &self.framing)?;
         let producer = self
             .create_pulsar_producer()
             .await
             .context(CreatePulsarSink)?;
-        let sink = PulsarSink::new(producer, self.encoding.clone(), cx.acker())?;
+        let sink = PulsarSink::new(producer, encoder, cx.acker());
 
         let producer = self
             .create_pulsar_producer()
@@ -154,35 +150,16 @@ async fn healthcheck(producer: PulsarProducer) -> crate::Result<()> {
 }
 
 impl PulsarSink {
-    fn new(
-        producer: PulsarProducer,
-        encoding: EncodingConfig<Encoding>,
-        acker: Acker,
-    ) -> crate::Result<Self> {
-        let schema = match &encoding.codec() {
-            Encoding::Avro => {
-                if let Some(schema) = &encoding.schema() {
-                    avro_rs::Schema::parse_str(schema).ok()
-                } else {
-                    return Err(
-                        "Avro requires a schema, specify a schema file with `encoding.schema`."
-                            .into(),
-                    );
-                }
-            }
-            _ => None,
-        };
-
-        Ok(Self {
-            encoding,
-            avro_schema: schema,
+    fn new(producer: PulsarProducer, encoder: Encoder, acker: Acker) -> Self {
+        Self {
+            encoder,
             state: PulsarSinkState::Ready(Box::new(producer)),
             in_flight: FuturesUnordered::new(),
             acker,
             seq_head: 0,
             seq_tail: 0,
             pending_acks: HashSet::new(),
-        })
+        }
     }
 
     fn poll_in_flight_prepare(&mut self, cx: &mut Context<'_>) -> Poll<()> {
@@ -220,11 +197,15 @@ impl Sink<Event> for PulsarSink {
             "Expected `poll_ready` to be called first."
         );
 
-        let message = encode_event(item, &self.encoding, &self.avro_schema).map_err(|e| {
-            emit!(PulsarEncodeEventFailed {
-                error: &*e.to_string()
-            })
-        })?;
+        let message = match self.encoder.try_encode(item) {
+            Ok(message) => message,
+            Err(error) => {
+                let error = error.to_string();
+                emit!(PulsarEncodeEventFailed { error: &*error });
+                self.acker.reject_last(Rejection::new(error, 0));
+                return Ok(());
+            }
+        };
 
         let mut producer = match std::mem::replace(&mut self.state, PulsarSinkState::None) {
             PulsarSinkState::Ready(producer) => producer,
@@ -281,39 +262,17 @@ impl Sink<Event> for PulsarSink {
     }
 }
 
-fn encode_event(
-    mut item: Event,
-    encoding: &EncodingConfig<Encoding>,
-    avro_schema: &Option<avro_rs::Schema>,
-) -> crate::Result<Vec<u8>> {
-    encoding.apply_rules(&mut item);
-    let log = item.into_log();
-
-    Ok(match encoding.codec() {
-        Encoding::Json => serde_json::to_vec(&log)?,
-        Encoding::Text => log
-            .get(log_schema().message_key())
-            .map(|v| v.as_bytes().to_vec())
-            .unwrap_or_default(),
-        Encoding::Avro => {
-            let value = avro_rs::to_value(log)?;
-            let resolved_value =
-                avro_rs::types::Value::resolve(value, avro_schema.as_ref().unwrap())?;
-            avro_rs::to_avro_datum(
-                &avro_schema
-                    .as_ref()
-                    .expect("Avro encoding selected but no schema found. Please report this."),
-                resolved_value,
-            )?
-        }
-    })
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::log_schema;
     use std::collections::HashMap;
 
+    fn encode_event(event: Event, encoding: EncodingConfig<Encoding>) -> Vec<u8> {
+        let encoder = Encoder::new(encoding, &FramingConfig::Bytes).unwrap();
+        encoder.try_encode(event).unwrap()
+    }
+
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<PulsarSinkConfig>();
@@ -324,7 +283,7 @@ mod tests {
         let msg = "hello_world".to_owned();
         let mut evt = Event::from(msg.clone());
         evt.as_mut_log().insert("key", "value");
-        let result = encode_event(evt, &EncodingConfig::from(Encoding::Json), &None).unwrap();
+        let result = encode_event(evt, EncodingConfig::from(Encoding::Json));
         let map: HashMap<String, String> = serde_json::from_slice(&result[..]).unwrap();
         assert_eq!(msg, map[&log_schema().message_key().to_string()]);
     }
@@ -333,7 +292,7 @@ mod tests {
     fn pulsar_event_text() {
         let msg = "hello_world".to_owned();
         let evt = Event::from(msg.clone());
-        let event = encode_event(evt, &EncodingConfig::from(Encoding::Text), &None).unwrap();
+        let event = encode_event(evt, EncodingConfig::from(Encoding::Text));
 
         assert_eq!(&event[..], msg.as_bytes());
     }
@@ -356,7 +315,7 @@ mod tests {
         let mut encoding = EncodingConfig::from(Encoding::Avro);
         encoding.schema = Some(raw_schema.to_string());
         let schema = avro_rs::Schema::parse_str(&raw_schema).unwrap();
-        let result = encode_event(evt.clone(), &encoding, &Some(schema.clone())).unwrap();
+        let result = encode_event(evt.clone(), encoding);
 
         let value = avro_rs::to_value(evt.into_log()).unwrap();
         let resolved_value = avro_rs::types::Value::resolve(value, &schema).unwrap();
@@ -374,16 +333,14 @@ mod tests {
 
         let event = encode_event(
             evt,
-            &EncodingConfig {
+            EncodingConfig {
                 codec: Encoding::Json,
                 schema: None,
                 only_fields: None,
                 except_fields: Some(vec!["key".into()]),
                 timestamp_format: None,
             },
-            &None,
-        )
-        .unwrap();
+        );
 
         let map: HashMap<String, String> = serde_json::from_slice(&event[..]).unwrap();
         assert!(!map.contains_key("key"));
@@ -410,6 +367,7 @@ mod integration_tests {
             endpoint: "pulsar://127.0.0.1:6650".to_owned(),
             topic: topic.clone(),
             encoding: Encoding::Text.into(),
+            framing: FramingConfig::Bytes,
             auth: None,
         };
 
@@ -429,7 +387,8 @@ mod integration_tests {
 
         let (acker, ack_counter) = Acker::new_for_testing();
         let producer = cnf.create_pulsar_producer().await.unwrap();
-        let sink = PulsarSink::new(producer, cnf.encoding, acker).unwrap();
+        let encoder = Encoder::new(cnf.encoding, &cnf.framing).unwrap();
+        let sink = PulsarSink::new(producer, encoder, acker);
         events.map(Ok).forward(sink).await.unwrap();
 
         assert_eq!(
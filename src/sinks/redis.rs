@@ -0,0 +1,444 @@
+use crate::{
+    buffers::Rejection,
+    codecs::{Encoder, Encoding, FramingConfig},
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::Event,
+    internal_events::{RedisEventMissingKeys, RedisEventSent},
+    sinks::util::{
+        encoding::EncodingConfig, retries::RetryLogic, BatchConfig, BatchSettings, PartitionBuffer,
+        PartitionInnerBuffer, TowerRequestConfig, VecBuffer,
+    },
+    template::{Template, TemplateError},
+};
+use bytes::Bytes;
+use futures::{future::BoxFuture, stream, FutureExt, SinkExt, StreamExt};
+use lazy_static::lazy_static;
+use redis::{aio::ConnectionManager, ErrorKind, RedisError};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{
+    convert::TryFrom,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::sync::Mutex;
+use tower::Service;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid key template: {}", source))]
+    KeyTemplate { source: TemplateError },
+    #[snafu(display("Invalid Redis endpoint: {}", source))]
+    Endpoint { source: RedisError },
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisDataType {
+    /// Pushes to a list.
+    #[derivative(Default)]
+    List,
+    /// Publishes to a Pub/Sub channel.
+    Channel,
+    /// Appends to a stream.
+    Stream,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ListMethod {
+    LPush,
+    #[derivative(Default)]
+    RPush,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct ListOptions {
+    #[serde(default)]
+    method: ListMethod,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RedisSinkConfig {
+    endpoint: String,
+    key: String,
+    #[serde(default)]
+    data_type: RedisDataType,
+    #[serde(default)]
+    list: ListOptions,
+    encoding: EncodingConfig<Encoding>,
+    #[serde(default)]
+    framing: FramingConfig,
+    #[serde(default)]
+    batch: BatchConfig,
+    #[serde(default)]
+    request: TowerRequestConfig,
+}
+
+lazy_static! {
+    static ref REQUEST_DEFAULTS: TowerRequestConfig = TowerRequestConfig {
+        rate_limit_num: Some(u64::max_value()),
+        ..Default::default()
+    };
+}
+
+inventory::submit! {
+    SinkDescription::new::<RedisSinkConfig>("redis")
+}
+
+impl GenerateConfig for RedisSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            encoding.codec = "json"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redis")]
+impl SinkConfig for RedisSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let connection = RedisConnection::new(self.client()?);
+        let healthcheck = healthcheck(connection.clone()).boxed();
+        let sink = self.new_sink(connection, cx)?;
+        Ok((sink, healthcheck))
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "redis"
+    }
+}
+
+impl RedisSinkConfig {
+    fn client(&self) -> crate::Result<redis::Client> {
+        Ok(redis::Client::open(self.endpoint.as_str()).context(Endpoint)?)
+    }
+
+    fn new_sink(
+        &self,
+        connection: RedisConnection,
+        cx: SinkContext,
+    ) -> crate::Result<super::VectorSink> {
+        let key = Template::try_from(self.key.as_str()).context(KeyTemplate)?;
+        let mut encoder = Encoder::new(self.encoding.clone(), &self.framing)?;
+        let batch = BatchSettings::default()
+            .events(100)
+            .bytes(1_000_000)
+            .timeout(1)
+            .parse_config(self.batch)?;
+        let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
+
+        let service = RedisService {
+            connection,
+            data_type: self.data_type,
+            method: self.list.method,
+        };
+
+        let acker = cx.acker();
+        let sink = request
+            .partition_sink(
+                RedisRetryLogic,
+                service,
+                PartitionBuffer::new(VecBuffer::new(batch.size)),
+                batch.timeout,
+                acker.clone(),
+            )
+            .sink_map_err(|error| error!(message = "Fatal redis sink error.", %error))
+            .with_flat_map(move |event| {
+                let encoded = encode_event(event, &key, &mut encoder);
+                if let Err(reason) = encoded {
+                    acker.reject_last(Rejection::new(reason, 0));
+                }
+                stream::iter(encoded.ok()).map(Ok)
+            });
+
+        Ok(super::VectorSink::Sink(Box::new(sink)))
+    }
+}
+
+/// Connects on first use, so an unreachable server is reported by the
+/// healthcheck and retried by requests instead of failing the build.
+#[derive(Clone)]
+struct RedisConnection {
+    client: redis::Client,
+    manager: Arc<Mutex<Option<ConnectionManager>>>,
+}
+
+impl RedisConnection {
+    fn new(client: redis::Client) -> Self {
+        Self {
+            client,
+            manager: Arc::default(),
+        }
+    }
+
+    async fn get(&self) -> Result<ConnectionManager, RedisError> {
+        let mut manager = self.manager.lock().await;
+        match &*manager {
+            Some(connection) => Ok(connection.clone()),
+            None => {
+                // Reconnects on its own when the connection is lost.
+                let connection = ConnectionManager::new(self.client.clone()).await?;
+                *manager = Some(connection.clone());
+                Ok(connection)
+            }
+        }
+    }
+}
+
+async fn healthcheck(connection: RedisConnection) -> crate::Result<()> {
+    let mut connection = connection.get().await?;
+    redis::cmd("PING")
+        .query_async::<_, ()>(&mut connection)
+        .await
+        .map_err(Into::into)
+}
+
+/// Encodes an event along with its key, or returns why it was dropped.
+fn encode_event(
+    event: Event,
+    key: &Template,
+    encoder: &mut Encoder,
+) -> Result<PartitionInnerBuffer<Bytes, String>, &'static str> {
+    let key = key.render_string(&event).map_err(|missing_keys| {
+        emit!(RedisEventMissingKeys {
+            keys: &missing_keys
+        });
+        "Key could not be rendered."
+    })?;
+    let message = encoder.encode(event).ok_or("Event could not be encoded.")?;
+    Ok(PartitionInnerBuffer::new(Bytes::from(message), key))
+}
+
+#[derive(Clone)]
+struct RedisService {
+    connection: RedisConnection,
+    data_type: RedisDataType,
+    method: ListMethod,
+}
+
+impl RedisService {
+    /// Builds the commands sending a batch of messages to `key`. Lists get
+    /// all of them at once, while channels and streams take one at a time.
+    fn commands(&self, key: &str, messages: &[Bytes]) -> redis::Pipeline {
+        let mut pipe = redis::pipe();
+        match self.data_type {
+            RedisDataType::List => {
+                let method = match self.method {
+                    ListMethod::LPush => "LPUSH",
+                    ListMethod::RPush => "RPUSH",
+                };
+                let command = pipe.cmd(method).arg(key);
+                for message in messages {
+                    command.arg(message.as_ref());
+                }
+            }
+            RedisDataType::Channel => {
+                for message in messages {
+                    pipe.cmd("PUBLISH").arg(key).arg(message.as_ref());
+                }
+            }
+            RedisDataType::Stream => {
+                for message in messages {
+                    pipe.cmd("XADD")
+                        .arg(key)
+                        .arg("*")
+                        .arg(log_schema().message_key())
+                        .arg(message.as_ref());
+                }
+            }
+        }
+        pipe
+    }
+}
+
+impl Service<PartitionInnerBuffer<Vec<Bytes>, String>> for RedisService {
+    type Response = ();
+    type Error = RedisError;
+    type Future = BoxFuture<'static, Result<(), RedisError>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: PartitionInnerBuffer<Vec<Bytes>, String>) -> Self::Future {
+        let (messages, key) = request.into_parts();
+        let pipe = self.commands(&key, &messages);
+        let connection = self.connection.clone();
+        Box::pin(async move {
+            let mut connection = connection.get().await?;
+            pipe.query_async(&mut connection).await?;
+            for message in &messages {
+                emit!(RedisEventSent {
+                    byte_size: message.len()
+                });
+            }
+            Ok(())
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RedisRetryLogic;
+
+impl RetryLogic for RedisRetryLogic {
+    type Error = RedisError;
+    type Response = ();
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        error.is_io_error()
+            || error.is_timeout()
+            || matches!(
+                error.kind(),
+                ErrorKind::BusyLoadingError | ErrorKind::TryAgain
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisSinkConfig>();
+    }
+
+    #[test]
+    fn parses_list_options() {
+        let config: RedisSinkConfig = toml::from_str(
+            r#"
+            encoding.codec = "json"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            list.method = "lpush"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.data_type, RedisDataType::List);
+        assert_eq!(config.list.method, ListMethod::LPush);
+    }
+
+    #[tokio::test]
+    async fn builds_without_server() {
+        let config: RedisSinkConfig = toml::from_str(
+            r#"
+            encoding.codec = "json"
+            endpoint = "redis://127.0.0.1:9/0"
+            key = "vector"
+            "#,
+        )
+        .unwrap();
+        let (_sink, healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+        assert!(healthcheck.await.is_err());
+    }
+
+    #[test]
+    fn encodes_events_by_key() {
+        let key = Template::try_from("logs-{{ app }}").unwrap();
+        let mut encoder = Encoder::new(
+            EncodingConfig::from(Encoding::Text),
+            &FramingConfig::default(),
+        )
+        .unwrap();
+
+        let mut event = Event::from("hello");
+        event.as_mut_log().insert("app", "web");
+        let (message, key_value) = encode_event(event, &key, &mut encoder)
+            .unwrap()
+            .into_parts();
+        assert_eq!(message, Bytes::from("hello"));
+        assert_eq!(key_value, "logs-web");
+
+        assert!(encode_event(Event::from("hello"), &key, &mut encoder).is_err());
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{random_lines_with_stream, random_string, trace_init};
+
+    const REDIS_SERVER: &str = "redis://127.0.0.1:6379/0";
+
+    fn make_config(key: &str, data_type: &str) -> RedisSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            encoding.codec = "text"
+            endpoint = "{}"
+            key = "{}"
+            data_type = "{}"
+            "#,
+            REDIS_SERVER, key, data_type
+        ))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn redis_sink_list() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let config = make_config(&key, "list");
+        let connection = RedisConnection::new(config.client().unwrap());
+        healthcheck(connection.clone()).await.unwrap();
+        let mut connection = connection.get().await.unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (input, events) = random_lines_with_stream(100, 10);
+        sink.run(events).await.unwrap();
+
+        let output: Vec<String> = redis::cmd("LRANGE")
+            .arg(&key)
+            .arg(0)
+            .arg(-1)
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert_eq!(output, input);
+    }
+
+    #[tokio::test]
+    async fn redis_sink_stream() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let config = make_config(&key, "stream");
+        let mut connection = RedisConnection::new(config.client().unwrap())
+            .get()
+            .await
+            .unwrap();
+
+        let (sink, _) = config.build(SinkContext::new_test()).await.unwrap();
+        let (input, events) = random_lines_with_stream(100, 10);
+        sink.run(events).await.unwrap();
+
+        let reply: redis::streams::StreamRangeReply = redis::cmd("XRANGE")
+            .arg(&key)
+            .arg("-")
+            .arg("+")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        let output: Vec<String> = reply
+            .ids
+            .iter()
+            .map(|entry| entry.get(log_schema().message_key()).unwrap())
+            .collect();
+        assert_eq!(output, input);
+    }
+}
@@ -1,11 +1,15 @@
 #[cfg(unix)]
 use crate::sinks::util::unix::UnixSinkConfig;
 use crate::{
+    codecs::{Encoder, Encoding, FramingConfig},
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     sinks::util::{
-        encode_event, encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig, Encoding,
+        encoding::{EncodingConfig, EncodingConfiguration},
+        tcp::TcpSinkConfig,
+        udp::UdpSinkConfig,
     },
 };
+use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug)]
@@ -15,6 +19,7 @@ pub struct SocketSinkConfig {
     #[serde(flatten)]
     pub mode: Mode,
     pub encoding: EncodingConfig<Encoding>,
+    pub framing: Option<FramingConfig>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -43,7 +48,11 @@ impl GenerateConfig for SocketSinkConfig {
 
 impl SocketSinkConfig {
     pub fn new(mode: Mode, encoding: EncodingConfig<Encoding>) -> Self {
-        SocketSinkConfig { mode, encoding }
+        SocketSinkConfig {
+            mode,
+            encoding,
+            framing: None,
+        }
     }
 
     pub fn make_basic_tcp_config(address: String) -> Self {
@@ -61,8 +70,19 @@ impl SinkConfig for SocketSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let encoding = self.encoding.clone();
-        let encode_event = move |event| encode_event(event, &encoding);
+        let framing = match (&self.framing, *self.encoding.codec(), &self.mode) {
+            (Some(framing), _, _) => framing.clone(),
+            // GELF messages are sent as whole datagrams over UDP, and null
+            // delimited otherwise.
+            (None, Encoding::Gelf, Mode::Udp(_)) => FramingConfig::Bytes,
+            (None, Encoding::Gelf, _) => FramingConfig::CharacterDelimited {
+                delimiter: '\0',
+                max_length: None,
+            },
+            (None, _, _) => FramingConfig::NewlineDelimited { max_length: None },
+        };
+        let encoder = Encoder::new(self.encoding.clone(), &framing)?;
+        let encode_event = move |event| encoder.encode(event).map(Bytes::from);
         match &self.mode {
             Mode::Tcp(config) => config.build(cx, encode_event),
             Mode::Udp(config) => config.build(cx, encode_event),
@@ -111,6 +131,7 @@ mod test {
         let config = SocketSinkConfig {
             mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
             encoding: Encoding::Json.into(),
+            framing: None,
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -131,6 +152,64 @@ mod test {
         assert_eq!(message, &Value::String("raw log line".into()));
     }
 
+    #[tokio::test]
+    async fn udp_framing() {
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+
+        let config = SocketSinkConfig {
+            mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
+            encoding: Encoding::Logfmt.into(),
+            framing: Some(FramingConfig::OctetCounting { max_length: None }),
+        };
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let mut event = Event::new_empty_log();
+        event.as_mut_log().insert("message", "raw log line");
+        sink.run(stream::once(ready(event))).await.unwrap();
+
+        let mut buf = [0; 256];
+        let (size, _src_addr) = receiver
+            .recv_from(&mut buf)
+            .expect("Did not receive message");
+        assert_eq!(&buf[..size], b"22 message=\"raw log line\"");
+    }
+
+    #[tokio::test]
+    async fn udp_gelf() {
+        trace_init();
+
+        let addr = next_addr();
+        let receiver = UdpSocket::bind(addr).unwrap();
+
+        let config = SocketSinkConfig {
+            mode: Mode::Udp(UdpSinkConfig::from_address(addr.to_string())),
+            encoding: Encoding::Gelf.into(),
+            framing: None,
+        };
+        let context = SinkContext::new_test();
+        let (sink, _healthcheck) = config.build(context).await.unwrap();
+
+        let mut event = Event::from("raw log line");
+        event.as_mut_log().insert("host", "example.org");
+        event.as_mut_log().insert("user_id", 9001);
+        sink.run(stream::once(ready(event))).await.unwrap();
+
+        let mut buf = [0; 256];
+        let (size, _src_addr) = receiver
+            .recv_from(&mut buf)
+            .expect("Did not receive message");
+        let data = serde_json::from_slice::<Value>(&buf[..size]).expect("Invalid JSON received");
+        assert_eq!(data["version"], "1.1");
+        assert_eq!(data["host"], "example.org");
+        assert_eq!(data["short_message"], "raw log line");
+        assert_eq!(data["_user_id"], 9001);
+        assert!(data["timestamp"].is_f64());
+    }
+
     #[tokio::test]
     async fn udp_ipv4() {
         trace_init();
@@ -153,6 +232,7 @@ mod test {
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
             encoding: Encoding::Json.into(),
+            framing: None,
         };
 
         let context = SinkContext::new_test();
@@ -225,6 +305,7 @@ mod test {
                 None,
             )),
             encoding: Encoding::Text.into(),
+            framing: None,
         };
         let context = SinkContext::new_test();
         let (sink, _healthcheck) = config.build(context).await.unwrap();
@@ -332,6 +413,7 @@ mod test {
         let config = SocketSinkConfig {
             mode: Mode::Tcp(TcpSinkConfig::from_address(addr.to_string())),
             encoding: Encoding::Text.into(),
+            framing: None,
         };
 
         let context = SinkContext::new_test();
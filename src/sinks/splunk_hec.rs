@@ -108,7 +108,7 @@ impl SinkConfig for HecSinkConfig {
             .parse_config(self.batch)?;
         let request = self.request.unwrap_with(&REQUEST_DEFAULTS);
         let tls_settings = TlsSettings::from_options(&self.tls)?;
-        let client = HttpClient::new(tls_settings)?;
+        let client = HttpClient::new(tls_settings, cx.proxy())?;
 
         let sink = BatchedHttpSink::new(
             self.clone(),
@@ -442,7 +442,7 @@ mod integration_tests {
     use crate::test_util::retry_until;
     use crate::{
         assert_downcast_matches,
-        config::{SinkConfig, SinkContext},
+        config::{ProxyConfig, SinkConfig, SinkContext},
         sinks,
         test_util::{random_lines_with_stream, random_string},
         Event,
@@ -673,7 +673,7 @@ mod integration_tests {
     async fn splunk_healthcheck() {
         let config_to_healthcheck = move |config: HecSinkConfig| {
             let tls_settings = TlsSettings::from_options(&config.tls).unwrap();
-            let client = HttpClient::new(tls_settings).unwrap();
+            let client = HttpClient::new(tls_settings, &ProxyConfig::default()).unwrap();
             sinks::splunk_hec::healthcheck(config, client)
         };
 
@@ -0,0 +1,524 @@
+//! Sends events as RFC 5424 or RFC 3164 syslog messages, whose body is the
+//! encoded event and whose header fields are rendered from templates.
+
+#[cfg(unix)]
+use crate::sinks::util::unix::UnixSinkConfig;
+use crate::{
+    codecs::{Encoder, Encoding, EncodingError, FrameEncoder, FramingConfig},
+    config::{log_schema, DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
+    event::{Event, Value},
+    internal_events::{EncoderEncodeFailed, SyslogEventMissingKeys, SyslogInvalidPriority},
+    sinks::util::{encoding::EncodingConfig, tcp::TcpSinkConfig, udp::UdpSinkConfig},
+    template::{Template, TemplateError},
+};
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+const FACILITIES: [&str; 24] = [
+    "kern",
+    "user",
+    "mail",
+    "daemon",
+    "auth",
+    "syslog",
+    "lpr",
+    "news",
+    "uucp",
+    "cron",
+    "authpriv",
+    "ftp",
+    "ntp",
+    "security",
+    "console",
+    "solaris-cron",
+    "local0",
+    "local1",
+    "local2",
+    "local3",
+    "local4",
+    "local5",
+    "local6",
+    "local7",
+];
+
+const SEVERITIES: [&str; 8] = [
+    "emerg", "alert", "crit", "err", "warning", "notice", "info", "debug",
+];
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("invalid {} template: {}", field, source))]
+    InvalidTemplate {
+        field: &'static str,
+        source: TemplateError,
+    },
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+// TODO: add back when serde-rs/serde#1358 is addressed
+// #[serde(deny_unknown_fields)]
+pub struct SyslogSinkConfig {
+    #[serde(flatten)]
+    mode: Mode,
+    #[serde(default)]
+    format: SyslogFormat,
+    #[serde(default = "default_facility")]
+    facility: String,
+    #[serde(default = "default_severity")]
+    severity: String,
+    hostname: Option<String>,
+    #[serde(default = "default_app_name")]
+    app_name: String,
+    procid: Option<String>,
+    msgid: Option<String>,
+    #[serde(default)]
+    structured_data: BTreeMap<String, BTreeMap<String, String>>,
+    encoding: EncodingConfig<Encoding>,
+    framing: Option<FramingConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Mode {
+    Tcp(TcpSinkConfig),
+    Udp(UdpSinkConfig),
+    #[cfg(unix)]
+    Unix(UnixSinkConfig),
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SyslogFormat {
+    #[derivative(Default)]
+    Rfc5424,
+    Rfc3164,
+}
+
+fn default_facility() -> String {
+    "user".into()
+}
+
+fn default_severity() -> String {
+    "info".into()
+}
+
+fn default_app_name() -> String {
+    "vector".into()
+}
+
+inventory::submit! {
+    SinkDescription::new::<SyslogSinkConfig>("syslog")
+}
+
+impl GenerateConfig for SyslogSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"address = "127.0.0.1:514"
+            mode = "tcp"
+            encoding.codec = "text""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "syslog")]
+impl SinkConfig for SyslogSinkConfig {
+    async fn build(
+        &self,
+        cx: SinkContext,
+    ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let framing = match (&self.framing, &self.mode) {
+            (Some(framing), _) => framing.clone(),
+            // Each datagram holds a single message, as in RFC 5426.
+            (None, Mode::Udp(_)) => FramingConfig::Bytes,
+            (None, _) => FramingConfig::OctetCounting { max_length: None },
+        };
+        let framer = framing.build_encoder()?;
+        let encoder = Encoder::new(self.encoding.clone(), &FramingConfig::Bytes)?;
+        let formatter = SyslogFormatter::new(self)?;
+
+        let encode_event = move |event| encode_event(event, &formatter, &encoder, &framer);
+        match &self.mode {
+            Mode::Tcp(config) => config.build(cx, encode_event),
+            Mode::Udp(config) => config.build(cx, encode_event),
+            #[cfg(unix)]
+            Mode::Unix(config) => config.build(cx, encode_event),
+        }
+    }
+
+    fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn sink_type(&self) -> &'static str {
+        "syslog"
+    }
+}
+
+fn encode_event(
+    event: Event,
+    formatter: &SyslogFormatter,
+    encoder: &Encoder,
+    framer: &FrameEncoder,
+) -> Option<Bytes> {
+    let header = formatter
+        .header(&event)
+        .map_err(|missing_keys| {
+            emit!(SyslogEventMissingKeys {
+                keys: &missing_keys
+            });
+        })
+        .ok()?;
+
+    let mut message = header.into_bytes();
+    message.append(&mut encoder.encode(event)?);
+    framer
+        .frame(message)
+        .map(Bytes::from)
+        .map_err(|source| {
+            emit!(EncoderEncodeFailed {
+                error: &EncodingError::Framing { source }
+            })
+        })
+        .ok()
+}
+
+/// Renders the syslog header of events, which is followed by their encoded
+/// body.
+struct SyslogFormatter {
+    format: SyslogFormat,
+    facility: Template,
+    severity: Template,
+    hostname: Option<Template>,
+    app_name: Template,
+    procid: Option<Template>,
+    msgid: Option<Template>,
+    structured_data: Vec<(String, Vec<(String, Template)>)>,
+    pid: u32,
+}
+
+impl SyslogFormatter {
+    fn new(config: &SyslogSinkConfig) -> crate::Result<Self> {
+        let template = |field: &'static str, template: &str| {
+            Template::try_from(template).context(InvalidTemplate { field })
+        };
+        let optional_template = |field: &'static str, template: &Option<String>| {
+            template
+                .as_deref()
+                .map(|template| Template::try_from(template).context(InvalidTemplate { field }))
+                .transpose()
+        };
+
+        let structured_data = config
+            .structured_data
+            .iter()
+            .map(|(id, params)| {
+                let params = params
+                    .iter()
+                    .map(|(name, value)| Ok((sd_name(name), template("structured_data", value)?)))
+                    .collect::<Result<Vec<_>, BuildError>>()?;
+                Ok((sd_name(id), params))
+            })
+            .collect::<Result<_, BuildError>>()?;
+
+        Ok(Self {
+            format: config.format,
+            facility: template("facility", &config.facility)?,
+            severity: template("severity", &config.severity)?,
+            hostname: optional_template("hostname", &config.hostname)?,
+            app_name: template("app_name", &config.app_name)?,
+            procid: optional_template("procid", &config.procid)?,
+            msgid: optional_template("msgid", &config.msgid)?,
+            structured_data,
+            pid: std::process::id(),
+        })
+    }
+
+    /// Renders the header of the message, including the space separating it
+    /// from the body.
+    fn header(&self, event: &Event) -> Result<String, Vec<String>> {
+        let log = event.as_log();
+
+        let facility = self.facility.render_string(event)?;
+        let facility = parse_priority(&facility, &FACILITIES).unwrap_or_else(|| {
+            emit!(SyslogInvalidPriority {
+                field: "facility",
+                value: &facility
+            });
+            1
+        });
+        let severity = self.severity.render_string(event)?;
+        let severity = parse_priority(&severity, &SEVERITIES).unwrap_or_else(|| {
+            emit!(SyslogInvalidPriority {
+                field: "severity",
+                value: &severity
+            });
+            6
+        });
+        let priority = facility * 8 + severity;
+
+        let timestamp = match log.get(log_schema().timestamp_key()) {
+            Some(Value::Timestamp(timestamp)) => *timestamp,
+            _ => Utc::now(),
+        };
+        let hostname = match &self.hostname {
+            Some(hostname) => hostname.render_string(event)?,
+            None => log
+                .get(log_schema().host_key())
+                .map(|host| host.to_string_lossy())
+                .unwrap_or_default(),
+        };
+        let app_name = self.app_name.render_string(event)?;
+        let procid = match &self.procid {
+            Some(procid) => procid.render_string(event)?,
+            None => self.pid.to_string(),
+        };
+
+        Ok(match self.format {
+            SyslogFormat::Rfc5424 => {
+                let msgid = match &self.msgid {
+                    Some(msgid) => msgid.render_string(event)?,
+                    None => String::new(),
+                };
+                format!(
+                    "<{}>1 {} {} {} {} {} {} ",
+                    priority,
+                    timestamp.to_rfc3339_opts(SecondsFormat::Micros, true),
+                    header_field(&hostname, 255),
+                    header_field(&app_name, 48),
+                    header_field(&procid, 128),
+                    header_field(&msgid, 32),
+                    self.structured_data(event)?,
+                )
+            }
+            SyslogFormat::Rfc3164 => format!(
+                "<{}>{} {} {}: ",
+                priority,
+                rfc3164_timestamp(timestamp),
+                header_field(&hostname, 255),
+                rfc3164_tag(&app_name, &procid),
+            ),
+        })
+    }
+
+    fn structured_data(&self, event: &Event) -> Result<String, Vec<String>> {
+        if self.structured_data.is_empty() {
+            return Ok("-".into());
+        }
+
+        let mut structured_data = String::new();
+        for (id, params) in &self.structured_data {
+            structured_data.push('[');
+            structured_data.push_str(id);
+            for (name, value) in params {
+                let value = value.render_string(event)?;
+                structured_data.push_str(&format!(" {}=\"{}\"", name, escape_param(&value)));
+            }
+            structured_data.push(']');
+        }
+        Ok(structured_data)
+    }
+}
+
+/// Parses a facility or severity given by name or by code, such as `local0`
+/// or `16`.
+fn parse_priority(value: &str, names: &[&str]) -> Option<u32> {
+    let value = value.trim().to_lowercase();
+    let name = match value.as_str() {
+        "emergency" | "panic" => "emerg",
+        "critical" => "crit",
+        "error" => "err",
+        "warn" => "warning",
+        "informational" => "info",
+        name => name,
+    };
+    names
+        .iter()
+        .position(|candidate| *candidate == name)
+        .map(|code| code as u32)
+        .or_else(|| name.parse().ok().filter(|code| *code < names.len() as u32))
+}
+
+/// Makes a value fit in a header field, which is printable ASCII without
+/// spaces, and `-` when empty.
+fn header_field(value: &str, max_length: usize) -> String {
+    let field = value
+        .chars()
+        .map(|c| if c.is_ascii_graphic() { c } else { '_' })
+        .take(max_length)
+        .collect::<String>();
+    if field.is_empty() {
+        "-".into()
+    } else {
+        field
+    }
+}
+
+/// Makes a structured data ID or parameter name valid.
+fn sd_name(name: &str) -> String {
+    header_field(name, 32).replace(&['=', ']', '"'][..], "_")
+}
+
+fn escape_param(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace(']', "\\]")
+}
+
+fn rfc3164_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%b %e %H:%M:%S").to_string()
+}
+
+/// The tag, made of the application name and the process ID, which is left
+/// out when empty.
+fn rfc3164_tag(app_name: &str, procid: &str) -> String {
+    let app_name = header_field(app_name, 32);
+    if procid.is_empty() || procid == "-" {
+        app_name
+    } else {
+        format!("{}[{}]", app_name, header_field(procid, 128))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_util::{next_addr, trace_init, CountReceiver};
+    use chrono::TimeZone;
+    use futures::stream;
+    use std::future::ready;
+
+    fn config(extra: &str) -> SyslogSinkConfig {
+        toml::from_str(&format!(
+            r#"
+            address = "127.0.0.1:514"
+            mode = "tcp"
+            encoding.codec = "text"
+            {}
+            "#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    fn event() -> Event {
+        let mut event = Event::from("hello world");
+        let log = event.as_mut_log();
+        log.insert(log_schema().host_key(), "web-1");
+        log.insert(
+            log_schema().timestamp_key(),
+            Utc.ymd(2021, 3, 4).and_hms_milli(5, 6, 7, 890),
+        );
+        log.insert("app", "checkout");
+        log.insert("level", "error");
+        log.insert("request", "a\"b]");
+        event
+    }
+
+    fn encode(config: &SyslogSinkConfig, event: Event) -> Option<String> {
+        let formatter = SyslogFormatter::new(config).unwrap();
+        let encoder = Encoder::new(config.encoding.clone(), &FramingConfig::Bytes).unwrap();
+        let framer = config
+            .framing
+            .clone()
+            .unwrap_or(FramingConfig::Bytes)
+            .build_encoder()
+            .unwrap();
+        encode_event(event, &formatter, &encoder, &framer)
+            .map(|bytes| String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<SyslogSinkConfig>();
+    }
+
+    #[test]
+    fn encodes_rfc5424() {
+        let config = config(
+            r#"
+            facility = "local0"
+            severity = "{{ level }}"
+            app_name = "{{ app }}"
+            procid = "42"
+            msgid = "ID47"
+            structured_data.origin.request = "{{ request }}"
+            "#,
+        );
+        assert_eq!(
+            encode(&config, event()).unwrap(),
+            r#"<131>1 2021-03-04T05:06:07.890000Z web-1 checkout 42 ID47 [origin request="a\"b\]"] hello world"#
+        );
+    }
+
+    #[test]
+    fn encodes_rfc3164() {
+        let config = config(
+            r#"
+            format = "rfc3164"
+            facility = "16"
+            procid = "-"
+            framing.method = "newline_delimited"
+            "#,
+        );
+        assert_eq!(
+            encode(&config, event()).unwrap(),
+            "<134>Mar  4 05:06:07 web-1 vector: hello world\n"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_default_priority() {
+        let config = config(
+            r#"
+            facility = "nope"
+            severity = "9"
+            hostname = "my host"
+            msgid = ""
+            "#,
+        );
+        let message = encode(&config, event()).unwrap();
+        assert!(message.starts_with("<14>1 2021-03-04T05:06:07.890000Z my_host vector "));
+        assert!(message.ends_with(" - - hello world"));
+    }
+
+    #[test]
+    fn drops_events_missing_keys() {
+        let config = config(r#"app_name = "{{ missing }}""#);
+        assert!(encode(&config, event()).is_none());
+    }
+
+    #[tokio::test]
+    async fn tcp_newline_delimited() {
+        trace_init();
+
+        let addr = next_addr();
+        let config: SyslogSinkConfig = toml::from_str(&format!(
+            r#"
+            address = "{}"
+            mode = "tcp"
+            encoding.codec = "text"
+            procid = "1"
+            framing.method = "newline_delimited"
+            "#,
+            addr
+        ))
+        .unwrap();
+        let mut receiver = CountReceiver::receive_lines(addr);
+        let (sink, _healthcheck) = config.build(SinkContext::new_test()).await.unwrap();
+
+        sink.run(stream::once(ready(event()))).await.unwrap();
+        receiver.connected().await;
+        let lines = receiver.await;
+        assert_eq!(
+            lines,
+            vec!["<14>1 2021-03-04T05:06:07.890000Z web-1 vector 1 - - hello world"]
+        );
+    }
+}
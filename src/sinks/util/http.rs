@@ -3,7 +3,11 @@ use super::{
     sink, Batch, Partition, TowerBatchedSink, TowerPartitionSink, TowerRequestConfig,
     TowerRequestSettings,
 };
-use crate::{buffers::Acker, http::HttpClient, Event};
+use crate::{
+    buffers::{Acker, Rejection},
+    http::HttpClient,
+    Event,
+};
 use bytes::{Buf, Bytes};
 use futures::{future::BoxFuture, ready, Sink};
 use http::StatusCode;
@@ -63,6 +67,7 @@ where
     // the inner sink is applying back pressure. This trick is used in the `WithFlatMap`
     // sink combinator. https://docs.rs/futures/0.1.29/src/futures/sink/with_flat_map.rs.html#20
     slot: Option<B::Input>,
+    acker: Acker,
 }
 
 impl<T, B> BatchedHttpSink<T, B, HttpRetryLogic>
@@ -117,12 +122,13 @@ where
             };
 
         let svc = HttpBatchService::new(client, request_builder);
-        let inner = request_settings.batch_sink(logic, svc, batch, batch_timeout, acker);
+        let inner = request_settings.batch_sink(logic, svc, batch, batch_timeout, acker.clone());
 
         Self {
             sink,
             inner,
             slot: None,
+            acker,
         }
     }
 }
@@ -153,8 +159,11 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        if let Some(item) = self.sink.encode_event(item) {
-            *self.project().slot = Some(item);
+        match self.sink.encode_event(item) {
+            Some(item) => *self.project().slot = Some(item),
+            None => self
+                .acker
+                .reject_last(Rejection::new("Event could not be encoded.", 0)),
         }
 
         Ok(())
@@ -196,6 +205,7 @@ where
         B::Output,
     >,
     slot: Option<B::Input>,
+    acker: Acker,
 }
 
 impl<T, B, K> PartitionHttpSink<T, B, K, HttpRetryLogic>
@@ -254,12 +264,14 @@ where
             };
 
         let svc = HttpBatchService::new(client, request_builder);
-        let inner = request_settings.partition_sink(logic, svc, batch, batch_timeout, acker);
+        let inner =
+            request_settings.partition_sink(logic, svc, batch, batch_timeout, acker.clone());
 
         Self {
             sink,
             inner,
             slot: None,
+            acker,
         }
     }
 }
@@ -292,8 +304,11 @@ where
     }
 
     fn start_send(self: Pin<&mut Self>, item: Event) -> Result<(), Self::Error> {
-        if let Some(item) = self.sink.encode_event(item) {
-            *self.project().slot = Some(item);
+        match self.sink.encode_event(item) {
+            Some(item) => *self.project().slot = Some(item),
+            None => self
+                .acker
+                .reject_last(Rejection::new("Event could not be encoded.", 0)),
         }
 
         Ok(())
@@ -426,7 +441,7 @@ impl RequestConfig {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{sinks::util::service::Concurrency, test_util::next_addr};
+    use crate::{config::ProxyConfig, sinks::util::service::Concurrency, test_util::next_addr};
     use futures::{future::ready, StreamExt};
     use hyper::{
         service::{make_service_fn, service_fn},
@@ -461,7 +476,7 @@ mod test {
             .unwrap();
 
         let request = b"hello".to_vec();
-        let client = HttpClient::new(None).unwrap();
+        let client = HttpClient::new(None, &ProxyConfig::default()).unwrap();
         let mut service = HttpBatchService::new(client, move |body: Vec<u8>| {
             Box::pin(ready(
                 http::Request::post(&uri).body(body).map_err(Into::into),
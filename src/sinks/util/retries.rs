@@ -1,6 +1,7 @@
 use crate::Error;
 use futures::FutureExt;
 use std::{
+    cell::RefCell,
     cmp,
     future::Future,
     pin::Pin,
@@ -31,6 +32,43 @@ pub trait RetryLogic: Clone + Send + Sync + 'static {
     }
 }
 
+/// What the retry policy did with a request, for reporting why its events
+/// were rejected.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RetryOutcome {
+    /// How often the request was sent, if it went through a retry policy.
+    pub attempts: usize,
+    /// Why the policy gave up on the request, if it did.
+    pub reason: Option<String>,
+}
+
+tokio::task_local! {
+    static RETRY_OUTCOME: RefCell<RetryOutcome>;
+}
+
+impl RetryOutcome {
+    /// Drives `future`, collecting what the retry policies polled by it did.
+    pub async fn track<F: Future>(future: F) -> (F::Output, Self) {
+        RETRY_OUTCOME
+            .scope(RefCell::new(Self::default()), async move {
+                let output = future.await;
+                let outcome = RETRY_OUTCOME.with(|outcome| outcome.replace(Self::default()));
+                (output, outcome)
+            })
+            .await
+    }
+
+    fn record(update: impl FnOnce(&mut Self)) {
+        // Requests sent outside of `track` aren't interested in the outcome.
+        let _ = RETRY_OUTCOME.try_with(|outcome| update(&mut outcome.borrow_mut()));
+    }
+
+    fn give_up(reason: impl Into<String>) {
+        let reason = reason.into();
+        Self::record(|outcome| outcome.reason = Some(reason));
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct FixedRetryPolicy<L> {
     remaining_attempts: usize,
@@ -94,10 +132,13 @@ where
     type Future = RetryPolicyFuture<L>;
 
     fn retry(&self, _: &Req, result: Result<&Res, &Error>) -> Option<Self::Future> {
+        RetryOutcome::record(|outcome| outcome.attempts += 1);
+
         match result {
             Ok(response) => {
                 if self.remaining_attempts == 0 {
                     error!("Retries exhausted; dropping the request.");
+                    RetryOutcome::give_up("Retries exhausted.");
                     return None;
                 }
 
@@ -109,6 +150,7 @@ where
 
                     RetryAction::DontRetry(reason) => {
                         error!(message = "Not retriable; dropping the request.", reason = ?reason);
+                        RetryOutcome::give_up(reason);
                         None
                     }
 
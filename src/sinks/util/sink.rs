@@ -34,8 +34,13 @@
 use super::{
     batch::{Batch, PushResult, StatefulBatch},
     buffer::partition::Partition,
+    retries::RetryOutcome,
+};
+use crate::{
+    buffers::{Acker, Rejection},
+    event::EventStatus,
+    Event,
 };
-use crate::{buffers::Acker, Event};
 use async_trait::async_trait;
 use futures::{
     future::BoxFuture,
@@ -446,13 +451,16 @@ where
 
 // === ServiceSink ===
 
+/// How a request ended, the rejection is set for permanently failed ones.
+type RequestStatus = (EventStatus, Option<Rejection>);
+
 struct ServiceSink<S, Request> {
     service: S,
-    in_flight: FuturesUnordered<oneshot::Receiver<(usize, usize)>>,
+    in_flight: FuturesUnordered<oneshot::Receiver<(usize, usize, RequestStatus)>>,
     acker: Acker,
     seq_head: usize,
     seq_tail: usize,
-    pending_acks: HashMap<usize, usize>,
+    pending_acks: HashMap<usize, (usize, RequestStatus)>,
     next_request_id: usize,
     _pd: PhantomData<Request>,
 }
@@ -496,26 +504,31 @@ where
             message = "Submitting service request.",
             in_flight_requests = self.in_flight.len()
         );
-        self.service
-            .call(req)
-            .err_into()
-            .map(move |result| {
-                match result {
+        RetryOutcome::track(self.service.call(req).err_into())
+            .map(move |(result, outcome)| {
+                let status = match result {
                     Ok(response) if response.is_successful() => {
                         trace!(message = "Response successful.", ?response);
+                        (EventStatus::Delivered, None)
                     }
                     Ok(response) => {
                         error!(message = "Response wasn't successful.", ?response);
+                        let error = outcome.reason.unwrap_or_else(|| {
+                            format!("Response wasn't successful: {:?}", response)
+                        });
+                        let rejection = Rejection::new(error, outcome.attempts.max(1));
+                        (EventStatus::Failed, Some(rejection))
                     }
                     Err(error) => {
                         error!(message = "Request failed.", %error);
+                        (EventStatus::Errored, None)
                     }
-                }
+                };
 
                 // If the rx end is dropped we still completed
                 // the request so this is a weird case that we can
                 // ignore for now.
-                let _ = tx.send((seqno, batch_size));
+                let _ = tx.send((seqno, batch_size, status));
             })
             .instrument(info_span!("request", %request_id))
             .boxed()
@@ -524,16 +537,17 @@ where
     fn poll_complete(&mut self, cx: &mut Context<'_>) -> Poll<()> {
         while !self.in_flight.is_empty() {
             match ready!(Pin::new(&mut self.in_flight).poll_next(cx)) {
-                Some(Ok((seqno, batch_size))) => {
-                    self.pending_acks.insert(seqno, batch_size);
-
-                    let mut num_to_ack = 0;
-                    while let Some(ack_size) = self.pending_acks.remove(&self.seq_tail) {
-                        num_to_ack += ack_size;
+                Some(Ok((seqno, batch_size, status))) => {
+                    self.pending_acks.insert(seqno, (batch_size, status));
+
+                    while let Some((ack_size, status)) = self.pending_acks.remove(&self.seq_tail) {
+                        trace!(message = "Acking events.", acking_num = ack_size, ?status);
+                        match status {
+                            (_, Some(rejection)) => self.acker.reject(ack_size, rejection),
+                            (status, None) => self.acker.ack_with_status(ack_size, status),
+                        }
                         self.seq_tail += 1
                     }
-                    trace!(message = "Acking events.", acking_num = num_to_ack);
-                    self.acker.ack(num_to_ack);
                 }
                 Some(Err(_)) => panic!("ServiceSink service sender dropped."),
                 None => break,
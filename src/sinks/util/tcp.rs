@@ -1,5 +1,5 @@
 use crate::{
-    buffers::Acker,
+    buffers::{Acker, Rejection},
     config::SinkContext,
     dns,
     internal_events::{
@@ -21,7 +21,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
+use futures::{future, stream::BoxStream, task::noop_waker_ref, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{
@@ -247,8 +247,15 @@ impl StreamSink for TcpSink {
         // We need [Peekable](https://docs.rs/futures/0.3.6/futures/stream/struct.Peekable.html) for initiating
         // connection only when we have something to send.
         let encode_event = Arc::clone(&self.encode_event);
+        let acker = self.acker.clone();
         let mut input = input
-            .map(|event| encode_event(event).unwrap_or_else(Bytes::new))
+            .filter_map(|event| {
+                let encoded = encode_event(event);
+                if encoded.is_none() {
+                    acker.reject_last(Rejection::new("Event could not be encoded.", 0));
+                }
+                future::ready(encoded)
+            })
             .peekable();
 
         while Pin::new(&mut input).peek().await.is_some() {
@@ -2,7 +2,7 @@ use super::SinkBuildError;
 #[cfg(unix)]
 use crate::udp;
 use crate::{
-    buffers::Acker,
+    buffers::{Acker, Rejection},
     config::SinkContext,
     dns,
     internal_events::{
@@ -253,12 +253,15 @@ impl StreamSink for UdpSink {
         while Pin::new(&mut input).peek().await.is_some() {
             let mut socket = self.connector.connect_backoff().await;
             while let Some(event) = input.next().await {
-                self.acker.ack(1);
-
                 let bytes = match (self.encode_event)(event) {
                     Some(bytes) => bytes,
-                    None => continue,
+                    None => {
+                        self.acker
+                            .reject_last(Rejection::new("Event could not be encoded.", 0));
+                        continue;
+                    }
                 };
+                self.acker.ack(1);
 
                 match udp_send(&mut socket, &bytes).await {
                     Ok(()) => emit!(SocketEventsSent {
@@ -1,5 +1,5 @@
 use crate::{
-    buffers::Acker,
+    buffers::{Acker, Rejection},
     config::SinkContext,
     internal_events::{
         ConnectionOpen, OpenGauge, SocketMode, UnixSocketConnectionEstablished,
@@ -18,7 +18,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{stream::BoxStream, SinkExt, StreamExt};
+use futures::{future, stream::BoxStream, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
 use std::{path::PathBuf, pin::Pin, sync::Arc, time::Duration};
@@ -135,8 +135,15 @@ impl StreamSink for UnixSink {
     // Same as TcpSink, more details there.
     async fn run(&mut self, input: BoxStream<'_, Event>) -> Result<(), ()> {
         let encode_event = Arc::clone(&self.encode_event);
+        let acker = self.acker.clone();
         let mut input = input
-            .map(|event| encode_event(event).unwrap_or_else(Bytes::new))
+            .filter_map(|event| {
+                let encoded = encode_event(event);
+                if encoded.is_none() {
+                    acker.reject_last(Rejection::new("Event could not be encoded.", 0));
+                }
+                future::ready(encoded)
+            })
             .peekable();
 
         while Pin::new(&mut input).peek().await.is_some() {
@@ -1,25 +1,53 @@
 use crate::{
     config::{DataType, GenerateConfig, SinkConfig, SinkContext, SinkDescription},
     event::proto,
-    sinks::util::tcp::TcpSinkConfig,
+    http::HttpClient,
+    sinks::util::{
+        buffer::compression::GZIP_DEFAULT,
+        http::{BatchedHttpSink, HttpSink},
+        tcp::TcpSinkConfig,
+        BatchConfig, BatchSettings, Compression, Concurrency, EncodedLength, TowerRequestConfig,
+        VecBuffer,
+    },
     tcp::TcpKeepaliveConfig,
-    tls::TlsConfig,
+    tls::{MaybeTlsSettings, TlsConfig},
+    vector_protocol::{VectorVersion, V2_EVENTS_PATH, V2_HEALTH_PATH},
     Event,
 };
 use bytes::{BufMut, Bytes, BytesMut};
+use flate2::write::GzEncoder;
+use futures::{FutureExt, SinkExt};
 use getset::Setters;
+use http::{Request, StatusCode, Uri};
 use prost::Message;
 use serde::{Deserialize, Serialize};
-use snafu::Snafu;
+use snafu::{ResultExt, Snafu};
+use std::io::Write;
+
+lazy_static::lazy_static! {
+    static ref REQUEST_DEFAULTS: TowerRequestConfig = TowerRequestConfig {
+        concurrency: Concurrency::Fixed(10),
+        timeout_secs: Some(30),
+        rate_limit_num: Some(u64::max_value()),
+        ..Default::default()
+    };
+}
 
 #[derive(Deserialize, Serialize, Debug, Setters)]
 #[serde(deny_unknown_fields)]
 pub struct VectorSinkConfig {
     address: String,
+    #[serde(default)]
+    #[set = "pub"]
+    version: VectorVersion,
     keepalive: Option<TcpKeepaliveConfig>,
     #[set = "pub"]
     tls: Option<TlsConfig>,
     send_buffer_bytes: Option<usize>,
+    #[set = "pub"]
+    compression: Option<Compression>,
+    batch: Option<BatchConfig>,
+    request: Option<TowerRequestConfig>,
 }
 
 impl VectorSinkConfig {
@@ -31,9 +59,13 @@ impl VectorSinkConfig {
     ) -> Self {
         Self {
             address,
+            version: VectorVersion::default(),
             keepalive,
             tls,
             send_buffer_bytes,
+            compression: None,
+            batch: None,
+            request: None,
         }
     }
 
@@ -48,6 +80,11 @@ enum BuildError {
     MissingHost,
     #[snafu(display("Missing port in address field"))]
     MissingPort,
+    #[snafu(display("Invalid address {:?}: {}", address, source))]
+    InvalidAddress {
+        address: String,
+        source: http::uri::InvalidUri,
+    },
 }
 
 inventory::submit! {
@@ -67,14 +104,30 @@ impl SinkConfig for VectorSinkConfig {
         &self,
         cx: SinkContext,
     ) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
-        let sink_config = TcpSinkConfig::new(
-            self.address.clone(),
-            self.keepalive,
-            self.tls.clone(),
-            self.send_buffer_bytes,
-        );
-
-        sink_config.build(cx, encode_event)
+        self.version.check_options(
+            &[
+                ("keepalive", self.keepalive.is_some()),
+                ("send_buffer_bytes", self.send_buffer_bytes.is_some()),
+            ],
+            &[
+                ("compression", self.compression.is_some()),
+                ("batch", self.batch.is_some()),
+                ("request", self.request.is_some()),
+            ],
+        )?;
+        match self.version {
+            VectorVersion::V1 => {
+                let sink_config = TcpSinkConfig::new(
+                    self.address.clone(),
+                    self.keepalive,
+                    self.tls.clone(),
+                    self.send_buffer_bytes,
+                );
+
+                sink_config.build(cx, encode_event)
+            }
+            VectorVersion::V2 => self.build_v2(cx),
+        }
     }
 
     fn input_type(&self) -> DataType {
@@ -90,6 +143,101 @@ impl SinkConfig for VectorSinkConfig {
 enum HealthcheckError {
     #[snafu(display("Connect error: {}", source))]
     ConnectError { source: std::io::Error },
+    #[snafu(display("Unexpected status: {}", status))]
+    UnexpectedStatus { status: StatusCode },
+}
+
+impl VectorSinkConfig {
+    fn build_v2(&self, cx: SinkContext) -> crate::Result<(super::VectorSink, super::Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let scheme = if tls.is_tls() { "https" } else { "http" };
+        let uri = |path| {
+            format!("{}://{}{}", scheme, self.address, path)
+                .parse::<Uri>()
+                .context(InvalidAddress {
+                    address: self.address.clone(),
+                })
+        };
+
+        let request_settings = self
+            .request
+            .unwrap_or_default()
+            .unwrap_with(&REQUEST_DEFAULTS);
+        let batch_settings = BatchSettings::default()
+            .bytes(bytesize::mib(1u64))
+            .events(1000)
+            .timeout(1)
+            .parse_config(self.batch.unwrap_or_default())?;
+        let client = HttpClient::new(tls, cx.proxy())?;
+
+        let sink = BatchedHttpSink::new(
+            VectorV2Sink {
+                uri: uri(V2_EVENTS_PATH)?,
+                compression: self.compression.unwrap_or_default(),
+            },
+            VecBuffer::new(batch_settings.size),
+            request_settings,
+            batch_settings.timeout,
+            client.clone(),
+            cx.acker(),
+        )
+        .sink_map_err(|error| error!(message = "Fatal vector sink error.", %error));
+
+        let healthcheck = healthcheck(uri(V2_HEALTH_PATH)?, client).boxed();
+
+        Ok((super::VectorSink::Sink(Box::new(sink)), healthcheck))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct VectorV2Sink {
+    uri: Uri,
+    compression: Compression,
+}
+
+impl EncodedLength for proto::EventWrapper {
+    fn encoded_length(&self) -> usize {
+        self.encoded_len()
+    }
+}
+
+#[async_trait::async_trait]
+impl HttpSink for VectorV2Sink {
+    type Input = proto::EventWrapper;
+    type Output = Vec<proto::EventWrapper>;
+
+    fn encode_event(&self, event: Event) -> Option<Self::Input> {
+        Some(event.into())
+    }
+
+    async fn build_request(&self, events: Self::Output) -> crate::Result<Request<Vec<u8>>> {
+        let batch = proto::EventBatch { events };
+        let mut body = Vec::with_capacity(batch.encoded_len());
+        batch.encode(&mut body)?;
+
+        let mut builder =
+            Request::post(self.uri.clone()).header("Content-Type", "application/x-protobuf");
+        if let Compression::Gzip(level) = self.compression {
+            builder = builder.header("Content-Encoding", "gzip");
+
+            let level = level.unwrap_or(GZIP_DEFAULT) as u32;
+            let mut w = GzEncoder::new(Vec::new(), flate2::Compression::new(level));
+            w.write_all(&body).expect("Writing to Vec can't fail");
+            body = w.finish().expect("Writing to Vec can't fail");
+        }
+
+        builder.body(body).map_err(Into::into)
+    }
+}
+
+async fn healthcheck(uri: Uri, client: HttpClient) -> crate::Result<()> {
+    let request = Request::get(uri).body(hyper::Body::empty()).unwrap();
+    let response = client.send(request).await?;
+
+    match response.status() {
+        StatusCode::OK => Ok(()),
+        status => Err(HealthcheckError::UnexpectedStatus { status }.into()),
+    }
 }
 
 fn encode_event(event: Event) -> Option<Bytes> {
@@ -1,5 +1,5 @@
 use crate::{
-    config::{self, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription},
+    config::{self, GenerateConfig, GlobalOptions, ProxyConfig, SourceConfig, SourceDescription},
     event::metric::{Metric, MetricKind, MetricValue},
     http::HttpClient,
     internal_events::{
@@ -31,6 +31,11 @@ struct ApacheMetricsConfig {
     scrape_interval_secs: u64,
     #[serde(default = "default_namespace")]
     namespace: String,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
 }
 
 pub fn default_scrape_interval_secs() -> u64 {
@@ -51,6 +56,7 @@ impl GenerateConfig for ApacheMetricsConfig {
             endpoints: vec!["http://localhost:8080/server-status/?auto".to_owned()],
             scrape_interval_secs: default_scrape_interval_secs(),
             namespace: default_namespace(),
+            proxy: Default::default(),
         })
         .unwrap()
     }
@@ -62,7 +68,7 @@ impl SourceConfig for ApacheMetricsConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
@@ -77,6 +83,7 @@ impl SourceConfig for ApacheMetricsConfig {
 
         Ok(apache_metrics(
             urls,
+            ProxyConfig::merge_with_env(&globals.proxy, &self.proxy),
             self.scrape_interval_secs,
             namespace,
             shutdown,
@@ -137,6 +144,7 @@ impl UriExt for http::Uri {
 
 fn apache_metrics(
     urls: Vec<http::Uri>,
+    proxy: ProxyConfig,
     interval: u64,
     namespace: Option<String>,
     shutdown: ShutdownSignal,
@@ -150,7 +158,7 @@ fn apache_metrics(
             .map(move |_| stream::iter(urls.clone()))
             .flatten()
             .map(move |url| {
-                let client = HttpClient::new(None).expect("HTTPS initialization failed");
+                let client = HttpClient::new(None, &proxy).expect("HTTPS initialization failed");
                 let sanitized_url = url.to_sanitized_string();
 
                 let request = Request::get(&url)
@@ -346,6 +354,7 @@ Scoreboard: ____S_____I______R____I_______KK___D__C__G_L____________W___________
             endpoints: vec![format!("http://foo:bar@{}/metrics", in_addr)],
             scrape_interval_secs: 1,
             namespace: "custom".to_string(),
+            proxy: Default::default(),
         }
         .build(
             "default",
@@ -412,6 +421,7 @@ Scoreboard: ____S_____I______R____I_______KK___D__C__G_L____________W___________
             endpoints: vec![format!("http://{}", in_addr)],
             scrape_interval_secs: 1,
             namespace: "apache".to_string(),
+            proxy: Default::default(),
         }
         .build(
             "default",
@@ -451,6 +461,7 @@ Scoreboard: ____S_____I______R____I_______KK___D__C__G_L____________W___________
             endpoints: vec![format!("http://{}", in_addr)],
             scrape_interval_secs: 1,
             namespace: "custom".to_string(),
+            proxy: Default::default(),
         }
         .build(
             "default",
@@ -1,6 +1,6 @@
 use super::util::MultilineConfig;
 use crate::{
-    config::{DataType, GlobalOptions, SourceConfig, SourceDescription},
+    config::{DataType, GlobalOptions, ProxyConfig, SourceConfig, SourceDescription},
     line_agg,
     rusoto::{self, AWSAuthentication, RegionOrEndpoint},
     shutdown::ShutdownSignal,
@@ -52,6 +52,11 @@ struct AwsS3Config {
     auth: AWSAuthentication,
 
     multiline: Option<MultilineConfig>,
+
+    acknowledgements: bool,
+
+    #[serde(skip_serializing_if = "crate::serde::skip_serializing_if_default")]
+    proxy: ProxyConfig,
 }
 
 inventory::submit! {
@@ -66,7 +71,7 @@ impl SourceConfig for AwsS3Config {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
@@ -78,7 +83,7 @@ impl SourceConfig for AwsS3Config {
 
         match self.strategy {
             Strategy::Sqs => Ok(Box::pin(
-                self.create_sqs_ingestor(multiline_config)
+                self.create_sqs_ingestor(multiline_config, &globals.proxy)
                     .await?
                     .run(out, shutdown),
             )),
@@ -98,12 +103,14 @@ impl AwsS3Config {
     async fn create_sqs_ingestor(
         &self,
         multiline: Option<line_agg::Config>,
+        proxy: &ProxyConfig,
     ) -> Result<sqs::Ingestor, CreateSqsIngestorError> {
         use std::sync::Arc;
 
         let region: Region = (&self.region).try_into().context(RegionParse {})?;
 
-        let client = rusoto::client().with_context(|| Client {})?;
+        let proxy = ProxyConfig::merge_with_env(proxy, &self.proxy);
+        let client = rusoto::client(&proxy).with_context(|| Client {})?;
         let creds: Arc<rusoto::AwsCredentialsProvider> = self
             .auth
             .build(&region, self.assume_role.clone())
@@ -130,6 +137,7 @@ impl AwsS3Config {
                     sqs.clone(),
                     self.compression,
                     multiline,
+                    self.acknowledgements,
                 )
                 .await
                 .context(Initialize {})
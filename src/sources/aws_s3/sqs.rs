@@ -1,29 +1,30 @@
 use crate::{
     config::log_schema,
-    event::Event,
+    event::{BatchNotifier, BatchStatus, Event},
     internal_events::aws_s3::source::{
-        SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageProcessingFailed,
-        SqsMessageProcessingSucceeded, SqsMessageReceiveFailed, SqsMessageReceiveSucceeded,
+        SqsMessageDeleteFailed, SqsMessageDeleteSucceeded, SqsMessageDeliveryFailed,
+        SqsMessageProcessingFailed, SqsMessageProcessingSucceeded, SqsMessageReceiveFailed,
+        SqsMessageReceiveSucceeded, SqsMessageVisibilityExtendFailed,
         SqsS3EventRecordInvalidEventIgnored,
     },
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
+    sources::util::sqs,
     Pipeline,
 };
 use bytes::Bytes;
 use chrono::{DateTime, TimeZone, Utc};
 use codec::BytesDelimitedCodec;
-use futures::{SinkExt, Stream, StreamExt, TryFutureExt};
+use futures::{future::join_all, SinkExt, Stream, StreamExt, TryFutureExt};
 use lazy_static::lazy_static;
 use rusoto_core::{Region, RusotoError};
 use rusoto_s3::{GetObjectError, GetObjectRequest, S3Client, S3};
 use rusoto_sqs::{
-    DeleteMessageError, DeleteMessageRequest, Message, ReceiveMessageError, ReceiveMessageRequest,
-    Sqs, SqsClient,
+    DeleteMessageRequest, Message, ReceiveMessageError, ReceiveMessageRequest, Sqs, SqsClient,
 };
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use snafu::{ResultExt, Snafu};
-use std::{future::ready, time::Duration};
+use std::{future::ready, sync::Arc, time::Duration};
 use tokio::time;
 use tokio_util::codec::FramedRead;
 
@@ -126,6 +127,7 @@ pub(super) struct Ingestor {
     poll_interval: Duration,
     visibility_timeout_secs: i64,
     delete_message: bool,
+    acknowledgements: bool,
 }
 
 impl Ingestor {
@@ -136,6 +138,7 @@ impl Ingestor {
         config: Config,
         compression: super::Compression,
         multiline: Option<line_agg::Config>,
+        acknowledgements: bool,
     ) -> Result<Ingestor, IngestorNewError> {
         let visibility_timeout_secs: i64 = config.visibility_timeout_secs.into();
 
@@ -152,6 +155,7 @@ impl Ingestor {
             poll_interval: Duration::from_secs(config.poll_secs),
             visibility_timeout_secs,
             delete_message: config.delete_message,
+            acknowledgements,
         })
     }
 
@@ -178,57 +182,106 @@ impl Ingestor {
             .await
             .unwrap_or_default();
 
-        for message in messages {
-            let receipt_handle = match message.receipt_handle {
-                None => {
-                    // I don't think this will ever actually happen, but is just an artifact of the
-                    // AWS's API predilection for returning nullable values for all response
-                    // attributes
-                    warn!(message = "Refusing to process message with no receipt_handle.", ?message.message_id);
-                    continue;
-                }
-                Some(ref handle) => handle.to_owned(),
-            };
-
-            let message_id = message
-                .message_id
-                .clone()
-                .unwrap_or_else(|| "<unknown>".to_owned());
-
-            match self.handle_sqs_message(message, out.clone()).await {
-                Ok(()) => {
-                    emit!(SqsMessageProcessingSucceeded {
-                        message_id: &message_id
-                    });
-                    if self.delete_message {
-                        match self.delete_message(receipt_handle).await {
-                            Ok(_) => {
-                                emit!(SqsMessageDeleteSucceeded {
-                                    message_id: &message_id
-                                });
-                            }
-                            Err(err) => {
-                                emit!(SqsMessageDeleteFailed {
-                                    error: &err,
-                                    message_id: &message_id,
-                                });
-                            }
-                        }
+        // The messages are handled concurrently, so that each one has its
+        // visibility timeout extended until it's finalized, instead of
+        // expiring while waiting for the messages before it. The next
+        // messages are only received once these are done.
+        join_all(
+            messages
+                .into_iter()
+                .map(|message| self.handle_message(message, out.clone())),
+        )
+        .await;
+    }
+
+    /// Processes the S3 event in `message` and deletes the message once its
+    /// events are processed, or delivered if acknowledgements are enabled.
+    /// The message is kept in the queue otherwise, so that it is retried.
+    async fn handle_message(&self, message: Message, out: Pipeline) {
+        let receipt_handle = match message.receipt_handle {
+            None => {
+                // I don't think this will ever actually happen, but is just an artifact of the
+                // AWS's API predilection for returning nullable values for all response
+                // attributes
+                warn!(message = "Refusing to process message with no receipt_handle.", ?message.message_id);
+                return;
+            }
+            Some(ref handle) => handle.to_owned(),
+        };
+
+        let message_id = message
+            .message_id
+            .clone()
+            .unwrap_or_else(|| "<unknown>".to_owned());
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let processed = async {
+            if let Err(err) = self.handle_sqs_message(message, batch, out).await {
+                emit!(SqsMessageProcessingFailed {
+                    message_id: &message_id,
+                    error: &err,
+                });
+                return false;
+            }
+            emit!(SqsMessageProcessingSucceeded {
+                message_id: &message_id
+            });
+
+            match receiver {
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => true,
+                    status => {
+                        emit!(SqsMessageDeliveryFailed {
+                            message_id: &message_id,
+                            status,
+                        });
+                        false
                     }
-                }
-                Err(err) => {
-                    emit!(SqsMessageProcessingFailed {
-                        message_id: &message_id,
-                        error: &err,
-                    });
-                }
+                },
+                None => true,
             }
+        };
+        let processed = sqs::extend_visibility_while(
+            &self.sqs_client,
+            &self.queue_url,
+            &receipt_handle,
+            self.visibility_timeout_secs,
+            |error| {
+                emit!(SqsMessageVisibilityExtendFailed {
+                    message_id: &message_id,
+                    error: &error,
+                })
+            },
+            processed,
+        )
+        .await;
+
+        if processed && self.delete_message {
+            self.delete_message(receipt_handle, &message_id).await;
+        }
+    }
+
+    async fn delete_message(&self, receipt_handle: String, message_id: &str) {
+        let result = self
+            .sqs_client
+            .delete_message(DeleteMessageRequest {
+                queue_url: self.queue_url.clone(),
+                receipt_handle,
+            })
+            .await;
+        match result {
+            Ok(_) => emit!(SqsMessageDeleteSucceeded { message_id }),
+            Err(err) => emit!(SqsMessageDeleteFailed {
+                error: &err,
+                message_id,
+            }),
         }
     }
 
     async fn handle_sqs_message(
         &self,
         message: Message,
+        batch: Option<Arc<BatchNotifier>>,
         out: Pipeline,
     ) -> Result<(), ProcessingError> {
         let s3_event: S3Event = serde_json::from_str(message.body.unwrap_or_default().as_ref())
@@ -236,16 +289,18 @@ impl Ingestor {
                 message_id: message.message_id.unwrap_or_else(|| "<empty>".to_owned()),
             })?;
 
-        self.handle_s3_event(s3_event, out).await
+        self.handle_s3_event(s3_event, batch, out).await
     }
 
     async fn handle_s3_event(
         &self,
         s3_event: S3Event,
+        batch: Option<Arc<BatchNotifier>>,
         mut out: Pipeline,
     ) -> Result<(), ProcessingError> {
         for record in s3_event.records {
-            self.handle_s3_event_record(record, &mut out).await?
+            self.handle_s3_event_record(record, &batch, &mut out)
+                .await?
         }
         Ok(())
     }
@@ -253,6 +308,7 @@ impl Ingestor {
     async fn handle_s3_event_record(
         &self,
         s3_event: S3EventRecord,
+        batch: &Option<Arc<BatchNotifier>>,
         out: &mut Pipeline,
     ) -> Result<(), ProcessingError> {
         let event_version: semver::Version = s3_event.event_version.clone().into();
@@ -352,7 +408,7 @@ impl Ingestor {
                 };
 
                 let stream = lines.filter_map(|line| {
-                    let mut event = Event::from(line);
+                    let mut event = Event::from(line).with_batch_notifier_option(batch);
 
                     let log = event.as_mut_log();
                     log.insert("bucket", s3_event.s3.bucket.name.clone());
@@ -412,18 +468,6 @@ impl Ingestor {
             .map_ok(|res| res.messages.unwrap_or_default())
             .await
     }
-
-    async fn delete_message(
-        &self,
-        receipt_handle: String,
-    ) -> Result<(), RusotoError<DeleteMessageError>> {
-        self.sqs_client
-            .delete_message(DeleteMessageRequest {
-                queue_url: self.queue_url.clone(),
-                receipt_handle,
-            })
-            .await
-    }
 }
 
 // https://docs.aws.amazon.com/AmazonS3/latest/dev/notification-content-structure.html
@@ -0,0 +1,617 @@
+//! Consumes the messages of an AWS SQS queue, decoding their bodies into
+//! events and deleting them once they are processed.
+
+use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, ProxyConfig, SourceConfig,
+        SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, Event, Value},
+    internal_events::{
+        AwsSqsEventsReceived, AwsSqsMessageDeleteFailed, AwsSqsMessageDeleteSucceeded,
+        AwsSqsMessageDeliveryFailed, AwsSqsMessageReceiveFailed, AwsSqsMessageReceiveSucceeded,
+        AwsSqsMessageVisibilityExtendFailed,
+    },
+    rusoto::{self, AWSAuthentication, RegionOrEndpoint},
+    shutdown::ShutdownSignal,
+    sources::util::sqs,
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::{future::join_all, stream, SinkExt, StreamExt};
+use rusoto_core::RusotoError;
+use rusoto_sqs::{
+    DeleteMessageRequest, Message, MessageAttributeValue, ReceiveMessageError,
+    ReceiveMessageRequest, Sqs, SqsClient,
+};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{collections::BTreeMap, convert::TryInto, sync::Arc};
+use tokio::time::{delay_for, Duration};
+
+const MESSAGE_ID: &str = "message_id";
+const MESSAGE_ATTRIBUTES: &str = "message_attributes";
+
+// The most messages a single receive request can return.
+const MAX_NUMBER_OF_MESSAGES: i64 = 10;
+// The longest a receive request can wait for messages.
+const MAX_POLL_SECS: u32 = 20;
+const RECEIVE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display(
+        "`poll_secs` must be at most {} seconds, got {}",
+        MAX_POLL_SECS,
+        poll_secs
+    ))]
+    InvalidPollSecs { poll_secs: u32 },
+    #[snafu(display("`client_concurrency` must be at least 1"))]
+    InvalidClientConcurrency,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct AwsSqsConfig {
+    queue_url: String,
+    #[serde(flatten)]
+    region: RegionOrEndpoint,
+    #[serde(default)]
+    auth: AWSAuthentication,
+    #[serde(default = "default_poll_secs")]
+    poll_secs: u32,
+    #[serde(default = "default_visibility_timeout_secs")]
+    visibility_timeout_secs: u32,
+    #[serde(default = "crate::serde::default_true")]
+    delete_message: bool,
+    #[serde(default = "default_client_concurrency")]
+    client_concurrency: u32,
+    #[serde(default)]
+    acknowledgements: bool,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+    #[serde(default)]
+    framing: FramingConfig,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+fn default_poll_secs() -> u32 {
+    15
+}
+
+fn default_visibility_timeout_secs() -> u32 {
+    300
+}
+
+fn default_client_concurrency() -> u32 {
+    1
+}
+
+inventory::submit! {
+    SourceDescription::new::<AwsSqsConfig>("aws_sqs")
+}
+
+impl GenerateConfig for AwsSqsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"queue_url = "https://sqs.us-east-2.amazonaws.com/123456789012/MyQueue"
+            region = "us-east-2""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "aws_sqs")]
+impl SourceConfig for AwsSqsConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.poll_secs > MAX_POLL_SECS {
+            return Err(BuildError::InvalidPollSecs {
+                poll_secs: self.poll_secs,
+            }
+            .into());
+        }
+        if self.client_concurrency == 0 {
+            return Err(BuildError::InvalidClientConcurrency.into());
+        }
+
+        let proxy = ProxyConfig::merge_with_env(&globals.proxy, &self.proxy);
+        let consumer = Consumer {
+            client: self.create_client(&proxy)?,
+            queue_url: self.queue_url.clone(),
+            poll_secs: self.poll_secs.into(),
+            visibility_timeout_secs: self.visibility_timeout_secs.into(),
+            delete_message: self.delete_message,
+            acknowledgements: self.acknowledgements,
+            decoder: Decoder::from_config(&self.framing, &self.decoding)?,
+        };
+
+        let concurrency = self.client_concurrency;
+        Ok(Box::pin(async move {
+            join_all((0..concurrency).map(|_| consumer.run(out.clone(), shutdown.clone()))).await;
+
+            Ok(())
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "aws_sqs"
+    }
+}
+
+impl AwsSqsConfig {
+    fn create_client(&self, proxy: &ProxyConfig) -> crate::Result<SqsClient> {
+        let region = (&self.region).try_into()?;
+        let client = rusoto::client(proxy)?;
+        let creds = self.auth.build(&region, None)?;
+
+        Ok(SqsClient::new_with(client, creds, region))
+    }
+}
+
+struct Consumer {
+    client: SqsClient,
+    queue_url: String,
+    poll_secs: i64,
+    visibility_timeout_secs: i64,
+    delete_message: bool,
+    acknowledgements: bool,
+    decoder: Decoder,
+}
+
+impl Consumer {
+    async fn run(&self, out: Pipeline, mut shutdown: ShutdownSignal) {
+        loop {
+            let result = tokio::select! {
+                result = self.receive_messages() => result,
+                _ = &mut shutdown => break,
+            };
+
+            match result {
+                Ok(messages) => {
+                    emit!(AwsSqsMessageReceiveSucceeded {
+                        count: messages.len()
+                    });
+                    // The messages are handled concurrently, so that each one
+                    // has its visibility timeout extended until it's
+                    // finalized, instead of expiring while waiting for the
+                    // messages before it.
+                    let results = join_all(
+                        messages
+                            .into_iter()
+                            .map(|message| self.handle_message(message, out.clone())),
+                    )
+                    .await;
+                    if results.iter().any(Result::is_err) {
+                        return;
+                    }
+                }
+                Err(error) => {
+                    emit!(AwsSqsMessageReceiveFailed { error: &error });
+                    tokio::select! {
+                        _ = delay_for(RECEIVE_RETRY_DELAY) => {},
+                        _ = &mut shutdown => break,
+                    }
+                }
+            }
+        }
+    }
+
+    async fn receive_messages(&self) -> Result<Vec<Message>, RusotoError<ReceiveMessageError>> {
+        self.client
+            .receive_message(ReceiveMessageRequest {
+                queue_url: self.queue_url.clone(),
+                max_number_of_messages: Some(MAX_NUMBER_OF_MESSAGES),
+                visibility_timeout: Some(self.visibility_timeout_secs),
+                wait_time_seconds: Some(self.poll_secs),
+                attribute_names: Some(vec!["SentTimestamp".to_owned()]),
+                message_attribute_names: Some(vec!["All".to_owned()]),
+                ..Default::default()
+            })
+            .await
+            .map(|output| output.messages.unwrap_or_default())
+    }
+
+    /// Sends the events of `message` and deletes it once they are
+    /// processed, or delivered if acknowledgements are enabled. Fails if the
+    /// pipeline is closed.
+    async fn handle_message(&self, message: Message, mut out: Pipeline) -> Result<(), ()> {
+        let message_id = message.message_id.clone().unwrap_or_default();
+        let receipt_handle = match message.receipt_handle.clone() {
+            Some(receipt_handle) => receipt_handle,
+            None => {
+                warn!(message = "SQS message without a receipt handle.", %message_id);
+                return Ok(());
+            }
+        };
+
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(self.acknowledgements);
+        let events = create_events(&mut self.decoder.clone(), &message, &batch);
+        drop(batch);
+
+        let processed = async {
+            let mut stream = stream::iter(events).map(Ok);
+            out.send_all(&mut stream)
+                .await
+                .map_err(|error| error!(message = "Error sending SQS events.", %error))?;
+
+            match receiver {
+                Some(receiver) => match receiver.await {
+                    BatchStatus::Delivered => Ok(true),
+                    status => {
+                        emit!(AwsSqsMessageDeliveryFailed {
+                            message_id: &message_id,
+                            status,
+                        });
+                        Ok(false)
+                    }
+                },
+                None => Ok(true),
+            }
+        };
+        let processed = sqs::extend_visibility_while(
+            &self.client,
+            &self.queue_url,
+            &receipt_handle,
+            self.visibility_timeout_secs,
+            |error| {
+                emit!(AwsSqsMessageVisibilityExtendFailed {
+                    message_id: &message_id,
+                    error: &error,
+                })
+            },
+            processed,
+        )
+        .await?;
+
+        if processed && self.delete_message {
+            self.delete_message(receipt_handle, &message_id).await;
+        }
+        Ok(())
+    }
+
+    async fn delete_message(&self, receipt_handle: String, message_id: &str) {
+        let result = self
+            .client
+            .delete_message(DeleteMessageRequest {
+                queue_url: self.queue_url.clone(),
+                receipt_handle,
+            })
+            .await;
+        match result {
+            Ok(_) => emit!(AwsSqsMessageDeleteSucceeded { message_id }),
+            Err(error) => emit!(AwsSqsMessageDeleteFailed {
+                message_id,
+                error: &error,
+            }),
+        }
+    }
+}
+
+/// Decodes the body of `message` into events, adding its ID, sent time and
+/// message attributes to them.
+fn create_events(
+    decoder: &mut Decoder,
+    message: &Message,
+    batch: &Option<Arc<BatchNotifier>>,
+) -> Vec<Event> {
+    let message_id = message.message_id.as_deref().unwrap_or_default();
+    let body = message.body.as_deref().unwrap_or_default();
+    let timestamp = message
+        .attributes
+        .as_ref()
+        .and_then(|attributes| attributes.get("SentTimestamp"))
+        .and_then(|timestamp| timestamp.parse::<i64>().ok())
+        .map(|millis| Utc.timestamp_millis(millis));
+    let attributes = message
+        .message_attributes
+        .as_ref()
+        .map(|attributes| {
+            attributes
+                .iter()
+                .filter_map(|(name, value)| {
+                    attribute_value(value).map(|value| (name.clone(), value))
+                })
+                .collect::<BTreeMap<_, _>>()
+        })
+        .unwrap_or_default();
+
+    let events = decoder
+        .decode_all(body.as_bytes())
+        .into_iter()
+        .map(|(event, _)| {
+            let mut event = event.with_batch_notifier_option(batch);
+            let log = event.as_mut_log();
+            log.insert(log_schema().source_type_key(), Bytes::from("aws_sqs"));
+            log.insert(MESSAGE_ID, message_id.to_owned());
+            if let Some(timestamp) = timestamp {
+                log.insert(log_schema().timestamp_key(), timestamp);
+            }
+            if !attributes.is_empty() {
+                log.insert(MESSAGE_ATTRIBUTES, Value::Map(attributes.clone()));
+            }
+            event
+        })
+        .collect::<Vec<_>>();
+    emit!(AwsSqsEventsReceived {
+        byte_size: body.len(),
+        count: events.len(),
+        message_id,
+    });
+    events
+}
+
+/// String and number attributes are mapped to strings, binary ones to bytes.
+/// List attributes aren't supported by SQS yet, so they are skipped.
+fn attribute_value(value: &MessageAttributeValue) -> Option<Value> {
+    value
+        .string_value
+        .clone()
+        .map(Value::from)
+        .or_else(|| value.binary_value.clone().map(Value::from))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<AwsSqsConfig>();
+    }
+
+    fn attribute(
+        data_type: &str,
+        string_value: Option<&str>,
+        binary_value: Option<&str>,
+    ) -> MessageAttributeValue {
+        MessageAttributeValue {
+            data_type: data_type.to_owned(),
+            string_value: string_value.map(Into::into),
+            binary_value: binary_value.map(|value| Bytes::from(value.to_owned())),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn creates_events_from_message() {
+        let mut attributes = HashMap::new();
+        attributes.insert("SentTimestamp".to_owned(), "1612345678901".to_owned());
+        let mut message_attributes = HashMap::new();
+        message_attributes.insert("team".to_owned(), attribute("String", Some("infra"), None));
+        message_attributes.insert("retries".to_owned(), attribute("Number", Some("3"), None));
+        message_attributes.insert("payload".to_owned(), attribute("Binary", None, Some("raw")));
+        let message = Message {
+            message_id: Some("e3e5b2d0".to_owned()),
+            body: Some(r#"[{"status": "ok"}, {"status": "degraded"}]"#.to_owned()),
+            attributes: Some(attributes),
+            message_attributes: Some(message_attributes),
+            ..Default::default()
+        };
+
+        let mut decoder = Decoder::from_config(
+            &FramingConfig::default(),
+            &toml::from_str("codec = \"json\"").unwrap(),
+        )
+        .unwrap();
+        let events = create_events(&mut decoder, &message, &None);
+
+        assert_eq!(events.len(), 2);
+        let log = events[1].as_log();
+        assert_eq!(log["status"], "degraded".into());
+        assert_eq!(log[MESSAGE_ID], "e3e5b2d0".into());
+        assert_eq!(log[log_schema().source_type_key()], "aws_sqs".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp_millis(1612345678901).into()
+        );
+        assert_eq!(log["message_attributes.team"], "infra".into());
+        assert_eq!(log["message_attributes.retries"], "3".into());
+        assert_eq!(
+            log["message_attributes.payload"],
+            Value::from(Bytes::from("raw"))
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_long_poll_secs() {
+        let config: AwsSqsConfig = toml::from_str(
+            r#"
+            queue_url = "https://sqs.us-east-2.amazonaws.com/123456789012/MyQueue"
+            region = "us-east-2"
+            poll_secs = 30
+            "#,
+        )
+        .unwrap();
+        let result = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                Pipeline::new_test().0,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(feature = "aws-sqs-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, collect_ready, random_string, trace_init};
+    use rusoto_core::Region;
+    use rusoto_sqs::{CreateQueueRequest, GetQueueAttributesRequest, SendMessageRequest};
+    use std::collections::HashMap;
+    use stream_cancel::Trigger;
+    use tokio::{sync::mpsc, task::JoinHandle};
+
+    const ENDPOINT: &str = "http://localhost:4566";
+
+    fn region() -> Region {
+        Region::Custom {
+            name: "localstack".into(),
+            endpoint: ENDPOINT.into(),
+        }
+    }
+
+    async fn create_queue(client: &SqsClient) -> String {
+        client
+            .create_queue(CreateQueueRequest {
+                queue_name: format!("test-{}", random_string(10).to_lowercase()),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .queue_url
+            .unwrap()
+    }
+
+    async fn send_message(client: &SqsClient, queue_url: &str, body: &str, team: &str) {
+        let mut message_attributes = HashMap::new();
+        message_attributes.insert(
+            "team".to_owned(),
+            MessageAttributeValue {
+                data_type: "String".to_owned(),
+                string_value: Some(team.to_owned()),
+                ..Default::default()
+            },
+        );
+        client
+            .send_message(SendMessageRequest {
+                queue_url: queue_url.to_owned(),
+                message_body: body.to_owned(),
+                message_attributes: Some(message_attributes),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn queued_messages(client: &SqsClient, queue_url: &str) -> usize {
+        client
+            .get_queue_attributes(GetQueueAttributesRequest {
+                queue_url: queue_url.to_owned(),
+                attribute_names: Some(vec![
+                    "ApproximateNumberOfMessages".to_owned(),
+                    "ApproximateNumberOfMessagesNotVisible".to_owned(),
+                ]),
+            })
+            .await
+            .unwrap()
+            .attributes
+            .unwrap()
+            .values()
+            .map(|count| count.parse::<usize>().unwrap())
+            .sum()
+    }
+
+    async fn run_source(
+        queue_url: &str,
+        options: &str,
+    ) -> (Trigger, JoinHandle<Result<(), ()>>, mpsc::Receiver<Event>) {
+        let config: AwsSqsConfig = toml::from_str(&format!(
+            r#"
+            queue_url = "{}"
+            endpoint = "{}"
+            poll_secs = 1
+            client_concurrency = 2
+            acknowledgements = true
+            {}
+            "#,
+            queue_url, ENDPOINT, options
+        ))
+        .unwrap();
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let source = config
+            .build("default", &GlobalOptions::default(), shutdown, tx)
+            .await
+            .unwrap();
+        (trigger, tokio::spawn(source), rx)
+    }
+
+    #[tokio::test]
+    async fn consumes_and_deletes_messages() {
+        trace_init();
+
+        let client = SqsClient::new(region());
+        let queue_url = create_queue(&client).await;
+        send_message(&client, &queue_url, "first line\nsecond line", "infra").await;
+        send_message(&client, &queue_url, "third line", "web").await;
+
+        let (trigger, source, rx) =
+            run_source(&queue_url, r#"framing.method = "newline_delimited""#).await;
+
+        let events = collect_n(rx, 3).await;
+        let mut lines = events
+            .iter()
+            .map(|event| {
+                let log = event.as_log();
+                let team = log["message_attributes.team"].to_string_lossy();
+                format!(
+                    "{} {}",
+                    log[log_schema().message_key()].to_string_lossy(),
+                    team
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.sort();
+        assert_eq!(
+            lines,
+            vec!["first line infra", "second line infra", "third line web"]
+        );
+
+        // Give the consumers a moment to delete the delivered messages.
+        delay_for(Duration::from_secs(1)).await;
+        drop(trigger);
+        source.await.unwrap().unwrap();
+
+        assert_eq!(queued_messages(&client, &queue_url).await, 0);
+    }
+
+    #[tokio::test]
+    async fn extends_visibility_until_delivered() {
+        trace_init();
+
+        let client = SqsClient::new(region());
+        let queue_url = create_queue(&client).await;
+        for line in &["first line", "second line", "third line"] {
+            send_message(&client, &queue_url, line, "infra").await;
+        }
+
+        let (trigger, source, mut rx) = run_source(&queue_url, "visibility_timeout_secs = 2").await;
+
+        // Hold on to the events, like a slow sink, for longer than the
+        // visibility timeout of their messages.
+        let events = rx.by_ref().take(3).collect::<Vec<_>>().await;
+        delay_for(Duration::from_secs(5)).await;
+        assert_eq!(queued_messages(&client, &queue_url).await, 3);
+
+        drop(events);
+        delay_for(Duration::from_secs(1)).await;
+        drop(trigger);
+        source.await.unwrap().unwrap();
+
+        // None of the messages became visible again and were redelivered.
+        assert!(collect_ready(rx).await.is_empty());
+        assert_eq!(queued_messages(&client, &queue_url).await, 0);
+    }
+}
@@ -0,0 +1,580 @@
+//! Runs a command and reads events from its output, with the same framing as
+//! the `stdin` source.
+//!
+//! In `scheduled` mode the command is run every `exec_interval_secs`, and the
+//! events of a run are sent once it exits, along with its exit code. A run is
+//! killed if it takes longer than `timeout_secs` or buffers more than
+//! `max_buffered_bytes` of output, and the events read so far are sent. In
+//! `streaming` mode the command is kept running, and respawned with a backoff
+//! whenever it exits.
+
+use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::Event,
+    internal_events::{
+        ExecBufferFull, ExecCommandExited, ExecEventReceived, ExecFailed, ExecTimedOut,
+    },
+    shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
+    Pipeline,
+};
+use bytes::Bytes;
+use futures::{stream, Sink, SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{
+    path::PathBuf,
+    process::Stdio,
+    time::{Duration, Instant},
+};
+use tokio::process::{Child, Command};
+use tokio_util::codec::FramedRead;
+
+const COMMAND: &str = "command";
+const EXIT_CODE: &str = "exit_code";
+const PID: &str = "pid";
+const STREAM: &str = "stream";
+const STDOUT: &str = "stdout";
+const STDERR: &str = "stderr";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("`command` must not be empty"))]
+    EmptyCommand,
+}
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Mode {
+    /// Runs the command every `exec_interval_secs`.
+    #[derivative(Default)]
+    Scheduled,
+    /// Keeps the command running.
+    Streaming,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduledOptions {
+    #[serde(default = "default_exec_interval_secs")]
+    exec_interval_secs: u64,
+    /// Defaults to `exec_interval_secs`.
+    timeout_secs: Option<u64>,
+    #[serde(default = "default_max_buffered_bytes")]
+    max_buffered_bytes: usize,
+}
+
+impl Default for ScheduledOptions {
+    fn default() -> Self {
+        Self {
+            exec_interval_secs: default_exec_interval_secs(),
+            timeout_secs: None,
+            max_buffered_bytes: default_max_buffered_bytes(),
+        }
+    }
+}
+
+fn default_exec_interval_secs() -> u64 {
+    60
+}
+
+fn default_max_buffered_bytes() -> usize {
+    bytesize::mib(10u64) as usize
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StreamingOptions {
+    #[serde(default = "crate::serde::default_true")]
+    respawn_on_exit: bool,
+    #[serde(default = "default_respawn_max_delay_secs")]
+    respawn_max_delay_secs: u64,
+}
+
+impl Default for StreamingOptions {
+    fn default() -> Self {
+        Self {
+            respawn_on_exit: true,
+            respawn_max_delay_secs: default_respawn_max_delay_secs(),
+        }
+    }
+}
+
+fn default_respawn_max_delay_secs() -> u64 {
+    60
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ExecConfig {
+    command: Vec<String>,
+    #[serde(default)]
+    mode: Mode,
+    #[serde(default)]
+    scheduled: ScheduledOptions,
+    #[serde(default)]
+    streaming: StreamingOptions,
+    working_directory: Option<PathBuf>,
+    #[serde(default = "crate::serde::default_true")]
+    include_stderr: bool,
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+    host_key: Option<String>,
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+fn default_max_length() -> usize {
+    bytesize::kib(100u64) as usize
+}
+
+inventory::submit! {
+    SourceDescription::new::<ExecConfig>("exec")
+}
+
+impl GenerateConfig for ExecConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"command = ["echo", "Hello World!"]"#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exec")]
+impl SourceConfig for ExecConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.command.is_empty() {
+            return Err(BuildError::EmptyCommand.into());
+        }
+
+        // Lines of up to `max_length` bytes are read unless `framing` is
+        // set, which is limited to `max_length` too unless it sets its own.
+        let framing = self
+            .framing
+            .clone()
+            .map(|framing| framing.with_default_max_length(self.max_length))
+            .unwrap_or_else(|| FramingConfig::newline_delimited(self.max_length));
+        let runner = Runner {
+            command_line: self.command.join(" "),
+            decoder: Decoder::from_config(&framing, &self.decoding)?,
+            host_key: self
+                .host_key
+                .clone()
+                .unwrap_or_else(|| log_schema().host_key().to_string()),
+            hostname: crate::get_hostname().ok(),
+            config: self.clone(),
+        };
+
+        Ok(Box::pin(async move {
+            let mut out =
+                out.sink_map_err(|error| error!(message = "Unable to send event to out.", %error));
+            match runner.config.mode {
+                Mode::Scheduled => runner.run_scheduled(shutdown, &mut out).await,
+                Mode::Streaming => runner.run_streaming(shutdown, &mut out).await,
+            }
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "exec"
+    }
+}
+
+/// How a run of the command ended.
+enum Exit {
+    /// The command exited, or couldn't be run.
+    Finished,
+    /// The source is shutting down.
+    Shutdown,
+}
+
+struct Runner {
+    config: ExecConfig,
+    command_line: String,
+    decoder: Decoder,
+    host_key: String,
+    hostname: Option<String>,
+}
+
+impl Runner {
+    /// Runs the command on every tick. If a run takes longer than the
+    /// interval, the next one starts as soon as it's over.
+    async fn run_scheduled(
+        &self,
+        shutdown: ShutdownSignal,
+        out: &mut (impl Sink<Event, Error = ()> + Unpin),
+    ) -> Result<(), ()> {
+        let interval = Duration::from_secs(self.config.scheduled.exec_interval_secs);
+        let mut ticks = tokio::time::interval(interval).take_until(shutdown.clone());
+        while ticks.next().await.is_some() {
+            if let Exit::Shutdown = self.run(true, shutdown.clone(), out).await? {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Keeps the command running. The delay before respawning it doubles
+    /// each time it exits, up to `respawn_max_delay_secs`, and is reset once
+    /// it has run for that long.
+    async fn run_streaming(
+        &self,
+        shutdown: ShutdownSignal,
+        out: &mut (impl Sink<Event, Error = ()> + Unpin),
+    ) -> Result<(), ()> {
+        let max_delay = Duration::from_secs(self.config.streaming.respawn_max_delay_secs);
+        let new_backoff = || {
+            ExponentialBackoff::from_millis(2)
+                .factor(250)
+                .max_delay(max_delay)
+        };
+        let mut backoff = new_backoff();
+        loop {
+            let start = Instant::now();
+            if let Exit::Shutdown = self.run(false, shutdown.clone(), out).await? {
+                break;
+            }
+            if !self.config.streaming.respawn_on_exit {
+                break;
+            }
+
+            if start.elapsed() >= max_delay {
+                backoff = new_backoff();
+            }
+            let delay = backoff.next().unwrap();
+            tokio::select! {
+                _ = tokio::time::delay_for(delay) => {}
+                _ = shutdown.clone() => break,
+            }
+        }
+        Ok(())
+    }
+
+    fn spawn(&self) -> std::io::Result<Child> {
+        let (program, args) = self.config.command.split_first().expect("checked on build");
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(if self.config.include_stderr {
+                Stdio::piped()
+            } else {
+                Stdio::inherit()
+            })
+            .kill_on_drop(true);
+        if let Some(working_directory) = &self.config.working_directory {
+            command.current_dir(working_directory);
+        }
+        command.spawn()
+    }
+
+    /// Runs the command once, sending the events read from its output as
+    /// they come or, if `buffer` is set, once it exits along with its exit
+    /// code. The command is killed if the source shuts down. Buffered runs
+    /// are also killed when they time out or exceed `max_buffered_bytes`.
+    async fn run(
+        &self,
+        buffer: bool,
+        shutdown: ShutdownSignal,
+        out: &mut (impl Sink<Event, Error = ()> + Unpin),
+    ) -> Result<Exit, ()> {
+        let command = self.command_line.as_str();
+        let mut child = match self.spawn() {
+            Ok(child) => child,
+            Err(error) => {
+                emit!(ExecFailed { command, error });
+                return Ok(Exit::Finished);
+            }
+        };
+        let pid = child.id();
+        let start = Instant::now();
+
+        let stdout = child.stdout.take().map(|stdout| {
+            FramedRead::new(stdout, self.decoder.clone()).map(|result| (STDOUT, result))
+        });
+        let stderr = child.stderr.take().map(|stderr| {
+            FramedRead::new(stderr, self.decoder.clone()).map(|result| (STDERR, result))
+        });
+        let mut output = stream::select(
+            stream::iter(stdout).flatten(),
+            stream::iter(stderr).flatten(),
+        )
+        .take_until(shutdown.clone());
+
+        let max_buffered_bytes = self.config.scheduled.max_buffered_bytes;
+        let mut events = Vec::new();
+        let mut buffered_bytes = 0;
+        // Resolves to `None` if the command has to be killed.
+        let running = async {
+            while let Some((stream, result)) = output.next().await {
+                match result {
+                    Ok((event, byte_size)) => {
+                        emit!(ExecEventReceived { command, byte_size });
+                        let event = self.create_event(event, stream, pid);
+                        if buffer {
+                            buffered_bytes += byte_size;
+                            if buffered_bytes > max_buffered_bytes {
+                                emit!(ExecBufferFull {
+                                    command,
+                                    max_bytes: max_buffered_bytes,
+                                });
+                                return Ok(None);
+                            }
+                            events.push(event);
+                        } else {
+                            out.send(event).await?;
+                        }
+                    }
+                    Err(error) => {
+                        emit!(ExecFailed { command, error });
+                        break;
+                    }
+                }
+            }
+            // Closes the pipes, so the command can't block writing to them.
+            drop(output);
+
+            let exit = tokio::select! {
+                status = &mut child => match status {
+                    Ok(status) => {
+                        emit!(ExecCommandExited {
+                            command,
+                            exit_code: status.code(),
+                            elapsed: start.elapsed(),
+                        });
+                        if let Some(exit_code) = status.code() {
+                            for event in &mut events {
+                                event.as_mut_log().insert(EXIT_CODE, exit_code as i64);
+                            }
+                        }
+                        Exit::Finished
+                    }
+                    Err(error) => {
+                        emit!(ExecFailed { command, error });
+                        Exit::Finished
+                    }
+                },
+                _ = shutdown.clone() => Exit::Shutdown,
+            };
+            Ok::<_, ()>(Some(exit))
+        };
+
+        let exit = if buffer {
+            let timeout = Duration::from_secs(
+                self.config
+                    .scheduled
+                    .timeout_secs
+                    .unwrap_or(self.config.scheduled.exec_interval_secs),
+            );
+            match tokio::time::timeout(timeout, running).await {
+                Ok(exit) => exit?,
+                Err(_) => {
+                    emit!(ExecTimedOut {
+                        command,
+                        elapsed: start.elapsed(),
+                    });
+                    None
+                }
+            }
+        } else {
+            running.await?
+        };
+        let exit = exit.unwrap_or_else(|| {
+            let _ = child.kill();
+            Exit::Finished
+        });
+
+        out.send_all(&mut stream::iter(events).map(Ok)).await?;
+        Ok(exit)
+    }
+
+    fn create_event(&self, mut event: Event, stream: &'static str, pid: u32) -> Event {
+        let log = event.as_mut_log();
+        log.insert(log_schema().source_type_key(), Bytes::from("exec"));
+        log.insert(COMMAND, self.config.command.clone());
+        log.insert(PID, pid as i64);
+        log.insert(STREAM, stream);
+        if let Some(hostname) = &self.hostname {
+            log.insert(&self.host_key, hostname.clone());
+        }
+        event
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::{
+        event::Value,
+        test_util::{collect_n, collect_ready, trace_init},
+    };
+    use tokio::sync::mpsc;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExecConfig>();
+    }
+
+    fn config(mode: &str, command: &str) -> ExecConfig {
+        toml::from_str(&format!(
+            r#"
+            mode = "{}"
+            command = ["sh", "-c", "{}"]
+            scheduled.exec_interval_secs = 1
+            streaming.respawn_on_exit = false
+            "#,
+            mode, command
+        ))
+        .unwrap()
+    }
+
+    async fn build(
+        config: &ExecConfig,
+        shutdown: ShutdownSignal,
+    ) -> (crate::sources::Source, mpsc::Receiver<Event>) {
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build("default", &GlobalOptions::default(), shutdown, tx)
+            .await
+            .unwrap();
+        (source, rx)
+    }
+
+    #[tokio::test]
+    async fn rejects_empty_command() {
+        let config: ExecConfig = toml::from_str("command = []").unwrap();
+        let result = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                Pipeline::new_test().0,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn runs_scheduled_commands() {
+        trace_init();
+
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let config = config("scheduled", "echo hello; echo world; exit 3");
+        let (source, rx) = build(&config, shutdown).await;
+        let source = tokio::spawn(source);
+
+        let events = collect_n(rx, 4).await;
+        drop(trigger);
+        source.await.unwrap().ok();
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["hello", "world", "hello", "world"]);
+
+        let log = events[0].as_log();
+        assert_eq!(log[EXIT_CODE], 3.into());
+        assert_eq!(log[STREAM], "stdout".into());
+        assert_eq!(log[log_schema().source_type_key()], "exec".into());
+        assert_eq!(
+            log[COMMAND],
+            Value::from(vec!["sh", "-c", "echo hello; echo world; exit 3"])
+        );
+        assert!(matches!(log[PID], Value::Integer(pid) if pid > 0));
+        assert_ne!(events[0].as_log()[PID], events[2].as_log()[PID]);
+    }
+
+    #[tokio::test]
+    async fn kills_timed_out_commands() {
+        trace_init();
+
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let config = config("scheduled", "echo hello; exec sleep 10");
+        let (source, rx) = build(&config, shutdown).await;
+        let source = tokio::spawn(source);
+
+        let events = collect_n(rx, 2).await;
+        drop(trigger);
+        source.await.unwrap().ok();
+
+        for event in events {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], "hello".into());
+            assert!(!log.contains(EXIT_CODE));
+        }
+    }
+
+    #[tokio::test]
+    async fn kills_commands_exceeding_buffer() {
+        trace_init();
+
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let mut config = config("scheduled", "echo hello; echo world; exec sleep 10");
+        config.scheduled.max_buffered_bytes = 5;
+        config.scheduled.timeout_secs = Some(60);
+        let (source, rx) = build(&config, shutdown).await;
+        let source = tokio::spawn(source);
+
+        let events = collect_n(rx, 1).await;
+        drop(trigger);
+        source.await.unwrap().ok();
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert!(!log.contains(EXIT_CODE));
+    }
+
+    #[tokio::test]
+    async fn streams_stdout_and_stderr() {
+        trace_init();
+
+        let config = config("streaming", "echo out; sleep 0.1; echo err >&2");
+        let (source, rx) = build(&config, ShutdownSignal::noop()).await;
+        source.await.unwrap();
+
+        let events = collect_ready(rx).await;
+        assert_eq!(events.len(), 2);
+        let out = events[0].as_log();
+        assert_eq!(out[log_schema().message_key()], "out".into());
+        assert_eq!(out[STREAM], "stdout".into());
+        assert!(!out.contains(EXIT_CODE));
+        let err = events[1].as_log();
+        assert_eq!(err[log_schema().message_key()], "err".into());
+        assert_eq!(err[STREAM], "stderr".into());
+    }
+
+    #[tokio::test]
+    async fn respawns_streaming_commands() {
+        trace_init();
+
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let mut config = config("streaming", "echo hello");
+        config.streaming.respawn_on_exit = true;
+        let (source, rx) = build(&config, shutdown).await;
+        let source = tokio::spawn(source);
+
+        let events = collect_n(rx, 3).await;
+        drop(trigger);
+        source.await.unwrap().ok();
+
+        assert!(events
+            .iter()
+            .all(|event| event.as_log()[log_schema().message_key()] == "hello".into()));
+    }
+}
@@ -1,8 +1,8 @@
-use super::util::{EncodingConfig, MultilineConfig};
+use super::util::{EncodingConfig, MultilineConfig, OrderedFinalizer};
 use crate::{
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
     encoding_transcode::{Decoder, Encoder},
-    event::Event,
+    event::{BatchNotifier, BatchStatus, Event},
     internal_events::{FileEventReceived, FileOpen, FileSourceInternalEventsEmitter},
     line_agg::{self, LineAgg},
     shutdown::ShutdownSignal,
@@ -13,7 +13,8 @@ use bytes::Bytes;
 use chrono::Utc;
 use file_source::{
     paths_provider::glob::{Glob, MatchOptions},
-    FileServer, FingerprintStrategy, Fingerprinter, ReadFrom,
+    Checkpointer, FileFingerprint, FilePosition, FileServer, FingerprintStrategy, Fingerprinter,
+    Line, ReadFrom,
 };
 use futures::{
     future::TryFutureExt,
@@ -23,8 +24,10 @@ use futures::{
 use regex::bytes::Regex;
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
+use std::collections::HashSet;
 use std::convert::TryInto;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::task::spawn_blocking;
 
@@ -84,6 +87,7 @@ pub struct FileConfig {
     pub remove_after: Option<u64>,
     pub line_delimiter: String,
     pub encoding: Option<EncodingConfig>,
+    pub acknowledgements: bool,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
@@ -170,6 +174,7 @@ impl Default for FileConfig {
             remove_after: None,
             line_delimiter: "\n".to_string(),
             encoding: None,
+            acknowledgements: false,
         }
     }
 }
@@ -262,7 +267,6 @@ pub fn file_source(
         ignore_before,
         max_line_bytes: config.max_line_bytes,
         line_delimiter: line_delimiter_as_bytes,
-        data_dir,
         glob_minimum_cooldown,
         fingerprinter: Fingerprinter {
             strategy: config.fingerprint.clone().into(),
@@ -271,6 +275,7 @@ pub fn file_source(
         },
         oldest_first: config.oldest_first,
         remove_after: config.remove_after.map(Duration::from_secs),
+        acknowledgements: config.acknowledgements,
         emitter: FileSourceInternalEventsEmitter,
         handle: tokio::runtime::Handle::current(),
     };
@@ -287,6 +292,10 @@ pub fn file_source(
     let multiline_config = config.multiline.clone();
     let message_start_indicator = config.message_start_indicator.clone();
     let multi_line_timeout = config.multi_line_timeout;
+    let acknowledgements = config.acknowledgements;
+
+    let checkpointer = Checkpointer::new(&data_dir);
+    let checkpoints = checkpointer.view();
 
     Box::pin(async move {
         info!(message = "Starting file server.", include = ?include, exclude = ?exclude);
@@ -294,19 +303,19 @@ pub fn file_source(
         let mut encoding_decoder = encoding_charset.map(|e| Decoder::new(e));
 
         // sizing here is just a guess
-        let (tx, rx) = futures::channel::mpsc::channel::<Vec<(Bytes, String)>>(2);
+        let (tx, rx) = futures::channel::mpsc::channel::<Vec<Line>>(2);
         let rx = rx
             .map(futures::stream::iter)
             .flatten()
-            .map(move |(line, src)| {
+            .map(move |mut line| {
                 // transcode each line from the file's encoding charset to utf8
-                match encoding_decoder.as_mut() {
-                    Some(d) => (d.decode_to_utf8(line), src),
-                    None => (line, src),
+                if let Some(d) = encoding_decoder.as_mut() {
+                    line.text = d.decode_to_utf8(line.text);
                 }
+                line
             });
 
-        let messages: Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin> =
+        let messages: Box<dyn Stream<Item = Line> + Send + std::marker::Unpin> =
             if let Some(ref multiline_config) = multiline_config {
                 wrap_with_line_agg(
                     rx,
@@ -324,22 +333,72 @@ pub fn file_source(
                 Box::new(rx)
             };
 
+        // With acknowledgements, lines are only checkpointed once they, and
+        // all lines read before them, have been finalized. Rejected lines
+        // won't ever be delivered, so they're skipped. Once a line of a file
+        // errors, the file isn't checkpointed anymore, so that the line is
+        // read again on restart.
+        let finalizer = if acknowledgements {
+            let mut errored_files = HashSet::new();
+            Some(OrderedFinalizer::new(
+                move |status, (file_id, offset): (FileFingerprint, FilePosition)| {
+                    if errored_files.contains(&file_id) {
+                        return;
+                    }
+                    match status {
+                        BatchStatus::Delivered => checkpoints.update(file_id, offset),
+                        BatchStatus::Failed => {
+                            error!(
+                                message = "Events were rejected, skipping over them.",
+                                ?status,
+                            );
+                            checkpoints.update(file_id, offset);
+                        }
+                        BatchStatus::Errored => {
+                            error!(
+                                message = "Events were not delivered, no longer updating the checkpoint of their file.",
+                                ?status,
+                            );
+                            errored_files.insert(file_id);
+                        }
+                    }
+                },
+            ))
+        } else {
+            None
+        };
+
         // Once file server ends this will run until it has finished processing remaining
         // logs in the queue.
         let span = current_span();
         let span2 = span.clone();
-        let mut messages = messages
-            .map(move |(msg, file): (Bytes, String)| {
-                let _enter = span2.enter();
-                create_event(msg, file, &host_key, &hostname, &file_key)
-            })
-            .map(Ok);
+        let finalizer = finalizer.map(Arc::new);
+        let mut messages = Box::pin(
+            messages
+                .then(move |line: Line| {
+                    let _enter = span2.enter();
+                    let mut event =
+                        create_event(line.text, line.filename, &host_key, &hostname, &file_key);
+                    let finalize = finalizer.as_ref().map(|finalizer| {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        event.add_batch_notifier(batch);
+                        (Arc::clone(finalizer), (line.file_id, line.offset), receiver)
+                    });
+                    async move {
+                        if let Some((finalizer, entry, receiver)) = finalize {
+                            finalizer.add(entry, receiver).await;
+                        }
+                        event
+                    }
+                })
+                .map(Ok),
+        );
         tokio::spawn(async move { out.send_all(&mut messages).instrument(span).await });
 
         let span = info_span!("file_server");
         spawn_blocking(move || {
             let _enter = span.enter();
-            let result = file_server.run(tx, shutdown);
+            let result = file_server.run(tx, shutdown, checkpointer);
             emit!(FileOpen { count: 0 });
             // Panic if we encounter any error originating from the file server.
             // We're at the `spawn_blocking` call, the panic will be caught and
@@ -375,16 +434,29 @@ fn reconcile_position_options(
 }
 
 fn wrap_with_line_agg(
-    rx: impl Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static,
+    rx: impl Stream<Item = Line> + Send + std::marker::Unpin + 'static,
     config: line_agg::Config,
-) -> Box<dyn Stream<Item = (Bytes, String)> + Send + std::marker::Unpin + 'static> {
+) -> Box<dyn Stream<Item = Line> + Send + std::marker::Unpin + 'static> {
     let logic = line_agg::Logic::new(config);
+    // Aggregated lines keep the position of their first line, which is
+    // conservative when they're used for checkpointing.
     Box::new(
-        LineAgg::new(rx.map(|(line, src)| (src, line, ())), logic)
-            .map(|(src, line, _context)| (line, src)),
+        LineAgg::new(
+            rx.map(|line| (line.filename, line.text, (line.file_id, line.offset))),
+            logic,
+        )
+        .map(|(filename, text, (file_id, offset))| Line {
+            text,
+            filename,
+            file_id,
+            offset,
+        }),
     )
 }
 
+// Aggregated lines keep the position of their first line.
+impl line_agg::LineContext for (FileFingerprint, FilePosition) {}
+
 fn create_event(
     line: Bytes,
     file: String,
@@ -1071,6 +1143,83 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn file_acknowledgements_delay_checkpoints() {
+        let dir = tempdir().unwrap();
+        let config = file::FileConfig {
+            include: vec![dir.path().join("*")],
+            acknowledgements: true,
+            ..test_default_file_config(&dir)
+        };
+
+        let path = dir.path().join("file");
+        let mut file = File::create(&path).unwrap();
+        writeln!(&mut file, "zeroth line").unwrap();
+        sleep_500_millis().await;
+
+        // Events still held on shutdown were never delivered, so they aren't checkpointed.
+        let undelivered = {
+            let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+
+            let (tx, rx) = Pipeline::new_test();
+            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            tokio::spawn(source);
+
+            sleep_500_millis().await;
+            drop(trigger_shutdown);
+
+            let received = wait_with_timeout(rx.collect::<Vec<_>>()).await;
+            assert_eq!(received.len(), 1);
+            received
+        };
+        // Restart server, the line is read again and this time delivered.
+        {
+            let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+
+            let (tx, mut rx) = Pipeline::new_test();
+            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            tokio::spawn(source);
+
+            let event = timeout(Duration::from_secs(5), rx.next())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                event.as_log()[log_schema().message_key()].to_string_lossy(),
+                "zeroth line"
+            );
+            drop(event);
+            sleep_500_millis().await;
+
+            drop(trigger_shutdown);
+
+            let received = wait_with_timeout(rx.collect::<Vec<_>>()).await;
+            assert!(received.is_empty());
+        }
+        drop(undelivered);
+        // Restart server, only new lines are read.
+        {
+            let (trigger_shutdown, shutdown, _) = ShutdownSignal::new_wired();
+
+            let (tx, rx) = Pipeline::new_test();
+            let source = file::file_source(&config, config.data_dir.clone().unwrap(), shutdown, tx);
+            tokio::spawn(source);
+
+            sleep_500_millis().await;
+            writeln!(&mut file, "first line").unwrap();
+            sleep_500_millis().await;
+
+            drop(trigger_shutdown);
+
+            let received = wait_with_timeout(rx.collect::<Vec<_>>()).await;
+            let lines = received
+                .into_iter()
+                .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+                .collect::<Vec<_>>();
+            assert_eq!(lines, vec!["first line"]);
+        }
+    }
+
     #[cfg(unix)] // this test uses unix-specific function `futimes` during test time
     #[tokio::test]
     async fn file_start_position_ignore_old_files() {
@@ -0,0 +1,900 @@
+//! Fluent source
+//!
+//! Receives logs from Fluentd and Fluent Bit over the Forward protocol, in
+//! all of its Message, Forward, PackedForward and CompressedPackedForward
+//! modes.
+//!
+//! https://github.com/fluent/fluentd/wiki/Forward-Protocol-Specification-v1
+//!
+//! Messages asking for an acknowledgement, with the `chunk` option, are
+//! acknowledged once their entries are sent, or once they are delivered if
+//! `acknowledgements` is enabled. Messages that fail to be delivered aren't
+//! acknowledged, so the client sends them again.
+
+use super::util::{SocketListenAddr, TcpSource};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
+    internal_events::{FluentMessageDecodeError, FluentMessageReceived},
+    shutdown::ShutdownSignal,
+    tcp::TcpKeepaliveConfig,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, TimeZone, Utc};
+use flate2::read::MultiGzDecoder;
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt,
+};
+use rmpv::Value as MsgPackValue;
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::VecDeque, convert::TryInto, io, io::Read};
+use tokio_util::codec::Decoder;
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct FluentConfig {
+    address: SocketListenAddr,
+    keepalive: Option<TcpKeepaliveConfig>,
+    tls: Option<TlsConfig>,
+    receive_buffer_bytes: Option<usize>,
+    #[serde(default = "default_shutdown_timeout_secs")]
+    shutdown_timeout_secs: u64,
+    #[serde(default = "default_max_length")]
+    max_length: usize,
+    #[serde(default)]
+    acknowledgements: bool,
+}
+
+fn default_shutdown_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_length() -> usize {
+    bytesize::mib(16u64) as usize
+}
+
+inventory::submit! {
+    SourceDescription::new::<FluentConfig>("fluent")
+}
+
+impl GenerateConfig for FluentConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: SocketListenAddr::SocketAddr("0.0.0.0:24224".parse().unwrap()),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            max_length: default_max_length(),
+            acknowledgements: false,
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "fluent")]
+impl SourceConfig for FluentConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let source = FluentSource {
+            max_length: self.max_length,
+            acknowledgements: self.acknowledgements,
+        };
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        source.run(
+            self.address,
+            self.keepalive,
+            self.shutdown_timeout_secs,
+            tls,
+            self.receive_buffer_bytes,
+            shutdown,
+            out,
+        )
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "fluent"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        vec![self.address.into()]
+    }
+}
+
+#[derive(Debug, Clone)]
+struct FluentSource {
+    max_length: usize,
+    acknowledgements: bool,
+}
+
+impl TcpSource for FluentSource {
+    type Error = io::Error;
+    type Decoder = FluentDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        FluentDecoder::new(self.max_length, self.acknowledgements)
+    }
+
+    fn build_event(&self, frame: FluentFrame, host: Bytes) -> Option<Event> {
+        let mut event = frame.event?;
+        emit!(FluentMessageReceived {
+            byte_size: frame.byte_size
+        });
+
+        let log = event.as_mut_log();
+        log.insert(log_schema().source_type_key(), Bytes::from("fluent"));
+        log.insert(log_schema().host_key(), host);
+        Some(event)
+    }
+
+    fn take_ack(&self, frame: &mut FluentFrame) -> Option<BoxFuture<'static, Option<Bytes>>> {
+        frame.ack.take()
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum DecodeError {
+    #[snafu(display("Invalid MessagePack: {}", source))]
+    InvalidMessagePack { source: rmpv::decode::Error },
+    #[snafu(display("Expected {}, got {}", expected, found))]
+    UnexpectedValue {
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[snafu(display("Message is missing its {}", part))]
+    MissingPart { part: &'static str },
+    #[snafu(display("Time is out of range"))]
+    InvalidTime,
+    #[snafu(display("Message is longer than the maximum of {} bytes", max_length))]
+    TooLong { max_length: usize },
+    #[snafu(display("Unsupported compression {:?}", compression))]
+    UnsupportedCompression { compression: String },
+    #[snafu(display("Failed to decompress entries: {}", source))]
+    Decompression { source: io::Error },
+}
+
+fn unexpected(expected: &'static str, value: &MsgPackValue) -> DecodeError {
+    let found = match value {
+        MsgPackValue::Nil => "nil",
+        MsgPackValue::Boolean(_) => "a boolean",
+        MsgPackValue::Integer(_) => "an integer",
+        MsgPackValue::F32(_) | MsgPackValue::F64(_) => "a float",
+        MsgPackValue::String(_) => "a string",
+        MsgPackValue::Binary(_) => "binary data",
+        MsgPackValue::Array(_) => "an array",
+        MsgPackValue::Map(_) => "a map",
+        MsgPackValue::Ext(_, _) => "an extension",
+    };
+    DecodeError::UnexpectedValue { expected, found }
+}
+
+/// A single entry of a message, along with the acknowledgement of the
+/// message for its last entry.
+struct FluentFrame {
+    event: Option<Event>,
+    byte_size: usize,
+    ack: Option<BoxFuture<'static, Option<Bytes>>>,
+}
+
+/// Reads the messages of the Forward protocol, each of which is a MessagePack
+/// array, and splits them into their entries. Messages longer than
+/// `max_length` bytes are dropped.
+struct FluentDecoder {
+    max_length: usize,
+    acknowledgements: bool,
+    pending: VecDeque<FluentFrame>,
+    scanner: MessageScanner,
+}
+
+impl FluentDecoder {
+    fn new(max_length: usize, acknowledgements: bool) -> Self {
+        Self {
+            max_length,
+            acknowledgements,
+            pending: VecDeque::new(),
+            scanner: MessageScanner::default(),
+        }
+    }
+
+    fn handle_message(
+        &mut self,
+        message: MsgPackValue,
+        byte_size: usize,
+    ) -> Result<(), DecodeError> {
+        let mut parts = match message {
+            MsgPackValue::Array(parts) => parts.into_iter(),
+            value => return Err(unexpected("an array", &value)),
+        };
+        let tag = match parts.next() {
+            Some(value) => into_string(value).map_err(|value| unexpected("a tag", &value))?,
+            None => return Err(DecodeError::MissingPart { part: "tag" }),
+        };
+
+        let (entries, options) = match parts.next() {
+            // Forward mode: `[tag, [[time, record], ...], options]`
+            Some(MsgPackValue::Array(entries)) => (entries, Options::new(parts.next())),
+            // (Compressed)PackedForward mode: `[tag, entries, options]`, with
+            // the entries concatenated as MessagePack.
+            Some(MsgPackValue::Binary(entries)) => {
+                let options = Options::new(parts.next());
+                (read_entries(&entries, &options, self.max_length)?, options)
+            }
+            Some(MsgPackValue::String(entries)) => {
+                let options = Options::new(parts.next());
+                (
+                    read_entries(entries.as_bytes(), &options, self.max_length)?,
+                    options,
+                )
+            }
+            // Message mode: `[tag, time, record, options]`
+            Some(time) => {
+                let record = parts
+                    .next()
+                    .ok_or(DecodeError::MissingPart { part: "record" })?;
+                let entry = MsgPackValue::Array(vec![time, record]);
+                (vec![entry], Options::new(parts.next()))
+            }
+            None => return Err(DecodeError::MissingPart { part: "entries" }),
+        };
+
+        let (notifier, receiver) = BatchNotifier::maybe_new_with_receiver(
+            self.acknowledgements && options.chunk.is_some(),
+        );
+        let mut byte_size = byte_size;
+        let mut frames = Vec::with_capacity(entries.len());
+        for entry in entries {
+            match event_from_entry(&tag, entry) {
+                Ok(event) => {
+                    frames.push(FluentFrame {
+                        event: Some(event.with_batch_notifier_option(&notifier)),
+                        byte_size,
+                        ack: None,
+                    });
+                    byte_size = 0;
+                }
+                Err(error) => emit!(FluentMessageDecodeError { error: &error }),
+            }
+        }
+        drop(notifier);
+
+        if let Some(chunk) = options.chunk {
+            let ack = encode_ack(chunk);
+            let ack = match receiver {
+                Some(receiver) => async move {
+                    match receiver.await {
+                        BatchStatus::Delivered => Some(ack),
+                        BatchStatus::Errored | BatchStatus::Failed => None,
+                    }
+                }
+                .boxed(),
+                None => future::ready(Some(ack)).boxed(),
+            };
+            match frames.last_mut() {
+                Some(frame) => frame.ack = Some(ack),
+                None => frames.push(FluentFrame {
+                    event: None,
+                    byte_size: 0,
+                    ack: Some(ack),
+                }),
+            }
+        }
+
+        self.pending.extend(frames);
+        Ok(())
+    }
+}
+
+impl Decoder for FluentDecoder {
+    type Item = FluentFrame;
+    type Error = io::Error;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        loop {
+            if let Some(frame) = self.pending.pop_front() {
+                return Ok(Some(frame));
+            }
+            if src.is_empty() {
+                return Ok(None);
+            }
+
+            // Incomplete messages are only scanned for their end as more of
+            // them is read, and decoded once complete.
+            let byte_size = match self.scanner.scan(src) {
+                Some(byte_size) => byte_size,
+                None => {
+                    if src.len() > self.max_length {
+                        emit!(FluentMessageDecodeError {
+                            error: &DecodeError::TooLong {
+                                max_length: self.max_length
+                            }
+                        });
+                        // There's no telling where the next message starts.
+                        src.clear();
+                        self.scanner = MessageScanner::default();
+                    }
+                    return Ok(None);
+                }
+            };
+            let message = src.split_to(byte_size);
+            if byte_size > self.max_length {
+                emit!(FluentMessageDecodeError {
+                    error: &DecodeError::TooLong {
+                        max_length: self.max_length
+                    }
+                });
+                continue;
+            }
+            let message = match rmpv::decode::read_value(&mut &message[..]) {
+                Ok(message) => message,
+                Err(source) => {
+                    emit!(FluentMessageDecodeError {
+                        error: &DecodeError::InvalidMessagePack { source }
+                    });
+                    continue;
+                }
+            };
+            if let Err(error) = self.handle_message(message, byte_size) {
+                emit!(FluentMessageDecodeError { error: &error });
+            }
+        }
+    }
+}
+
+/// Finds where the MessagePack value at the start of a buffer ends without
+/// decoding it, continuing from where it stopped once more is read, so that
+/// a long message read in many parts is only scanned once.
+struct MessageScanner {
+    /// The bytes scanned so far.
+    scanned: usize,
+    /// The number of values still to be scanned for the value being scanned
+    /// and each array or map it is in.
+    remaining: Vec<u64>,
+}
+
+impl Default for MessageScanner {
+    fn default() -> Self {
+        Self {
+            scanned: 0,
+            remaining: vec![1],
+        }
+    }
+}
+
+impl MessageScanner {
+    /// Returns the length of the value once all of it is in `buf`.
+    fn scan(&mut self, buf: &[u8]) -> Option<usize> {
+        loop {
+            match self.remaining.last_mut() {
+                None => {
+                    let length = self.scanned;
+                    *self = Self::default();
+                    return Some(length);
+                }
+                Some(0) => {
+                    self.remaining.pop();
+                    continue;
+                }
+                Some(count) => *count -= 1,
+            }
+            let (length, children) = match value_header(&buf[self.scanned..]) {
+                Some((length, children)) if self.scanned + length <= buf.len() => {
+                    (length, children)
+                }
+                _ => {
+                    // Scan this value again once more is read.
+                    *self.remaining.last_mut().expect("Value is being scanned.") += 1;
+                    return None;
+                }
+            };
+            self.scanned += length;
+            if children > 0 {
+                self.remaining.push(children);
+            }
+        }
+    }
+}
+
+/// Returns the length of the MessagePack value starting `buf`, excluding the
+/// values in it if it's an array or map, along with the number of those, or
+/// `None` if `buf` is too short to tell.
+fn value_header(buf: &[u8]) -> Option<(usize, u64)> {
+    let length = |size: usize| -> Option<usize> {
+        let bytes = buf.get(1..1 + size)?;
+        Some(
+            bytes
+                .iter()
+                .fold(0, |length, &byte| length << 8 | byte as usize),
+        )
+    };
+    Some(match *buf.get(0)? {
+        0x80..=0x8f => (1, 2 * (buf[0] & 0x0f) as u64),
+        0x90..=0x9f => (1, (buf[0] & 0x0f) as u64),
+        0xa0..=0xbf => (1 + (buf[0] & 0x1f) as usize, 0),
+        0xc4 | 0xd9 => (2 + length(1)?, 0),
+        0xc5 | 0xda => (3 + length(2)?, 0),
+        0xc6 | 0xdb => (5 + length(4)?, 0),
+        0xc7 => (3 + length(1)?, 0),
+        0xc8 => (4 + length(2)?, 0),
+        0xc9 => (6 + length(4)?, 0),
+        0xcc | 0xd0 => (2, 0),
+        0xcd | 0xd1 => (3, 0),
+        0xca | 0xce | 0xd2 => (5, 0),
+        0xcb | 0xcf | 0xd3 => (9, 0),
+        0xd4 => (3, 0),
+        0xd5 => (4, 0),
+        0xd6 => (6, 0),
+        0xd7 => (10, 0),
+        0xd8 => (18, 0),
+        0xdc => (3, length(2)? as u64),
+        0xdd => (5, length(4)? as u64),
+        0xde => (3, 2 * length(2)? as u64),
+        0xdf => (5, 2 * length(4)? as u64),
+        // Fixints, nil, booleans and the unused 0xc1, which fails decoding.
+        _ => (1, 0),
+    })
+}
+
+#[derive(Default)]
+struct Options {
+    chunk: Option<String>,
+    compressed: Option<String>,
+}
+
+impl Options {
+    fn new(options: Option<MsgPackValue>) -> Self {
+        let mut parsed = Self::default();
+        if let Some(MsgPackValue::Map(options)) = options {
+            for (key, value) in options {
+                match (key.as_str(), into_string(value)) {
+                    (Some("chunk"), Ok(chunk)) => parsed.chunk = Some(chunk),
+                    (Some("compressed"), Ok(compressed)) => parsed.compressed = Some(compressed),
+                    _ => {}
+                }
+            }
+        }
+        parsed
+    }
+}
+
+fn read_entries(
+    bytes: &[u8],
+    options: &Options,
+    max_length: usize,
+) -> Result<Vec<MsgPackValue>, DecodeError> {
+    let decompressed;
+    let mut bytes = match options.compressed.as_deref() {
+        None | Some("text") => bytes,
+        Some("gzip") => {
+            let mut buf = Vec::new();
+            MultiGzDecoder::new(bytes)
+                .take(max_length as u64 + 1)
+                .read_to_end(&mut buf)
+                .context(Decompression)?;
+            if buf.len() > max_length {
+                return Err(DecodeError::TooLong { max_length });
+            }
+            decompressed = buf;
+            &decompressed[..]
+        }
+        Some(compression) => {
+            return Err(DecodeError::UnsupportedCompression {
+                compression: compression.to_owned(),
+            })
+        }
+    };
+
+    let mut entries = Vec::new();
+    while !bytes.is_empty() {
+        entries.push(rmpv::decode::read_value(&mut bytes).context(InvalidMessagePack)?);
+    }
+    Ok(entries)
+}
+
+fn event_from_entry(tag: &str, entry: MsgPackValue) -> Result<Event, DecodeError> {
+    let mut entry = match entry {
+        MsgPackValue::Array(entry) => entry.into_iter(),
+        value => return Err(unexpected("an entry", &value)),
+    };
+    let time = match entry.next() {
+        // Fluent Bit 2 sends `[time, metadata]` in place of the time.
+        Some(MsgPackValue::Array(time)) => time.into_iter().next(),
+        time => time,
+    }
+    .ok_or(DecodeError::MissingPart { part: "time" })?;
+    let timestamp = timestamp_from_value(time)?;
+    let record = match entry.next() {
+        Some(MsgPackValue::Map(record)) => record,
+        Some(value) => return Err(unexpected("a record", &value)),
+        None => return Err(DecodeError::MissingPart { part: "record" }),
+    };
+
+    let mut log = LogEvent::default();
+    for (key, value) in record {
+        let key = into_string(key).unwrap_or_else(|key| key.to_string());
+        log.insert_flat(key, into_value(value));
+    }
+    log.insert(log_schema().timestamp_key(), timestamp);
+    log.insert("tag", tag.to_owned());
+    Ok(Event::from(log))
+}
+
+fn into_string(value: MsgPackValue) -> Result<String, MsgPackValue> {
+    match value {
+        MsgPackValue::String(string) if string.is_str() => {
+            Ok(string.into_str().expect("String is valid UTF-8."))
+        }
+        MsgPackValue::Binary(bytes) => {
+            String::from_utf8(bytes).map_err(|error| MsgPackValue::Binary(error.into_bytes()))
+        }
+        value => Err(value),
+    }
+}
+
+/// Reads the time of an entry, either as seconds since the epoch or as an
+/// `EventTime`, the extension holding the seconds and nanoseconds as two big
+/// endian 32 bit integers. Times that can't be represented are rejected, as
+/// they come from the peer.
+fn timestamp_from_value(value: MsgPackValue) -> Result<DateTime<Utc>, DecodeError> {
+    let timestamp = match &value {
+        MsgPackValue::Integer(seconds) => seconds
+            .as_i64()
+            .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single()),
+        MsgPackValue::F64(seconds) if seconds.is_finite() => {
+            let nanoseconds = seconds * 1e9;
+            if nanoseconds.abs() < i64::MAX as f64 {
+                Some(Utc.timestamp_nanos(nanoseconds as i64))
+            } else {
+                None
+            }
+        }
+        MsgPackValue::F64(_) => None,
+        MsgPackValue::Ext(0, data) => event_time(data),
+        value => return Err(unexpected("a time", value)),
+    };
+    timestamp.ok_or(DecodeError::InvalidTime)
+}
+
+fn event_time(data: &[u8]) -> Option<DateTime<Utc>> {
+    let seconds = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+    let nanoseconds = u32::from_be_bytes(data.get(4..8)?.try_into().ok()?);
+    // Chrono represents leap seconds with nanoseconds past a second, which
+    // event times never use.
+    Utc.timestamp_opt(seconds as i64, nanoseconds)
+        .single()
+        .filter(|_| nanoseconds < 1_000_000_000)
+}
+
+fn into_value(value: MsgPackValue) -> Value {
+    match value {
+        MsgPackValue::Nil => Value::Null,
+        MsgPackValue::Boolean(boolean) => boolean.into(),
+        MsgPackValue::Integer(integer) => match integer.as_i64() {
+            Some(integer) => Value::Integer(integer),
+            None => integer.as_f64().map(Value::Float).unwrap_or(Value::Null),
+        },
+        MsgPackValue::F32(float) => Value::Float(float as f64),
+        MsgPackValue::F64(float) => Value::Float(float),
+        MsgPackValue::String(string) => Value::Bytes(string.into_bytes().into()),
+        MsgPackValue::Binary(bytes) => Value::Bytes(bytes.into()),
+        MsgPackValue::Array(array) => Value::Array(array.into_iter().map(into_value).collect()),
+        MsgPackValue::Map(map) => Value::Map(
+            map.into_iter()
+                .map(|(key, value)| {
+                    let key = into_string(key).unwrap_or_else(|key| key.to_string());
+                    (key, into_value(value))
+                })
+                .collect(),
+        ),
+        MsgPackValue::Ext(0, data) => match event_time(&data) {
+            Some(timestamp) => Value::Timestamp(timestamp),
+            None => Value::Bytes(data.into()),
+        },
+        MsgPackValue::Ext(_, data) => Value::Bytes(data.into()),
+    }
+}
+
+fn encode_ack(chunk: String) -> Bytes {
+    let ack = MsgPackValue::Map(vec![("ack".into(), chunk.into())]);
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &ack).expect("Writing to a Vec can't fail.");
+    buf.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{collect_ready, next_addr, wait_for_tcp};
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpStream,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<FluentConfig>();
+    }
+
+    fn encode(value: &MsgPackValue) -> Vec<u8> {
+        let mut buf = Vec::new();
+        rmpv::encode::write_value(&mut buf, value).unwrap();
+        buf
+    }
+
+    fn entry(seconds: i64, message: &str) -> MsgPackValue {
+        MsgPackValue::Array(vec![
+            seconds.into(),
+            MsgPackValue::Map(vec![("message".into(), message.into())]),
+        ])
+    }
+
+    fn decode_buf(decoder: &mut FluentDecoder, buf: &mut BytesMut) -> Vec<FluentFrame> {
+        let mut frames = Vec::new();
+        while let Some(frame) = decoder.decode(buf).unwrap() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    fn decode_all(decoder: &mut FluentDecoder, bytes: &[u8]) -> Vec<FluentFrame> {
+        decode_buf(decoder, &mut BytesMut::from(bytes))
+    }
+
+    fn messages(frames: &[FluentFrame]) -> Vec<String> {
+        frames
+            .iter()
+            .filter_map(|frame| frame.event.as_ref())
+            .map(|event| event.as_log()["message"].to_string_lossy())
+            .collect()
+    }
+
+    #[test]
+    fn decodes_all_modes() {
+        let mut decoder = FluentDecoder::new(default_max_length(), false);
+
+        let message = MsgPackValue::Array(vec![
+            "app.log".into(),
+            1_600_000_000.into(),
+            MsgPackValue::Map(vec![("message".into(), "one".into())]),
+        ]);
+        let forward = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Array(vec![entry(1, "two"), entry(2, "three")]),
+        ]);
+        let packed = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Binary([encode(&entry(3, "four")), encode(&entry(4, "five"))].concat()),
+        ]);
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(&encode(&entry(5, "six"))).unwrap();
+        let compressed = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Binary(gzip.finish().unwrap()),
+            MsgPackValue::Map(vec![("compressed".into(), "gzip".into())]),
+        ]);
+
+        let bytes = [
+            encode(&message),
+            encode(&forward),
+            encode(&packed),
+            encode(&compressed),
+        ]
+        .concat();
+        // Messages split across reads are decoded once complete.
+        let mut buf = BytesMut::from(&bytes[..bytes.len() - 3]);
+        let frames = decode_buf(&mut decoder, &mut buf);
+        assert_eq!(
+            messages(&frames),
+            vec!["one", "two", "three", "four", "five"]
+        );
+        buf.extend_from_slice(&bytes[bytes.len() - 3..]);
+        let frames = decode_buf(&mut decoder, &mut buf);
+        assert_eq!(messages(&frames), vec!["six"]);
+
+        let frames = decode_all(&mut decoder, &bytes);
+        assert_eq!(frames.len(), 6);
+        let log = frames[0].event.as_ref().unwrap().as_log();
+        assert_eq!(log["tag"], "app.log".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_600_000_000, 0).into()
+        );
+    }
+
+    #[test]
+    fn decodes_event_time() {
+        let time = MsgPackValue::Ext(0, [1u32.to_be_bytes(), 500u32.to_be_bytes()].concat());
+        assert_eq!(timestamp_from_value(time).unwrap(), Utc.timestamp(1, 500));
+    }
+
+    #[test]
+    fn rejects_out_of_range_times() {
+        let times = vec![
+            MsgPackValue::from(i64::MAX),
+            MsgPackValue::from(i64::MIN),
+            MsgPackValue::from(1e300),
+            MsgPackValue::from(f64::NAN),
+            MsgPackValue::Ext(
+                0,
+                [1u32.to_be_bytes(), 3_000_000_000u32.to_be_bytes()].concat(),
+            ),
+        ];
+        for time in times {
+            assert!(matches!(
+                timestamp_from_value(time),
+                Err(DecodeError::InvalidTime)
+            ));
+        }
+
+        let mut decoder = FluentDecoder::new(default_max_length(), false);
+        let message = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Array(vec![entry(i64::MAX, "huge"), entry(1, "one")]),
+        ]);
+        let frames = decode_all(&mut decoder, &encode(&message));
+        assert_eq!(messages(&frames), vec!["one"]);
+    }
+
+    #[test]
+    fn skips_invalid_messages() {
+        let mut decoder = FluentDecoder::new(default_max_length(), false);
+        let bytes = [
+            encode(&MsgPackValue::Array(vec!["app.log".into()])),
+            encode(&MsgPackValue::Map(vec![])),
+            encode(&MsgPackValue::Array(vec![
+                "app.log".into(),
+                MsgPackValue::Array(vec![entry(1, "one"), "not an entry".into()]),
+            ])),
+        ]
+        .concat();
+
+        let frames = decode_all(&mut decoder, &bytes);
+        assert_eq!(messages(&frames), vec!["one"]);
+    }
+
+    #[test]
+    fn drops_long_messages() {
+        let mut decoder = FluentDecoder::new(100, false);
+        let long = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Array(vec![entry(1, &"a".repeat(100))]),
+        ]);
+        let short = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Array(vec![entry(2, "short")]),
+        ]);
+
+        // Complete messages are skipped.
+        let bytes = [encode(&long), encode(&short)].concat();
+        let frames = decode_all(&mut decoder, &bytes);
+        assert_eq!(messages(&frames), vec!["short"]);
+
+        // Incomplete ones aren't buffered past the maximum.
+        let long = encode(&long);
+        let mut buf = BytesMut::from(&long[..long.len() - 1]);
+        assert!(decode_buf(&mut decoder, &mut buf).is_empty());
+        assert!(buf.is_empty());
+
+        // Nor are compressed entries decompressed past it.
+        let mut gzip = GzEncoder::new(Vec::new(), Compression::default());
+        gzip.write_all(&encode(&entry(1, &"a".repeat(1000))))
+            .unwrap();
+        let compressed = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Binary(gzip.finish().unwrap()),
+            MsgPackValue::Map(vec![("compressed".into(), "gzip".into())]),
+        ]);
+        let compressed = encode(&compressed);
+        assert!(compressed.len() < 100);
+        let bytes = [compressed, encode(&short)].concat();
+        let frames = decode_all(&mut decoder, &bytes);
+        assert_eq!(messages(&frames), vec!["short"]);
+    }
+
+    #[test]
+    fn decodes_messages_read_byte_by_byte() {
+        let mut decoder = FluentDecoder::new(default_max_length(), false);
+        let message = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Array(vec![entry(1, "one"), entry(2, &"a".repeat(300))]),
+            MsgPackValue::Map(vec![("size".into(), 2.into())]),
+        ]);
+        let bytes = [encode(&message), encode(&message)].concat();
+
+        let mut buf = BytesMut::new();
+        let mut frames = Vec::new();
+        for &byte in &bytes {
+            buf.extend_from_slice(&[byte]);
+            frames.extend(decode_buf(&mut decoder, &mut buf));
+        }
+        assert_eq!(frames.len(), 4);
+        assert!(buf.is_empty());
+        assert_eq!(messages(&frames)[2], "one");
+    }
+
+    #[tokio::test]
+    async fn acknowledges_chunks() {
+        let mut decoder = FluentDecoder::new(default_max_length(), false);
+        let message = MsgPackValue::Array(vec![
+            "app.log".into(),
+            MsgPackValue::Array(vec![entry(1, "one"), entry(2, "two")]),
+            MsgPackValue::Map(vec![("chunk".into(), "p8n9gmxTQVC8/nh2wlKKeQ==".into())]),
+        ]);
+
+        let mut frames = decode_all(&mut decoder, &encode(&message));
+        assert!(frames[0].ack.is_none());
+        let ack = frames[1].ack.take().unwrap().await.unwrap();
+        assert_eq!(
+            rmpv::decode::read_value(&mut &ack[..]).unwrap(),
+            MsgPackValue::Map(vec![("ack".into(), "p8n9gmxTQVC8/nh2wlKKeQ==".into())])
+        );
+    }
+
+    #[tokio::test]
+    async fn receives_over_tcp() {
+        let (tx, rx) = Pipeline::new_test();
+        let address = next_addr();
+        let config = FluentConfig {
+            address: address.into(),
+            keepalive: None,
+            tls: None,
+            receive_buffer_bytes: None,
+            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            max_length: default_max_length(),
+            acknowledgements: false,
+        };
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        wait_for_tcp(address).await;
+
+        let message = MsgPackValue::Array(vec![
+            "app.log".into(),
+            1.into(),
+            MsgPackValue::Map(vec![("message".into(), "hello".into())]),
+            MsgPackValue::Map(vec![("chunk".into(), "abc".into())]),
+        ]);
+        let mut stream = TcpStream::connect(address).await.unwrap();
+        stream.write_all(&encode(&message)).await.unwrap();
+
+        let mut ack = [0u8; 9];
+        stream.read_exact(&mut ack).await.unwrap();
+        assert_eq!(
+            rmpv::decode::read_value(&mut &ack[..]).unwrap(),
+            MsgPackValue::Map(vec![("ack".into(), "abc".into())])
+        );
+
+        let events = collect_ready(rx).await;
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["tag"], "app.log".into());
+        assert_eq!(log[log_schema().source_type_key()], "fluent".into());
+        assert!(log.contains(log_schema().host_key()));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     config::{
         log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
         SourceDescription,
@@ -16,7 +17,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use std::{collections::HashMap, net::SocketAddr};
 
-use tokio_util::codec::Decoder;
+use tokio_util::codec::Decoder as _;
 use warp::http::{HeaderMap, HeaderValue, StatusCode};
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -30,6 +31,9 @@ pub struct SimpleHttpConfig {
     query_parameters: Vec<String>,
     tls: Option<TlsConfig>,
     auth: Option<HttpSourceAuthConfig>,
+    /// Takes precedence over `encoding` when set, as does `decoding`.
+    framing: Option<FramingConfig>,
+    decoding: Option<DecodingConfig>,
 }
 
 inventory::submit! {
@@ -45,6 +49,8 @@ impl GenerateConfig for SimpleHttpConfig {
             query_parameters: Vec::new(),
             tls: None,
             auth: None,
+            framing: None,
+            decoding: None,
         })
         .unwrap()
     }
@@ -53,6 +59,7 @@ impl GenerateConfig for SimpleHttpConfig {
 #[derive(Clone)]
 struct SimpleHttpSource {
     encoding: Encoding,
+    decoder: Option<Decoder>,
     headers: Vec<String>,
     query_parameters: Vec<String>,
 }
@@ -74,7 +81,16 @@ impl HttpSource for SimpleHttpSource {
         header_map: HeaderMap,
         query_parameters: HashMap<String, String>,
     ) -> Result<Vec<Event>, ErrorMessage> {
-        decode_body(body, self.encoding)
+        let events = match &self.decoder {
+            Some(decoder) => Ok(decoder
+                .clone()
+                .decode_all(&body)
+                .into_iter()
+                .map(|(event, _)| event)
+                .collect()),
+            None => decode_body(body, self.encoding),
+        };
+        events
             .map(|events| add_headers(events, &self.headers, header_map))
             .map(|events| add_query_parameters(events, &self.query_parameters, query_parameters))
             .map(|mut events| {
@@ -98,8 +114,19 @@ impl SourceConfig for SimpleHttpConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
+        let decoder = if self.framing.is_some() || self.decoding.is_some() {
+            let framing = self
+                .framing
+                .clone()
+                .unwrap_or(FramingConfig::NewlineDelimited { max_length: None });
+            let decoding = self.decoding.clone().unwrap_or_default();
+            Some(Decoder::from_config(&framing, &decoding)?)
+        } else {
+            None
+        };
         let source = SimpleHttpSource {
             encoding: self.encoding,
+            decoder,
             headers: self.headers.clone(),
             query_parameters: self.query_parameters.clone(),
         };
@@ -233,6 +260,7 @@ fn json_value_to_type_string(value: &JsonValue) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::{Encoding, SimpleHttpConfig};
+    use crate::codecs::DecodingConfig;
 
     use crate::shutdown::ShutdownSignal;
     use crate::{
@@ -261,6 +289,15 @@ mod tests {
         encoding: Encoding,
         headers: Vec<String>,
         query_parameters: Vec<String>,
+    ) -> (mpsc::Receiver<Event>, SocketAddr) {
+        source_with_decoding(encoding, None, headers, query_parameters).await
+    }
+
+    async fn source_with_decoding(
+        encoding: Encoding,
+        decoding: Option<DecodingConfig>,
+        headers: Vec<String>,
+        query_parameters: Vec<String>,
     ) -> (mpsc::Receiver<Event>, SocketAddr) {
         let (sender, recv) = Pipeline::new_test();
         let address = next_addr();
@@ -272,6 +309,8 @@ mod tests {
                 query_parameters,
                 tls: None,
                 auth: None,
+                framing: None,
+                decoding,
             }
             .build(
                 "default",
@@ -361,6 +400,26 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn http_decoding_overrides_encoding() {
+        trace_init();
+
+        let body = "{\"key\": \"value\"}\nnot json\n{\"key\": \"value 2\"}";
+
+        let (rx, addr) =
+            source_with_decoding(Encoding::Text, Some(DecodingConfig::Json), vec![], vec![]).await;
+
+        assert_eq!(200, send(addr, body).await);
+
+        let events = collect_n(rx, 2).await;
+        assert_eq!(events[0].as_log()["key"], "value".into());
+        assert_eq!(events[1].as_log()["key"], "value 2".into());
+        assert_eq!(
+            events[1].as_log()[log_schema().source_type_key()],
+            "http".into()
+        );
+    }
+
     #[tokio::test]
     async fn http_multiline_text2() {
         trace_init();
@@ -0,0 +1,406 @@
+//! Polls HTTP endpoints on an interval and decodes the bodies of their
+//! responses into events, e.g. JSON documents, NDJSON or lines of text.
+
+use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, ProxyConfig, SourceConfig,
+        SourceDescription,
+    },
+    event::Event,
+    http::{Auth, HttpClient},
+    internal_events::{
+        HttpScrapeErrorResponse, HttpScrapeEventReceived, HttpScrapeRequestCompleted,
+        HttpScrapeRequestError,
+    },
+    shutdown::ShutdownSignal,
+    template::Template,
+    tls::{TlsOptions, TlsSettings},
+    Pipeline,
+};
+use bytes::Bytes;
+use futures::{future::join_all, stream, SinkExt, StreamExt};
+use http::{
+    header::{HeaderMap, HeaderName, HeaderValue, InvalidHeaderName, InvalidHeaderValue},
+    uri::InvalidUri,
+    Request, StatusCode, Uri,
+};
+use hyper::{body::to_bytes as body_to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use std::{collections::BTreeMap, time::Instant};
+use tokio::time::{self, Duration};
+
+const ENDPOINT: &str = "endpoint";
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Invalid endpoint {:?}: {}", endpoint, source))]
+    InvalidEndpoint {
+        endpoint: String,
+        source: InvalidUri,
+    },
+    #[snafu(display("Invalid header name {:?}: {}", name, source))]
+    InvalidHeaderName {
+        name: String,
+        source: InvalidHeaderName,
+    },
+    #[snafu(display("Invalid header value {:?}: {}", value, source))]
+    InvalidHeaderValue {
+        value: String,
+        source: InvalidHeaderValue,
+    },
+    #[snafu(display(
+        "Query parameter {:?} can't reference event fields, only strftime specifiers",
+        name
+    ))]
+    QueryParameterFields { name: String },
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct HttpScrapeConfig {
+    endpoints: Vec<String>,
+    #[serde(default = "default_scrape_interval_secs")]
+    scrape_interval_secs: u64,
+    #[serde(default)]
+    query: BTreeMap<String, Template>,
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    auth: Option<Auth>,
+    tls: Option<TlsOptions>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
+    #[serde(default)]
+    framing: FramingConfig,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+pub fn default_scrape_interval_secs() -> u64 {
+    15
+}
+
+inventory::submit! {
+    SourceDescription::new::<HttpScrapeConfig>("http_scrape")
+}
+
+impl GenerateConfig for HttpScrapeConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoints = ["http://localhost:8080/status"]
+            decoding.codec = "json""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "http_scrape")]
+impl SourceConfig for HttpScrapeConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        for (name, template) in &self.query {
+            if template.get_fields().is_some() {
+                return Err(BuildError::QueryParameterFields { name: name.clone() }.into());
+            }
+        }
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.headers {
+            headers.insert(
+                HeaderName::from_bytes(name.as_bytes())
+                    .with_context(|| InvalidHeaderName { name })?,
+                HeaderValue::from_str(value).with_context(|| InvalidHeaderValue { value })?,
+            );
+        }
+        let endpoints = self
+            .endpoints
+            .iter()
+            .map(|endpoint| {
+                let uri = endpoint
+                    .parse::<Uri>()
+                    .with_context(|| InvalidEndpoint { endpoint })?;
+                Ok(Endpoint {
+                    endpoint: endpoint.clone(),
+                    uri,
+                })
+            })
+            .collect::<Result<Vec<_>, BuildError>>()?;
+
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let proxy = ProxyConfig::merge_with_env(&globals.proxy, &self.proxy);
+        let scraper = Scraper {
+            client: HttpClient::new(tls, &proxy)?,
+            query: self.query.clone(),
+            headers,
+            auth: self.auth.clone(),
+            decoder: Decoder::from_config(&self.framing, &self.decoding)?,
+        };
+
+        let mut out =
+            out.sink_map_err(|error| error!(message = "Error sending scraped events.", %error));
+
+        let duration = Duration::from_secs(self.scrape_interval_secs);
+        Ok(Box::pin(async move {
+            let mut interval = time::interval(duration).take_until(shutdown);
+            while interval.next().await.is_some() {
+                let events =
+                    join_all(endpoints.iter().map(|endpoint| scraper.scrape(endpoint))).await;
+
+                let mut stream = stream::iter(events).flat_map(stream::iter).map(Ok);
+                out.send_all(&mut stream).await?;
+            }
+
+            Ok(())
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "http_scrape"
+    }
+}
+
+struct Endpoint {
+    /// The endpoint as configured, added to the events scraped from it.
+    endpoint: String,
+    uri: Uri,
+}
+
+struct Scraper {
+    client: HttpClient,
+    query: BTreeMap<String, Template>,
+    headers: HeaderMap,
+    auth: Option<Auth>,
+    decoder: Decoder,
+}
+
+impl Scraper {
+    /// The endpoint with the query parameters, rendered at the current time,
+    /// added to its own.
+    fn uri(&self, endpoint: &Endpoint) -> String {
+        if self.query.is_empty() {
+            return endpoint.endpoint.clone();
+        }
+
+        let now = Event::new_empty_log();
+        let mut query = url::form_urlencoded::Serializer::new(String::new());
+        for (name, template) in &self.query {
+            let value = template
+                .render_string(&now)
+                .expect("templates without fields always render");
+            query.append_pair(name, &value);
+        }
+        let separator = match endpoint.uri.query() {
+            Some(_) => "&",
+            None => "?",
+        };
+        format!("{}{}{}", endpoint.endpoint, separator, query.finish())
+    }
+
+    async fn scrape(&self, endpoint: &Endpoint) -> Vec<Event> {
+        let start = Instant::now();
+        let body = match self.get(endpoint).await {
+            Ok(Ok(body)) => body,
+            Ok(Err(code)) => {
+                emit!(HttpScrapeErrorResponse {
+                    code,
+                    endpoint: &endpoint.endpoint,
+                });
+                return Vec::new();
+            }
+            Err(error) => {
+                emit!(HttpScrapeRequestError {
+                    error,
+                    endpoint: &endpoint.endpoint,
+                });
+                return Vec::new();
+            }
+        };
+        emit!(HttpScrapeRequestCompleted {
+            start,
+            end: Instant::now()
+        });
+
+        let events = self
+            .decoder
+            .clone()
+            .decode_all(&body)
+            .into_iter()
+            .map(|(mut event, _)| {
+                let log = event.as_mut_log();
+                log.insert(log_schema().source_type_key(), Bytes::from("http_scrape"));
+                log.insert(ENDPOINT, endpoint.endpoint.clone());
+                event
+            })
+            .collect::<Vec<_>>();
+        emit!(HttpScrapeEventReceived {
+            byte_size: body.len(),
+            count: events.len(),
+            endpoint: &endpoint.endpoint,
+        });
+        events
+    }
+
+    /// Returns the body of the response, or its status if it isn't a
+    /// success.
+    async fn get(&self, endpoint: &Endpoint) -> crate::Result<Result<Bytes, StatusCode>> {
+        let mut request = Request::get(self.uri(endpoint)).body(Body::empty())?;
+        request.headers_mut().extend(self.headers.clone());
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await?;
+        let (parts, body) = response.into_parts();
+        if parts.status.is_success() {
+            Ok(Ok(body_to_bytes(body).await?))
+        } else {
+            Ok(Err(parts.status))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_util::{collect_ready, next_addr, trace_init, wait_for_tcp},
+        Error,
+    };
+    use chrono::Utc;
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Response, Server,
+    };
+    use std::net::SocketAddr;
+    use tokio::time::delay_for;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<HttpScrapeConfig>();
+    }
+
+    /// Serves `body`, after the query and `x-api-key` header of the request
+    /// are substituted for `{query}` and `{key}`.
+    async fn serve(body: &'static str) -> SocketAddr {
+        let addr = next_addr();
+        let make_svc = make_service_fn(move |_| async move {
+            Ok::<_, Error>(service_fn(move |request: Request<Body>| async move {
+                let query = request.uri().query().unwrap_or("").to_owned();
+                let key = request
+                    .headers()
+                    .get("x-api-key")
+                    .map(|key| key.to_str().unwrap().to_owned())
+                    .unwrap_or_default();
+                Ok::<_, Error>(Response::new(Body::from(
+                    body.replace("{query}", &query).replace("{key}", &key),
+                )))
+            }))
+        });
+        tokio::spawn(async move {
+            if let Err(error) = Server::bind(&addr).serve(make_svc).await {
+                error!(message = "Server error.", %error);
+            }
+        });
+        wait_for_tcp(addr).await;
+        addr
+    }
+
+    async fn scrape(config: &str) -> Vec<Event> {
+        let config: HttpScrapeConfig = toml::from_str(config).unwrap();
+        let (tx, rx) = Pipeline::new_test();
+        let (trigger, shutdown, _) = ShutdownSignal::new_wired();
+        let source = config
+            .build("default", &GlobalOptions::default(), shutdown, tx)
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        delay_for(Duration::from_millis(500)).await;
+        drop(trigger);
+        collect_ready(rx).await
+    }
+
+    #[tokio::test]
+    async fn scrapes_json() {
+        trace_init();
+
+        let addr = serve(r#"[{"query": "{query}", "key": "{key}"}, {"count": 2}]"#).await;
+        let events = scrape(&format!(
+            r#"
+            endpoints = ["http://{}/status?verbose=true"]
+            decoding.codec = "json"
+            query.year = "%Y"
+            headers.x-api-key = "secret"
+            "#,
+            addr
+        ))
+        .await;
+
+        assert_eq!(events.len(), 2);
+        let log = events[0].as_log();
+        let query = format!("verbose=true&year={}", Utc::now().format("%Y"));
+        assert_eq!(log["query"], query.into());
+        assert_eq!(log["key"], "secret".into());
+        assert_eq!(log[log_schema().source_type_key()], "http_scrape".into());
+        assert_eq!(
+            log[ENDPOINT],
+            format!("http://{}/status?verbose=true", addr).into()
+        );
+        assert_eq!(events[1].as_log()["count"], 2.into());
+    }
+
+    #[tokio::test]
+    async fn scrapes_text_lines() {
+        trace_init();
+
+        let addr = serve("first line\nsecond line\n").await;
+        let events = scrape(&format!(
+            r#"
+            endpoints = ["http://{}"]
+            framing.method = "newline_delimited"
+            "#,
+            addr
+        ))
+        .await;
+
+        let messages = events
+            .iter()
+            .map(|event| event.as_log()[log_schema().message_key()].to_string_lossy())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first line", "second line"]);
+    }
+
+    #[tokio::test]
+    async fn rejects_query_field_templates() {
+        let config: HttpScrapeConfig = toml::from_str(
+            r#"
+            endpoints = ["http://localhost:8080"]
+            query.host = "{{ host }}"
+            "#,
+        )
+        .unwrap();
+        let result = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                Pipeline::new_test().0,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
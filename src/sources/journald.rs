@@ -1,8 +1,9 @@
 use crate::{
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
-    event::{Event, LogEvent, Value},
+    event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
     internal_events::{JournaldEventReceived, JournaldInvalidRecord},
     shutdown::ShutdownSignal,
+    sources::util::OrderedFinalizer,
     Pipeline,
 };
 use bytes::Bytes;
@@ -24,6 +25,7 @@ use std::{
     path::PathBuf,
     process::Stdio,
     str::FromStr,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 use tokio_util::codec::FramedRead;
@@ -75,6 +77,7 @@ pub struct JournaldConfig {
     pub data_dir: Option<PathBuf>,
     pub batch_size: Option<usize>,
     pub journalctl_path: Option<PathBuf>,
+    pub acknowledgements: bool,
     /// Deprecated
     #[serde(default)]
     remap_priority: bool,
@@ -145,6 +148,7 @@ impl SourceConfig for JournaldConfig {
                 checkpoint_path,
                 batch_size,
                 remap_priority: self.remap_priority,
+                acknowledgements: self.acknowledgements,
                 out,
             }
             .run_shutdown(shutdown, start)
@@ -161,12 +165,39 @@ impl SourceConfig for JournaldConfig {
     }
 }
 
+/// The cursor up to which batches were finalized, which is what gets
+/// checkpointed with acknowledgements.
+#[derive(Default)]
+struct Acked {
+    cursor: Option<String>,
+    /// Set once a batch errored, so that it's read again on restart.
+    held: bool,
+}
+
+impl Acked {
+    fn finalized(&mut self, status: BatchStatus, cursor: String) {
+        match status {
+            _ if self.held => {}
+            BatchStatus::Errored => {
+                error!(
+                    message = "Events were not delivered, no longer updating the checkpoint.",
+                    ?status,
+                );
+                self.held = true;
+            }
+            // Rejected events won't ever be delivered, so they're skipped.
+            BatchStatus::Delivered | BatchStatus::Failed => self.cursor = Some(cursor),
+        }
+    }
+}
+
 struct JournaldSource {
     include_units: HashSet<String>,
     exclude_units: HashSet<String>,
     checkpoint_path: PathBuf,
     batch_size: usize,
     remap_priority: bool,
+    acknowledgements: bool,
     out: Pipeline,
 }
 
@@ -197,12 +228,30 @@ impl JournaldSource {
             }
         };
 
+        // Batches are finalized off the read path, and in the order they
+        // were read, so that a checkpoint never skips over a pending batch.
+        let acked = Arc::new(Mutex::new(Acked {
+            cursor: cursor.clone(),
+            held: false,
+        }));
+        let finalizer = if self.acknowledgements {
+            let acked = Arc::clone(&acked);
+            Some(OrderedFinalizer::new(move |status, cursor| {
+                acked.lock().unwrap().finalized(status, cursor)
+            }))
+        } else {
+            None
+        };
+        let acknowledgements = self.acknowledgements;
+
         let mut on_stop = None;
         let run = Box::pin(self.run(
             &mut checkpointer,
             &mut cursor,
             &mut on_stop,
             start_journalctl,
+            &finalizer,
+            &acked,
         ));
         future::select(run, shutdown).await;
 
@@ -210,6 +259,9 @@ impl JournaldSource {
             stop();
         }
 
+        if acknowledgements {
+            cursor = acked.lock().unwrap().cursor.clone();
+        }
         Self::save_checkpoint(&mut checkpointer, &cursor).await;
 
         Ok(())
@@ -221,13 +273,17 @@ impl JournaldSource {
         cursor: &'a mut Option<String>,
         on_stop: &'a mut Option<StopJournalctlFn>,
         start_journalctl: StartJournalctlFn,
+        finalizer: &'a Option<OrderedFinalizer<String>>,
+        acked: &'a Mutex<Acked>,
     ) {
         loop {
             info!("Starting journalctl.");
             match start_journalctl(&*cursor) {
                 Ok((stream, stop)) => {
                     *on_stop = Some(stop);
-                    let should_restart = self.run_stream(stream, checkpointer, cursor).await;
+                    let should_restart = self
+                        .run_stream(stream, checkpointer, cursor, finalizer, acked)
+                        .await;
                     if let Some(stop) = on_stop.take() {
                         stop();
                     }
@@ -248,14 +304,22 @@ impl JournaldSource {
 
     /// Process `journalctl` output until some error occurs.
     /// Return `true` if should restart `journalctl`.
+    ///
+    /// `cursor` is where `journalctl` is restarted from. With
+    /// acknowledgements, the checkpoint is instead the cursor in `acked`,
+    /// which only advances once a batch is finalized.
     async fn run_stream<'a>(
         &'a mut self,
         mut stream: BoxStream<'static, io::Result<Bytes>>,
         checkpointer: &'a mut Checkpointer,
         cursor: &'a mut Option<String>,
+        finalizer: &'a Option<OrderedFinalizer<String>>,
+        acked: &'a Mutex<Acked>,
     ) -> bool {
         loop {
             let mut saw_record = false;
+            let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+            let mut batch_cursor = None;
 
             for _ in 0..self.batch_size {
                 let bytes = match stream.next().await {
@@ -284,6 +348,7 @@ impl JournaldSource {
                     }
                 };
                 if let Some(tmp) = record.remove(&*CURSOR) {
+                    batch_cursor = Some(tmp.clone());
                     *cursor = Some(tmp);
                 }
 
@@ -298,7 +363,8 @@ impl JournaldSource {
                     byte_size: bytes.len()
                 });
 
-                match self.out.send(create_event(record)).await {
+                let event = create_event(record).with_batch_notifier_option(&batch);
+                match self.out.send(event).await {
                     Ok(_) => {}
                     Err(error) => {
                         error!(message = "Could not send journald log.", %error);
@@ -308,8 +374,20 @@ impl JournaldSource {
                 }
             }
 
+            drop(batch);
+            if let (Some(finalizer), Some(receiver), Some(batch_cursor)) =
+                (finalizer, receiver, batch_cursor)
+            {
+                finalizer.add(batch_cursor, receiver).await;
+            }
+
             if saw_record {
-                Self::save_checkpoint(checkpointer, &*cursor).await;
+                if finalizer.is_some() {
+                    let acked = acked.lock().unwrap().cursor.clone();
+                    Self::save_checkpoint(checkpointer, &acked).await;
+                } else {
+                    Self::save_checkpoint(checkpointer, &*cursor).await;
+                }
             }
         }
     }
@@ -674,6 +752,7 @@ mod tests {
             checkpoint_path,
             batch_size: DEFAULT_BATCH_SIZE,
             remap_priority: true,
+            acknowledgements: false,
             out: tx,
         }
         .run_shutdown(shutdown, Box::new(FakeJournal::new));
@@ -685,6 +764,18 @@ mod tests {
         timeout(Duration::from_secs(1), rx.collect()).await.unwrap()
     }
 
+    #[test]
+    fn acked_cursor_skips_failed_and_holds_errored() {
+        let mut acked = Acked::default();
+        acked.finalized(BatchStatus::Delivered, "1".into());
+        acked.finalized(BatchStatus::Failed, "2".into());
+        assert_eq!(acked.cursor, Some("2".into()));
+
+        acked.finalized(BatchStatus::Errored, "3".into());
+        acked.finalized(BatchStatus::Delivered, "4".into());
+        assert_eq!(acked.cursor, Some("2".into()));
+    }
+
     #[tokio::test]
     async fn reads_journal() {
         let received = run_journal(&[], &[], None).await;
@@ -1,7 +1,11 @@
+use super::util::OrderedFinalizer;
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     config::{log_schema, DataType, GlobalOptions, SourceConfig, SourceDescription},
-    event::{Event, Value},
-    internal_events::{KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed},
+    event::{BatchNotifier, BatchStatus, Value},
+    internal_events::{
+        KafkaEventDeliveryFailed, KafkaEventFailed, KafkaEventReceived, KafkaOffsetUpdateFailed,
+    },
     kafka::KafkaAuthConfig,
     shutdown::ShutdownSignal,
     Pipeline,
@@ -13,10 +17,14 @@ use rdkafka::{
     config::ClientConfig,
     consumer::{Consumer, StreamConsumer},
     message::Message,
+    Offset, TopicPartitionList,
 };
 use serde::{Deserialize, Serialize};
 use snafu::{ResultExt, Snafu};
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +57,12 @@ pub struct KafkaSourceConfig {
     librdkafka_options: Option<HashMap<String, String>>,
     #[serde(flatten)]
     auth: KafkaAuthConfig,
+    #[serde(default)]
+    acknowledgements: bool,
+    #[serde(default)]
+    framing: FramingConfig,
+    #[serde(default)]
+    decoding: DecodingConfig,
 }
 
 fn default_session_timeout_ms() -> u64 {
@@ -109,8 +123,45 @@ fn kafka_source(
     let partition_key = config.partition_key.clone();
     let offset_key = config.offset_key.clone();
     let consumer = Arc::new(create_consumer(config)?);
+    let acknowledgements = config.acknowledgements;
+    let decoder = Decoder::from_config(&config.framing, &config.decoding)?;
 
     Ok(Box::pin(async move {
+        // Offsets are stored in the order the messages were consumed, so that
+        // storing an offset never skips over a message still being delivered.
+        // Rejected messages won't ever be delivered, so they're skipped. Once
+        // a message of a partition errors, no later offset of the partition
+        // is stored, so that it's consumed again on restart.
+        let finalizer = if acknowledgements {
+            let consumer = Arc::clone(&consumer);
+            let mut errored_partitions = HashSet::new();
+            Some(Arc::new(OrderedFinalizer::new(
+                move |status, (topic, partition, offset): (String, i32, i64)| {
+                    if errored_partitions.contains(&(topic.clone(), partition)) {
+                        return;
+                    }
+                    if status != BatchStatus::Delivered {
+                        emit!(KafkaEventDeliveryFailed {
+                            topic: &topic,
+                            partition,
+                            offset,
+                            status,
+                        });
+                    }
+                    match status {
+                        BatchStatus::Delivered | BatchStatus::Failed => {
+                            store_offset(&consumer, &topic, partition, offset)
+                        }
+                        BatchStatus::Errored => {
+                            errored_partitions.insert((topic, partition));
+                        }
+                    }
+                },
+            )))
+        } else {
+            None
+        };
+
         Arc::clone(&consumer)
             .start()
             .take_until(shutdown.clone())
@@ -120,6 +171,8 @@ fn kafka_source(
                 let partition_key = partition_key.clone();
                 let offset_key = offset_key.clone();
                 let consumer = Arc::clone(&consumer);
+                let finalizer = finalizer.clone();
+                let mut decoder = decoder.clone();
 
                 async move {
                     match message {
@@ -136,13 +189,6 @@ fn kafka_source(
                                 None => return Err(()), // skip messages with empty payload
                                 Some(payload) => payload,
                             };
-                            let mut event = Event::new_empty_log();
-                            let log = event.as_mut_log();
-
-                            log.insert(
-                                log_schema().message_key(),
-                                Value::from(Bytes::from(payload.to_owned())),
-                            );
 
                             // Extract timestamp from kafka message
                             let timestamp = msg
@@ -150,40 +196,65 @@ fn kafka_source(
                                 .to_millis()
                                 .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
                                 .unwrap_or_else(Utc::now);
-                            log.insert(log_schema().timestamp_key(), timestamp);
-
-                            // Add source type
-                            log.insert(log_schema().source_type_key(), Bytes::from("kafka"));
-
-                            if let Some(key_field) = &key_field {
-                                match msg.key() {
-                                    None => (),
-                                    Some(key) => {
-                                        log.insert(
-                                            key_field,
-                                            Value::from(String::from_utf8_lossy(key).to_string()),
-                                        );
+
+                            let (batch, receiver) =
+                                BatchNotifier::maybe_new_with_receiver(finalizer.is_some());
+                            let events = decoder
+                                .decode_all(payload)
+                                .into_iter()
+                                .map(|(mut event, _)| {
+                                    let log = event.as_mut_log();
+
+                                    log.insert(log_schema().timestamp_key(), timestamp);
+
+                                    // Add source type
+                                    log.insert(
+                                        log_schema().source_type_key(),
+                                        Bytes::from("kafka"),
+                                    );
+
+                                    if let Some(key_field) = &key_field {
+                                        match msg.key() {
+                                            None => (),
+                                            Some(key) => {
+                                                log.insert(
+                                                    key_field,
+                                                    Value::from(
+                                                        String::from_utf8_lossy(key).to_string(),
+                                                    ),
+                                                );
+                                            }
+                                        }
                                     }
-                                }
-                            }
 
-                            if let Some(topic_key) = &topic_key {
-                                log.insert(topic_key, Value::from(msg.topic().to_string()));
-                            }
+                                    if let Some(topic_key) = &topic_key {
+                                        log.insert(topic_key, Value::from(msg.topic().to_string()));
+                                    }
 
-                            if let Some(partition_key) = &partition_key {
-                                log.insert(partition_key, Value::from(msg.partition()));
-                            }
+                                    if let Some(partition_key) = &partition_key {
+                                        log.insert(partition_key, Value::from(msg.partition()));
+                                    }
 
-                            if let Some(offset_key) = &offset_key {
-                                log.insert(offset_key, Value::from(msg.offset()));
-                            }
+                                    if let Some(offset_key) = &offset_key {
+                                        log.insert(offset_key, Value::from(msg.offset()));
+                                    }
 
-                            consumer.store_offset(&msg).map_err(|error| {
-                                emit!(KafkaOffsetUpdateFailed { error });
-                            })?;
+                                    event.with_batch_notifier_option(&batch)
+                                })
+                                .collect::<Vec<_>>();
 
-                            Ok(event)
+                            match (&finalizer, receiver) {
+                                (Some(finalizer), Some(receiver)) => {
+                                    let entry =
+                                        (msg.topic().to_string(), msg.partition(), msg.offset());
+                                    finalizer.add(entry, receiver).await
+                                }
+                                _ => consumer.store_offset(&msg).map_err(|error| {
+                                    emit!(KafkaOffsetUpdateFailed { error });
+                                })?,
+                            }
+
+                            Ok(events)
                         }
                     }
                 }
@@ -194,11 +265,11 @@ fn kafka_source(
             //     out.sink_compat()
             //         .sink_map_err(|error| error!(message = "Error sending to sink.", %error)),
             // )
-            .for_each(|item| {
+            .for_each(|events| {
                 let mut out = out.clone();
                 async move {
-                    if let Ok(item) = item {
-                        if let Err(error) = out.send(item).await {
+                    for event in events.into_iter().flatten() {
+                        if let Err(error) = out.send(event).await {
                             error!(message = "Error sending to sink.", %error);
                         }
                     }
@@ -209,6 +280,17 @@ fn kafka_source(
     }))
 }
 
+fn store_offset(consumer: &StreamConsumer, topic: &str, partition: i32, offset: i64) {
+    // The stored offset is the one of the next message to consume.
+    let mut offsets = TopicPartitionList::new();
+    if let Err(error) = offsets
+        .add_partition_offset(topic, partition, Offset::Offset(offset + 1))
+        .and_then(|_| consumer.store_offsets(&offsets))
+    {
+        emit!(KafkaOffsetUpdateFailed { error });
+    }
+}
+
 fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer> {
     let mut client_config = ClientConfig::new();
     client_config
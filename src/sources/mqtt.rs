@@ -0,0 +1,261 @@
+use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    internal_events::{MqttConnectionFailed, MqttEventReceived, MqttSubscribeFailed},
+    mqtt::{MqttConnectionConfig, MqttQos},
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use rumqttc::{AsyncClient, Event, EventLoop, Packet, QoS};
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::time::Duration;
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one topic filter is required in `topics`"))]
+    NoTopics,
+    #[snafu(display("Persistent sessions require a fixed `client_id`"))]
+    PersistentSessionWithoutClientId,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MqttSourceConfig {
+    #[serde(flatten)]
+    connection: MqttConnectionConfig,
+    topics: Vec<String>,
+    #[serde(default)]
+    qos: MqttQos,
+    #[serde(default = "crate::serde::default_true")]
+    clean_session: bool,
+    #[serde(default = "default_topic_key")]
+    topic_key: String,
+    #[serde(default)]
+    framing: FramingConfig,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+fn default_topic_key() -> String {
+    "topic".into()
+}
+
+inventory::submit! {
+    SourceDescription::new::<MqttSourceConfig>("mqtt")
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics.into());
+        }
+        if !self.clean_session && self.connection.client_id.is_none() {
+            return Err(BuildError::PersistentSessionWithoutClientId.into());
+        }
+
+        let mut options = self.connection.options()?;
+        options.set_clean_session(self.clean_session);
+        // The subscriptions are requested at once after connecting, while the
+        // event loop isn't polled, so the channel has to fit all of them.
+        let (client, eventloop) = AsyncClient::new(options, self.topics.len().max(10));
+        let decoder = Decoder::from_config(&self.framing, &self.decoding)?;
+
+        Ok(Box::pin(mqtt_source(
+            client,
+            eventloop,
+            self.topics.clone(),
+            self.qos.into(),
+            self.topic_key.clone(),
+            decoder,
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "mqtt"
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_source(
+    client: AsyncClient,
+    mut eventloop: EventLoop,
+    topics: Vec<String>,
+    qos: QoS,
+    topic_key: String,
+    mut decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    out: Pipeline,
+) -> Result<(), ()> {
+    let mut out = out.sink_map_err(|error| error!(message = "Error sending event.", %error));
+
+    loop {
+        let notification = tokio::select! {
+            notification = eventloop.poll() => notification,
+            _ = &mut shutdown => break,
+        };
+
+        match notification {
+            // The broker keeps the subscriptions of persistent sessions, so
+            // they are only requested for new sessions.
+            Ok(Event::Incoming(Packet::ConnAck(connack))) if !connack.session_present => {
+                for topic in &topics {
+                    if let Err(error) = client.subscribe(topic.clone(), qos).await {
+                        emit!(MqttSubscribeFailed { error, topic });
+                    }
+                }
+            }
+            Ok(Event::Incoming(Packet::Publish(publish))) => {
+                emit!(MqttEventReceived {
+                    byte_size: publish.payload.len(),
+                    topic: &publish.topic,
+                });
+
+                let mut events = futures::stream::iter(decoder.decode_all(&publish.payload)).map(
+                    |(mut event, _)| {
+                        let log = event.as_mut_log();
+                        log.insert(log_schema().source_type_key(), Bytes::from("mqtt"));
+                        log.insert(topic_key.as_str(), publish.topic.clone());
+                        Ok(event)
+                    },
+                );
+                out.send_all(&mut events).await?;
+            }
+            Ok(_) => {}
+            Err(error) => {
+                // Polling again reconnects.
+                emit!(MqttConnectionFailed { error });
+                tokio::select! {
+                    _ = tokio::time::delay_for(Duration::from_secs(1)) => {},
+                    _ = &mut shutdown => break,
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[tokio::test]
+    async fn persistent_sessions_require_client_id() {
+        let mut config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["vector/#"]
+            clean_session = false
+            "#,
+        )
+        .unwrap();
+        let build = |config: MqttSourceConfig| async move {
+            config
+                .build(
+                    "default",
+                    &GlobalOptions::default(),
+                    ShutdownSignal::noop(),
+                    Pipeline::new_test().0,
+                )
+                .await
+        };
+        assert!(build(config.clone()).await.is_err());
+
+        config.connection.client_id = Some("vector".into());
+        assert!(build(config).await.is_ok());
+    }
+}
+
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+
+    #[tokio::test]
+    async fn receives_published_messages() {
+        trace_init();
+
+        let topic = format!("test/{}", random_string(10));
+        let config: MqttSourceConfig = toml::from_str(&format!(
+            r#"
+            host = "127.0.0.1"
+            topics = ["{}/#"]
+            "#,
+            topic
+        ))
+        .unwrap();
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        tokio::time::delay_for(Duration::from_secs(1)).await;
+
+        let (client, mut eventloop) = AsyncClient::new(
+            MqttConnectionConfig::new("127.0.0.1").options().unwrap(),
+            10,
+        );
+        let subtopic = format!("{}/device", topic);
+        for message in &["one", "two"] {
+            client
+                .publish(
+                    subtopic.clone(),
+                    QoS::AtLeastOnce,
+                    false,
+                    message.as_bytes(),
+                )
+                .await
+                .unwrap();
+        }
+        tokio::spawn(async move { while eventloop.poll().await.is_ok() {} });
+
+        let events = collect_n(rx, 2).await;
+        for (event, message) in events.iter().zip(&["one", "two"]) {
+            let log = event.as_log();
+            assert_eq!(log[log_schema().message_key()], (*message).into());
+            assert_eq!(log["topic"], subtopic.clone().into());
+            assert_eq!(log[log_schema().source_type_key()], "mqtt".into());
+        }
+    }
+}
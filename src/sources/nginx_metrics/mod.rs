@@ -1,5 +1,5 @@
 use crate::{
-    config::{DataType, GlobalOptions, SourceConfig, SourceDescription},
+    config::{DataType, GlobalOptions, ProxyConfig, SourceConfig, SourceDescription},
     event::metric::{Metric, MetricKind, MetricValue},
     http::{Auth, HttpClient},
     internal_events::{
@@ -60,6 +60,11 @@ struct NginxMetricsConfig {
     namespace: String,
     tls: Option<TlsOptions>,
     auth: Option<Auth>,
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
 }
 
 pub fn default_scrape_interval_secs() -> u64 {
@@ -82,12 +87,13 @@ impl SourceConfig for NginxMetricsConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
         let tls = TlsSettings::from_options(&self.tls)?;
-        let http_client = HttpClient::new(tls)?;
+        let proxy = ProxyConfig::merge_with_env(&globals.proxy, &self.proxy);
+        let http_client = HttpClient::new(tls, &proxy)?;
 
         let namespace = Some(self.namespace.clone()).filter(|namespace| !namespace.is_empty());
         let mut sources = Vec::with_capacity(self.endpoints.len());
@@ -255,6 +261,7 @@ mod integration_tests {
                 namespace: "vector_nginx".to_owned(),
                 tls: None,
                 auth,
+                proxy: Default::default(),
             }
             .build(
                 "default",
@@ -0,0 +1,834 @@
+use super::util::{decode, path_filter, ErrorMessage};
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
+        SourceDescription,
+    },
+    event::{
+        metric::{Bucket, Metric, MetricKind, MetricTags, MetricValue},
+        Event, LogEvent, Value,
+    },
+    internal_events::{
+        OpentelemetryEventsReceived, OpentelemetryMetricUnsupported, OpentelemetryProtoDecodeError,
+    },
+    opentelemetry::{
+        attributes_to_map, encode_message, id_to_hex,
+        proto::{
+            collector::{
+                logs::v1::{
+                    logs_service_server::{LogsService, LogsServiceServer},
+                    ExportLogsServiceRequest, ExportLogsServiceResponse,
+                },
+                metrics::v1::{
+                    metrics_service_server::{MetricsService, MetricsServiceServer},
+                    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+                },
+            },
+            common::v1::{InstrumentationScope, KeyValue},
+            logs::v1::LogRecord,
+            metrics::v1::{
+                metric, number_data_point, AggregationTemporality, HistogramDataPoint,
+                NumberDataPoint,
+            },
+        },
+        LOGS_PATH, METRICS_PATH, PROTOBUF_CONTENT_TYPE,
+    },
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsConfig},
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{future::try_join_all, stream, FutureExt, SinkExt};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+use std::{convert::TryFrom, net::SocketAddr};
+use tonic::{transport::Server, Request, Response, Status};
+use warp::{
+    filters::BoxedFilter,
+    http::{header::CONTENT_TYPE, HeaderValue, StatusCode},
+    Filter,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("At least one of `grpc` or `http` must be configured"))]
+    NoListeners,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub struct OpentelemetryConfig {
+    grpc: Option<ListenerConfig>,
+    http: Option<ListenerConfig>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+struct ListenerConfig {
+    address: SocketAddr,
+    tls: Option<TlsConfig>,
+}
+
+inventory::submit! {
+    SourceDescription::new::<OpentelemetryConfig>("opentelemetry")
+}
+
+impl GenerateConfig for OpentelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            grpc: Some(ListenerConfig {
+                address: "0.0.0.0:4317".parse().unwrap(),
+                tls: None,
+            }),
+            http: Some(ListenerConfig {
+                address: "0.0.0.0:4318".parse().unwrap(),
+                tls: None,
+            }),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SourceConfig for OpentelemetryConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        if self.grpc.is_none() && self.http.is_none() {
+            return Err(BuildError::NoListeners.into());
+        }
+
+        let mut servers = Vec::new();
+        if let Some(grpc) = &self.grpc {
+            let tls = MaybeTlsSettings::from_config(&grpc.tls, true)?;
+            servers.push(run_grpc(grpc.address, tls, out.clone(), shutdown.clone()).boxed());
+        }
+        if let Some(http) = &self.http {
+            let tls = MaybeTlsSettings::from_config(&http.tls, true)?;
+            servers.push(run_http(http.address, tls, out, shutdown).boxed());
+        }
+        Ok(Box::pin(async move {
+            try_join_all(servers).await?;
+            Ok(())
+        }))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Any
+    }
+
+    fn source_type(&self) -> &'static str {
+        "opentelemetry"
+    }
+
+    fn resources(&self) -> Vec<Resource> {
+        self.grpc
+            .iter()
+            .chain(self.http.iter())
+            .map(|listener| Resource::tcp(listener.address))
+            .collect()
+    }
+}
+
+async fn run_grpc(
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    out: Pipeline,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let service = GrpcService { out };
+
+    info!(message = "Building gRPC server.", address = %address);
+
+    let listener = tls.bind(&address).await.map_err(|error| {
+        error!(message = "Failed to bind to listener socket.", %error);
+    })?;
+    Server::builder()
+        .add_service(LogsServiceServer::new(service.clone()))
+        .add_service(MetricsServiceServer::new(service))
+        .serve_with_incoming_shutdown(listener.accept_stream(), shutdown.clone().map(|_| ()))
+        .await
+        .map_err(|error| error!(message = "gRPC server failed.", %error))?;
+    // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+    drop(shutdown);
+    Ok(())
+}
+
+#[derive(Clone)]
+struct GrpcService {
+    out: Pipeline,
+}
+
+#[tonic::async_trait]
+impl LogsService for GrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        let events = logs_to_events(request);
+        forward(self.out.clone(), events, byte_size, "grpc")
+            .await
+            .map_err(|error| Status::unavailable(error.to_string()))?;
+        Ok(Response::new(ExportLogsServiceResponse::default()))
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for GrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        let request = request.into_inner();
+        let byte_size = request.encoded_len();
+        let events = metrics_to_events(request);
+        forward(self.out.clone(), events, byte_size, "grpc")
+            .await
+            .map_err(|error| Status::unavailable(error.to_string()))?;
+        Ok(Response::new(ExportMetricsServiceResponse::default()))
+    }
+}
+
+async fn run_http(
+    address: SocketAddr,
+    tls: MaybeTlsSettings,
+    out: Pipeline,
+    shutdown: ShutdownSignal,
+) -> Result<(), ()> {
+    let logs = http_route::<_, ExportLogsServiceResponse>(LOGS_PATH, out.clone(), logs_to_events);
+    let metrics =
+        http_route::<_, ExportMetricsServiceResponse>(METRICS_PATH, out, metrics_to_events);
+
+    info!(message = "Building HTTP server.", address = %address);
+
+    let listener = tls.bind(&address).await.map_err(|error| {
+        error!(message = "Failed to bind to listener socket.", %error);
+    })?;
+    warp::serve(logs.or(metrics))
+        .serve_incoming_with_graceful_shutdown(
+            listener.accept_stream(),
+            shutdown.clone().map(|_| ()),
+        )
+        .await;
+    // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+    drop(shutdown);
+    Ok(())
+}
+
+/// Accepts export requests of type `T` posted to `path`, answering with an
+/// empty response of type `R` once their events are forwarded.
+fn http_route<T, R>(
+    path: &'static str,
+    out: Pipeline,
+    to_events: fn(T) -> Vec<Event>,
+) -> BoxedFilter<(warp::http::Response<Vec<u8>>,)>
+where
+    T: Message + Default + 'static,
+    R: Message + Default + 'static,
+{
+    warp::post()
+        .and(path_filter(path))
+        .and(warp::header::optional::<String>("content-type"))
+        .and(warp::header::optional::<String>("content-encoding"))
+        .and(warp::body::bytes())
+        .and_then(
+            move |content_type: Option<String>, encoding: Option<String>, body: Bytes| {
+                let out = out.clone();
+                async move {
+                    let result = match handle_http(&content_type, &encoding, body, to_events) {
+                        Ok((events, byte_size)) => forward(out, events, byte_size, "http")
+                            .await
+                            .map(|()| encode_message(&R::default())),
+                        Err(error) => Err(error),
+                    };
+                    Ok::<_, warp::Rejection>(http_reply(result))
+                }
+            },
+        )
+        .boxed()
+}
+
+/// Decompresses and decodes the body of an OTLP/HTTP request, returning its
+/// events along with the size of the decompressed body.
+fn handle_http<T: Message + Default>(
+    content_type: &Option<String>,
+    encoding: &Option<String>,
+    body: Bytes,
+    to_events: fn(T) -> Vec<Event>,
+) -> Result<(Vec<Event>, usize), ErrorMessage> {
+    if let Some(content_type) = content_type {
+        if content_type != PROTOBUF_CONTENT_TYPE {
+            return Err(ErrorMessage::new(
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                format!(
+                    "Unsupported content type {:?}, only {:?} is supported.",
+                    content_type, PROTOBUF_CONTENT_TYPE
+                ),
+            ));
+        }
+    }
+
+    let body = decode(encoding, body)?;
+    let byte_size = body.len();
+    let request = T::decode(body).map_err(|error| {
+        let message = format!("Failed to decode request: {}", error);
+        emit!(OpentelemetryProtoDecodeError { error });
+        ErrorMessage::new(StatusCode::BAD_REQUEST, message)
+    })?;
+    Ok((to_events(request), byte_size))
+}
+
+fn http_reply(result: Result<Vec<u8>, ErrorMessage>) -> warp::http::Response<Vec<u8>> {
+    match result {
+        Ok(body) => {
+            let mut response = warp::http::Response::new(body);
+            response.headers_mut().insert(
+                CONTENT_TYPE,
+                HeaderValue::from_static(PROTOBUF_CONTENT_TYPE),
+            );
+            response
+        }
+        Err(error) => {
+            let mut response = warp::http::Response::new(error.to_string().into_bytes());
+            *response.status_mut() = error.status();
+            response
+        }
+    }
+}
+
+async fn forward(
+    mut out: Pipeline,
+    events: Vec<Event>,
+    byte_size: usize,
+    protocol: &'static str,
+) -> Result<(), ErrorMessage> {
+    emit!(OpentelemetryEventsReceived {
+        count: events.len(),
+        byte_size,
+        protocol,
+    });
+    out.send_all(&mut stream::iter(events.into_iter().map(Ok)))
+        .await
+        .map_err(|_| {
+            ErrorMessage::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Failed to forward events, downstream is closed.".to_owned(),
+            )
+        })
+}
+
+fn timestamp_from_nanos(nanos: u64) -> Option<DateTime<Utc>> {
+    match nanos {
+        0 => None,
+        nanos => Some(Utc.timestamp_nanos(nanos as i64)),
+    }
+}
+
+fn logs_to_events(request: ExportLogsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+    for resource_logs in request.resource_logs {
+        let resources = resource_logs
+            .resource
+            .map(|resource| attributes_to_map(resource.attributes))
+            .filter(|resources| !resources.is_empty());
+        for scope_logs in resource_logs.scope_logs {
+            for record in scope_logs.log_records {
+                let mut log = log_from_record(record);
+                if let Some(resources) = &resources {
+                    log.insert("resources", resources.clone());
+                }
+                if let Some(scope) = &scope_logs.scope {
+                    insert_scope(&mut log, scope);
+                }
+                events.push(Event::Log(log));
+            }
+        }
+    }
+    events
+}
+
+fn log_from_record(record: LogRecord) -> LogEvent {
+    let mut log = LogEvent::default();
+    if let Some(body) = record.body {
+        log.insert(log_schema().message_key(), Value::from(body));
+    }
+
+    let observed_timestamp = timestamp_from_nanos(record.observed_time_unix_nano);
+    let timestamp = timestamp_from_nanos(record.time_unix_nano)
+        .or(observed_timestamp)
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    if let Some(observed_timestamp) = observed_timestamp {
+        log.insert("observed_timestamp", observed_timestamp);
+    }
+
+    if !record.severity_text.is_empty() {
+        log.insert("severity_text", record.severity_text);
+    }
+    if record.severity_number != 0 {
+        log.insert("severity_number", record.severity_number);
+    }
+    if !record.attributes.is_empty() {
+        log.insert("attributes", attributes_to_map(record.attributes));
+    }
+    if !record.trace_id.is_empty() {
+        log.insert("trace_id", id_to_hex(&record.trace_id));
+    }
+    if !record.span_id.is_empty() {
+        log.insert("span_id", id_to_hex(&record.span_id));
+    }
+    if record.flags != 0 {
+        log.insert("flags", record.flags as i64);
+    }
+    if record.dropped_attributes_count != 0 {
+        log.insert(
+            "dropped_attributes_count",
+            record.dropped_attributes_count as i64,
+        );
+    }
+    log
+}
+
+fn insert_scope(log: &mut LogEvent, scope: &InstrumentationScope) {
+    if !scope.name.is_empty() {
+        log.insert("scope.name", scope.name.clone());
+    }
+    if !scope.version.is_empty() {
+        log.insert("scope.version", scope.version.clone());
+    }
+    if !scope.attributes.is_empty() {
+        log.insert(
+            "scope.attributes",
+            attributes_to_map(scope.attributes.clone()),
+        );
+    }
+}
+
+fn metrics_to_events(request: ExportMetricsServiceRequest) -> Vec<Event> {
+    let mut events = Vec::new();
+    for resource_metrics in request.resource_metrics {
+        let resource_tags = resource_metrics
+            .resource
+            .map(|resource| attributes_to_tags(resource.attributes, MetricTags::new()))
+            .unwrap_or_default();
+        for scope_metrics in resource_metrics.scope_metrics {
+            for metric in scope_metrics.metrics {
+                events.extend(
+                    metric_to_metrics(metric, &resource_tags)
+                        .into_iter()
+                        .map(Event::Metric),
+                );
+            }
+        }
+    }
+    events
+}
+
+/// Adds the attributes of a resource or data point to `tags`, overriding the
+/// tags of the same name.
+fn attributes_to_tags(attributes: Vec<KeyValue>, mut tags: MetricTags) -> MetricTags {
+    for (key, value) in attributes_to_map(attributes) {
+        tags.insert(key, value.to_string_lossy());
+    }
+    tags
+}
+
+fn metric_to_metrics(
+    metric: crate::opentelemetry::proto::metrics::v1::Metric,
+    resource_tags: &MetricTags,
+) -> Vec<Metric> {
+    let name = metric.name;
+    match metric.data {
+        Some(metric::Data::Gauge(gauge)) => gauge
+            .data_points
+            .into_iter()
+            .map(|point| number_metric(&name, MetricKind::Absolute, false, point, resource_tags))
+            .collect(),
+        Some(metric::Data::Sum(sum)) => {
+            let kind = kind_from_temporality(sum.aggregation_temporality());
+            // Sums that can decrease, e.g. the number of active requests,
+            // are gauges rather than counters.
+            let is_counter = sum.is_monotonic;
+            sum.data_points
+                .into_iter()
+                .map(|point| number_metric(&name, kind, is_counter, point, resource_tags))
+                .collect()
+        }
+        Some(metric::Data::Histogram(histogram)) => {
+            let kind = kind_from_temporality(histogram.aggregation_temporality());
+            histogram
+                .data_points
+                .into_iter()
+                .map(|point| histogram_metric(&name, kind, point, resource_tags))
+                .collect()
+        }
+        None => {
+            emit!(OpentelemetryMetricUnsupported { name: &name });
+            Vec::new()
+        }
+    }
+}
+
+fn kind_from_temporality(temporality: AggregationTemporality) -> MetricKind {
+    match temporality {
+        AggregationTemporality::Delta => MetricKind::Incremental,
+        AggregationTemporality::Cumulative | AggregationTemporality::Unspecified => {
+            MetricKind::Absolute
+        }
+    }
+}
+
+fn number_metric(
+    name: &str,
+    kind: MetricKind,
+    is_counter: bool,
+    point: NumberDataPoint,
+    resource_tags: &MetricTags,
+) -> Metric {
+    let value = match point.value {
+        Some(number_data_point::Value::AsDouble(value)) => value,
+        Some(number_data_point::Value::AsInt(value)) => value as f64,
+        None => 0.0,
+    };
+    let value = if is_counter {
+        MetricValue::Counter { value }
+    } else {
+        MetricValue::Gauge { value }
+    };
+    Metric::new(name, kind, value)
+        .with_timestamp(timestamp_from_nanos(point.time_unix_nano))
+        .with_tags(non_empty_tags(attributes_to_tags(
+            point.attributes,
+            resource_tags.clone(),
+        )))
+}
+
+fn histogram_metric(
+    name: &str,
+    kind: MetricKind,
+    point: HistogramDataPoint,
+    resource_tags: &MetricTags,
+) -> Metric {
+    // The last count is the one of the values above the highest bound,
+    // which is implied by the total count.
+    let buckets = point
+        .explicit_bounds
+        .iter()
+        .zip(point.bucket_counts.iter())
+        .map(|(&upper_limit, &count)| Bucket {
+            upper_limit,
+            count: saturating_u32(count),
+        })
+        .collect();
+    Metric::new(
+        name,
+        kind,
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count: saturating_u32(point.count),
+            sum: point.sum,
+        },
+    )
+    .with_timestamp(timestamp_from_nanos(point.time_unix_nano))
+    .with_tags(non_empty_tags(attributes_to_tags(
+        point.attributes,
+        resource_tags.clone(),
+    )))
+}
+
+/// Vector's histograms count with `u32`, so larger OTLP counts are capped
+/// instead of wrapping around.
+fn saturating_u32(count: u64) -> u32 {
+    u32::try_from(count).unwrap_or(u32::MAX)
+}
+
+fn non_empty_tags(tags: MetricTags) -> Option<MetricTags> {
+    Some(tags).filter(|tags| !tags.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        opentelemetry::proto::{
+            common::v1::{any_value, AnyValue},
+            logs::v1::{ResourceLogs, ScopeLogs},
+            metrics::v1::{Histogram, ResourceMetrics, ScopeMetrics, Sum},
+            resource::v1::Resource as OtlpResource,
+        },
+        test_util::{collect_ready, next_addr, wait_for_tcp},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<OpentelemetryConfig>();
+    }
+
+    fn attribute(key: &str, value: &str) -> KeyValue {
+        KeyValue {
+            key: key.into(),
+            value: Some(AnyValue {
+                value: Some(any_value::Value::StringValue(value.into())),
+            }),
+        }
+    }
+
+    fn resource() -> Option<OtlpResource> {
+        Some(OtlpResource {
+            attributes: vec![attribute("service.name", "checkout")],
+            dropped_attributes_count: 0,
+        })
+    }
+
+    fn logs_request() -> ExportLogsServiceRequest {
+        ExportLogsServiceRequest {
+            resource_logs: vec![ResourceLogs {
+                resource: resource(),
+                scope_logs: vec![ScopeLogs {
+                    scope: Some(InstrumentationScope {
+                        name: "checkout.http".into(),
+                        version: "1.2.0".into(),
+                        ..Default::default()
+                    }),
+                    log_records: vec![LogRecord {
+                        time_unix_nano: 1_600_000_000_000_000_000,
+                        severity_number: 9,
+                        severity_text: "INFO".into(),
+                        body: Some(AnyValue {
+                            value: Some(any_value::Value::StringValue("order placed".into())),
+                        }),
+                        attributes: vec![attribute("order.id", "42")],
+                        trace_id: vec![0xab; 16],
+                        ..Default::default()
+                    }],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        }
+    }
+
+    #[test]
+    fn converts_log_records() {
+        let events = logs_to_events(logs_request());
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+
+        assert_eq!(log[log_schema().message_key()], "order placed".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.timestamp(1_600_000_000, 0).into()
+        );
+        assert_eq!(log["severity_text"], "INFO".into());
+        assert_eq!(log["severity_number"], Value::Integer(9));
+        assert_eq!(log["attributes.order\\.id"], "42".into());
+        assert_eq!(log["resources.service\\.name"], "checkout".into());
+        assert_eq!(log["scope.name"], "checkout.http".into());
+        assert_eq!(log["scope.version"], "1.2.0".into());
+        assert_eq!(log["trace_id"], "ab".repeat(16).into());
+        assert!(!log.contains("span_id"));
+    }
+
+    #[test]
+    fn converts_sums_gauges_and_histograms() {
+        let number_point = |value| NumberDataPoint {
+            attributes: vec![attribute("host", "a")],
+            time_unix_nano: 1_600_000_000_000_000_000,
+            value: Some(value),
+            ..Default::default()
+        };
+        let metric = |name: &str, data| crate::opentelemetry::proto::metrics::v1::Metric {
+            name: name.into(),
+            data: Some(data),
+            ..Default::default()
+        };
+        let request = ExportMetricsServiceRequest {
+            resource_metrics: vec![ResourceMetrics {
+                resource: resource(),
+                scope_metrics: vec![ScopeMetrics {
+                    scope: None,
+                    metrics: vec![
+                        metric(
+                            "requests",
+                            metric::Data::Sum(Sum {
+                                data_points: vec![number_point(number_data_point::Value::AsInt(3))],
+                                aggregation_temporality: AggregationTemporality::Delta as i32,
+                                is_monotonic: true,
+                            }),
+                        ),
+                        metric(
+                            "active_requests",
+                            metric::Data::Sum(Sum {
+                                data_points: vec![number_point(number_data_point::Value::AsInt(2))],
+                                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                                is_monotonic: false,
+                            }),
+                        ),
+                        metric(
+                            "latency",
+                            metric::Data::Histogram(Histogram {
+                                data_points: vec![HistogramDataPoint {
+                                    count: 6,
+                                    sum: 2.5,
+                                    bucket_counts: vec![1, 3, 2],
+                                    explicit_bounds: vec![0.1, 1.0],
+                                    ..Default::default()
+                                }],
+                                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                            }),
+                        ),
+                    ],
+                    schema_url: String::new(),
+                }],
+                schema_url: String::new(),
+            }],
+        };
+
+        let metrics = metrics_to_events(request)
+            .into_iter()
+            .map(Event::into_metric)
+            .collect::<Vec<_>>();
+        let tags = vec![
+            ("host".to_owned(), "a".to_owned()),
+            ("service.name".to_owned(), "checkout".to_owned()),
+        ]
+        .into_iter()
+        .collect::<MetricTags>();
+
+        assert_eq!(
+            metrics[0],
+            Metric::new(
+                "requests",
+                MetricKind::Incremental,
+                MetricValue::Counter { value: 3.0 }
+            )
+            .with_timestamp(Some(Utc.timestamp(1_600_000_000, 0)))
+            .with_tags(Some(tags.clone()))
+        );
+        assert_eq!(
+            metrics[1],
+            Metric::new(
+                "active_requests",
+                MetricKind::Absolute,
+                MetricValue::Gauge { value: 2.0 }
+            )
+            .with_timestamp(Some(Utc.timestamp(1_600_000_000, 0)))
+            .with_tags(Some(tags))
+        );
+        assert_eq!(
+            metrics[2].data.value,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.1,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 3
+                    },
+                ],
+                count: 6,
+                sum: 2.5,
+            }
+        );
+    }
+
+    #[test]
+    fn saturates_histogram_counts() {
+        let point = HistogramDataPoint {
+            count: u64::from(u32::MAX) + 1,
+            bucket_counts: vec![u64::MAX, 0],
+            explicit_bounds: vec![1.0],
+            ..Default::default()
+        };
+        let metric = histogram_metric("latency", MetricKind::Absolute, point, &MetricTags::new());
+        assert_eq!(
+            metric.data.value,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![Bucket {
+                    upper_limit: 1.0,
+                    count: u32::MAX
+                }],
+                count: u32::MAX,
+                sum: 0.0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn requires_a_listener() {
+        let config: OpentelemetryConfig = toml::from_str("").unwrap();
+        let result = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                Pipeline::new_test().0,
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn receives_logs_over_http() {
+        let grpc_address = next_addr();
+        let http_address = next_addr();
+        let (tx, rx) = Pipeline::new_test();
+        let config = OpentelemetryConfig {
+            grpc: Some(ListenerConfig {
+                address: grpc_address,
+                tls: None,
+            }),
+            http: Some(ListenerConfig {
+                address: http_address,
+                tls: None,
+            }),
+        };
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        wait_for_tcp(http_address).await;
+
+        let response = reqwest::Client::new()
+            .post(&format!("http://{}{}", http_address, LOGS_PATH))
+            .header("content-type", PROTOBUF_CONTENT_TYPE)
+            .body(encode_message(&logs_request()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let response = reqwest::Client::new()
+            .post(&format!("http://{}{}", http_address, METRICS_PATH))
+            .header("content-type", "application/json")
+            .body("{}")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 415);
+
+        let events = collect_ready(rx).await;
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].as_log()[log_schema().message_key()],
+            "order placed".into()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use super::parser;
 use crate::{
-    config::{self, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription},
+    config::{self, GenerateConfig, GlobalOptions, ProxyConfig, SourceConfig, SourceDescription},
     http::Auth,
     http::HttpClient,
     internal_events::{
@@ -38,6 +38,12 @@ struct PrometheusScrapeConfig {
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
 }
 
 pub fn default_scrape_interval_secs() -> u64 {
@@ -59,6 +65,7 @@ impl GenerateConfig for PrometheusScrapeConfig {
             scrape_interval_secs: default_scrape_interval_secs(),
             tls: None,
             auth: None,
+            proxy: Default::default(),
         })
         .unwrap()
     }
@@ -70,7 +77,7 @@ impl SourceConfig for PrometheusScrapeConfig {
     async fn build(
         &self,
         _name: &str,
-        _globals: &GlobalOptions,
+        globals: &GlobalOptions,
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<sources::Source> {
@@ -83,6 +90,7 @@ impl SourceConfig for PrometheusScrapeConfig {
         Ok(prometheus(
             urls,
             tls,
+            ProxyConfig::merge_with_env(&globals.proxy, &self.proxy),
             self.auth.clone(),
             self.scrape_interval_secs,
             shutdown,
@@ -113,6 +121,12 @@ struct PrometheusCompatConfig {
     tls: Option<TlsOptions>,
 
     auth: Option<Auth>,
+
+    #[serde(
+        default,
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    proxy: ProxyConfig,
 }
 
 #[async_trait::async_trait]
@@ -132,6 +146,7 @@ impl SourceConfig for PrometheusCompatConfig {
             scrape_interval_secs: self.scrape_interval_secs,
             tls: self.tls.clone(),
             auth: self.auth.clone(),
+            proxy: self.proxy.clone(),
         }
         .build(name, globals, shutdown, out)
         .await
@@ -149,6 +164,7 @@ impl SourceConfig for PrometheusCompatConfig {
 fn prometheus(
     urls: Vec<http::Uri>,
     tls: TlsSettings,
+    proxy: ProxyConfig,
     auth: Option<Auth>,
     interval: u64,
     shutdown: ShutdownSignal,
@@ -161,7 +177,7 @@ fn prometheus(
         .map(move |_| stream::iter(urls.clone()))
         .flatten()
         .map(move |url| {
-            let client = HttpClient::new(tls.clone()).expect("Building HTTP client failed");
+            let client = HttpClient::new(tls.clone(), &proxy).expect("Building HTTP client failed");
 
             let mut request = Request::get(&url)
                 .body(Body::empty())
@@ -321,6 +337,7 @@ mod test {
                 scrape_interval_secs: 1,
                 tls: None,
                 auth: None,
+                proxy: Default::default(),
             },
         );
         config.add_sink(
@@ -401,6 +418,7 @@ mod integration_tests {
             scrape_interval_secs: 1,
             auth: None,
             tls: None,
+            proxy: Default::default(),
         };
 
         let (tx, rx) = Pipeline::new_test();
@@ -0,0 +1,573 @@
+//! Reads from Redis lists with `BLPOP`, Pub/Sub channels with `SUBSCRIBE`, or
+//! streams with `XREADGROUP` in a consumer group.
+//!
+//! Stream entries are acknowledged with `XACK` once their event is accepted,
+//! or once it's delivered by all sinks if `acknowledgements` is enabled.
+//! Entries that failed to be delivered stay pending, and are read again when
+//! the source restarts.
+
+use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
+    config::{
+        log_schema, DataType, GenerateConfig, GlobalOptions, SourceConfig, SourceDescription,
+    },
+    event::{BatchNotifier, BatchStatus, Event, LogEvent},
+    internal_events::{
+        RedisAcknowledgementFailed, RedisConnectionFailed, RedisEventDeliveryFailed,
+        RedisEventReceived,
+    },
+    shutdown::ShutdownSignal,
+    Pipeline,
+};
+use bytes::Bytes;
+use chrono::{TimeZone, Utc};
+use futures::{FutureExt, SinkExt, StreamExt};
+use redis::{
+    aio::MultiplexedConnection,
+    streams::{StreamId, StreamReadReply},
+    RedisError, Value,
+};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How long blocking reads wait for data, in seconds, so that shutdowns
+/// don't interrupt them.
+const BLOCK_SECS: usize = 1;
+/// The maximum number of stream entries read at once.
+const STREAM_READ_COUNT: usize = 100;
+
+#[derive(Clone, Copy, Debug, Derivative, Deserialize, Serialize, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RedisDataType {
+    /// Pops from a list.
+    #[derivative(Default)]
+    List,
+    /// Subscribes to a Pub/Sub channel.
+    Channel,
+    /// Reads from a stream in a consumer group.
+    Stream,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StreamOptions {
+    #[serde(default = "default_group")]
+    group: String,
+    #[serde(default = "default_consumer")]
+    consumer: String,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            group: default_group(),
+            consumer: default_consumer(),
+        }
+    }
+}
+
+fn default_group() -> String {
+    "vector".into()
+}
+
+fn default_consumer() -> String {
+    "vector".into()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RedisSourceConfig {
+    endpoint: String,
+    key: String,
+    #[serde(default)]
+    data_type: RedisDataType,
+    #[serde(default)]
+    stream: StreamOptions,
+    redis_key: Option<String>,
+    #[serde(default)]
+    acknowledgements: bool,
+    #[serde(default)]
+    framing: FramingConfig,
+    #[serde(default)]
+    decoding: DecodingConfig,
+}
+
+inventory::submit! {
+    SourceDescription::new::<RedisSourceConfig>("redis")
+}
+
+impl GenerateConfig for RedisSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redis")]
+impl SourceConfig for RedisSourceConfig {
+    async fn build(
+        &self,
+        _name: &str,
+        _globals: &GlobalOptions,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let client = redis::Client::open(self.endpoint.as_str())?;
+        let decoder = Decoder::from_config(&self.framing, &self.decoding)?;
+        Ok(Box::pin(redis_source(
+            self.clone(),
+            client,
+            decoder,
+            shutdown,
+            out,
+        )))
+    }
+
+    fn output_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    fn source_type(&self) -> &'static str {
+        "redis"
+    }
+}
+
+/// Why reading stopped, other than shutting down.
+enum ReadError {
+    Redis(RedisError),
+    PipelineClosed,
+}
+
+impl From<RedisError> for ReadError {
+    fn from(error: RedisError) -> Self {
+        Self::Redis(error)
+    }
+}
+
+async fn redis_source(
+    config: RedisSourceConfig,
+    client: redis::Client,
+    mut decoder: Decoder,
+    mut shutdown: ShutdownSignal,
+    mut out: Pipeline,
+) -> Result<(), ()> {
+    loop {
+        let result = match config.data_type {
+            RedisDataType::List => {
+                read_list(&config, &client, &mut decoder, &mut shutdown, &mut out).await
+            }
+            RedisDataType::Channel => {
+                read_channel(&config, &client, &mut decoder, &mut shutdown, &mut out).await
+            }
+            RedisDataType::Stream => read_stream(&config, &client, &mut shutdown, &mut out).await,
+        };
+        match result {
+            Ok(()) => break,
+            Err(ReadError::PipelineClosed) => return Err(()),
+            Err(ReadError::Redis(error)) => emit!(RedisConnectionFailed { error }),
+        }
+
+        tokio::select! {
+            _ = tokio::time::delay_for(Duration::from_secs(1)) => {},
+            _ = &mut shutdown => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// Pops messages until shutting down. The source only stops between blocking
+/// reads, as a message popped by an interrupted read would be lost.
+async fn read_list(
+    config: &RedisSourceConfig,
+    client: &redis::Client,
+    decoder: &mut Decoder,
+    shutdown: &mut ShutdownSignal,
+    out: &mut Pipeline,
+) -> Result<(), ReadError> {
+    let mut connection = client.get_async_connection().await?;
+    while (&mut *shutdown).now_or_never().is_none() {
+        let popped: Option<(String, Vec<u8>)> = redis::cmd("BLPOP")
+            .arg(&config.key)
+            .arg(BLOCK_SECS)
+            .query_async(&mut connection)
+            .await?;
+        if let Some((key, message)) = popped {
+            let events = decode_message(config, decoder, &key, &message);
+            send(out, events).await?;
+        }
+    }
+    Ok(())
+}
+
+async fn read_channel(
+    config: &RedisSourceConfig,
+    client: &redis::Client,
+    decoder: &mut Decoder,
+    shutdown: &mut ShutdownSignal,
+    out: &mut Pipeline,
+) -> Result<(), ReadError> {
+    let mut pubsub = client.get_async_connection().await?.into_pubsub();
+    pubsub.subscribe(&config.key).await?;
+    let mut messages = pubsub.on_message();
+
+    loop {
+        let message = tokio::select! {
+            message = messages.next() => message,
+            _ = &mut *shutdown => return Ok(()),
+        };
+        let message = message.ok_or_else(|| {
+            RedisError::from(std::io::Error::new(
+                std::io::ErrorKind::ConnectionAborted,
+                "The subscription was closed",
+            ))
+        })?;
+        let events = decode_message(
+            config,
+            decoder,
+            message.get_channel_name(),
+            message.get_payload_bytes(),
+        );
+        send(out, events).await?;
+    }
+}
+
+/// Reads the entries left pending by a previous run first, and then new
+/// entries, until shutting down.
+async fn read_stream(
+    config: &RedisSourceConfig,
+    client: &redis::Client,
+    shutdown: &mut ShutdownSignal,
+    out: &mut Pipeline,
+) -> Result<(), ReadError> {
+    let mut connection = client.get_async_connection().await?;
+    // Acknowledgements are sent on their own connection, as the other one
+    // blocks on reads.
+    let acks = client.get_multiplexed_tokio_connection().await?;
+    let group = &config.stream.group;
+
+    let created = redis::cmd("XGROUP")
+        .arg("CREATE")
+        .arg(&config.key)
+        .arg(group)
+        .arg("$")
+        .arg("MKSTREAM")
+        .query_async::<_, ()>(&mut connection)
+        .await;
+    match created {
+        Err(error) if error.code() != Some("BUSYGROUP") => return Err(error.into()),
+        _ => {}
+    }
+
+    let mut pending_id = Some("0".to_owned());
+    while (&mut *shutdown).now_or_never().is_none() {
+        let id = pending_id.as_deref().unwrap_or(">");
+        let reply: Option<StreamReadReply> = redis::cmd("XREADGROUP")
+            .arg("GROUP")
+            .arg(group)
+            .arg(&config.stream.consumer)
+            .arg("COUNT")
+            .arg(STREAM_READ_COUNT)
+            .arg("BLOCK")
+            .arg(BLOCK_SECS * 1000)
+            .arg("STREAMS")
+            .arg(&config.key)
+            .arg(id)
+            .query_async(&mut connection)
+            .await?;
+        let entries = reply
+            .into_iter()
+            .flat_map(|reply| reply.keys)
+            .flat_map(|key| key.ids)
+            .collect::<Vec<_>>();
+
+        if pending_id.is_some() {
+            pending_id = entries.last().map(|entry| entry.id.clone());
+        }
+        for entry in entries {
+            handle_entry(config, &acks, out, entry).await?;
+        }
+    }
+    Ok(())
+}
+
+fn decode_message(
+    config: &RedisSourceConfig,
+    decoder: &mut Decoder,
+    key: &str,
+    message: &[u8],
+) -> Vec<Event> {
+    emit!(RedisEventReceived {
+        byte_size: message.len()
+    });
+
+    decoder
+        .decode_all(message)
+        .into_iter()
+        .map(|(mut event, _)| {
+            insert_fields(config, event.as_mut_log(), key);
+            event
+        })
+        .collect()
+}
+
+async fn handle_entry(
+    config: &RedisSourceConfig,
+    acks: &MultiplexedConnection,
+    out: &mut Pipeline,
+    entry: StreamId,
+) -> Result<(), ReadError> {
+    // Entries deleted while pending are returned without fields.
+    if !entry.map.is_empty() {
+        let (batch, receiver) = BatchNotifier::maybe_new_with_receiver(config.acknowledgements);
+        let event = event_from_entry(config, &entry).with_batch_notifier_option(&batch);
+        drop(batch);
+        send(out, vec![event]).await?;
+
+        if let Some(receiver) = receiver {
+            let config = config.clone();
+            let acks = acks.clone();
+            tokio::spawn(async move {
+                match receiver.await {
+                    BatchStatus::Delivered => acknowledge(&config, acks, &entry.id).await,
+                    status => emit!(RedisEventDeliveryFailed {
+                        id: &entry.id,
+                        status
+                    }),
+                }
+            });
+            return Ok(());
+        }
+    }
+
+    acknowledge(config, acks.clone(), &entry.id).await;
+    Ok(())
+}
+
+async fn acknowledge(config: &RedisSourceConfig, mut acks: MultiplexedConnection, id: &str) {
+    let result = redis::cmd("XACK")
+        .arg(&config.key)
+        .arg(&config.stream.group)
+        .arg(id)
+        .query_async::<_, ()>(&mut acks)
+        .await;
+    if let Err(error) = result {
+        emit!(RedisAcknowledgementFailed { error, id });
+    }
+}
+
+/// Turns the fields of a stream entry into the fields of an event. Its
+/// timestamp is the one in its ID.
+fn event_from_entry(config: &RedisSourceConfig, entry: &StreamId) -> Event {
+    let mut log = LogEvent::default();
+    let mut byte_size = 0;
+    for (field, value) in &entry.map {
+        if let Value::Data(data) = value {
+            byte_size += data.len();
+            log.insert(field.as_str(), Bytes::from(data.clone()));
+        }
+    }
+    emit!(RedisEventReceived { byte_size });
+
+    let timestamp = entry
+        .id
+        .split('-')
+        .next()
+        .and_then(|millis| millis.parse().ok())
+        .and_then(|millis| Utc.timestamp_millis_opt(millis).latest())
+        .unwrap_or_else(Utc::now);
+    log.insert(log_schema().timestamp_key(), timestamp);
+    insert_fields(config, &mut log, &config.key);
+    Event::from(log)
+}
+
+fn insert_fields(config: &RedisSourceConfig, log: &mut LogEvent, key: &str) {
+    log.insert(log_schema().source_type_key(), Bytes::from("redis"));
+    if let Some(redis_key) = &config.redis_key {
+        log.insert(redis_key.as_str(), key.to_owned());
+    }
+}
+
+async fn send(out: &mut Pipeline, events: Vec<Event>) -> Result<(), ReadError> {
+    out.send_all(&mut futures::stream::iter(events).map(Ok))
+        .await
+        .map_err(|error| {
+            error!(message = "Error sending event.", %error);
+            ReadError::PipelineClosed
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<RedisSourceConfig>();
+    }
+
+    #[test]
+    fn converts_stream_entries() {
+        let config: RedisSourceConfig = toml::from_str(
+            r#"
+            endpoint = "redis://127.0.0.1:6379/0"
+            key = "vector"
+            data_type = "stream"
+            redis_key = "redis_key"
+            "#,
+        )
+        .unwrap();
+        let entry = StreamId {
+            id: "1609459200000-0".into(),
+            map: vec![
+                ("message".to_owned(), Value::Data(b"hello".to_vec())),
+                ("app".to_owned(), Value::Data(b"web".to_vec())),
+            ]
+            .into_iter()
+            .collect::<HashMap<_, _>>(),
+        };
+
+        let event = event_from_entry(&config, &entry);
+        let log = event.as_log();
+        assert_eq!(log["message"], "hello".into());
+        assert_eq!(log["app"], "web".into());
+        assert_eq!(log["redis_key"], "vector".into());
+        assert_eq!(log[log_schema().source_type_key()], "redis".into());
+        assert_eq!(
+            log[log_schema().timestamp_key()],
+            Utc.ymd(2021, 1, 1).and_hms(0, 0, 0).into()
+        );
+    }
+}
+
+#[cfg(feature = "redis-integration-tests")]
+#[cfg(test)]
+mod integration_tests {
+    use super::*;
+    use crate::test_util::{collect_n, random_string, trace_init};
+
+    const REDIS_SERVER: &str = "redis://127.0.0.1:6379/0";
+
+    async fn start_source(key: &str, data_type: &str) -> futures::channel::mpsc::Receiver<Event> {
+        let config: RedisSourceConfig = toml::from_str(&format!(
+            r#"
+            endpoint = "{}"
+            key = "{}"
+            data_type = "{}"
+            redis_key = "redis_key"
+            "#,
+            REDIS_SERVER, key, data_type
+        ))
+        .unwrap();
+        let (tx, rx) = Pipeline::new_test();
+        let source = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(source);
+        tokio::time::delay_for(Duration::from_secs(1)).await;
+        rx
+    }
+
+    async fn connect() -> MultiplexedConnection {
+        redis::Client::open(REDIS_SERVER)
+            .unwrap()
+            .get_multiplexed_tokio_connection()
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn redis_source_list() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let rx = start_source(&key, "list").await;
+        let mut connection = connect().await;
+        redis::cmd("RPUSH")
+            .arg(&key)
+            .arg("one")
+            .arg("two")
+            .query_async::<_, ()>(&mut connection)
+            .await
+            .unwrap();
+
+        let events = collect_n(rx, 2).await;
+        for (event, message) in events.iter().zip(&["one", "two"]) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key()],
+                (*message).into()
+            );
+            assert_eq!(event.as_log()["redis_key"], key.clone().into());
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_source_channel() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let rx = start_source(&key, "channel").await;
+        let mut connection = connect().await;
+        for message in &["one", "two"] {
+            redis::cmd("PUBLISH")
+                .arg(&key)
+                .arg(*message)
+                .query_async::<_, ()>(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let events = collect_n(rx, 2).await;
+        for (event, message) in events.iter().zip(&["one", "two"]) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key()],
+                (*message).into()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn redis_source_stream() {
+        trace_init();
+
+        let key = format!("test-{}", random_string(10));
+        let rx = start_source(&key, "stream").await;
+        let mut connection = connect().await;
+        for message in &["one", "two"] {
+            redis::cmd("XADD")
+                .arg(&key)
+                .arg("*")
+                .arg("message")
+                .arg(*message)
+                .query_async::<_, ()>(&mut connection)
+                .await
+                .unwrap();
+        }
+
+        let events = collect_n(rx, 2).await;
+        for (event, message) in events.iter().zip(&["one", "two"]) {
+            assert_eq!(event.as_log()["message"], (*message).into());
+        }
+        tokio::time::delay_for(Duration::from_secs(1)).await;
+
+        // Both entries were acknowledged.
+        let pending: (usize, Value, Value, Value) = redis::cmd("XPENDING")
+            .arg(&key)
+            .arg("vector")
+            .query_async(&mut connection)
+            .await
+            .unwrap();
+        assert_eq!(pending.0, 0);
+    }
+}
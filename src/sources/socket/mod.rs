@@ -88,9 +88,7 @@ impl SourceConfig for SocketConfig {
     ) -> crate::Result<super::Source> {
         match self.mode.clone() {
             Mode::Tcp(config) => {
-                let tcp = tcp::RawTcpSource {
-                    config: config.clone(),
-                };
+                let tcp = tcp::RawTcpSource::new(config.clone())?;
                 let tls = MaybeTlsSettings::from_config(&config.tls(), true)?;
                 tcp.run(
                     config.address(),
@@ -111,6 +109,7 @@ impl SourceConfig for SocketConfig {
                     config.address(),
                     config.max_length(),
                     host_key,
+                    config.decoder()?,
                     #[cfg(unix)]
                     config.receive_buffer_bytes(),
                     shutdown,
@@ -119,6 +118,7 @@ impl SourceConfig for SocketConfig {
             }
             #[cfg(unix)]
             Mode::UnixDatagram(config) => {
+                let decoder = config.decoder()?;
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
@@ -126,19 +126,21 @@ impl SourceConfig for SocketConfig {
                     config.path,
                     config.max_length,
                     host_key,
+                    decoder,
                     shutdown,
                     out,
                 ))
             }
             #[cfg(unix)]
             Mode::UnixStream(config) => {
+                let decoder = config.decoder()?;
                 let host_key = config
                     .host_key
                     .unwrap_or_else(|| log_schema().host_key().to_string());
                 Ok(unix::unix_stream(
                     config.path,
-                    config.max_length,
                     host_key,
+                    decoder,
                     shutdown,
                     out,
                 ))
@@ -170,7 +172,9 @@ impl SourceConfig for SocketConfig {
 mod test {
     use super::{tcp::TcpConfig, udp::UdpConfig, SocketConfig};
     use crate::{
+        codecs::{DecodingConfig, FramingConfig},
         config::{log_schema, GlobalOptions, SinkContext, SourceConfig},
+        event::Value,
         shutdown::{ShutdownSignal, SourceShutdownCoordinator},
         sinks::util::tcp::TcpSinkConfig,
         test_util::{
@@ -303,6 +307,47 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn tcp_decodes_frames() {
+        let (tx, rx) = Pipeline::new_test();
+        let addr = next_addr();
+
+        let mut config = TcpConfig::from_address(addr.into());
+        config
+            .set_framing(Some(FramingConfig::CharacterDelimited {
+                delimiter: ';',
+                max_length: None,
+            }))
+            .set_decoding(DecodingConfig::Json);
+
+        let server = SocketConfig::from(config)
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+
+        wait_for_tcp(addr).await;
+        send_lines(
+            addr,
+            vec![r#"{"message": "one", "count": 1};not json;[{"message": "two"}];"#.to_owned()]
+                .into_iter(),
+        )
+        .await
+        .unwrap();
+
+        let events = collect_n(rx, 2).await;
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "one".into());
+        assert_eq!(log["count"], Value::Integer(1));
+        assert_eq!(log[log_schema().source_type_key()], "socket".into());
+        assert_eq!(events[1].as_log()[log_schema().message_key()], "two".into());
+    }
+
     #[tokio::test]
     async fn tcp_with_tls() {
         let (tx, mut rx) = Pipeline::new_test();
@@ -611,6 +656,50 @@ mod test {
         );
     }
 
+    #[tokio::test]
+    async fn udp_reassembles_gelf_chunks() {
+        let (tx, rx) = Pipeline::new_test();
+        let address = next_addr();
+        let config = toml::from_str::<SocketConfig>(&format!(
+            r#"
+               mode = "udp"
+               address = "{}"
+
+               [decoding]
+               codec = "gelf"
+            "#,
+            address
+        ))
+        .unwrap();
+        let server = config
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        tokio::time::delay_for(Duration::from_millis(100)).await;
+
+        let message = r#"{"version": "1.1", "host": "example.org", "short_message": "chunked", "_user_id": 9001}"#;
+        let (first, second) = message.split_at(40);
+        send_lines_udp(
+            address,
+            vec![
+                format!("\x1e\x0fmessage1\x01\x02{}", second),
+                format!("\x1e\x0fmessage1\x00\x02{}", first),
+            ],
+        );
+        let events = collect_n(rx, 1).await;
+
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "chunked".into());
+        assert_eq!(log["hostname"], "example.org".into());
+        assert_eq!(log["user_id"], 9001.into());
+    }
+
     #[tokio::test]
     async fn udp_it_includes_host() {
         let (tx, rx) = Pipeline::new_test();
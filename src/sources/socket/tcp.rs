@@ -1,4 +1,5 @@
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     sources::util::{SocketListenAddr, TcpSource},
@@ -6,7 +7,6 @@ use crate::{
     tls::TlsConfig,
 };
 use bytes::Bytes;
-use codec::BytesDelimitedCodec;
 use getset::{CopyGetters, Getters, Setters};
 use serde::{Deserialize, Serialize};
 
@@ -28,6 +28,11 @@ pub struct TcpConfig {
     tls: Option<TlsConfig>,
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[getset(get = "pub", set = "pub")]
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    #[getset(get = "pub", set = "pub")]
+    decoding: DecodingConfig,
 }
 
 fn default_max_length() -> usize {
@@ -56,6 +61,8 @@ impl TcpConfig {
             host_key,
             tls,
             receive_buffer_bytes,
+            framing: None,
+            decoding: Default::default(),
         }
     }
 
@@ -68,27 +75,42 @@ impl TcpConfig {
             host_key: None,
             tls: None,
             receive_buffer_bytes: None,
+            framing: None,
+            decoding: Default::default(),
         }
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct RawTcpSource {
-    pub config: TcpConfig,
+    config: TcpConfig,
+    decoder: Decoder,
+}
+
+impl RawTcpSource {
+    pub fn new(config: TcpConfig) -> crate::Result<Self> {
+        // Lines, or null delimited GELF messages, of up to `max_length` bytes
+        // are read unless `framing` is set. Frames are limited to `max_length`
+        // unless `framing` sets its own.
+        let framing = config
+            .framing
+            .clone()
+            .map(|framing| framing.with_default_max_length(config.max_length))
+            .unwrap_or_else(|| config.decoding.default_stream_framing(config.max_length));
+        let decoder = Decoder::from_config(&framing, &config.decoding)?;
+        Ok(Self { config, decoder })
+    }
 }
 
 impl TcpSource for RawTcpSource {
     type Error = std::io::Error;
-    type Decoder = BytesDelimitedCodec;
+    type Decoder = Decoder;
 
     fn decoder(&self) -> Self::Decoder {
-        BytesDelimitedCodec::new_with_max_length(b'\n', self.config.max_length)
+        self.decoder.clone()
     }
 
-    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
-        let byte_size = frame.len();
-        let mut event = Event::from(frame);
-
+    fn build_event(&self, (mut event, byte_size): (Event, usize), host: Bytes) -> Option<Event> {
         event.as_mut_log().insert(
             crate::config::log_schema().source_type_key(),
             Bytes::from("socket"),
@@ -1,20 +1,18 @@
 #[cfg(unix)]
 use crate::udp;
 use crate::{
-    event::Event,
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     internal_events::{SocketEventReceived, SocketMode, SocketReceiveError},
     shutdown::ShutdownSignal,
     sources::Source,
     Pipeline,
 };
 use bytes::{Bytes, BytesMut};
-use codec::BytesDelimitedCodec;
 use futures::SinkExt;
 use getset::{CopyGetters, Getters};
 use serde::{Deserialize, Serialize};
 use std::net::SocketAddr;
 use tokio::net::UdpSocket;
-use tokio_util::codec::Decoder;
 
 /// UDP processes messages per packet, where messages are separated by newline.
 #[derive(Deserialize, Serialize, Debug, Clone, Getters, CopyGetters)]
@@ -30,6 +28,11 @@ pub struct UdpConfig {
     #[cfg(unix)]
     #[get_copy = "pub"]
     receive_buffer_bytes: Option<usize>,
+    #[get = "pub"]
+    framing: Option<FramingConfig>,
+    #[serde(default)]
+    #[get = "pub"]
+    decoding: DecodingConfig,
 }
 
 fn default_max_length() -> usize {
@@ -44,14 +47,29 @@ impl UdpConfig {
             host_key: None,
             #[cfg(unix)]
             receive_buffer_bytes: None,
+            framing: None,
+            decoding: Default::default(),
         }
     }
+
+    /// Each datagram is split into lines, or holds a GELF message or chunk
+    /// with the `gelf` codec, unless `framing` is set. Frames are limited to
+    /// `max_length` unless `framing` sets its own.
+    pub fn decoder(&self) -> crate::Result<Decoder> {
+        let framing = self
+            .framing
+            .clone()
+            .map(|framing| framing.with_default_max_length(self.max_length))
+            .unwrap_or_else(|| self.decoding.default_datagram_framing(self.max_length));
+        Ok(Decoder::from_config(&framing, &self.decoding)?)
+    }
 }
 
 pub fn udp(
     address: SocketAddr,
     max_length: usize,
     host_key: String,
+    mut decoder: Decoder,
     #[cfg(unix)] receive_buffer_bytes: Option<usize>,
     mut shutdown: ShutdownSignal,
     out: Pipeline,
@@ -89,14 +107,10 @@ pub fn udp(
                         });
                     })?;
 
-                    let mut payload = buf.split_to(byte_size);
-
-                    // UDP processes messages per payload, where messages are separated by newline
-                    // and stretch to end of payload.
-                    let mut decoder = BytesDelimitedCodec::new(b'\n');
-                    while let Ok(Some(line)) = decoder.decode_eof(&mut payload) {
-                        let mut event = Event::from(line);
+                    let payload = buf.split_to(byte_size);
 
+                    // UDP processes messages per payload, where frames stretch to end of payload.
+                    for (mut event, byte_size) in decoder.decode_all(&payload) {
                         event
                             .as_mut_log()
                             .insert(crate::config::log_schema().source_type_key(), Bytes::from("socket"));
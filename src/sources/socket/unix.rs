@@ -1,4 +1,5 @@
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     event::Event,
     internal_events::{SocketEventReceived, SocketMode},
     shutdown::ShutdownSignal,
@@ -11,7 +12,6 @@ use crate::{
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use tokio_util::codec::LinesCodec;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
@@ -20,6 +20,9 @@ pub struct UnixConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub framing: Option<FramingConfig>,
+    #[serde(default)]
+    pub decoding: DecodingConfig,
 }
 
 fn default_max_length() -> usize {
@@ -32,17 +35,33 @@ impl UnixConfig {
             path,
             max_length: default_max_length(),
             host_key: None,
+            framing: None,
+            decoding: Default::default(),
         }
     }
+
+    /// Lines, or null delimited GELF messages, of up to `max_length` bytes
+    /// are read unless `framing` is set. Frames are limited to `max_length`
+    /// unless `framing` sets its own.
+    pub fn decoder(&self) -> crate::Result<Decoder> {
+        let framing = self
+            .framing
+            .clone()
+            .map(|framing| framing.with_default_max_length(self.max_length))
+            .unwrap_or_else(|| self.decoding.default_stream_framing(self.max_length));
+        Ok(Decoder::from_config(&framing, &self.decoding)?)
+    }
 }
 
 /**
 * Function to pass to build_unix_*_source, specific to the basic unix source.
-* Takes a single line of a received message and builds an Event object.
+* Takes a single decoded event of a received message and adds the source fields.
 **/
-fn build_event(host_key: &str, received_from: Option<Bytes>, line: &str) -> Option<Event> {
-    let byte_size = line.len();
-    let mut event = Event::from(line);
+fn build_event(
+    host_key: &str,
+    received_from: Option<Bytes>,
+    (mut event, byte_size): (Event, usize),
+) -> Option<Event> {
     event.as_mut_log().insert(
         crate::config::log_schema().source_type_key(),
         Bytes::from("socket"),
@@ -61,6 +80,7 @@ pub(super) fn unix_datagram(
     path: PathBuf,
     max_length: usize,
     host_key: String,
+    decoder: Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
@@ -68,7 +88,7 @@ pub(super) fn unix_datagram(
         path,
         max_length,
         host_key,
-        LinesCodec::new_with_max_length(max_length),
+        decoder,
         shutdown,
         out,
         build_event,
@@ -77,17 +97,10 @@ pub(super) fn unix_datagram(
 
 pub(super) fn unix_stream(
     path: PathBuf,
-    max_length: usize,
     host_key: String,
+    decoder: Decoder,
     shutdown: ShutdownSignal,
     out: Pipeline,
 ) -> Source {
-    build_unix_stream_source(
-        path,
-        LinesCodec::new_with_max_length(max_length),
-        host_key,
-        shutdown,
-        out,
-        build_event,
-    )
+    build_unix_stream_source(path, decoder, host_key, shutdown, out, build_event)
 }
@@ -1,15 +1,17 @@
 use crate::{
+    codecs::{Decoder, DecodingConfig, FramingConfig},
     config::{log_schema, DataType, GlobalOptions, Resource, SourceConfig, SourceDescription},
     event::Event,
     internal_events::{StdinEventReceived, StdinReadFailed},
     shutdown::ShutdownSignal,
     Pipeline,
 };
-use bytes::Bytes;
-use futures::{executor, FutureExt, SinkExt, StreamExt, TryStreamExt};
+use bytes::{Bytes, BytesMut};
+use futures::{executor, FutureExt, SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
 use std::{io, thread};
 use tokio::sync::mpsc::channel;
+use tokio_util::codec::Decoder as _;
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields, default)]
@@ -17,6 +19,8 @@ pub struct StdinConfig {
     #[serde(default = "default_max_length")]
     pub max_length: usize,
     pub host_key: Option<String>,
+    pub framing: Option<FramingConfig>,
+    pub decoding: DecodingConfig,
 }
 
 impl Default for StdinConfig {
@@ -24,10 +28,25 @@ impl Default for StdinConfig {
         StdinConfig {
             max_length: default_max_length(),
             host_key: None,
+            framing: None,
+            decoding: Default::default(),
         }
     }
 }
 
+impl StdinConfig {
+    /// Lines of up to `max_length` bytes are read unless `framing` is set,
+    /// which is limited to `max_length` too unless it sets its own.
+    fn decoder(&self) -> crate::Result<Decoder> {
+        let framing = self
+            .framing
+            .clone()
+            .map(|framing| framing.with_default_max_length(self.max_length))
+            .unwrap_or_else(|| FramingConfig::newline_delimited(self.max_length));
+        Ok(Decoder::from_config(&framing, &self.decoding)?)
+    }
+}
+
 fn default_max_length() -> usize {
     bytesize::kib(100u64) as usize
 }
@@ -65,7 +84,7 @@ impl SourceConfig for StdinConfig {
 }
 
 pub fn stdin_source<R>(
-    stdin: R,
+    mut stdin: R,
     config: StdinConfig,
     shutdown: ShutdownSignal,
     out: Pipeline,
@@ -73,6 +92,7 @@ pub fn stdin_source<R>(
 where
     R: Send + io::BufRead + 'static,
 {
+    let mut decoder = config.decoder()?;
     let host_key = config
         .host_key
         .unwrap_or_else(|| log_schema().host_key().to_string());
@@ -84,8 +104,18 @@ where
     thread::spawn(move || {
         info!("Capturing STDIN.");
 
-        for line in stdin.lines() {
-            if executor::block_on(sender.send(line)).is_err() {
+        loop {
+            let chunk = match stdin.fill_buf() {
+                Ok(buf) if buf.is_empty() => return,
+                Ok(buf) => Ok(Bytes::copy_from_slice(buf)),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => Err(error),
+            };
+            if let Ok(chunk) = &chunk {
+                stdin.consume(chunk.len());
+            }
+            let failed = chunk.is_err();
+            if executor::block_on(sender.send(chunk)).is_err() || failed {
                 // receiver has closed so we should shutdown
                 return;
             }
@@ -96,15 +126,50 @@ where
         let mut out =
             out.sink_map_err(|error| error!(message = "Unable to send event to out.", %error));
 
-        let res = receiver
-            .take_until(shutdown)
-            .map_err(|error| emit!(StdinReadFailed { error }))
-            .map_ok(move |line| {
-                emit!(StdinEventReceived {
-                    byte_size: line.len()
-                });
-                create_event(Bytes::from(line), &host_key, &hostname)
-            })
+        let events = async_stream::stream! {
+            let mut receiver = receiver.take_until(shutdown);
+            let mut buf = BytesMut::new();
+            'read: loop {
+                let eof = match receiver.next().await {
+                    Some(Ok(chunk)) => {
+                        buf.extend_from_slice(&chunk);
+                        false
+                    }
+                    Some(Err(error)) => {
+                        emit!(StdinReadFailed { error });
+                        yield Err(());
+                        break 'read;
+                    }
+                    None => true,
+                };
+
+                loop {
+                    let decoded = if eof {
+                        decoder.decode_eof(&mut buf)
+                    } else {
+                        decoder.decode(&mut buf)
+                    };
+                    match decoded {
+                        Ok(Some((event, byte_size))) => {
+                            emit!(StdinEventReceived { byte_size });
+                            yield Ok(create_event(event, &host_key, &hostname));
+                        }
+                        Ok(None) => break,
+                        Err(error) => {
+                            emit!(StdinReadFailed { error });
+                            yield Err(());
+                            break 'read;
+                        }
+                    }
+                }
+
+                if eof {
+                    break;
+                }
+            }
+        };
+
+        let res = events
             .forward(&mut out)
             .inspect(|_| info!("Finished sending."))
             .await;
@@ -115,9 +180,7 @@ where
     }))
 }
 
-fn create_event(line: Bytes, host_key: &str, hostname: &Option<String>) -> Event {
-    let mut event = Event::from(line);
-
+fn create_event(mut event: Event, host_key: &str, hostname: &Option<String>) -> Event {
     // Add source type
     event
         .as_mut_log()
@@ -148,7 +211,7 @@ mod tests {
         let host_key = "host".to_string();
         let hostname = Some("Some.Machine".to_string());
 
-        let event = create_event(line, &host_key, &hostname);
+        let event = create_event(Event::from(line), &host_key, &hostname);
         let log = event.into_log();
 
         assert_eq!(log["host"], "Some.Machine".into());
@@ -188,4 +251,31 @@ mod tests {
         assert!(event.is_err());
         assert_eq!(Err(mpsc::error::TryRecvError::Closed), event);
     }
+
+    #[tokio::test]
+    async fn stdin_decodes_json() {
+        trace_init();
+
+        let (tx, rx) = Pipeline::new_test();
+        let config: StdinConfig = toml::from_str(
+            r#"
+            [decoding]
+            codec = "json"
+            "#,
+        )
+        .unwrap();
+        let buf = Cursor::new("{\"message\": \"hello\", \"level\": \"info\"}\nnot json\n");
+
+        stdin_source(buf, config, ShutdownSignal::noop(), tx)
+            .unwrap()
+            .await
+            .unwrap();
+
+        let events = rx.collect::<Vec<_>>().await;
+        assert_eq!(events.len(), 1);
+        let log = events[0].as_log();
+        assert_eq!(log[log_schema().message_key()], "hello".into());
+        assert_eq!(log["level"], "info".into());
+        assert_eq!(log[log_schema().source_type_key()], "stdin".into());
+    }
 }
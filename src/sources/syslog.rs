@@ -4,19 +4,19 @@ use crate::sources::util::build_unix_stream_source;
 #[cfg(unix)]
 use crate::udp;
 use crate::{
+    codecs::{event_from_syslog, OctetCountingDecoder},
     config::{
         log_schema, DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig,
         SourceDescription,
     },
-    event::{Event, Value},
+    event::Event,
     internal_events::{SyslogEventReceived, SyslogUdpReadError, SyslogUdpUtf8Error},
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
     Pipeline,
 };
-use bytes::{Buf, Bytes, BytesMut};
-use chrono::{Datelike, Utc};
+use bytes::Bytes;
 use derive_is_enum_variant::is_enum_variant;
 use futures::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
@@ -24,12 +24,8 @@ use std::io;
 use std::net::SocketAddr;
 #[cfg(unix)]
 use std::path::PathBuf;
-use syslog_loose::{IncompleteDate, Message, ProcId, Protocol};
 use tokio::net::UdpSocket;
-use tokio_util::{
-    codec::{BytesCodec, Decoder, LinesCodec, LinesCodecError},
-    udp::UdpFramed,
-};
+use tokio_util::{codec::BytesCodec, udp::UdpFramed};
 
 #[derive(Deserialize, Serialize, Debug)]
 // TODO: add back when serde-rs/serde#1358 is addressed
@@ -150,11 +146,11 @@ impl SourceConfig for SyslogConfig {
             #[cfg(unix)]
             Mode::Unix { path } => Ok(build_unix_stream_source(
                 path,
-                SyslogDecoder::new(self.max_length),
+                OctetCountingDecoder::new_with_max_length(self.max_length),
                 host_key,
                 shutdown,
                 out,
-                event_from_str,
+                event_from_bytes,
             )),
         }
     }
@@ -184,117 +180,15 @@ struct SyslogTcpSource {
 }
 
 impl TcpSource for SyslogTcpSource {
-    type Error = LinesCodecError;
-    type Decoder = SyslogDecoder;
+    type Error = io::Error;
+    type Decoder = OctetCountingDecoder;
 
     fn decoder(&self) -> Self::Decoder {
-        SyslogDecoder::new(self.max_length)
-    }
-
-    fn build_event(&self, frame: String, host: Bytes) -> Option<Event> {
-        event_from_str(&self.host_key, Some(host), &frame)
-    }
-}
-
-/// Decodes according to `Octet Counting` in https://tools.ietf.org/html/rfc6587
-#[derive(Clone, Debug)]
-struct SyslogDecoder {
-    other: LinesCodec,
-}
-
-impl SyslogDecoder {
-    fn new(max_length: usize) -> Self {
-        Self {
-            other: LinesCodec::new_with_max_length(max_length),
-        }
-    }
-
-    fn octet_decode(&self, src: &mut BytesMut) -> Result<Option<String>, LinesCodecError> {
-        // Encoding scheme:
-        //
-        // len ' ' data
-        // |    |  | len number of bytes that contain syslog message
-        // |    |
-        // |    | Separating whitespace
-        // |
-        // | ASCII decimal number of unknown length
-
-        if let Some(i) = src.iter().position(|&b| b == b' ') {
-            let len: usize = std::str::from_utf8(&src[..i])
-                .map_err(|_| ())
-                .and_then(|num| num.parse().map_err(|_| ()))
-                .map_err(|_| {
-                    LinesCodecError::Io(io::Error::new(
-                        io::ErrorKind::InvalidData,
-                        "Unable to decode message len as number",
-                    ))
-                })?;
-
-            let from = i + 1;
-            let to = from + len;
-
-            if let Some(msg) = src.get(from..to) {
-                let s = std::str::from_utf8(msg)
-                    .map_err(|_| {
-                        LinesCodecError::Io(io::Error::new(
-                            io::ErrorKind::InvalidData,
-                            "Unable to decode message as UTF8",
-                        ))
-                    })?
-                    .to_string();
-                src.advance(to);
-                Ok(Some(s))
-            } else {
-                Ok(None)
-            }
-        } else if src.len() < self.other.max_length() {
-            Ok(None)
-        } else {
-            // This is certainly malformed, and there is no recovering from this.
-            Err(LinesCodecError::Io(io::Error::new(
-                io::ErrorKind::Other,
-                "Frame length limit exceeded",
-            )))
-        }
-    }
-
-    /// None if this is not octet counting encoded
-    fn checked_decode(
-        &self,
-        src: &mut BytesMut,
-    ) -> Option<Result<Option<String>, LinesCodecError>> {
-        if let Some(&first_byte) = src.get(0) {
-            if (49..=57).contains(&first_byte) {
-                // First character is non zero number so we can assume that
-                // octet count framing is used.
-                trace!("Octet counting encoded event detected.");
-                return Some(self.octet_decode(src));
-            }
-        }
-        None
-    }
-}
-
-impl Decoder for SyslogDecoder {
-    type Item = String;
-    type Error = LinesCodecError;
-
-    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(ret) = self.checked_decode(src) {
-            ret
-        } else {
-            // Octet counting isn't used so fallback to newline codec.
-            self.other.decode(src)
-        }
+        OctetCountingDecoder::new_with_max_length(self.max_length)
     }
 
-    fn decode_eof(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
-        if let Some(ret) = self.checked_decode(buf) {
-            ret
-        } else {
-            // Octet counting isn't used so fallback to newline codec.
-            self.other.decode_eof(buf)
-        }
+    fn build_event(&self, frame: Bytes, host: Bytes) -> Option<Event> {
+        event_from_bytes(&self.host_key, Some(host), frame)
     }
 }
 
@@ -355,20 +249,12 @@ pub fn udp(
     })
 }
 
-/// Function used to resolve the year for syslog messages that don't include the year.
-/// If the current month is January, and the syslog message is for December, it will take the previous year.
-/// Otherwise, take the current year.
-fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
-    let now = Utc::now();
-    if now.month() == 1 && month == 12 {
-        now.year() - 1
-    } else {
-        now.year()
-    }
+fn event_from_bytes(host_key: &str, default_host: Option<Bytes>, frame: Bytes) -> Option<Event> {
+    event_from_str(host_key, default_host, &String::from_utf8_lossy(&frame))
 }
 
 /**
-* Function to pass to build_unix_stream_source, specific to the Unix mode of the syslog source.
+* Function used by all modes of the syslog source to build an event from a received line.
 * Handles the logic of parsing and decoding the syslog message format.
 **/
 // TODO: many more cases to handle:
@@ -376,8 +262,7 @@ fn resolve_year((month, _date, _hour, _min, _sec): IncompleteDate) -> i32 {
 // null byte delimiter in place of newline
 fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Option<Event> {
     let line = line.trim();
-    let parsed = syslog_loose::parse_message_with_year(line, resolve_year);
-    let mut event = Event::from(&parsed.msg[..]);
+    let mut event = event_from_syslog(line);
 
     // Add source type
     event
@@ -388,21 +273,11 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Op
         event.as_mut_log().insert("source_ip", default_host);
     }
 
-    let parsed_hostname = parsed.hostname.map(|x| Bytes::from(x.to_owned()));
+    let parsed_hostname = event.as_log().get("hostname").map(|host| host.as_bytes());
     if let Some(parsed_host) = parsed_hostname.or(default_host) {
         event.as_mut_log().insert(host_key, parsed_host);
     }
 
-    let timestamp = parsed
-        .timestamp
-        .map(|ts| ts.into())
-        .unwrap_or_else(Utc::now);
-    event
-        .as_mut_log()
-        .insert(log_schema().timestamp_key(), timestamp);
-
-    insert_fields_from_syslog(&mut event, parsed);
-
     emit!(SyslogEventReceived {
         byte_size: line.len()
     });
@@ -415,43 +290,6 @@ fn event_from_str(host_key: &str, default_host: Option<Bytes>, line: &str) -> Op
     Some(event)
 }
 
-fn insert_fields_from_syslog(event: &mut Event, parsed: Message<&str>) {
-    let log = event.as_mut_log();
-
-    if let Some(host) = parsed.hostname {
-        log.insert("hostname", host.to_string());
-    }
-    if let Some(severity) = parsed.severity {
-        log.insert("severity", severity.as_str().to_owned());
-    }
-    if let Some(facility) = parsed.facility {
-        log.insert("facility", facility.as_str().to_owned());
-    }
-    if let Protocol::RFC5424(version) = parsed.protocol {
-        log.insert("version", version as i64);
-    }
-    if let Some(app_name) = parsed.appname {
-        log.insert("appname", app_name.to_owned());
-    }
-    if let Some(msg_id) = parsed.msgid {
-        log.insert("msgid", msg_id.to_owned());
-    }
-    if let Some(procid) = parsed.procid {
-        let value: Value = match procid {
-            ProcId::PID(pid) => pid.into(),
-            ProcId::Name(name) => name.to_string().into(),
-        };
-        log.insert("procid", value);
-    }
-
-    for element in parsed.structured_data.into_iter() {
-        for (name, value) in element.params.into_iter() {
-            let key = format!("{}.{}", element.id, name);
-            log.insert(key, value.to_string());
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::{event_from_str, Mode, SyslogConfig};
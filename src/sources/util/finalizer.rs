@@ -0,0 +1,80 @@
+use crate::event::{BatchStatus, BatchStatusReceiver};
+use futures::{channel::mpsc, StreamExt};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+/// Upper bound on the entries waiting to be finalized. It's well above the
+/// number of events sinks hold in their batches, so that it only limits the
+/// memory used when events aren't being finalized at all.
+const MAX_PENDING: usize = 100_000;
+
+/// Calls `apply_done` with the status of each entry's batch once it is
+/// finalized, in the order the entries were added. Sources use this to only
+/// checkpoint positions whose events, and all events before them, are done.
+pub struct OrderedFinalizer<T> {
+    sender: mpsc::UnboundedSender<(BatchStatusReceiver, T)>,
+    pending: Arc<Semaphore>,
+}
+
+impl<T: Send + 'static> OrderedFinalizer<T> {
+    pub fn new(mut apply_done: impl FnMut(BatchStatus, T) + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::unbounded();
+        let pending = Arc::new(Semaphore::new(MAX_PENDING));
+        let finalized = Arc::clone(&pending);
+        tokio::spawn(
+            receiver
+                .map(|(receiver, entry): (BatchStatusReceiver, T)| async move {
+                    (receiver.await, entry)
+                })
+                .buffered(MAX_PENDING)
+                .for_each(move |(status, entry)| {
+                    apply_done(status, entry);
+                    finalized.add_permits(1);
+                    futures::future::ready(())
+                }),
+        );
+        Self { sender, pending }
+    }
+
+    /// Waits for the oldest entries to be finalized first if too many are
+    /// pending, so that the source slows down.
+    pub async fn add(&self, entry: T, receiver: BatchStatusReceiver) {
+        self.pending.acquire().await.forget();
+        if self.sender.unbounded_send((receiver, entry)).is_err() {
+            error!(message = "Finalizer task ended prematurely.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OrderedFinalizer;
+    use crate::event::{BatchNotifier, BatchStatus};
+    use std::sync::{Arc, Mutex};
+    use tokio::time::{delay_for, Duration};
+
+    #[tokio::test]
+    async fn applies_in_order() {
+        let done = Arc::new(Mutex::new(Vec::new()));
+        let done2 = Arc::clone(&done);
+        let finalizer = OrderedFinalizer::new(move |status, entry| {
+            done2.lock().unwrap().push((entry, status));
+        });
+
+        let (first, receiver) = BatchNotifier::new_with_receiver();
+        finalizer.add(1, receiver).await;
+        let (second, receiver) = BatchNotifier::new_with_receiver();
+        finalizer.add(2, receiver).await;
+
+        drop(second);
+        delay_for(Duration::from_millis(10)).await;
+        assert!(done.lock().unwrap().is_empty());
+
+        drop(first);
+        delay_for(Duration::from_millis(10)).await;
+        assert_eq!(
+            *done.lock().unwrap(),
+            vec![(1, BatchStatus::Delivered), (2, BatchStatus::Delivered)]
+        );
+    }
+}
@@ -52,6 +52,10 @@ impl ErrorMessage {
             message,
         }
     }
+
+    pub fn status(&self) -> StatusCode {
+        StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
 }
 impl Error for ErrorMessage {}
 impl fmt::Display for ErrorMessage {
@@ -173,6 +177,18 @@ fn handle_decode_error(encoding: &str, error: impl std::error::Error) -> ErrorMe
     )
 }
 
+/// Matches requests to exactly `path`, e.g. `/v1/logs`.
+#[cfg(any(feature = "sources-opentelemetry", feature = "sources-vector"))]
+pub fn path_filter(path: &'static str) -> BoxedFilter<()> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .fold(warp::any().boxed(), |filter, segment| {
+            filter.and(warp::path(segment)).boxed()
+        })
+        .and(warp::path::end())
+        .boxed()
+}
+
 #[async_trait]
 pub trait HttpSource: Clone + Send + Sync + 'static {
     fn build_event(
@@ -1,9 +1,17 @@
 mod encoding_config;
 #[cfg(feature = "sources-utils-fake")]
 pub mod fake;
+#[cfg(any(
+    feature = "sources-file",
+    feature = "sources-journald",
+    feature = "sources-kafka"
+))]
+mod finalizer;
 #[cfg(feature = "sources-utils-http")]
 mod http;
 pub mod multiline_config;
+#[cfg(any(feature = "sources-aws_s3", feature = "sources-aws_sqs"))]
+pub mod sqs;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 mod tcp;
 #[cfg(all(unix, feature = "sources-socket"))]
@@ -13,11 +21,23 @@ mod unix_stream;
 
 #[cfg(any(feature = "sources-http", feature = "sources-heroku_logs"))]
 pub(crate) use self::http::add_query_parameters;
-#[cfg(feature = "sources-prometheus")]
+#[cfg(any(
+    feature = "sources-opentelemetry",
+    feature = "sources-prometheus",
+    feature = "sources-vector"
+))]
 pub(crate) use self::http::decode;
+#[cfg(any(feature = "sources-opentelemetry", feature = "sources-vector"))]
+pub(crate) use self::http::path_filter;
 #[cfg(feature = "sources-utils-http")]
 pub(crate) use self::http::{ErrorMessage, HttpSource, HttpSourceAuthConfig};
 pub use encoding_config::EncodingConfig;
+#[cfg(any(
+    feature = "sources-file",
+    feature = "sources-journald",
+    feature = "sources-kafka"
+))]
+pub use finalizer::OrderedFinalizer;
 pub use multiline_config::MultilineConfig;
 #[cfg(all(feature = "sources-utils-tls", feature = "listenfd"))]
 pub use tcp::{SocketListenAddr, TcpSource};
@@ -0,0 +1,37 @@
+use futures::{pin_mut, Future};
+use rusoto_core::RusotoError;
+use rusoto_sqs::{ChangeMessageVisibilityError, ChangeMessageVisibilityRequest, Sqs, SqsClient};
+use tokio::time::{self, Duration, Instant};
+
+/// Drives `future` to completion while extending the visibility timeout of
+/// the message every half of it, so that it isn't received again by another
+/// consumer in the meantime. Failed extensions are passed to `on_error`.
+pub async fn extend_visibility_while<T>(
+    client: &SqsClient,
+    queue_url: &str,
+    receipt_handle: &str,
+    visibility_timeout_secs: i64,
+    on_error: impl Fn(RusotoError<ChangeMessageVisibilityError>),
+    future: impl Future<Output = T>,
+) -> T {
+    let period = Duration::from_secs((visibility_timeout_secs as u64 / 2).max(1));
+    let mut interval = time::interval_at(Instant::now() + period, period);
+    pin_mut!(future);
+    loop {
+        tokio::select! {
+            output = &mut future => return output,
+            _ = interval.tick() => {
+                let result = client
+                    .change_message_visibility(ChangeMessageVisibilityRequest {
+                        queue_url: queue_url.to_owned(),
+                        receipt_handle: receipt_handle.to_owned(),
+                        visibility_timeout: visibility_timeout_secs,
+                    })
+                    .await;
+                if let Err(error) = result {
+                    on_error(error);
+                }
+            }
+        }
+    }
+}
@@ -7,11 +7,15 @@ use crate::{
     Event, Pipeline,
 };
 use bytes::Bytes;
-use futures::{future::BoxFuture, stream, FutureExt, Sink, SinkExt, StreamExt, TryFutureExt};
+use futures::{
+    future::{self, BoxFuture},
+    FutureExt, Sink, SinkExt, StreamExt,
+};
 use listenfd::ListenFd;
 use serde::{de, Deserialize, Deserializer, Serialize};
-use std::{fmt, future::ready, io, mem::drop, net::SocketAddr, task::Poll, time::Duration};
+use std::{fmt, io, mem::drop, net::SocketAddr, time::Duration};
 use tokio::{
+    io::AsyncWriteExt,
     net::{TcpListener, TcpStream},
     time::delay_for,
 };
@@ -61,6 +65,17 @@ pub trait TcpSource: Clone + Send + Sync + 'static {
 
     fn build_event(&self, frame: <Self::Decoder as Decoder>::Item, host: Bytes) -> Option<Event>;
 
+    /// Takes the acknowledgement of `frame` out of it, to be written back to
+    /// the peer once the event built from the frame is sent. The future may
+    /// wait for the event to be delivered, and resolves to `None` if nothing
+    /// should be written. Frames aren't acknowledged by default.
+    fn take_ack(
+        &self,
+        _frame: &mut <Self::Decoder as Decoder>::Item,
+    ) -> Option<BoxFuture<'static, Option<Bytes>>> {
+        None
+    }
+
     fn run(
         self,
         addr: SocketListenAddr,
@@ -199,49 +214,75 @@ async fn handle_stream(
 
     let mut _token = None;
     let mut shutdown = Some(shutdown);
+    let mut tripwire = tripwire;
     let mut reader = FramedRead::new(socket, source.decoder());
-    stream::poll_fn(move |cx| {
-        if let Some(fut) = shutdown.as_mut() {
-            match fut.poll_unpin(cx) {
-                Poll::Ready(token) => {
-                    debug!("Start graceful shutdown.");
-                    // Close our write part of TCP socket to signal the other side
-                    // that it should stop writing and close the channel.
-                    let socket: Option<&TcpStream> = reader.get_ref().get_ref();
-                    if let Some(socket) = socket {
-                        if let Err(error) = socket.shutdown(std::net::Shutdown::Write) {
-                            warn!(message = "Failed in signalling to the other side to close the TCP channel.", %error);
-                        }
-                    } else {
-                        // Connection hasn't yet been established so we are done here.
-                        debug!("Closing connection that hasn't yet been fully established.");
-                        return Poll::Ready(None);
+    futures::pin_mut!(out);
+    loop {
+        tokio::select! {
+            _ = &mut tripwire => break,
+            token = async {
+                match shutdown.as_mut() {
+                    Some(shutdown) => shutdown.await,
+                    None => future::pending().await,
+                }
+            } => {
+                debug!("Start graceful shutdown.");
+                // Close our write part of TCP socket to signal the other side
+                // that it should stop writing and close the channel.
+                let socket: Option<&TcpStream> = reader.get_ref().get_ref();
+                if let Some(socket) = socket {
+                    if let Err(error) = socket.shutdown(std::net::Shutdown::Write) {
+                        warn!(message = "Failed in signalling to the other side to close the TCP channel.", %error);
                     }
+                } else {
+                    // Connection hasn't yet been established so we are done here.
+                    debug!("Closing connection that hasn't yet been fully established.");
+                    break;
+                }
 
-                    _token = Some(token);
-                    shutdown = None;
+                _token = Some(token);
+                shutdown = None;
+            }
+            frame = reader.next() => match frame {
+                Some(Ok(mut frame)) => {
+                    let ack = source.take_ack(&mut frame);
+                    if let Some(event) = source.build_event(frame, host.clone()) {
+                        if out.send(event).await.is_err() {
+                            warn!(message = "Error received while processing TCP source.");
+                            break;
+                        }
+                    }
+                    if let Some(ack) = ack {
+                        // Acknowledgements can wait for the event to be
+                        // delivered, so keep watching for shutdown meanwhile.
+                        // Once it starts, the write half is closed and there's
+                        // no writing the acknowledgement anymore.
+                        let ack = tokio::select! {
+                            ack = ack => ack,
+                            _ = &mut tripwire => break,
+                            _ = async {
+                                match shutdown.as_mut() {
+                                    Some(shutdown) => shutdown.await,
+                                    None => future::pending().await,
+                                }
+                            } => break,
+                        };
+                        if let Some(ack) = ack {
+                            if let Err(error) = reader.get_mut().write_all(&ack).await {
+                                warn!(message = "Failed to write acknowledgement to TCP source.", %error);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Some(Err(error)) => {
+                    warn!(message = "Failed to read data from TCP source.", %error);
                 }
-                Poll::Pending => {}
+                None => break,
             }
         }
-
-        reader.poll_next_unpin(cx)
-    })
-    .take_until(tripwire)
-    .filter_map(move |frame| ready(match frame {
-        Ok(frame) => {
-            let host = host.clone();
-            source.build_event(frame, host).map(Ok)
-        }
-        Err(error) => {
-            warn!(message = "Failed to read data from TCP source.", %error);
-            None
-        }
-    }))
-    .forward(out)
-    .map_err(|_| warn!(message = "Error received while processing TCP source."))
-    .map(|_| debug!("Connection closed."))
-    .await
+    }
+    debug!("Connection closed.");
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
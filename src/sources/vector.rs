@@ -1,26 +1,33 @@
-use super::util::{SocketListenAddr, TcpSource};
+use super::util::{decode, path_filter, ErrorMessage, SocketListenAddr, TcpSource};
 use crate::{
     config::{DataType, GenerateConfig, GlobalOptions, Resource, SourceConfig, SourceDescription},
-    event::proto,
+    event::{proto, BatchNotifier, BatchStatus},
     internal_events::{VectorEventReceived, VectorProtoDecodeError},
     shutdown::ShutdownSignal,
     tcp::TcpKeepaliveConfig,
     tls::{MaybeTlsSettings, TlsConfig},
+    vector_protocol::{VectorVersion, V2_EVENTS_PATH, V2_HEALTH_PATH},
     Event, Pipeline,
 };
 use bytes::{Bytes, BytesMut};
+use futures::{stream, FutureExt, SinkExt};
 use getset::Setters;
 use prost::Message;
 use serde::{Deserialize, Serialize};
 use tokio_util::codec::LengthDelimitedCodec;
+use warp::{http::StatusCode, Filter};
 
 #[derive(Deserialize, Serialize, Debug, Clone, Setters)]
 #[serde(deny_unknown_fields)]
 pub struct VectorConfig {
     address: SocketListenAddr,
+    #[serde(default)]
+    #[set = "pub"]
+    version: VectorVersion,
+    #[set = "pub"]
+    acknowledgements: Option<bool>,
     keepalive: Option<TcpKeepaliveConfig>,
-    #[serde(default = "default_shutdown_timeout_secs")]
-    shutdown_timeout_secs: u64,
+    shutdown_timeout_secs: Option<u64>,
     #[set = "pub"]
     tls: Option<TlsConfig>,
     receive_buffer_bytes: Option<usize>,
@@ -34,8 +41,10 @@ impl VectorConfig {
     pub fn from_address(address: SocketListenAddr) -> Self {
         Self {
             address,
+            version: VectorVersion::default(),
+            acknowledgements: None,
             keepalive: None,
-            shutdown_timeout_secs: default_shutdown_timeout_secs(),
+            shutdown_timeout_secs: None,
             tls: None,
             receive_buffer_bytes: None,
         }
@@ -65,12 +74,28 @@ impl SourceConfig for VectorConfig {
         shutdown: ShutdownSignal,
         out: Pipeline,
     ) -> crate::Result<super::Source> {
-        let vector = VectorSource;
+        self.version.check_options(
+            &[
+                ("keepalive", self.keepalive.is_some()),
+                (
+                    "shutdown_timeout_secs",
+                    self.shutdown_timeout_secs.is_some(),
+                ),
+                ("receive_buffer_bytes", self.receive_buffer_bytes.is_some()),
+            ],
+            &[("acknowledgements", self.acknowledgements.is_some())],
+        )?;
         let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        if self.version == VectorVersion::V2 {
+            return self.build_v2(tls, shutdown, out);
+        }
+
+        let vector = VectorSource;
         vector.run(
             self.address,
             self.keepalive,
-            self.shutdown_timeout_secs,
+            self.shutdown_timeout_secs
+                .unwrap_or_else(default_shutdown_timeout_secs),
             tls,
             self.receive_buffer_bytes,
             shutdown,
@@ -91,6 +116,110 @@ impl SourceConfig for VectorConfig {
     }
 }
 
+impl VectorConfig {
+    fn build_v2(
+        &self,
+        tls: MaybeTlsSettings,
+        shutdown: ShutdownSignal,
+        out: Pipeline,
+    ) -> crate::Result<super::Source> {
+        let address = match self.address {
+            SocketListenAddr::SocketAddr(address) => address,
+            SocketListenAddr::SystemdFd(_) => {
+                return Err("Version 2 of the protocol can't listen on systemd sockets.".into())
+            }
+        };
+        let acknowledgements = self.acknowledgements.unwrap_or(false);
+
+        Ok(Box::pin(async move {
+            let events = warp::post()
+                .and(path_filter(V2_EVENTS_PATH))
+                .and(warp::header::optional::<String>("content-encoding"))
+                .and(warp::body::bytes())
+                .and_then(move |encoding: Option<String>, body: Bytes| {
+                    let out = out.clone();
+                    async move {
+                        let (status, message) =
+                            match handle_batch(&encoding, body, out, acknowledgements).await {
+                                Ok(()) => (StatusCode::OK, String::new()),
+                                Err(error) => (error.status(), error.to_string()),
+                            };
+                        Ok::<_, warp::Rejection>(warp::reply::with_status(message, status))
+                    }
+                });
+            let health = warp::get()
+                .and(path_filter(V2_HEALTH_PATH))
+                .map(warp::reply);
+
+            info!(message = "Building HTTP server.", address = %address);
+
+            let listener = tls.bind(&address).await.map_err(|error| {
+                error!(message = "Failed to bind to listener socket.", %error);
+            })?;
+            warp::serve(events.or(health))
+                .serve_incoming_with_graceful_shutdown(
+                    listener.accept_stream(),
+                    shutdown.clone().map(|_| ()),
+                )
+                .await;
+            // We need to drop the last copy of ShutdownSignalToken only after server has shut down.
+            drop(shutdown);
+            Ok(())
+        }))
+    }
+}
+
+/// Forwards a batch of events, answering once they are delivered if
+/// acknowledgements are enabled. The sink retries the batch on server errors.
+async fn handle_batch(
+    encoding: &Option<String>,
+    body: Bytes,
+    mut out: Pipeline,
+    acknowledgements: bool,
+) -> Result<(), ErrorMessage> {
+    let body = decode(encoding, body)?;
+    let batch = proto::EventBatch::decode(body).map_err(|error| {
+        let message = format!("Failed to decode batch: {}", error);
+        emit!(VectorProtoDecodeError { error });
+        ErrorMessage::new(StatusCode::BAD_REQUEST, message)
+    })?;
+
+    let (notifier, receiver) = BatchNotifier::maybe_new_with_receiver(acknowledgements);
+    let events = batch
+        .events
+        .into_iter()
+        .map(|event| {
+            emit!(VectorEventReceived {
+                byte_size: event.encoded_len()
+            });
+            Ok(Event::from(event).with_batch_notifier_option(&notifier))
+        })
+        .collect::<Vec<_>>();
+    drop(notifier);
+
+    out.send_all(&mut stream::iter(events)).await.map_err(|_| {
+        ErrorMessage::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "Failed to forward events, downstream is closed.".to_owned(),
+        )
+    })?;
+
+    match receiver {
+        None => Ok(()),
+        Some(receiver) => match receiver.await {
+            BatchStatus::Delivered => Ok(()),
+            BatchStatus::Errored => Err(ErrorMessage::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Failed to deliver events.".to_owned(),
+            )),
+            BatchStatus::Failed => Err(ErrorMessage::new(
+                StatusCode::BAD_REQUEST,
+                "Events were rejected.".to_owned(),
+            )),
+        },
+    }
+}
+
 #[derive(Debug, Clone)]
 struct VectorSource;
 
@@ -128,20 +257,58 @@ mod test {
             metric::{MetricKind, MetricValue},
             Metric,
         },
-        sinks::vector::VectorSinkConfig,
+        sinks::{util::Compression, vector::VectorSinkConfig},
         test_util::{collect_ready, next_addr, wait_for_tcp},
         tls::{TlsConfig, TlsOptions},
+        vector_protocol::VectorVersion,
         Event, Pipeline,
     };
-    use futures::stream;
+    use futures::{stream, StreamExt};
     use std::net::SocketAddr;
-    use tokio::time::{delay_for, Duration};
+    use tokio::time::{delay_for, timeout, Duration};
 
     #[test]
     fn generate_config() {
         crate::test_util::test_generate_config::<VectorConfig>();
     }
 
+    #[tokio::test]
+    async fn rejects_options_of_other_version() {
+        let source: VectorConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:9000"
+            acknowledgements = true
+            "#,
+        )
+        .unwrap();
+        let error = source
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                Pipeline::new_test().0,
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("`acknowledgements` is only supported by version 2 of the protocol"));
+
+        let sink: VectorSinkConfig = toml::from_str(
+            r#"
+            address = "127.0.0.1:9000"
+            version = "2"
+            send_buffer_bytes = 1024
+            "#,
+        )
+        .unwrap();
+        let error = sink.build(SinkContext::new_test()).await.err().unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("`send_buffer_bytes` is only supported by version 1 of the protocol"));
+    }
+
     async fn stream_test(addr: SocketAddr, source: VectorConfig, sink: VectorSinkConfig) {
         let (tx, rx) = Pipeline::new_test();
 
@@ -220,4 +387,61 @@ mod test {
         )
         .await;
     }
+
+    #[tokio::test]
+    async fn it_works_with_vector_sink_v2() {
+        let addr = next_addr();
+        stream_test(
+            addr,
+            {
+                let mut config = VectorConfig::from_address(addr.into());
+                config.set_version(VectorVersion::V2);
+                config
+            },
+            {
+                let mut config =
+                    VectorSinkConfig::from_address(format!("localhost:{}", addr.port()));
+                config.set_version(VectorVersion::V2);
+                config.set_compression(Some(Compression::gzip_default()));
+                config
+            },
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn v2_answers_once_events_are_delivered() {
+        let addr = next_addr();
+        let (tx, rx) = Pipeline::new_test();
+
+        let mut source = VectorConfig::from_address(addr.into());
+        source.set_version(VectorVersion::V2);
+        source.set_acknowledgements(Some(true));
+        let server = source
+            .build(
+                "default",
+                &GlobalOptions::default(),
+                ShutdownSignal::noop(),
+                tx,
+            )
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        wait_for_tcp(addr).await;
+
+        let mut sink = VectorSinkConfig::from_address(format!("localhost:{}", addr.port()));
+        sink.set_version(VectorVersion::V2);
+        let (sink, _) = sink.build(SinkContext::new_test()).await.unwrap();
+
+        let events = vec![Event::from("first"), Event::from("second")];
+        let mut run = tokio::spawn(sink.run(stream::iter(events.clone())));
+
+        let output = rx.take(2).collect::<Vec<_>>().await;
+        assert_eq!(events, output);
+        // The source only answers once the events are done with.
+        assert!(timeout(Duration::from_millis(100), &mut run).await.is_err());
+
+        drop(output);
+        run.await.unwrap().unwrap();
+    }
 }
@@ -21,6 +21,9 @@ use std::{
 
 const PEM_START_MARKER: &str = "-----BEGIN ";
 
+/// `X509_V_ERR_HOSTNAME_MISMATCH`, which the `openssl` crate doesn't name.
+const X509_V_ERR_HOSTNAME_MISMATCH: i32 = 62;
+
 #[cfg(test)]
 pub const TEST_PEM_CA_PATH: &str = "tests/data/Vector_CA.crt";
 #[cfg(test)]
@@ -182,6 +185,20 @@ impl TlsSettings {
     pub fn apply_connect_configuration(&self, connection: &mut ConnectConfiguration) {
         connection.set_verify_hostname(self.verify_hostname);
     }
+
+    /// Applies `verify_hostname` to a connector that can't be configured per
+    /// connection, such as the one tunneling through a proxy, by accepting
+    /// certificates whose only error is not matching the hostname.
+    pub fn apply_connector_configuration(&self, context: &mut SslContextBuilder) {
+        if self.verify_certificate && !self.verify_hostname {
+            context.set_verify_callback(
+                SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT,
+                |verified, store| {
+                    verified || store.error().as_raw() == X509_V_ERR_HOSTNAME_MISMATCH
+                },
+            );
+        }
+    }
 }
 
 impl TlsOptions {
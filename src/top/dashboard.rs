@@ -73,7 +73,9 @@ impl HumanFormatter for i64 {
     }
 }
 
-static HEADER: [&str; 6] = ["Name", "Kind", "Type", "Events", "Bytes", "Errors"];
+static HEADER: [&str; 7] = [
+    "Name", "Kind", "Type", "Events", "Bytes", "Buffer", "Errors",
+];
 
 struct Widgets<'a> {
     constraints: Vec<Constraint>,
@@ -156,6 +158,18 @@ impl<'a> Widgets<'a> {
                         r.processed_bytes_throughput_sec.human_format_bytes()
                     ),
                 },
+                match r.buffer_usage {
+                    None => "N/A".to_string(),
+                    Some((events, byte_size)) => format!(
+                        "{} ({})",
+                        if self.opts.human_metrics {
+                            events.human_format()
+                        } else {
+                            events.thousands_format()
+                        },
+                        byte_size.human_format_bytes()
+                    ),
+                },
                 if self.opts.human_metrics {
                     r.errors.human_format()
                 } else {
@@ -172,12 +186,13 @@ impl<'a> Widgets<'a> {
             .block(Block::default().borders(Borders::ALL).title("Components"))
             .column_spacing(2)
             .widths(&[
-                Constraint::Percentage(20),
-                Constraint::Percentage(10),
+                Constraint::Percentage(15),
                 Constraint::Percentage(10),
-                Constraint::Percentage(20),
-                Constraint::Percentage(20),
                 Constraint::Percentage(10),
+                Constraint::Percentage(17),
+                Constraint::Percentage(17),
+                Constraint::Percentage(13),
+                Constraint::Percentage(8),
             ]);
 
         f.render_widget(w, area);
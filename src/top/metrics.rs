@@ -26,6 +26,7 @@ async fn component_added(client: Arc<SubscriptionClient>, mut tx: state::EventTx
                     processed_events_throughput_sec: 0,
                     processed_bytes_total: 0,
                     processed_bytes_throughput_sec: 0,
+                    buffer_usage: None,
                     errors: 0,
                 }))
                 .await;
@@ -147,6 +148,27 @@ async fn processed_bytes_throughputs(
     }
 }
 
+async fn buffer_metrics(client: Arc<SubscriptionClient>, mut tx: state::EventTx, interval: i64) {
+    let res = client.component_buffer_metrics_subscription(interval);
+
+    tokio::pin! {
+        let stream = res.stream();
+    };
+
+    while let Some(Some(res)) = stream.next().await {
+        if let Some(d) = res.data {
+            let c = d.component_buffer_metrics;
+            let _ = tx
+                .send(state::EventType::BufferUsages(
+                    c.into_iter()
+                        .map(|c| (c.name, c.buffer.events as i64, c.buffer.byte_size as i64))
+                        .collect(),
+                ))
+                .await;
+        }
+    }
+}
+
 /// Subscribe to each metrics channel through a separate client. This is a temporary workaround
 /// until client multiplexing is fixed. In future, we should be able to use a single client
 pub fn subscribe(client: SubscriptionClient, tx: state::EventTx, interval: i64) {
@@ -171,9 +193,10 @@ pub fn subscribe(client: SubscriptionClient, tx: state::EventTx, interval: i64)
     ));
     tokio::spawn(processed_bytes_throughputs(
         Arc::clone(&client),
-        tx,
+        tx.clone(),
         interval,
     ));
+    tokio::spawn(buffer_metrics(Arc::clone(&client), tx, interval));
 }
 
 /// Retrieve the initial components/metrics for first paint. Further updating the metrics
@@ -204,6 +227,7 @@ pub async fn init_components(client: &Client) -> Result<state::State, ()> {
                         processed_events_throughput_sec: 0,
                         processed_bytes_total: d.on.processed_bytes_total(),
                         processed_bytes_throughput_sec: 0,
+                        buffer_usage: d.on.buffer_usage(),
                         errors: 0,
                     },
                 ))
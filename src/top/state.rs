@@ -11,6 +11,8 @@ pub enum EventType {
     ProcessedBytesTotals(Vec<NamedMetric>),
     /// Interval + named metric
     ProcessedBytesThroughputs(i64, Vec<NamedMetric>),
+    /// Name + events and bytes held in the buffer
+    BufferUsages(Vec<(String, i64, i64)>),
     ComponentAdded(ComponentRow),
     ComponentRemoved(String),
 }
@@ -29,6 +31,8 @@ pub struct ComponentRow {
     pub processed_events_throughput_sec: i64,
     pub processed_bytes_total: i64,
     pub processed_bytes_throughput_sec: i64,
+    /// Events and bytes held in the buffer, for sinks that report buffer usage
+    pub buffer_usage: Option<(i64, i64)>,
     pub errors: i64,
 }
 
@@ -75,6 +79,13 @@ pub async fn updater(mut state: State, mut event_rx: EventRx) -> StateRx {
                             }
                         }
                     }
+                    EventType::BufferUsages(rows) => {
+                        for (name, events, byte_size) in rows {
+                            if let Some(r) = state.get_mut(&name) {
+                                r.buffer_usage = Some((events, byte_size));
+                            }
+                        }
+                    }
                     EventType::ComponentAdded(c) => {
                         let _ = state.insert(c.name.clone(), c);
                     }
@@ -5,12 +5,12 @@ use super::{
 };
 use crate::{
     buffers,
-    config::{DataType, SinkContext},
+    config::{DataType, ProxyConfig, SinkContext},
     event::Event,
     internal_events::EventProcessed,
     shutdown::SourceShutdownCoordinator,
     stream::VecStreamExt,
-    transforms::Transform,
+    transforms::{Transform, TransformOutputs},
     Pipeline,
 };
 use futures::{future, stream, FutureExt, StreamExt, TryFutureExt};
@@ -21,6 +21,7 @@ use std::{
 };
 use stream_cancel::{StreamExt as StreamCancelExt, Trigger, Tripwire};
 use tokio::time::{timeout, Duration};
+use tracing_futures::Instrument;
 
 pub struct Pieces {
     pub inputs: HashMap<String, (buffers::BufferInputCloner, Vec<String>)>,
@@ -30,6 +31,9 @@ pub struct Pieces {
     pub healthchecks: HashMap<String, Task>,
     pub shutdown_coordinator: SourceShutdownCoordinator,
     pub detach_triggers: HashMap<String, Trigger>,
+    /// Stops a sink that is still sending events after its inputs have been
+    /// detached, handing back its buffer.
+    pub drain_triggers: HashMap<String, Trigger>,
 }
 
 /// Builds only the new pieces, and doesn't check their topology.
@@ -45,6 +49,7 @@ pub async fn build_pieces(
     let mut healthchecks = HashMap::new();
     let mut shutdown_coordinator = SourceShutdownCoordinator::default();
     let mut detach_triggers = HashMap::new();
+    let mut drain_triggers = HashMap::new();
 
     let mut errors = vec![];
 
@@ -101,6 +106,7 @@ pub async fn build_pieces(
         .filter(|(name, _)| diff.transforms.contains_new(&name))
     {
         let trans_inputs = &transform.inputs;
+        let named_outputs = transform.named_outputs(name);
 
         let typetag = transform.inner.transform_type();
 
@@ -110,6 +116,13 @@ pub async fn build_pieces(
                 errors.push(format!("Transform \"{}\": {}", name, error));
                 continue;
             }
+            Ok(Transform::Task(_)) if !named_outputs.is_empty() => {
+                errors.push(format!(
+                    "Transform \"{}\": Named outputs are only supported by function transforms.",
+                    name
+                ));
+                continue;
+            }
             Ok(transform) => transform,
         };
 
@@ -119,6 +132,25 @@ pub async fn build_pieces(
         let (output, control) = Fanout::new();
 
         let transform = match transform {
+            Transform::Function(mut t) if !named_outputs.is_empty() => {
+                let mut fanouts = vec![output];
+                for output_name in named_outputs {
+                    let (output, control) = Fanout::new();
+                    fanouts.push(output);
+                    outputs.insert(output_name, control);
+                }
+
+                input_rx
+                    .filter(move |event| ready(filter_event_type(event, input_type)))
+                    .flat_map(move |v| {
+                        let mut buf = TransformOutputs::default();
+                        t.transform_outputs(&mut buf, v);
+                        emit!(EventProcessed);
+                        stream::iter(buf.into_events()).map(Ok)
+                    })
+                    .forward(fanout::OutputsFanout::new(fanouts))
+                    .boxed()
+            }
             Transform::Function(mut t) => input_rx
                 .filter(move |event| ready(filter_event_type(event, input_type)))
                 .flat_map(move |v| {
@@ -172,13 +204,28 @@ pub async fn build_pieces(
                     errors.push(format!("Sink \"{}\": {}", name, error));
                     continue;
                 }
-                Ok((tx, rx, acker)) => (tx, Arc::new(Mutex::new(Some(rx.into()))), acker),
+                Ok((tx, rx, acker)) => {
+                    let span = error_span!(
+                        parent: None,
+                        "sink",
+                        component_kind = "sink",
+                        component_name = %name,
+                        component_type = %typetag,
+                    );
+                    let (tx, rx) = buffers::instrument(tx, rx, span);
+                    (
+                        tx,
+                        Arc::new(Mutex::new(Some(rx.into()))),
+                        acker.with_finalization(),
+                    )
+                }
             }
         };
 
         let cx = SinkContext {
             acker: acker.clone(),
             healthcheck,
+            proxy: ProxyConfig::merge_with_env(&config.global.proxy, &sink.proxy),
         };
 
         let (sink, healthcheck) = match sink.inner.build(cx).await {
@@ -189,7 +236,24 @@ pub async fn build_pieces(
             Ok(built) => built,
         };
 
+        // Rejected events are sent on an output like the ones of transforms,
+        // pumped into its fanout for as long as the sink runs.
+        let (dead_letter, dead_letter_pump) = match sink.dead_letter_output(name) {
+            Some(output_name) => {
+                let (dead_letter_tx, dead_letter_rx) = futures::channel::mpsc::channel(100);
+                let (output, control) = Fanout::new();
+                outputs.insert(output_name, control);
+                let pump = dead_letter_rx.map(Ok).forward(output).map(|_| ());
+                (
+                    Some(buffers::DeadLetter::new(name, dead_letter_tx)),
+                    Some(pump),
+                )
+            }
+            None => (None, None),
+        };
+
         let (trigger, tripwire) = Tripwire::new();
+        let (drain_trigger, drain_deadline) = Tripwire::new();
 
         let sink = async move {
             // Why is this Arc<Mutex<Option<_>>> needed you ask.
@@ -204,14 +268,28 @@ pub async fn build_pieces(
                 .take()
                 .expect("Task started but input has been taken.");
 
-            sink.run(
-                rx.by_ref()
-                    .filter(|event| ready(filter_event_type(event, input_type)))
-                    .take_until_if(tripwire),
-            )
-            .await
-            .map(|_| {
+            if let Some(pump) = dead_letter_pump {
+                tokio::spawn(pump.in_current_span());
+            }
+
+            let input = rx
+                .by_ref()
+                .filter(|event| ready(filter_event_type(event, input_type)))
+                .take_until_if(tripwire);
+            let run = Box::pin(sink.run(acker.finalizing(input, dead_letter)));
+            let result = match future::select(run, drain_deadline).await {
+                future::Either::Left((result, _)) => result,
+                future::Either::Right((false, run)) => run.await,
+                // The events the sink has read but not sent yet are
+                // abandoned, the ones left in the buffer are kept.
+                future::Either::Right((true, _)) => {
+                    debug!("Stopped before sending all events.");
+                    Ok(())
+                }
+            };
+            result.map(|_| {
                 debug!("Finished.");
+                acker.abandon_pending();
                 TaskOutput::Sink(rx, acker)
             })
         };
@@ -259,6 +337,7 @@ pub async fn build_pieces(
         healthchecks.insert(name.clone(), healthcheck_task);
         tasks.insert(name.clone(), task);
         detach_triggers.insert(name.clone(), trigger);
+        drain_triggers.insert(name.clone(), drain_trigger);
     }
 
     if errors.is_empty() {
@@ -270,6 +349,7 @@ pub async fn build_pieces(
             healthchecks,
             shutdown_coordinator,
             detach_triggers,
+            drain_triggers,
         };
 
         Ok(pieces)
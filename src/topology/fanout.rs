@@ -189,9 +189,60 @@ impl Sink<Event> for Fanout {
     }
 }
 
+/// Fans out the events of a transform with named outputs to the consumers of
+/// the output each event was emitted on. Outputs are indexed as in
+/// `TransformOutputs`.
+pub struct OutputsFanout {
+    outputs: Vec<Fanout>,
+}
+
+impl OutputsFanout {
+    pub fn new(outputs: Vec<Fanout>) -> Self {
+        Self { outputs }
+    }
+
+    fn poll_outputs<F>(&mut self, cx: &mut Context<'_>, poll: F) -> Poll<Result<(), ()>>
+    where
+        F: Fn(Pin<&mut Fanout>, &mut Context<'_>) -> Poll<Result<(), ()>>,
+    {
+        let mut poll_result = Poll::Ready(Ok(()));
+        for output in &mut self.outputs {
+            match poll(Pin::new(output), cx) {
+                Poll::Pending => poll_result = Poll::Pending,
+                Poll::Ready(Ok(())) => (),
+                Poll::Ready(Err(())) => return Poll::Ready(Err(())),
+            }
+        }
+        poll_result
+    }
+}
+
+impl Sink<(usize, Event)> for OutputsFanout {
+    type Error = ();
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.get_mut()
+            .poll_outputs(cx, |output, cx| output.poll_ready(cx))
+    }
+
+    fn start_send(self: Pin<&mut Self>, (index, event): (usize, Event)) -> Result<(), ()> {
+        Pin::new(&mut self.get_mut().outputs[index]).start_send(event)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.get_mut()
+            .poll_outputs(cx, |output, cx| output.poll_flush(cx))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<(), ()>> {
+        self.get_mut()
+            .poll_outputs(cx, |output, cx| output.poll_close(cx))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{ControlMessage, Fanout};
+    use super::{ControlMessage, Fanout, OutputsFanout};
     use crate::{sink::BoundedSink, test_util::collect_ready, Event};
     use futures::{stream, Sink, SinkExt, StreamExt};
     use std::{
@@ -221,6 +272,35 @@ mod tests {
         assert_eq!(collect_ready(rx_b).await, recs);
     }
 
+    #[tokio::test]
+    async fn outputs_fanout_routes_by_output() {
+        let (tx_a, rx_a) = unbounded_channel();
+        let tx_a = Box::new(tx_a.sink_map_err(|_| unreachable!()));
+        let (tx_b, rx_b) = unbounded_channel();
+        let tx_b = Box::new(tx_b.sink_map_err(|_| unreachable!()));
+
+        let mut default = Fanout::new().0;
+        default.add("a".to_string(), tx_a);
+        let mut named = Fanout::new().0;
+        named.add("b".to_string(), tx_b);
+        let fanout = OutputsFanout::new(vec![default, named]);
+
+        let recs = make_events(3);
+        let routed = vec![
+            (1, recs[0].clone()),
+            (0, recs[1].clone()),
+            (1, recs[2].clone()),
+        ];
+        let send = stream::iter(routed).map(Ok).forward(fanout);
+        let _ = send.await.unwrap();
+
+        assert_eq!(collect_ready(rx_a).await, &recs[1..2]);
+        assert_eq!(
+            collect_ready(rx_b).await,
+            vec![recs[0].clone(), recs[2].clone()]
+        );
+    }
+
     #[tokio::test]
     async fn fanout_notready() {
         let (tx_a, rx_a) = channel(2);
@@ -30,7 +30,7 @@ use std::{
 };
 use tokio::{
     sync::mpsc,
-    time::{delay_until, interval, Duration, Instant},
+    time::{delay_until, interval, timeout_at, Duration, Instant},
 };
 use tracing_futures::Instrument;
 
@@ -50,6 +50,7 @@ pub struct RunningTopology {
     tasks: HashMap<String, TaskHandle>,
     shutdown_coordinator: SourceShutdownCoordinator,
     detach_triggers: HashMap<String, DisabledTrigger>,
+    drain_triggers: HashMap<String, DisabledTrigger>,
     config: Config,
     abort_tx: mpsc::UnboundedSender<()>,
 }
@@ -67,6 +68,7 @@ pub async fn start_validated(
         config,
         shutdown_coordinator: SourceShutdownCoordinator::default(),
         detach_triggers: HashMap::new(),
+        drain_triggers: HashMap::new(),
         source_tasks: HashMap::new(),
         tasks: HashMap::new(),
         abort_tx,
@@ -361,6 +363,14 @@ impl RunningTopology {
             self.remove_outputs(&name);
         }
 
+        // Named outputs are rebuilt with their transforms, and may be gone
+        // from the new config.
+        for name in diff.transforms.removed_and_changed() {
+            for output in self.config.transforms[name].named_outputs(name) {
+                self.remove_outputs(&output);
+            }
+        }
+
         // Sinks
 
         // Resource conflicts
@@ -410,8 +420,18 @@ impl RunningTopology {
             .chain(reuse_buffers.iter().cloned())
             .collect::<HashSet<_>>();
 
+        // Sinks are given until the deadline to send the events they hold.
+        let deadline = Instant::now() + new_config.global.reload_drain_timeout();
+
         // First pass
 
+        // Dead letter outputs are rebuilt with their sinks.
+        for name in diff.sinks.removed_and_changed() {
+            if let Some(output) = self.config.sinks[name].dead_letter_output(name) {
+                self.remove_outputs(&output);
+            }
+        }
+
         // Detach removed sinks
         for name in &diff.sinks.to_remove {
             info!(message = "Removing sink.", name = ?name);
@@ -437,9 +457,9 @@ impl RunningTopology {
         for name in &diff.sinks.to_remove {
             let previous = self.tasks.remove(name).unwrap();
             if wait_for_sinks.contains(name) {
-                debug!(message = "Waiting for sink to shutdown.", %name);
-                previous.await.unwrap().unwrap();
+                self.drain_sink(name, previous, deadline).await;
             } else {
+                self.drain_sink_detached(name, deadline);
                 drop(previous); // detach and forget
             }
         }
@@ -449,8 +469,7 @@ impl RunningTopology {
         for name in &diff.sinks.to_change {
             if wait_for_sinks.contains(name) {
                 let previous = self.tasks.remove(name).unwrap();
-                debug!(message = "Waiting for sink to shutdown.", %name);
-                let buffer = previous.await.unwrap().unwrap();
+                let buffer = self.drain_sink(name, previous, deadline).await;
 
                 if reuse_buffers.contains(name) {
                     let tx = self.inputs.remove(name).unwrap();
@@ -461,12 +480,46 @@ impl RunningTopology {
 
                     buffers.insert(name.clone(), (tx, Arc::new(Mutex::new(Some(rx))), acker));
                 }
+            } else {
+                // The previous sink keeps running until its buffer is empty.
+                self.drain_sink_detached(name, deadline);
             }
         }
 
         buffers
     }
 
+    /// Waits for a detached sink to send the events it holds, and stops it
+    /// if it doesn't finish by `deadline`.
+    async fn drain_sink(
+        &mut self,
+        name: &str,
+        mut task: TaskHandle,
+        deadline: Instant,
+    ) -> TaskOutput {
+        debug!(message = "Waiting for sink to shutdown.", %name);
+        let drain_trigger = self.drain_triggers.remove(name);
+        if timeout_at(deadline, &mut task).await.is_err() {
+            warn!(message = "Sink didn't drain before the deadline; stopping it.", %name);
+            if let Some(trigger) = drain_trigger {
+                trigger.into_inner().cancel();
+            }
+        }
+        task.await.unwrap().unwrap()
+    }
+
+    /// Stops a sink left to finish on its own if it's still running by
+    /// `deadline`.
+    fn drain_sink_detached(&mut self, name: &str, deadline: Instant) {
+        if let Some(trigger) = self.drain_triggers.remove(name) {
+            let trigger = trigger.into_inner();
+            tokio::spawn(async move {
+                delay_until(deadline).await;
+                trigger.cancel();
+            });
+        }
+    }
+
     /// Rewires topology
     async fn connect_diff(&mut self, diff: &ConfigDiff, new_pieces: &mut Pieces) {
         // Sources
@@ -481,6 +534,13 @@ impl RunningTopology {
             self.setup_outputs(&name, new_pieces);
         }
 
+        // What's left are the named outputs of transforms and the dead letter
+        // outputs of sinks, which can be inputs of transforms too.
+        let outputs = new_pieces.outputs.keys().cloned().collect::<Vec<_>>();
+        for name in outputs {
+            self.setup_outputs(&name, new_pieces);
+        }
+
         for name in &diff.transforms.to_change {
             self.replace_inputs(&name, new_pieces);
         }
@@ -537,6 +597,9 @@ impl RunningTopology {
 
     fn spawn_sink(&mut self, name: &str, new_pieces: &mut builder::Pieces) {
         let task = new_pieces.tasks.remove(name).unwrap();
+        if let Some(trigger) = new_pieces.drain_triggers.remove(name) {
+            self.drain_triggers.insert(name.to_string(), trigger.into());
+        }
         let span = error_span!(
             "sink",
             component_kind = "sink",
@@ -1,4 +1,7 @@
-use crate::event::{Event, LogEvent, Value};
+use crate::{
+    config::secret::{self, Redacting},
+    event::{Event, LogEvent, Value},
+};
 use metrics_tracing_context::MetricsLayer;
 use once_cell::sync::OnceCell;
 use std::{
@@ -47,6 +50,7 @@ pub fn init(color: bool, json: bool, levels: &str) {
     let dispatch = if json {
         let formatter = FmtSubscriber::builder()
             .with_env_filter(levels)
+            .with_writer(|| Redacting::new(std::io::stdout()))
             .json()
             .flatten_event(true)
             .finish()
@@ -61,6 +65,7 @@ pub fn init(color: bool, json: bool, levels: &str) {
         let formatter = FmtSubscriber::builder()
             .with_ansi(color)
             .with_env_filter(levels)
+            .with_writer(|| Redacting::new(std::io::stdout()))
             .finish()
             .with(Limit::default());
         if metrics_layer_enabled {
@@ -225,11 +230,12 @@ struct MakeLogEvent(LogEvent);
 
 impl Visit for MakeLogEvent {
     fn record_str(&mut self, field: &Field, value: &str) {
-        self.0.insert(field.name(), value.to_string());
+        self.0.insert(field.name(), secret::redact(value));
     }
 
     fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
-        self.0.insert(field.name(), format!("{:?}", value));
+        self.0
+            .insert(field.name(), secret::redact(&format!("{:?}", value)));
     }
 
     fn record_i64(&mut self, field: &Field, value: i64) {
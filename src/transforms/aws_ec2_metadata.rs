@@ -1,5 +1,5 @@
 use crate::{
-    config::{DataType, TransformConfig, TransformDescription},
+    config::{DataType, ProxyConfig, TransformConfig, TransformDescription},
     event::Event,
     http::HttpClient,
     internal_events::{AwsEc2MetadataRefreshFailed, AwsEc2MetadataRefreshSuccessful},
@@ -140,7 +140,8 @@ impl TransformConfig for Ec2Metadata {
             .clone()
             .unwrap_or_else(|| DEFAULT_FIELD_WHITELIST.clone());
 
-        let http_client = HttpClient::new(None)?;
+        // The metadata endpoint is link-local, so it's never proxied.
+        let http_client = HttpClient::new(None, &ProxyConfig::default())?;
 
         let mut client =
             MetadataClient::new(http_client, host, keys, write, refresh_interval, fields);
@@ -64,6 +64,7 @@ impl FunctionTransform for Coercer {
             // conversion.
             let mut new_event = Event::new_empty_log();
             let new_log = new_event.as_mut_log();
+            new_log.add_finalizers(log.take_finalizers());
             for (field, conv) in &self.types {
                 if let Some(value) = log.remove(field) {
                     match conv.convert::<Value>(value.into_bytes()) {
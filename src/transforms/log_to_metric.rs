@@ -341,10 +341,13 @@ fn to_metric(config: &MetricConfig, event: &Event) -> Result<Metric, TransformEr
 }
 
 impl FunctionTransform for LogToMetric {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
+    fn transform(&mut self, output: &mut Vec<Event>, mut event: Event) {
+        // Each metric is only finalized once all of them are.
+        let finalizers = event.take_finalizers();
         for config in self.config.metrics.iter() {
             match to_metric(&config, &event) {
-                Ok(metric) => {
+                Ok(mut metric) => {
+                    metric.add_finalizers(finalizers.clone());
                     output.push(Event::Metric(metric));
                 }
                 Err(TransformError::FieldNotFound { field }) => emit!(LogToMetricFieldNotFound {
@@ -3,7 +3,7 @@ mod interop;
 use crate::{
     config::DataType,
     config::CONFIG_PATHS,
-    event::Event,
+    event::{Event, EventFinalizers},
     internal_events::{LuaBuildError, LuaGcTriggered},
     transforms::{
         util::runtime_transform::{RuntimeTransform, Timer},
@@ -117,6 +117,12 @@ pub struct Lua {
     lua: rlua::Lua,
     invocations_after_gc: usize,
     timers: Vec<Timer>,
+    /// Whether events can be emitted by the timer handlers or the shutdown
+    /// hook, e.g. after being aggregated by the process hook.
+    emits_later: bool,
+    /// The finalizers of the processed events that didn't emit anything
+    /// yet, if `emits_later`. They are attached to the next emitted events.
+    pending_finalizers: EventFinalizers,
 }
 
 impl Lua {
@@ -184,7 +190,9 @@ impl Lua {
         Ok(Self {
             lua,
             invocations_after_gc: 0,
+            emits_later: !timers.is_empty() || config.hooks.shutdown.is_some(),
             timers,
+            pending_finalizers: EventFinalizers::default(),
         })
     }
 
@@ -230,6 +238,20 @@ impl Lua {
     }
 }
 
+// Attaches `finalizers` to each event passed to `emit_fn`.
+fn finalizing_emit_fn<'a, F>(
+    finalizers: &'a EventFinalizers,
+    mut emit_fn: F,
+) -> impl FnMut(Event) + 'a
+where
+    F: FnMut(Event) + 'a,
+{
+    move |mut event: Event| {
+        event.add_finalizers(finalizers.clone());
+        emit_fn(event)
+    }
+}
+
 // A helper that reduces code duplication.
 fn wrap_emit_fn<'lua, 'scope, F: 'scope>(
     scope: &rlua::Scope<'lua, 'scope>,
@@ -245,10 +267,21 @@ where
 }
 
 impl RuntimeTransform for Lua {
-    fn hook_process<F>(&mut self, event: Event, emit_fn: F)
+    fn hook_process<F>(&mut self, mut event: Event, emit_fn: F)
     where
         F: FnMut(Event),
     {
+        // The converted event loses its finalizers, so they're attached to
+        // the events emitted in its stead.
+        let mut finalizers = std::mem::take(&mut self.pending_finalizers);
+        finalizers.merge(event.take_finalizers());
+        let mut emitted = false;
+        let mut emit_fn = finalizing_emit_fn(&finalizers, emit_fn);
+        let emit_fn = |event| {
+            emitted = true;
+            emit_fn(event)
+        };
+
         let _ = self
             .lua
             .context(|ctx: rlua::Context<'_>| {
@@ -261,6 +294,10 @@ impl RuntimeTransform for Lua {
             .context(RuntimeErrorHooksProcess)
             .map_err(|e| emit!(LuaBuildError { error: e }));
 
+        if !emitted && self.emits_later {
+            self.pending_finalizers = finalizers;
+        }
+
         self.attempt_gc();
     }
 
@@ -288,6 +325,9 @@ impl RuntimeTransform for Lua {
     where
         F: FnMut(Event),
     {
+        let finalizers = std::mem::take(&mut self.pending_finalizers);
+        let emit_fn = finalizing_emit_fn(&finalizers, emit_fn);
+
         let _ = self
             .lua
             .context(|ctx: rlua::Context<'_>| {
@@ -310,6 +350,9 @@ impl RuntimeTransform for Lua {
     where
         F: FnMut(Event),
     {
+        let finalizers = std::mem::take(&mut self.pending_finalizers);
+        let emit_fn = finalizing_emit_fn(&finalizers, emit_fn);
+
         let _ = self
             .lua
             .context(|ctx: rlua::Context<'_>| {
@@ -68,7 +68,8 @@ impl MetricToLog {
 
 impl FunctionTransform for MetricToLog {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        let metric = event.into_metric();
+        let mut metric = event.into_metric();
+        let finalizers = metric.take_finalizers();
 
         let retval = serde_json::to_value(&metric)
             .map_err(|error| emit!(MetricToLogFailedSerialize { error }))
@@ -76,6 +77,7 @@ impl FunctionTransform for MetricToLog {
             .and_then(|value| match value {
                 Value::Object(object) => {
                     let mut log = LogEvent::default();
+                    log.add_finalizers(finalizers);
 
                     for (key, value) in object {
                         log.insert_flat(key, value);
@@ -156,6 +156,16 @@ impl Transform {
 pub trait FunctionTransform: Send + dyn_clone::DynClone + Sync {
     fn transform(&mut self, output: &mut Vec<Event>, event: Event);
 
+    /// Transforms with named outputs implement this to choose the output of
+    /// each event. By default, all events are emitted on the default output.
+    fn transform_outputs(&mut self, outputs: &mut TransformOutputs, event: Event) {
+        let mut buf = Vec::with_capacity(1);
+        self.transform(&mut buf, event);
+        for event in buf {
+            outputs.push(event);
+        }
+    }
+
     /// A handy test function that inputs and outputs only one event.
     ///
     /// In a prior time, Vector primarily used this API to handle events.
@@ -174,6 +184,32 @@ pub trait FunctionTransform: Send + dyn_clone::DynClone + Sync {
 
 dyn_clone::clone_trait_object!(FunctionTransform);
 
+/// The events emitted by a function transform, along with the output each of
+/// them is emitted on.
+///
+/// Output `0` is the default output, output `n` is the `n`th entry of the
+/// transform's `TransformConfig::named_outputs`.
+#[derive(Debug, Default)]
+pub struct TransformOutputs {
+    events: Vec<(usize, Event)>,
+}
+
+impl TransformOutputs {
+    /// Emits `event` on the default output.
+    pub fn push(&mut self, event: Event) {
+        self.events.push((0, event));
+    }
+
+    /// Emits `event` on the named output at `index`.
+    pub fn push_named(&mut self, index: usize, event: Event) {
+        self.events.push((index + 1, event));
+    }
+
+    pub fn into_events(self) -> Vec<(usize, Event)> {
+        self.events
+    }
+}
+
 /// Transforms that tend to be more complicated runtime style components.
 ///
 /// These require coordination and map a stream of some `T` to some `U`.
@@ -2,7 +2,7 @@ use crate::{
     conditions::{AnyCondition, Condition},
     config::{DataType, TransformConfig, TransformDescription},
     event::discriminant::Discriminant,
-    event::{Event, LogEvent},
+    event::{Event, EventFinalizers, LogEvent},
     internal_events::ReduceStaleEventFlushed,
     transforms::{TaskTransform, Transform},
 };
@@ -73,12 +73,16 @@ impl TransformConfig for ReduceConfig {
 struct ReduceState {
     fields: HashMap<String, Box<dyn ReduceValueMerger>>,
     stale_since: Instant,
+    /// The finalizers of all the merged events, so that they are only
+    /// finalized once the reduced event is.
+    finalizers: EventFinalizers,
 }
 
 impl ReduceState {
-    fn new(e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
+    fn new(mut e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) -> Self {
         Self {
             stale_since: Instant::now(),
+            finalizers: e.take_finalizers(),
             fields: e
                 .into_iter()
                 .filter_map(|(k, v)| {
@@ -98,7 +102,8 @@ impl ReduceState {
         }
     }
 
-    fn add_event(&mut self, e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+    fn add_event(&mut self, mut e: LogEvent, strategies: &IndexMap<String, MergeStrategy>) {
+        self.finalizers.merge(e.take_finalizers());
         for (k, v) in e.into_iter() {
             let strategy = strategies.get(&k);
             match self.fields.entry(k) {
@@ -128,6 +133,7 @@ impl ReduceState {
 
     fn flush(mut self) -> LogEvent {
         let mut event = Event::new_empty_log().into_log();
+        event.add_finalizers(self.finalizers);
         for (k, v) in self.fields.drain() {
             if let Err(error) = v.insert_into(k, &mut event) {
                 warn!(message = "Failed to merge values for field.", %error);
@@ -289,7 +295,12 @@ impl TaskTransform for Reduce {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{config::TransformConfig, event::Value, Event};
+    use crate::{
+        config::TransformConfig,
+        event::{BatchNotifier, BatchStatus, EventStatus, Value},
+        Event,
+    };
+    use futures::FutureExt;
     use serde_json::json;
 
     #[test]
@@ -459,6 +470,48 @@ group_by = [ "request_id" ]
         assert_eq!(output_2["counter"], Value::from(7));
     }
 
+    #[tokio::test]
+    async fn keeps_finalizers_until_reduced_event_is_finalized() {
+        let reduce = toml::from_str::<ReduceConfig>(
+            r#"
+group_by = [ "request_id" ]
+
+[ends_when]
+  type = "check_fields"
+  "test_end.exists" = true
+"#,
+        )
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+        let reduce = reduce.into_task();
+
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+        let mut e_1 = Event::from("test message 1").with_batch_notifier(&batch);
+        e_1.as_mut_log().insert("request_id", "1");
+
+        let mut e_2 = Event::from("test message 2").with_batch_notifier(&batch);
+        e_2.as_mut_log().insert("request_id", "1");
+        e_2.as_mut_log().insert("test_end", "yep");
+        drop(batch);
+
+        let inputs = vec![e_1, e_2];
+        let in_stream = Box::pin(stream::iter(inputs));
+        let mut out_stream = reduce.transform(in_stream);
+
+        let mut output = out_stream.next().await.unwrap();
+        assert_eq!((&mut receiver).now_or_never(), None);
+
+        // Acknowledged by the sink.
+        let finalizers = output.take_finalizers();
+        drop(output);
+        assert_eq!((&mut receiver).now_or_never(), None);
+        finalizers.update_status(EventStatus::Errored);
+        drop(finalizers);
+        assert_eq!(receiver.now_or_never(), Some(BatchStatus::Errored));
+    }
+
     #[tokio::test]
     async fn arrays() {
         let reduce = toml::from_str::<ReduceConfig>(
@@ -3,65 +3,13 @@ use crate::{
     config::{DataType, GenerateConfig, TransformConfig, TransformDescription},
     event::Event,
     internal_events::RouteEventDiscarded,
-    transforms::{FunctionTransform, Transform},
+    transforms::{FunctionTransform, Transform, TransformOutputs},
 };
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 //------------------------------------------------------------------------------
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-#[serde(deny_unknown_fields)]
-pub struct LaneConfig {
-    #[serde(flatten)]
-    condition: AnyCondition,
-}
-
-#[async_trait::async_trait]
-#[typetag::serde(name = "lane")]
-impl TransformConfig for LaneConfig {
-    async fn build(&self) -> crate::Result<Transform> {
-        Ok(Transform::function(Lane::new(self.condition.build()?)))
-    }
-
-    fn input_type(&self) -> DataType {
-        DataType::Log
-    }
-
-    fn output_type(&self) -> DataType {
-        DataType::Log
-    }
-
-    fn transform_type(&self) -> &'static str {
-        "lane"
-    }
-}
-
-#[derive(Clone, Derivative)]
-#[derivative(Debug)]
-pub struct Lane {
-    #[derivative(Debug = "ignore")]
-    condition: Box<dyn Condition>,
-}
-
-impl Lane {
-    pub fn new(condition: Box<dyn Condition>) -> Self {
-        Self { condition }
-    }
-}
-
-impl FunctionTransform for Lane {
-    fn transform(&mut self, output: &mut Vec<Event>, event: Event) {
-        if self.condition.check(&event) {
-            output.push(event);
-        } else {
-            emit!(RouteEventDiscarded);
-        }
-    }
-}
-
-//------------------------------------------------------------------------------
-
 #[derive(Deserialize, Serialize, Debug, Clone)]
 #[serde(deny_unknown_fields)]
 pub struct RouteConfig {
@@ -91,21 +39,16 @@ impl GenerateConfig for RouteConfig {
 #[typetag::serde(name = "route")]
 impl TransformConfig for RouteConfig {
     async fn build(&self) -> crate::Result<Transform> {
-        Err("this transform must be expanded".into())
-    }
-
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        let mut map: IndexMap<String, Box<dyn TransformConfig>> = IndexMap::new();
-
-        while let Some((k, v)) = self.route.pop() {
-            map.insert(k.clone(), Box::new(LaneConfig { condition: v }));
+        if self.route.is_empty() {
+            return Err("must specify at least one lane".into());
         }
 
-        if !map.is_empty() {
-            Ok(Some(map))
-        } else {
-            Err("must specify at least one lane".into())
-        }
+        let conditions = self
+            .route
+            .values()
+            .map(|condition| condition.build())
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Transform::function(Route::new(conditions)))
     }
 
     fn input_type(&self) -> DataType {
@@ -119,6 +62,14 @@ impl TransformConfig for RouteConfig {
     fn transform_type(&self) -> &'static str {
         "route"
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        self.route.keys().cloned().collect()
+    }
+
+    fn has_default_output(&self) -> bool {
+        false
+    }
 }
 
 // Add a compatibility alias to avoid breaking existing configs
@@ -132,10 +83,6 @@ impl TransformConfig for RouteCompatConfig {
         self.0.build().await
     }
 
-    fn expand(&mut self) -> crate::Result<Option<IndexMap<String, Box<dyn TransformConfig>>>> {
-        self.0.expand()
-    }
-
     fn input_type(&self) -> DataType {
         self.0.input_type()
     }
@@ -147,6 +94,45 @@ impl TransformConfig for RouteCompatConfig {
     fn transform_type(&self) -> &'static str {
         self.0.transform_type()
     }
+
+    fn named_outputs(&self) -> Vec<String> {
+        self.0.named_outputs()
+    }
+
+    fn has_default_output(&self) -> bool {
+        self.0.has_default_output()
+    }
+}
+
+//------------------------------------------------------------------------------
+
+/// Emits each event on the output of every lane whose condition it matches.
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
+pub struct Route {
+    #[derivative(Debug = "ignore")]
+    conditions: Vec<Box<dyn Condition>>,
+}
+
+impl Route {
+    pub fn new(conditions: Vec<Box<dyn Condition>>) -> Self {
+        Self { conditions }
+    }
+}
+
+impl FunctionTransform for Route {
+    // Nothing is emitted on the default output.
+    fn transform(&mut self, _output: &mut Vec<Event>, _event: Event) {}
+
+    fn transform_outputs(&mut self, outputs: &mut TransformOutputs, event: Event) {
+        for (index, condition) in self.conditions.iter().enumerate() {
+            if condition.check(&event) {
+                outputs.push_named(index, event.clone());
+            } else {
+                emit!(RouteEventDiscarded);
+            }
+        }
+    }
 }
 
 //------------------------------------------------------------------------------
@@ -154,6 +140,11 @@ impl TransformConfig for RouteCompatConfig {
 #[cfg(test)]
 mod test {
     use super::RouteConfig;
+    use crate::{
+        config::{log_schema, TransformConfig},
+        event::Event,
+        transforms::TransformOutputs,
+    };
 
     #[test]
     fn generate_config() {
@@ -170,4 +161,36 @@ mod test {
         )
         .unwrap();
     }
+
+    #[tokio::test]
+    async fn routes_to_matching_lanes() {
+        let config = toml::from_str::<RouteConfig>(
+            r#"
+            route.first.type = "check_fields"
+            route.first."message.eq" = "foo"
+            route.second.type = "check_fields"
+            route.second."message.exists" = true
+        "#,
+        )
+        .unwrap();
+        assert_eq!(config.named_outputs(), vec!["first", "second"]);
+
+        let mut transform = config.build().await.unwrap().into_function();
+        let mut outputs = TransformOutputs::default();
+        transform.transform_outputs(&mut outputs, Event::from("foo"));
+        transform.transform_outputs(&mut outputs, Event::from("bar"));
+
+        let outputs = outputs.into_events();
+        assert_eq!(
+            outputs
+                .iter()
+                .map(|(output, _)| *output)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 2]
+        );
+        assert_eq!(
+            outputs[2].1.as_log()[log_schema().message_key()],
+            "bar".into()
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    config::{self, Config, ConfigDiff},
+    config::{self, secret, Config, ConfigDiff},
     topology::{self, builder::Pieces},
 };
 use colored::*;
@@ -321,8 +321,8 @@ impl Formatter {
     }
 
     fn print(&mut self, print: impl AsRef<str>) {
+        let print = secret::redact(print.as_ref());
         let width = print
-            .as_ref()
             .lines()
             .map(|line| {
                 String::from_utf8_lossy(&strip_ansi_escapes::strip(line).unwrap())
@@ -333,6 +333,6 @@ impl Formatter {
             .unwrap_or(0);
         self.max_line_width = width.max(self.max_line_width);
         self.print_space = true;
-        print!("{}", print.as_ref())
+        print!("{}", print)
     }
 }
@@ -0,0 +1,60 @@
+//! What the `vector` sink and source share about the protocol between them.
+
+use serde::{Deserialize, Serialize};
+use snafu::Snafu;
+
+/// The path batches of events are posted to with version 2 of the protocol.
+pub const V2_EVENTS_PATH: &str = "/v2/events";
+/// The path health checks are made against with version 2 of the protocol.
+pub const V2_HEALTH_PATH: &str = "/v2/health";
+
+/// The version of the protocol spoken between the `vector` sink and source.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+pub enum VectorVersion {
+    /// Length delimited events over TCP, without any responses.
+    #[serde(rename = "1")]
+    V1,
+    /// Batches of events posted over HTTP, each acknowledged by the source.
+    #[serde(rename = "2")]
+    V2,
+}
+
+impl Default for VectorVersion {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "`{}` is only supported by version {} of the protocol, set `version = \"{}\"` to use it",
+    option,
+    version,
+    version
+))]
+pub struct UnsupportedOption {
+    option: &'static str,
+    version: u8,
+}
+
+impl VectorVersion {
+    /// Errors if any of the options only supported by the other version is
+    /// set, as it would be silently ignored otherwise.
+    pub fn check_options(
+        self,
+        v1_only: &[(&'static str, bool)],
+        v2_only: &[(&'static str, bool)],
+    ) -> Result<(), UnsupportedOption> {
+        let (other, version) = match self {
+            Self::V1 => (v2_only, 2),
+            Self::V2 => (v1_only, 1),
+        };
+        match other.iter().find(|(_, set)| *set) {
+            Some((option, _)) => Err(UnsupportedOption {
+                option: *option,
+                version,
+            }),
+            None => Ok(()),
+        }
+    }
+}
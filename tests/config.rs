@@ -826,3 +826,64 @@ async fn route() {
 
     assert_eq!(0, warnings.len());
 }
+
+#[cfg(all(
+    feature = "sources-socket",
+    feature = "transforms-route",
+    feature = "sinks-socket"
+))]
+#[tokio::test]
+async fn route_without_default_output() {
+    let err = load(
+        r#"
+        [sources.in]
+        type = "socket"
+        mode = "tcp"
+        address = "127.0.0.1:1235"
+
+        [transforms.splitting_gerrys]
+        type = "route"
+        inputs = ["in"]
+
+        [transforms.splitting_gerrys.route.only_gerrys]
+        type = "check_fields"
+        "host.eq" = "gerry"
+
+        [sinks.out]
+        type = "socket"
+        mode = "tcp"
+        inputs = ["splitting_gerrys"]
+        encoding = "text"
+        address = "127.0.0.1:9999"
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec![
+            r#"Input "splitting_gerrys" for sink "out" has no default output, use one of ["splitting_gerrys.only_gerrys"] instead."#
+        ]
+    );
+}
+
+#[tokio::test]
+async fn relative_include_without_file() {
+    let err = load(
+        r#"
+        include = ["tenants/*.toml"]
+        "#,
+        Some(Format::TOML),
+    )
+    .await
+    .unwrap_err();
+
+    assert_eq!(
+        err,
+        vec![
+            "Relative include \"tenants/*.toml\" can only be used in config files, use an absolute path instead."
+        ]
+    );
+}
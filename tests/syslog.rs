@@ -5,10 +5,11 @@ use rand::{thread_rng, Rng};
 use serde::Deserialize;
 use serde_json::Value;
 use sinks::socket::{self, SocketSinkConfig};
-use sinks::util::{encoding::EncodingConfig, tcp::TcpSinkConfig, Encoding};
+use sinks::util::{encoding::EncodingConfig, tcp::TcpSinkConfig};
 use std::{collections::HashMap, fmt, str::FromStr};
 use tokio_util::codec::BytesCodec;
 use vector::{
+    codecs::Encoding,
     config, sinks,
     sources::syslog::{Mode, SyslogConfig},
     test_util::{
@@ -1,6 +1,8 @@
 mod support;
 
-use crate::support::{sink, sink_failing_healthcheck, source, transform, MockSourceConfig};
+use crate::support::{
+    sink, sink_dead, sink_failing_healthcheck, source, transform, MockSourceConfig,
+};
 use futures::{future, stream, FutureExt, SinkExt, StreamExt};
 use std::{
     collections::HashMap,
@@ -194,6 +196,43 @@ async fn topology_transform_chain() {
     assert_eq!(vec!["this first second"], res);
 }
 
+#[cfg(feature = "transforms-route")]
+#[tokio::test]
+async fn topology_named_outputs() {
+    let (mut in1, source1) = source();
+    let route: vector::transforms::route::RouteConfig = toml::from_str(
+        r#"
+        route.first.type = "check_fields"
+        route.first."message.eq" = "first"
+        route.all.type = "check_fields"
+        route.all."message.exists" = true
+        "#,
+    )
+    .unwrap();
+    let (out1, sink1) = sink(10);
+    let (out2, sink2) = sink(10);
+
+    let mut config = Config::builder();
+    config.add_source("in1", source1);
+    config.add_transform("router", &["in1"], route);
+    config.add_transform("t1", &["router.first"], transform(" routed", 0.0));
+    config.add_sink("out1", &["t1"], sink1);
+    config.add_sink("out2", &["router.all"], sink2);
+
+    let (topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    in1.send(Event::from("first")).await.unwrap();
+    in1.send(Event::from("second")).await.unwrap();
+
+    topology.stop().await;
+
+    let res1 = out1.map(into_message).collect::<Vec<_>>().await;
+    let res2 = out2.map(into_message).collect::<Vec<_>>().await;
+
+    assert_eq!(vec!["first routed"], res1);
+    assert_eq!(vec!["first", "second"], res2);
+}
+
 #[tokio::test]
 async fn topology_remove_one_source() {
     let (mut in1, source1) = source();
@@ -372,6 +411,42 @@ async fn topology_swap_sink() {
     assert_eq!(vec![event], res2);
 }
 
+#[tokio::test]
+async fn topology_reload_hands_over_buffer_of_stuck_sink() {
+    let (mut in1, source1) = source();
+
+    let mut config = Config::builder();
+    config.global.reload_drain_timeout_secs = Some(1);
+    config.add_source("in1", source1);
+    config.add_sink("out1", &["in1"], sink_dead());
+
+    let (mut topology, _crash) = start_topology(config.build().unwrap(), false).await;
+
+    // The first event is stuck in the sink, the second one in its buffer.
+    let event = Event::from("buffered");
+    in1.send(Event::from("stuck")).await.unwrap();
+    in1.send(event.clone()).await.unwrap();
+    delay_for(Duration::from_millis(50)).await;
+
+    let (out1, sink1) = sink(10);
+
+    let mut config = Config::builder();
+    config.global.reload_drain_timeout_secs = Some(1);
+    config.add_source("in1", source().1);
+    config.add_source("in2", source().1);
+    config.add_sink("out1", &["in1", "in2"], sink1);
+
+    assert!(topology
+        .reload_config_and_respawn(config.build().unwrap())
+        .await
+        .unwrap());
+
+    let h_out1 = tokio::spawn(out1.collect::<Vec<_>>());
+    topology.stop().await;
+
+    assert_eq!(vec![event], h_out1.await.unwrap());
+}
+
 #[tokio::test]
 async fn topology_swap_transform() {
     let (mut in1, source1) = source();